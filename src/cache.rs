@@ -26,6 +26,12 @@
 //!   modification times). Computed once per source file and shared across all
 //!   its output variants.
 //!
+//!   With `[processing] cache_mode = "mtime"`, a source whose size and
+//!   modification time still match the [`SourceStamp`] recorded on the
+//!   last run reuses the recorded hash instead of being read again. That
+//!   trades the `git checkout` resilience for far less I/O on large
+//!   catalogs; any size or mtime change falls back to hashing.
+//!
 //! - **`params_hash`**: SHA-256 of the encoding parameters. For responsive
 //!   variants this includes (target width, quality). For thumbnails it includes
 //!   (aspect ratio, short edge, quality, sharpening). If any config value
@@ -38,17 +44,36 @@
 //! When a hit is found but the output path has changed (e.g. album renamed),
 //! the cached file is copied to the new location instead of re-encoding.
 //!
+//! Each entry also records an `output_hash` of the file it wrote. With
+//! `--verify-cache` a hit additionally requires the file on disk to still
+//! hash to it, so an output that was edited or damaged after it was written
+//! is re-encoded instead of shipped.
+//!
 //! ## Storage
 //!
 //! The cache manifest is a JSON file at `<output_dir>/.cache-manifest.json`.
 //! It lives alongside the processed images so it travels with the output
 //! directory when cached in CI (e.g. `actions/cache` on `dist/`).
 //!
+//! `--cache-file <path>` moves the manifest elsewhere so it can be persisted
+//! on its own. Its entries are still paths relative to the output
+//! directory, so the same file stays valid for any run that processes into
+//! that directory.
+//!
 //! ## Bypassing the cache
 //!
 //! Pass `--no-cache` to the `build` or `process` command to force a full
 //! rebuild. This loads an empty manifest, so every image is re-encoded. The
-//! old output files are overwritten naturally.
+//! old output files are overwritten naturally; outputs the previous manifest
+//! tracked that are no longer live are still pruned.
+//!
+//! ## Garbage collection
+//!
+//! After every run, entries for outputs that weren't produced (deleted
+//! images, renamed or removed albums) are dropped and their files deleted
+//! via [`CacheManifest::prune`]. Files the manifest never tracked — left by
+//! an older binary or an interrupted run — are only swept with
+//! `--prune-untracked` (see [`remove_untracked`]).
 
 use sha2::{Digest, Sha256};
 use std::collections::{HashMap, HashSet};
@@ -101,11 +126,48 @@ pub enum CacheLoadError {
     },
 }
 
+/// How the process stage uses the cache.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheMode {
+    /// Start from an empty manifest and encode everything (`--no-cache`).
+    Disabled,
+    /// Reuse an output whenever its entry matches and the file exists.
+    Enabled,
+    /// Like `Enabled`, but also hash the output and compare it with the
+    /// recorded [`CacheEntry::output_hash`] (`--verify-cache`).
+    Verify,
+}
+
 /// A single cached output file.
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
 pub struct CacheEntry {
     pub source_hash: String,
     pub params_hash: String,
+    /// SHA-256 of the output file as written. Absent in entries from older
+    /// manifests until the next run backfills it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_hash: Option<String>,
+}
+
+/// Size, modification time and content hash of a source file, as seen on
+/// the last run.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+pub struct SourceStamp {
+    pub size: u64,
+    /// Modification time in nanoseconds since the Unix epoch.
+    pub mtime_ns: u64,
+    pub hash: String,
+}
+
+/// Read the size and modification time of `path` for a [`SourceStamp`].
+pub fn file_stamp(path: &Path) -> io::Result<(u64, u64)> {
+    let meta = std::fs::metadata(path)?;
+    let mtime_ns = meta
+        .modified()?
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or(0);
+    Ok((meta.len(), mtime_ns))
 }
 
 /// On-disk cache manifest mapping output paths to their cache entries.
@@ -117,6 +179,9 @@ pub struct CacheEntry {
 pub struct CacheManifest {
     pub version: u32,
     pub entries: HashMap<String, CacheEntry>,
+    /// Source paths (relative to the source root) → their last-seen stamp.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub sources: HashMap<String, SourceStamp>,
     /// Runtime reverse index: `"{source_hash}:{params_hash}"` → output_path.
     /// Built at load time, maintained on insert. Never serialized.
     #[serde(skip)]
@@ -129,6 +194,7 @@ impl CacheManifest {
         Self {
             version: MANIFEST_VERSION,
             entries: HashMap::new(),
+            sources: HashMap::new(),
             content_index: HashMap::new(),
         }
     }
@@ -142,7 +208,12 @@ impl CacheManifest {
     /// so a version mismatch surfaces as a loud failure rather than a
     /// silent cache drop (see `docs/dev/data-model-refactor.md` §5.2).
     pub fn load(output_dir: &Path) -> Self {
-        Self::load_strict(output_dir).unwrap_or_else(|_| Self::empty())
+        Self::load_from(&manifest_path(output_dir))
+    }
+
+    /// Lenient load from an explicit manifest file (`--cache-file`).
+    pub fn load_from(path: &Path) -> Self {
+        Self::load_strict_from(path).unwrap_or_else(|_| Self::empty())
     }
 
     /// Strict load: distinguish missing file, corrupt file, and version
@@ -156,7 +227,13 @@ impl CacheManifest {
     /// - File present but can't be deserialized → `Err(Corrupt)`.
     /// - File present, version mismatch → `Err(VersionMismatch)`.
     pub fn load_strict(output_dir: &Path) -> Result<Self, CacheLoadError> {
-        let path = output_dir.join(MANIFEST_FILENAME);
+        Self::load_strict_from(&manifest_path(output_dir))
+    }
+
+    /// Strict load from an explicit manifest file; same outcomes as
+    /// [`load_strict`](Self::load_strict).
+    pub fn load_strict_from(path: &Path) -> Result<Self, CacheLoadError> {
+        let path = path.to_path_buf();
         let content = match std::fs::read_to_string(&path) {
             Ok(c) => c,
             Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Self::empty()),
@@ -185,7 +262,17 @@ impl CacheManifest {
 
     /// Save to the output directory.
     pub fn save(&self, output_dir: &Path) -> io::Result<()> {
-        let path = output_dir.join(MANIFEST_FILENAME);
+        self.save_to(&manifest_path(output_dir))
+    }
+
+    /// Save to an explicit manifest file, creating its parent directory.
+    /// Entries stay relative to the output directory, wherever the file is.
+    pub fn save_to(&self, path: &Path) -> io::Result<()> {
+        if let Some(parent) = path.parent()
+            && !parent.as_os_str().is_empty()
+        {
+            std::fs::create_dir_all(parent)?;
+        }
         let json = serde_json::to_string_pretty(self)?;
         std::fs::write(path, json)
     }
@@ -212,6 +299,21 @@ impl CacheManifest {
         }
     }
 
+    /// Like [`find_cached`](Self::find_cached), but the file on disk must
+    /// also still hash to the entry's recorded `output_hash`. An entry with
+    /// no recorded hash can't be verified and counts as a miss.
+    pub fn find_verified(
+        &self,
+        source_hash: &str,
+        params_hash: &str,
+        output_dir: &Path,
+    ) -> Option<String> {
+        let stored_path = self.find_cached(source_hash, params_hash, output_dir)?;
+        let expected = self.entries.get(&stored_path)?.output_hash.as_deref()?;
+        let actual = hash_file(&output_dir.join(&stored_path)).ok()?;
+        (actual == expected).then_some(stored_path)
+    }
+
     /// Record a cache entry for an output file.
     ///
     /// If an entry with the same content (source_hash + params_hash) already
@@ -247,10 +349,37 @@ impl CacheManifest {
             CacheEntry {
                 source_hash,
                 params_hash,
+                output_hash: None,
             },
         );
     }
 
+    /// Record the hash of the file written for `output_path`, so later
+    /// [`find_verified`](Self::find_verified) lookups can check it.
+    pub fn set_output_hash(&mut self, output_path: &str, output_hash: String) {
+        if let Some(entry) = self.entries.get_mut(output_path) {
+            entry.output_hash = Some(output_hash);
+        }
+    }
+
+    /// The recorded hash of `source`, if its size and mtime are unchanged.
+    pub fn stamped_hash(&self, source: &str, size: u64, mtime_ns: u64) -> Option<&str> {
+        self.sources
+            .get(source)
+            .filter(|s| s.size == size && s.mtime_ns == mtime_ns)
+            .map(|s| s.hash.as_str())
+    }
+
+    /// Remember the stamp and hash of `source` for the next run.
+    pub fn record_source(&mut self, source: String, stamp: SourceStamp) {
+        self.sources.insert(source, stamp);
+    }
+
+    /// Forget the stamps of sources not in `live_sources`.
+    pub fn retain_sources(&mut self, live_sources: &HashSet<String>) {
+        self.sources.retain(|path, _| live_sources.contains(path));
+    }
+
     /// Remove all entries whose output path is not in `live_paths`, and
     /// delete the corresponding files from `output_dir`.
     ///
@@ -273,6 +402,7 @@ impl CacheManifest {
             let file = output_dir.join(path);
             if file.exists() {
                 let _ = std::fs::remove_file(&file);
+                remove_empty_parents(&file, output_dir);
             }
             removed += 1;
         }
@@ -280,6 +410,60 @@ impl CacheManifest {
     }
 }
 
+/// Delete processed outputs under `output_dir` that are not in `live_paths`.
+///
+/// Unlike [`CacheManifest::prune`], this walks the directory instead of the
+/// manifest, so it also catches files the manifest never knew about. Only
+/// generated image files (`.avif` and leftover `.prescaled.png` scratch
+/// copies) are considered; the manifests and anything else are kept.
+/// Returns the number of files removed.
+pub fn remove_untracked(output_dir: &Path, live_paths: &HashSet<String>) -> io::Result<u32> {
+    // Collect first: deleting while walking would pull directories out from
+    // under the iterator.
+    let mut stale = Vec::new();
+    for entry in walkdir::WalkDir::new(output_dir).min_depth(1) {
+        let entry = entry.map_err(io::Error::other)?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy();
+        if !(name.ends_with(".avif") || name.ends_with(".prescaled.png")) {
+            continue;
+        }
+        let relative = path
+            .strip_prefix(output_dir)
+            .unwrap()
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        if !live_paths.contains(&relative) {
+            stale.push(path.to_path_buf());
+        }
+    }
+
+    for path in &stale {
+        std::fs::remove_file(path)?;
+        remove_empty_parents(path, output_dir);
+    }
+    Ok(stale.len() as u32)
+}
+
+/// Remove directories between `file` and `output_dir` left empty by a
+/// deletion, so removed albums don't leave empty folders behind.
+pub(crate) fn remove_empty_parents(file: &Path, output_dir: &Path) {
+    for dir in file.ancestors().skip(1) {
+        if dir == output_dir || !dir.starts_with(output_dir) {
+            break;
+        }
+        // Fails (and stops) on the first non-empty directory.
+        if std::fs::remove_dir(dir).is_err() {
+            break;
+        }
+    }
+}
+
 /// Build the content_index reverse map from the entries map.
 fn build_content_index(entries: &HashMap<String, CacheEntry>) -> HashMap<String, String> {
     entries
@@ -313,13 +497,20 @@ pub fn hash_file(path: &Path) -> io::Result<String> {
     Ok(format!("{:x}", hasher.finalize()))
 }
 
+/// Tag for how decoded pixels are prepared before encoding, mixed into
+/// every params hash. Change it when that alters the output for an
+/// unchanged source, so cached variants are re-encoded instead of served
+/// stale: `srgb1` is ICC-tagged sources converted to sRGB.
+const PIXEL_PIPELINE: &[u8] = b"pipeline:srgb1\0";
+
 /// SHA-256 hash of encoding parameters for a responsive variant.
 ///
-/// Inputs: target width and quality. If any of these change, the
-/// previously cached output is invalid.
+/// Inputs: target width and quality, plus [`PIXEL_PIPELINE`]. If any of
+/// these change, the previously cached output is invalid.
 pub fn hash_responsive_params(target_width: u32, quality: u32) -> String {
     let mut hasher = Sha256::new();
     hasher.update(b"responsive\0");
+    hasher.update(PIXEL_PIPELINE);
     hasher.update(target_width.to_le_bytes());
     hasher.update(quality.to_le_bytes());
     format!("{:x}", hasher.finalize())
@@ -328,14 +519,15 @@ pub fn hash_responsive_params(target_width: u32, quality: u32) -> String {
 /// SHA-256 hash of encoding parameters for a thumbnail.
 ///
 /// Inputs: aspect ratio, short edge size, quality, and sharpening
-/// settings. If any of these change, the thumbnail is re-generated.
+/// settings, plus [`PIXEL_PIPELINE`]. If any of these change, the
+/// thumbnail is re-generated.
 pub fn hash_thumbnail_params(
     aspect: (u32, u32),
     short_edge: u32,
     quality: u32,
     sharpening: Option<(f32, i32)>,
 ) -> String {
-    hash_thumbnail_variant_params(aspect, short_edge, quality, sharpening, "")
+    hash_thumbnail_variant_params(aspect, short_edge, quality, sharpening, None, "")
 }
 
 /// SHA-256 hash of encoding parameters for a named thumbnail variant.
@@ -348,16 +540,20 @@ pub fn hash_thumbnail_params(
 ///
 /// `variant` is a short discriminator (e.g. `"full-index"`). Passing an
 /// empty string reproduces the legacy `hash_thumbnail_params` hash, so
-/// existing per-album thumbnail caches are not invalidated.
+/// existing per-album thumbnail caches are not invalidated. Likewise a
+/// `focal` point is only mixed in when set: images without one keep
+/// their existing keys, and moving it re-crops the thumbnails.
 pub fn hash_thumbnail_variant_params(
     aspect: (u32, u32),
     short_edge: u32,
     quality: u32,
     sharpening: Option<(f32, i32)>,
+    focal: Option<(u32, u32)>,
     variant: &str,
 ) -> String {
     let mut hasher = Sha256::new();
     hasher.update(b"thumbnail\0");
+    hasher.update(PIXEL_PIPELINE);
     hasher.update(aspect.0.to_le_bytes());
     hasher.update(aspect.1.to_le_bytes());
     hasher.update(short_edge.to_le_bytes());
@@ -372,6 +568,11 @@ pub fn hash_thumbnail_variant_params(
             hasher.update(b"\x00");
         }
     }
+    if let Some((x, y)) = focal {
+        hasher.update(b"\0focal\0");
+        hasher.update(x.to_le_bytes());
+        hasher.update(y.to_le_bytes());
+    }
     if !variant.is_empty() {
         hasher.update(b"\0variant\0");
         hasher.update(variant.as_bytes());
@@ -380,7 +581,7 @@ pub fn hash_thumbnail_variant_params(
 }
 
 /// Summary of cache performance for a build run.
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, serde::Serialize)]
 pub struct CacheStats {
     pub hits: u32,
     pub copies: u32,
@@ -403,6 +604,13 @@ impl CacheStats {
     pub fn total(&self) -> u32 {
         self.hits + self.copies + self.misses
     }
+
+    /// Add another run's counts (e.g. one album's) to these.
+    pub fn merge(&mut self, other: &CacheStats) {
+        self.hits += other.hits;
+        self.copies += other.copies;
+        self.misses += other.misses;
+    }
 }
 
 impl fmt::Display for CacheStats {
@@ -455,6 +663,27 @@ mod tests {
         assert!(m.content_index.is_empty());
     }
 
+    #[test]
+    fn stamped_hash_requires_same_size_and_mtime() {
+        let mut m = CacheManifest::empty();
+        m.record_source(
+            "a/001.jpg".into(),
+            SourceStamp {
+                size: 100,
+                mtime_ns: 5,
+                hash: "h".into(),
+            },
+        );
+
+        assert_eq!(m.stamped_hash("a/001.jpg", 100, 5), Some("h"));
+        assert_eq!(m.stamped_hash("a/001.jpg", 101, 5), None);
+        assert_eq!(m.stamped_hash("a/001.jpg", 100, 6), None);
+        assert_eq!(m.stamped_hash("a/002.jpg", 100, 5), None);
+
+        m.retain_sources(&HashSet::new());
+        assert!(m.sources.is_empty());
+    }
+
     #[test]
     fn find_cached_hit() {
         let tmp = TempDir::new().unwrap();
@@ -507,6 +736,28 @@ mod tests {
         assert_eq!(m.find_cached("h", "p", tmp.path()), None);
     }
 
+    #[test]
+    fn find_verified_checks_output_hash() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("a.avif");
+        fs::write(&path, "avif data").unwrap();
+        let mut m = CacheManifest::empty();
+        m.insert("a.avif".into(), "h".into(), "p".into());
+
+        // No recorded hash: can't be verified
+        assert_eq!(m.find_verified("h", "p", tmp.path()), None);
+
+        m.set_output_hash("a.avif", hash_file(&path).unwrap());
+        assert_eq!(
+            m.find_verified("h", "p", tmp.path()),
+            Some("a.avif".to_string())
+        );
+
+        fs::write(&path, "damaged").unwrap();
+        assert_eq!(m.find_verified("h", "p", tmp.path()), None);
+        assert!(m.find_cached("h", "p", tmp.path()).is_some());
+    }
+
     #[test]
     fn find_cached_returns_old_path_after_content_match() {
         let tmp = TempDir::new().unwrap();
@@ -592,6 +843,52 @@ mod tests {
         assert!(!dir.join("stale.avif").exists());
     }
 
+    #[test]
+    fn prune_removes_emptied_album_dirs() {
+        let tmp = TempDir::new().unwrap();
+        let mut m = CacheManifest::empty();
+        m.insert("group/gone/a.avif".into(), "s1".into(), "p1".into());
+        m.insert("kept/b.avif".into(), "s2".into(), "p2".into());
+
+        for path in ["group/gone/a.avif", "kept/b.avif"] {
+            let file = tmp.path().join(path);
+            fs::create_dir_all(file.parent().unwrap()).unwrap();
+            fs::write(&file, "data").unwrap();
+        }
+
+        let live: HashSet<String> = ["kept/b.avif".to_string()].into();
+        assert_eq!(m.prune(&live, tmp.path()), 1);
+        assert!(!tmp.path().join("group").exists());
+        assert!(tmp.path().join("kept/b.avif").exists());
+        assert!(tmp.path().exists());
+    }
+
+    #[test]
+    fn remove_untracked_deletes_only_orphaned_outputs() {
+        let tmp = TempDir::new().unwrap();
+        for path in [
+            "album/live.avif",
+            "album/orphan.avif",
+            "old-album/x.avif",
+            "album/001-pano.prescaled.png",
+            "manifest.json",
+        ] {
+            let file = tmp.path().join(path);
+            fs::create_dir_all(file.parent().unwrap()).unwrap();
+            fs::write(&file, "data").unwrap();
+        }
+
+        let live: HashSet<String> = ["album/live.avif".to_string()].into();
+        let removed = remove_untracked(tmp.path(), &live).unwrap();
+
+        assert_eq!(removed, 3);
+        assert!(tmp.path().join("album/live.avif").exists());
+        assert!(!tmp.path().join("album/orphan.avif").exists());
+        assert!(!tmp.path().join("album/001-pano.prescaled.png").exists());
+        assert!(!tmp.path().join("old-album").exists());
+        assert!(tmp.path().join("manifest.json").exists());
+    }
+
     #[test]
     fn content_index_rebuilt_on_load() {
         let tmp = TempDir::new().unwrap();
@@ -635,11 +932,26 @@ mod tests {
             loaded.entries["x.avif"],
             CacheEntry {
                 source_hash: "s1".into(),
-                params_hash: "p1".into()
+                params_hash: "p1".into(),
+                output_hash: None,
             }
         );
     }
 
+    #[test]
+    fn save_to_custom_path_creates_parent() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("ci-cache/simple-gal.json");
+        let mut m = CacheManifest::empty();
+        m.insert("x.avif".into(), "s1".into(), "p1".into());
+
+        m.save_to(&path).unwrap();
+        let loaded = CacheManifest::load_strict_from(&path).unwrap();
+
+        assert_eq!(loaded.entries.len(), 1);
+        assert!(!manifest_path(tmp.path()).exists());
+    }
+
     #[test]
     fn load_missing_file_returns_empty() {
         let tmp = TempDir::new().unwrap();
@@ -720,6 +1032,17 @@ mod tests {
         );
     }
 
+    #[test]
+    fn hash_responsive_params_changed_with_srgb_conversion() {
+        // The key before ICC-tagged sources were converted to sRGB.
+        let mut hasher = Sha256::new();
+        hasher.update(b"responsive\0");
+        hasher.update(1400u32.to_le_bytes());
+        hasher.update(90u32.to_le_bytes());
+        let before = format!("{:x}", hasher.finalize());
+        assert_ne!(hash_responsive_params(1400, 90), before);
+    }
+
     #[test]
     fn hash_thumbnail_params_deterministic() {
         let h1 = hash_thumbnail_params((4, 5), 400, 90, Some((0.5, 0)));
@@ -743,13 +1066,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn hash_thumbnail_params_varies_with_focal_point() {
+        let centered = hash_thumbnail_variant_params((4, 5), 400, 90, None, None, "");
+        let focal = hash_thumbnail_variant_params((4, 5), 400, 90, None, Some((50, 30)), "");
+        let moved = hash_thumbnail_variant_params((4, 5), 400, 90, None, Some((50, 60)), "");
+        assert_eq!(centered, hash_thumbnail_params((4, 5), 400, 90, None));
+        assert_ne!(centered, focal);
+        assert_ne!(focal, moved);
+    }
+
     #[test]
     fn hash_thumbnail_variant_empty_tag_matches_legacy() {
         // Passing an empty variant tag must produce the exact same hash as
         // hash_thumbnail_params so existing per-album thumbnail caches are
         // not silently invalidated by the new variant-aware call.
         let legacy = hash_thumbnail_params((4, 5), 400, 90, Some((0.5, 0)));
-        let empty_tag = hash_thumbnail_variant_params((4, 5), 400, 90, Some((0.5, 0)), "");
+        let empty_tag = hash_thumbnail_variant_params((4, 5), 400, 90, Some((0.5, 0)), None, "");
         assert_eq!(legacy, empty_tag);
     }
 
@@ -760,14 +1093,14 @@ mod tests {
         // to distinct cache keys so one doesn't evict the other on insert.
         let regular = hash_thumbnail_params((4, 5), 400, 90, Some((0.5, 0)));
         let full_index =
-            hash_thumbnail_variant_params((4, 5), 400, 90, Some((0.5, 0)), "full-index");
+            hash_thumbnail_variant_params((4, 5), 400, 90, Some((0.5, 0)), None, "full-index");
         assert_ne!(regular, full_index);
     }
 
     #[test]
     fn hash_thumbnail_variant_different_tags_differ() {
-        let a = hash_thumbnail_variant_params((4, 5), 400, 90, None, "full-index");
-        let b = hash_thumbnail_variant_params((4, 5), 400, 90, None, "print-sheet");
+        let a = hash_thumbnail_variant_params((4, 5), 400, 90, None, None, "full-index");
+        let b = hash_thumbnail_variant_params((4, 5), 400, 90, None, None, "print-sheet");
         assert_ne!(a, b);
     }
 
@@ -777,8 +1110,8 @@ mod tests {
         // thumbnail and then a variant thumbnail with matching encode
         // settings must leave BOTH entries in the manifest.
         let mut m = CacheManifest::empty();
-        let regular_hash = hash_thumbnail_variant_params((4, 5), 400, 90, None, "");
-        let fi_hash = hash_thumbnail_variant_params((4, 5), 400, 90, None, "full-index");
+        let regular_hash = hash_thumbnail_variant_params((4, 5), 400, 90, None, None, "");
+        let fi_hash = hash_thumbnail_variant_params((4, 5), 400, 90, None, None, "full-index");
 
         m.insert("a/001-test-thumb.avif".into(), "src".into(), regular_hash);
         m.insert("a/001-test-fi-thumb.avif".into(), "src".into(), fi_hash);
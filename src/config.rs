@@ -21,10 +21,16 @@
 //! ```toml
 //! site_title = "Gallery"
 //! assets_dir = "assets"
+//! show_attribution = false        # "Built with Simple Gal" footer
 //!
 //! [thumbnails]
 //! aspect_ratio = [4, 5]
+//! aspect_mode = "fixed"         # or "original", "adaptive"
 //! size = 400
+//! hidpi = false
+//! preload = 4
+//! # card_size = 600    # omit to use `size` on album cards
+//! # inline_below_bytes = 4096   # embed smaller thumbnails as data: URIs
 //!
 //! [full_index]
 //! generates = false
@@ -36,10 +42,22 @@
 //! [images]
 //! sizes = [800, 1400, 2080]
 //! quality = 90
+//! default_width = 1400
 //!
 //! [theme]
 //! thumbnail_gap = "0.2rem"
 //! grid_padding = "2rem"
+//! image_max_width = "none"           # cap the photo frame, e.g. "1600px"
+//! caption_max_len = 160
+//! index_layout = "grid"             # or "list", "masonry"
+//! breadcrumb_separator = "›"
+//! show_index = true                 # "1. Dawn" labels and 1-dawn/ URLs
+//! grid_columns = "auto"             # or a fixed count, e.g. 3
+//! description_position = "top"      # or "bottom", after the thumbnails
+//! print_credit = true               # album and image label on printouts
+//! image_fade = false                # fade the photo in once loaded
+//! nav_style = "hamburger"           # or "bar", "auto"
+//! nav_breakpoint = 1024             # px; "auto" shows the bar from here up
 //!
 //! [theme.mat_x]
 //! size = "3vw"
@@ -51,6 +69,11 @@
 //! min  = "2rem"
 //! max  = "5rem"
 //!
+//! [theme.frame]
+//! background = "transparent"
+//! border     = "none"           # e.g. "1px solid #ddd"
+//! shadow     = "none"
+//!
 //! [colors.light]
 //! background = "#ffffff"
 //! # ...
@@ -66,15 +89,59 @@
 //!
 //! [processing]
 //! # max_processes = 4   # omit for auto-detect
+//! # io_threads = 16      # omit for twice the cores
+//! cache_mode = "hash"    # or "mtime"
+//! # max_dimension = 8000 # omit for no limit
+//!
+//! [naming]
+//! titleize = false
+//!
+//! [output]
+//! image_page_names = "index-title"   # or "index", "slug"
+//! asset_precedence = "processed"     # or "assets", on output path clashes
+//!
+//! [pwa]
+//! display = "standalone"   # or "fullscreen", "minimal-ui", "browser"
+//!
+//! [pages]
+//! toc = false
+//! highlight_code = false
+//!
+//! [download]
+//! album_zip = false
+//! contents = "web"         # or "originals"
+//!
+//! [albums]
+//! description_from_iptc = false
+//!
+//! [album]                  # album-local, never inherited
+//! # title = "Dawn Patrol"  # omit to use the directory name
+//! # description = "..."    # omit to use description.md / .txt
+//!
+//! [metadata]
+//! title_source = "iptc"           # or "filename"
+//! description_source = "sidecar"  # or "iptc"
+//!
+//! [ordering]
+//! sort = "number"          # or "title"
+//!
+//! [i18n]
+//! alternates = { en = "https://example.com/", fr = "https://example.com/fr/" }
+//!
+//! [search]
+//! enabled = false          # search-index.json + a search box on the home page
 //! ```
 //!
 //! Run `simple-gal config gen` to print a documented template derived
 //! directly from this struct.
 
+use crate::imaging::calculations::calculate_thumbnail_dimensions;
+use crate::imaging::{AspectMode, ImageSizes};
 use confique::Config;
 use confique::Layer;
 use confique::meta::Meta;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
@@ -149,25 +216,85 @@ pub struct SiteConfig {
     #[config(default = "Gallery")]
     pub site_title: String,
 
+    /// Label of the home link that starts every breadcrumb. Defaults to
+    /// `site_title` when unset.
+    pub home_label: Option<String>,
+
     /// Public origin of the deployed site (e.g. `"https://gallery.example.com"`),
     /// with no trailing slash. When set, the generator emits Open Graph meta
     /// tags on gallery-list, album, and image pages so chat apps (WhatsApp,
     /// iMessage, Slack, Discord) render link previews with an image, title,
     /// and breadcrumb description. When unset, no OG tags are emitted — the
     /// site still works, it just won't produce rich link previews.
+    /// Absolute URLs are built from it and `base_path` (see
+    /// [`SiteConfig::site_url`]).
     pub base_url: Option<String>,
 
+    /// URL path the site is served under (e.g. `"/my-gallery/"` for a
+    /// GitHub Pages project site). A missing trailing slash is added on
+    /// load. Every root-relative link, asset URL and the PWA scope start
+    /// here.
+    #[config(default = "/")]
+    pub base_path: String,
+
     /// Directory for static assets (favicon, fonts, etc.), relative to
     /// content root. Contents are copied verbatim to the output root during
     /// generation. If the directory doesn't exist, it is silently skipped.
     #[config(default = "assets")]
     pub assets_dir: String,
 
+    /// Square source image (PNG or JPEG, ideally 512px or larger) relative
+    /// to the content root, e.g. `"assets/icon.png"`. When set, the
+    /// favicon, PWA icons and Apple touch icon are all resized from it
+    /// instead of using the built-in defaults. Non-square sources are
+    /// center-cropped.
+    pub icon_source: Option<String>,
+
+    /// Image shown as the breadcrumb's home link in place of the text
+    /// label, relative to the site root (e.g. `"logo.svg"`). Place it in
+    /// the assets directory so it gets copied to the output. The label
+    /// becomes its `alt` text.
+    pub logo: Option<String>,
+
     /// Stem of the site description file in the content root (e.g. `site`
     /// → looks for `site.md` / `site.txt`). Rendered on the index page.
     #[config(default = "site")]
     pub site_description_file: String,
 
+    /// Curl straight quotes and turn `--` into an em dash and `...` into an
+    /// ellipsis in descriptions and content pages. Code is left untouched.
+    #[config(default = false)]
+    pub smart_typography: bool,
+
+    /// Open links to other sites in a new tab: every `http(s)://` link in
+    /// descriptions and content pages whose host differs from `base_url`'s
+    /// gets `target="_blank" rel="noopener noreferrer"`.
+    #[config(default = false)]
+    pub external_links_new_tab: bool,
+
+    /// Extensions (without the dot) of non-image files in album directories
+    /// to publish next to the album page, e.g. a PDF linked from the album
+    /// description as `[Price list](prices.pdf)`.
+    #[config(default = ["pdf"])]
+    pub album_file_extensions: Vec<String>,
+
+    /// Raw HTML injected at the end of `<head>` on every page — a config
+    /// alternative to `assets/head.html` for small snippets (analytics,
+    /// meta tags). Emitted verbatim with no escaping: only put trusted
+    /// markup here. When both are present, the file comes first.
+    pub head_html: Option<String>,
+
+    /// Raw HTML injected immediately before `</body>` on every page — a
+    /// config alternative to `assets/body-end.html`. Emitted verbatim with
+    /// no escaping: only put trusted markup here. When both are present,
+    /// the file comes first.
+    pub body_end_html: Option<String>,
+
+    /// Show a small "Built with Simple Gal" link in a footer at the end of
+    /// every page except image pages.
+    #[config(default = false)]
+    pub show_attribution: bool,
+
     /// Color schemes for light and dark modes.
     #[config(nested)]
     pub colors: ColorConfig,
@@ -199,6 +326,50 @@ pub struct SiteConfig {
     /// Auto file-name index reindexing settings.
     #[config(nested)]
     pub auto_indexing: AutoIndexingConfig,
+
+    /// How display titles are derived from file names.
+    #[config(nested)]
+    pub naming: NamingConfig,
+
+    /// Shape of generated output paths.
+    #[config(nested)]
+    pub output: OutputConfig,
+
+    /// Installable web app (PWA) settings.
+    #[config(nested)]
+    pub pwa: PwaConfig,
+
+    /// Markdown content page settings.
+    #[config(nested)]
+    pub pages: PagesConfig,
+
+    /// Album download settings.
+    #[config(nested)]
+    pub download: DownloadConfig,
+
+    /// Album page settings.
+    #[config(nested)]
+    pub albums: AlbumsConfig,
+
+    /// Title and description of a single album.
+    #[config(nested)]
+    pub album: AlbumConfig,
+
+    /// Which metadata source wins for image titles and descriptions.
+    #[config(nested)]
+    pub metadata: MetadataConfig,
+
+    /// How a directory's albums and groups are ordered.
+    #[config(nested)]
+    pub ordering: OrderingConfig,
+
+    /// Parallel versions of the site in other languages.
+    #[config(nested)]
+    pub i18n: I18nConfig,
+
+    /// Client-side search over album and image titles.
+    #[config(nested)]
+    pub search: SearchConfig,
 }
 
 impl Default for SiteConfig {
@@ -231,6 +402,38 @@ impl<'de> Deserialize<'de> for SiteConfig {
 }
 
 impl SiteConfig {
+    /// Rewrite forgiving spellings that have one obvious meaning before
+    /// [`validate`](Self::validate) sees them. Currently just the trailing
+    /// slash on `base_path`, so `"/portfolio"` means `"/portfolio/"`.
+    fn normalize(&mut self) {
+        if self.base_path.starts_with('/') && !self.base_path.ends_with('/') {
+            self.base_path.push('/');
+        }
+    }
+
+    /// Absolute URL of the site root, without a trailing slash: `base_url`
+    /// followed by `base_path`, or `None` without a `base_url`. A `base_url`
+    /// that already ends in `base_path` doesn't get it twice.
+    pub fn site_url(&self) -> Option<String> {
+        let base = self.base_url.as_deref()?.trim_end_matches('/');
+        let path = self.base_path.trim_end_matches('/');
+        Some(if base.ends_with(path) {
+            base.to_string()
+        } else {
+            format!("{base}{path}")
+        })
+    }
+
+    /// Whether an album directory file with this name is published with
+    /// the album (its extension is listed in `album_file_extensions`).
+    pub fn is_album_file(&self, name: &str) -> bool {
+        Path::new(name).extension().is_some_and(|ext| {
+            self.album_file_extensions
+                .iter()
+                .any(|e| ext.eq_ignore_ascii_case(e))
+        })
+    }
+
     /// Validate semantic constraints that confique's type system can't
     /// express: numeric ranges, non-empty arrays, and so on.
     ///
@@ -243,11 +446,69 @@ impl SiteConfig {
                 "images.quality must be 0-100".into(),
             ));
         }
+        if cfg!(not(feature = "highlight")) && self.pages.highlight_code {
+            return Err(ConfigError::Validation(
+                "pages.highlight_code needs simple-gal built with the \"highlight\" feature".into(),
+            ));
+        }
+        let base_path = &self.base_path;
+        let path_chars = |c: char| c.is_ascii_alphanumeric() || "-._~/".contains(c);
+        if !base_path.starts_with('/')
+            || !base_path.ends_with('/')
+            || base_path.contains("//")
+            || !base_path.chars().all(path_chars)
+        {
+            return Err(ConfigError::Validation(format!(
+                "base_path = {base_path:?} must be a URL path that starts and ends with \"/\" \
+                 (e.g. \"/\" or \"/my-gallery/\")"
+            )));
+        }
+        if let Some(ext) = self
+            .album_file_extensions
+            .iter()
+            .find(|e| e.is_empty() || !e.chars().all(|c| c.is_ascii_alphanumeric()))
+        {
+            return Err(ConfigError::Validation(format!(
+                "album_file_extensions entry {ext:?} must be a bare extension like \"pdf\""
+            )));
+        }
+        if let Some((lang, url)) = self.i18n.alternates.iter().find(|(lang, url)| {
+            lang.is_empty() || !(url.starts_with("https://") || url.starts_with("http://"))
+        }) {
+            return Err(ConfigError::Validation(format!(
+                "i18n.alternates.{lang} = {url:?} must be an absolute http(s) URL"
+            )));
+        }
+        if self.images.default_width == 0 {
+            return Err(ConfigError::Validation(
+                "images.default_width must be non-zero".into(),
+            ));
+        }
+        if parse_font_weights(&self.font.weight).is_none() {
+            return Err(ConfigError::Validation(format!(
+                "font.weight = {:?} must be a weight or comma-separated list of weights \
+                 between 1 and 1000 (e.g. \"600\" or \"400,700\")",
+                self.font.weight
+            )));
+        }
+        for (name, value) in self.font.metric_overrides.descriptors() {
+            if !is_css_percentage(value) {
+                return Err(ConfigError::Validation(format!(
+                    "font.metric_overrides.{} = {value:?} must be a percentage such as \"105%\"",
+                    name.replace('-', "_")
+                )));
+            }
+        }
         if self.thumbnails.aspect_ratio[0] == 0 || self.thumbnails.aspect_ratio[1] == 0 {
             return Err(ConfigError::Validation(
                 "thumbnails.aspect_ratio values must be non-zero".into(),
             ));
         }
+        if self.thumbnails.card_size == Some(0) {
+            return Err(ConfigError::Validation(
+                "thumbnails.card_size must be non-zero".into(),
+            ));
+        }
         if self.full_index.thumb_ratio[0] == 0 || self.full_index.thumb_ratio[1] == 0 {
             return Err(ConfigError::Validation(
                 "full_index.thumb_ratio values must be non-zero".into(),
@@ -258,7 +519,7 @@ impl SiteConfig {
                 "full_index.thumb_size must be non-zero".into(),
             ));
         }
-        if self.images.sizes.is_empty() {
+        if self.images.sizes.explicit().is_some_and(<[u32]>::is_empty) {
             return Err(ConfigError::Validation(
                 "images.sizes must not be empty".into(),
             ));
@@ -277,6 +538,77 @@ impl SiteConfig {
                 "auto_indexing.padding must be 0-12".into(),
             ));
         }
+        // Colors go verbatim into CSS custom properties, where a typo
+        // silently invalidates the declaration.
+        for (key, value) in self.colors.entries() {
+            if !is_css_color(value) {
+                return Err(ConfigError::Validation(format!(
+                    "{key} = {value:?} is not a valid CSS color \
+                     (expected #rgb/#rrggbb hex, rgb()/hsl(), or a named color)"
+                )));
+            }
+        }
+        // Same for lengths: a bad unit drops the whole `clamp()` / `gap`
+        // declaration and the layout falls back without a trace.
+        let lengths = self
+            .theme
+            .entries()
+            .into_iter()
+            .chain([("full_index.thumb_gap", self.full_index.thumb_gap.as_str())]);
+        for (key, value) in lengths {
+            if !is_css_length(value) {
+                return Err(ConfigError::Validation(format!(
+                    "{key} = {value:?} is not a valid CSS length \
+                     (expected a number with a unit such as 1rem/3vw/10px/5%, or calc()/clamp())"
+                )));
+            }
+        }
+        let image_max_width = &self.theme.image_max_width;
+        if image_max_width != "none" && !is_css_length(image_max_width) {
+            return Err(ConfigError::Validation(format!(
+                "theme.image_max_width = {image_max_width:?} must be a CSS length or \"none\""
+            )));
+        }
+        if self.theme.breadcrumb_separator.trim().is_empty() {
+            return Err(ConfigError::Validation(
+                "theme.breadcrumb_separator must not be empty".into(),
+            ));
+        }
+        // Outputs are made from the downscaled copy, so anything larger
+        // than it would be upscaled. The auto ladder follows the downscaled
+        // copy, so only explicit sizes count. Thumbnail sizes are short
+        // edges; their long edge at the configured aspect ratio is what
+        // has to fit.
+        if let Some(max_dimension) = self.processing.max_dimension {
+            let [aspect_w, aspect_h] = self.thumbnails.aspect_ratio;
+            let long_edge = |short_edge| {
+                let (w, h) = calculate_thumbnail_dimensions((aspect_w, aspect_h), short_edge);
+                w.max(h)
+            };
+            let largest = self
+                .images
+                .sizes
+                .explicit()
+                .unwrap_or_default()
+                .iter()
+                .copied()
+                .chain(
+                    [self.thumbnails.size]
+                        .into_iter()
+                        .chain(self.thumbnails.hidpi.then_some(self.thumbnails.size * 2))
+                        .chain(self.thumbnails.card_size)
+                        .map(long_edge),
+                )
+                .max()
+                .unwrap_or(0);
+            if max_dimension < largest {
+                return Err(ConfigError::Validation(format!(
+                    "processing.max_dimension ({max_dimension}) must be at least the largest \
+                     images.sizes value or thumbnail long edge (thumbnails.size, doubled with \
+                     hidpi, or thumbnails.card_size at thumbnails.aspect_ratio) ({largest})"
+                )));
+            }
+        }
         Ok(())
     }
 }
@@ -293,9 +625,40 @@ pub struct ThumbnailsConfig {
     /// Aspect ratio as `[width, height]`, e.g. `[4, 5]` for portrait.
     #[config(default = [4, 5])]
     pub aspect_ratio: [u32; 2],
+    /// How `aspect_ratio` applies: `"fixed"` crops every thumbnail to it,
+    /// `"original"` keeps each image's own shape, and `"adaptive"` turns it
+    /// to portrait, landscape or square to match each image.
+    #[config(default = "fixed")]
+    pub aspect_mode: AspectMode,
     /// Thumbnail short-edge size in pixels.
     #[config(default = 400)]
     pub size: u32,
+    /// Apply a light unsharp mask to thumbnails after downscaling.
+    #[config(default = true)]
+    pub sharpen: bool,
+    /// Also generate every thumbnail at twice `size` and offer both to
+    /// the browser with `1x`/`2x` `srcset` descriptors, so grids stay sharp
+    /// on high-density screens.
+    #[config(default = false)]
+    pub hidpi: bool,
+    /// How many of an album's first thumbnails its page preloads from the
+    /// `<head>`, so the top of the grid paints sooner. `0` turns it off.
+    #[config(default = 4)]
+    pub preload: u32,
+    /// Short-edge size in pixels of a separate preview, at the same aspect
+    /// ratio, used for the album's card on index and gallery-list pages.
+    /// Unset, cards show the regular thumbnail.
+    pub card_size: Option<u32>,
+    /// Embed thumbnails whose encoded file is smaller than this many bytes
+    /// directly in album and index pages as `data:` URIs, saving a request
+    /// each. Unset, every thumbnail is linked as a file.
+    pub inline_below_bytes: Option<u64>,
+    /// Image that represents the album on index and gallery-list pages,
+    /// named by its number prefix (`"003"`) or file name. Overrides the
+    /// thumb > #1 > first-by-sort fallback without removing the image from
+    /// the album. Album-local: the scan stage never cascades it into
+    /// subdirectories.
+    pub featured: Option<String>,
 }
 
 // =============================================================================
@@ -339,12 +702,38 @@ pub struct FullIndexConfig {
 #[config(layer_attr(serde(deny_unknown_fields)))]
 pub struct ImagesConfig {
     /// Pixel widths (longer edge) to generate for responsive `<picture>`
-    /// elements.
+    /// elements, or `"auto"` to derive a ladder from each source's size.
     #[config(default = [800, 1400, 2080])]
-    pub sizes: Vec<u32>,
+    pub sizes: ImageSizes,
+    /// Sizes to drop from the inherited `sizes` list, for an album whose
+    /// images are too small for the largest ones. Applied while the config
+    /// cascade is folded (see [`cascade_layer`]) and again for command-line
+    /// overrides when it is finalized, so the resolved config has the
+    /// reduced `sizes` and an empty `exclude_sizes`.
+    #[config(default = [])]
+    pub exclude_sizes: Vec<u32>,
     /// AVIF encoding quality (0 = worst, 100 = best).
     #[config(default = 90)]
     pub quality: u32,
+    /// Target width of the `src` fallback and of the prefetched neighbour
+    /// images on image pages. The generated size closest to it is used.
+    #[config(default = 1400)]
+    pub default_width: u32,
+    /// Warn when a source image's longer edge is below this many pixels,
+    /// which usually means a thumbnail or screenshot was uploaded by
+    /// mistake. `0` turns the warning off.
+    #[config(default = 0)]
+    pub min_dimension: u32,
+    /// Remove GPS location from originals published for download
+    /// (`[download] contents = "originals"`). Generated AVIFs never carry
+    /// EXIF, so this only affects the copied source files.
+    #[config(default = true)]
+    pub strip_gps: bool,
+    /// With `strip_gps`, keep the rest of the EXIF block (camera, lens,
+    /// exposure, date) and remove only GPS and serial numbers. When off, all
+    /// EXIF and XMP is removed except Orientation.
+    #[config(default = false)]
+    pub preserve_exif: bool,
 }
 
 // =============================================================================
@@ -405,6 +794,24 @@ impl MatY {
     }
 }
 
+/// Appearance of the frame around the photo on image pages. Each value is
+/// a CSS value for the matching property of `.image-frame`; the defaults
+/// draw no frame at all.
+#[derive(Config, Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[config(layer_attr(derive(Clone)))]
+#[config(layer_attr(serde(deny_unknown_fields)))]
+pub struct FrameConfig {
+    /// Background behind the photo (CSS `background`).
+    #[config(default = "transparent")]
+    pub background: String,
+    /// Border around the photo (CSS `border`), e.g. `"1px solid #ddd"`.
+    #[config(default = "none")]
+    pub border: String,
+    /// Shadow under the photo (CSS `box-shadow`).
+    #[config(default = "none")]
+    pub shadow: String,
+}
+
 /// Theme / layout settings.
 #[derive(Config, Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[config(layer_attr(derive(Clone)))]
@@ -416,12 +823,247 @@ pub struct ThemeConfig {
     /// Vertical mat around images. See `docs/dev/photo-page-layout.md`.
     #[config(nested)]
     pub mat_y: MatY,
+    /// Border, shadow and background of the photo frame.
+    #[config(nested)]
+    pub frame: FrameConfig,
     /// Gap between thumbnails in both album and image grids (CSS value).
     #[config(default = "0.2rem")]
     pub thumbnail_gap: String,
     /// Padding around the thumbnail grid container (CSS value).
     #[config(default = "2rem")]
     pub grid_padding: String,
+    /// Largest width the photo frame on image pages may grow to (CSS
+    /// length), so photos stop scaling up on ultrawide screens. `"none"`
+    /// lets the photo fill the mat.
+    #[config(default = "none")]
+    pub image_max_width: String,
+    /// Longest image description (in characters) shown as an inline
+    /// caption under the photo. Longer or multi-line descriptions are
+    /// shown in a scrollable block instead.
+    #[config(default = 160)]
+    pub caption_max_len: u32,
+    /// How album cards are laid out on the index and gallery-list pages.
+    #[config(default = "grid")]
+    pub index_layout: IndexLayout,
+    /// Separator drawn between breadcrumb segments (padded with a space
+    /// on each side).
+    #[config(default = "›")]
+    pub breadcrumb_separator: String,
+    /// Show each image's position in its page label ("1. Dawn") and page
+    /// URL (`1-dawn/`). Off, titled images are labelled by title alone and
+    /// image page URLs use the name without the index.
+    #[config(default = true)]
+    pub show_index: bool,
+    /// Columns in the album thumbnail grid and the `"grid"` index layout:
+    /// `"auto"` fits as many as the window allows, a number fixes the count
+    /// at every window width.
+    #[config(default = "auto")]
+    pub grid_columns: GridColumns,
+    /// Where an album's description goes on its page: `"top"` in the
+    /// header above the thumbnails, or `"bottom"` after them.
+    #[config(default = "top")]
+    pub description_position: DescriptionPosition,
+    /// When an image page is printed, put the album title and image label
+    /// under the photo. Navigation and controls are left off the printout
+    /// either way.
+    #[config(default = true)]
+    pub print_credit: bool,
+    /// Fade the photo on image pages in once it has loaded, instead of
+    /// letting it paint in as it arrives. Skipped for visitors who ask
+    /// for reduced motion.
+    #[config(default = false)]
+    pub image_fade: bool,
+    /// How the site navigation is shown: `"hamburger"` opens a panel from
+    /// the right, `"bar"` lists the top-level entries in the header, and
+    /// `"auto"` shows the bar on windows at least `nav_breakpoint` wide.
+    #[config(default = "hamburger")]
+    pub nav_style: NavStyle,
+    /// Window width in pixels from which `nav_style = "auto"` shows the bar.
+    #[config(default = 1024)]
+    pub nav_breakpoint: u32,
+}
+
+impl ThemeConfig {
+    /// Every CSS length value paired with its dotted config key
+    /// (e.g. `theme.mat_x.min`), for validation messages.
+    fn entries(&self) -> [(&'static str, &str); 8] {
+        let (x, y) = (&self.mat_x, &self.mat_y);
+        [
+            ("theme.mat_x.size", &x.size),
+            ("theme.mat_x.min", &x.min),
+            ("theme.mat_x.max", &x.max),
+            ("theme.mat_y.size", &y.size),
+            ("theme.mat_y.min", &y.min),
+            ("theme.mat_y.max", &y.max),
+            ("theme.thumbnail_gap", &self.thumbnail_gap),
+            ("theme.grid_padding", &self.grid_padding),
+        ]
+    }
+}
+
+/// Whether `value` is a non-negative CSS percentage such as `105%` or `0%`.
+fn is_css_percentage(value: &str) -> bool {
+    value
+        .trim()
+        .strip_suffix('%')
+        .and_then(|n| n.parse::<f64>().ok())
+        .is_some_and(|n| n.is_finite() && n >= 0.0)
+}
+
+/// CSS length units accepted by [`is_css_length`], lowercase.
+#[rustfmt::skip]
+const CSS_LENGTH_UNITS: &[&str] = &[
+    "%", "px", "em", "rem", "ex", "rex", "ch", "rch", "cap", "ic", "lh", "rlh",
+    "vw", "vh", "vi", "vb", "vmin", "vmax", "svw", "svh", "svmin", "svmax",
+    "lvw", "lvh", "lvmin", "lvmax", "dvw", "dvh", "dvmin", "dvmax",
+    "cqw", "cqh", "cqi", "cqb", "cqmin", "cqmax", "cm", "mm", "q", "in", "pt", "pc",
+];
+
+/// CSS math functions accepted by [`is_css_length`].
+const CSS_LENGTH_FUNCTIONS: &[&str] = &["calc", "clamp", "min", "max"];
+
+/// Whether `value` is a CSS length or percentage: a number followed by a
+/// known unit (`1rem`, `3vw`, `50%`), a bare `0`, or a math function such
+/// as `calc(…)` / `clamp(…)`.
+///
+/// Function arguments are only checked for plausible characters and
+/// balanced parentheses, not full CSS grammar.
+fn is_css_length(value: &str) -> bool {
+    let lower = value.trim().to_ascii_lowercase();
+    if let Some((name, rest)) = lower.split_once('(') {
+        let Some(args) = rest.strip_suffix(')') else {
+            return false;
+        };
+        let mut depth = 0i32;
+        let balanced = args.chars().all(|c| {
+            match c {
+                '(' => depth += 1,
+                ')' => depth -= 1,
+                _ => {}
+            }
+            depth >= 0
+        }) && depth == 0;
+        return CSS_LENGTH_FUNCTIONS.contains(&name.trim_end())
+            && balanced
+            && !args.trim().is_empty()
+            && args.chars().all(|c| {
+                c.is_ascii_alphanumeric() || c.is_whitespace() || ".,%+-*/()".contains(c)
+            });
+    }
+    let unit_start = lower
+        .find(|c: char| !(c.is_ascii_digit() || c == '.' || c == '+' || c == '-'))
+        .unwrap_or(lower.len());
+    let (number, unit) = lower.split_at(unit_start);
+    let digits = number.strip_prefix(['+', '-']).unwrap_or(number);
+    let valid_number = digits.chars().any(|c| c.is_ascii_digit())
+        && digits.chars().all(|c| c.is_ascii_digit() || c == '.')
+        && digits.matches('.').count() <= 1;
+    if !valid_number {
+        return false;
+    }
+    if unit.is_empty() {
+        return digits.chars().all(|c| c == '0' || c == '.');
+    }
+    CSS_LENGTH_UNITS.contains(&unit)
+}
+
+/// Layout of album cards on the index and gallery-list pages.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum IndexLayout {
+    /// Uniform grid of thumbnails with titles.
+    #[default]
+    Grid,
+    /// One album per row: thumbnail beside title and description excerpt.
+    List,
+    /// CSS-columns masonry; cards keep their own thumbnail aspect ratio.
+    Masonry,
+}
+
+/// Presentation of the site navigation (`[theme] nav_style`).
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum NavStyle {
+    /// Hamburger button and slide-in panel at every width.
+    #[default]
+    Hamburger,
+    /// Horizontal bar of top-level entries at every width.
+    Bar,
+    /// Bar from `nav_breakpoint` up, hamburger below it.
+    Auto,
+}
+
+/// Placement of the album description relative to the thumbnail grid.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum DescriptionPosition {
+    /// In the album header, above the grid (beside it on wide screens).
+    #[default]
+    Top,
+    /// After the grid, as a closing statement.
+    Bottom,
+}
+
+/// Column count for thumbnail grids (`[theme] grid_columns`).
+///
+/// In config this is either the string `"auto"` or a positive integer
+/// (`grid_columns = 3`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum GridColumns {
+    /// Responsive: as many columns as fit the window.
+    #[default]
+    Auto,
+    /// Exactly this many columns, whatever the window width.
+    Fixed(u32),
+}
+
+impl Serialize for GridColumns {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::Auto => serializer.serialize_str("auto"),
+            Self::Fixed(n) => serializer.serialize_u32(*n),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for GridColumns {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::{self, Visitor};
+
+        struct ColumnsVisitor;
+
+        impl Visitor<'_> for ColumnsVisitor {
+            type Value = GridColumns;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                f.write_str("a positive column count or the string \"auto\"")
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<GridColumns, E> {
+                if value == "auto" {
+                    Ok(GridColumns::Auto)
+                } else {
+                    Err(E::invalid_value(de::Unexpected::Str(value), &self))
+                }
+            }
+
+            fn visit_u64<E: de::Error>(self, value: u64) -> Result<GridColumns, E> {
+                match u32::try_from(value) {
+                    Ok(n) if n > 0 => Ok(GridColumns::Fixed(n)),
+                    _ => Err(E::invalid_value(de::Unexpected::Unsigned(value), &self)),
+                }
+            }
+
+            fn visit_i64<E: de::Error>(self, value: i64) -> Result<GridColumns, E> {
+                match u64::try_from(value) {
+                    Ok(n) => self.visit_u64(n),
+                    Err(_) => Err(E::invalid_value(de::Unexpected::Signed(value), &self)),
+                }
+            }
+        }
+
+        deserializer.deserialize_any(ColumnsVisitor)
+    }
 }
 
 // =============================================================================
@@ -442,6 +1084,87 @@ pub struct ColorConfig {
     pub dark: DarkColors,
 }
 
+impl ColorConfig {
+    /// Every color value paired with its dotted config key
+    /// (e.g. `colors.dark.link`), for validation messages.
+    fn entries(&self) -> [(&'static str, &str); 14] {
+        let (l, d) = (&self.light, &self.dark);
+        [
+            ("colors.light.background", &l.background),
+            ("colors.light.text", &l.text),
+            ("colors.light.text_muted", &l.text_muted),
+            ("colors.light.border", &l.border),
+            ("colors.light.separator", &l.separator),
+            ("colors.light.link", &l.link),
+            ("colors.light.link_hover", &l.link_hover),
+            ("colors.dark.background", &d.background),
+            ("colors.dark.text", &d.text),
+            ("colors.dark.text_muted", &d.text_muted),
+            ("colors.dark.border", &d.border),
+            ("colors.dark.separator", &d.separator),
+            ("colors.dark.link", &d.link),
+            ("colors.dark.link_hover", &d.link_hover),
+        ]
+    }
+}
+
+/// CSS named colors (CSS Color Module Level 4), plus `transparent` and
+/// `currentcolor`.
+#[rustfmt::skip]
+const CSS_NAMED_COLORS: &[&str] = &[
+    "aliceblue", "antiquewhite", "aqua", "aquamarine", "azure", "beige", "bisque", "black",
+    "blanchedalmond", "blue", "blueviolet", "brown", "burlywood", "cadetblue", "chartreuse",
+    "chocolate", "coral", "cornflowerblue", "cornsilk", "crimson", "cyan", "darkblue", "darkcyan",
+    "darkgoldenrod", "darkgray", "darkgreen", "darkgrey", "darkkhaki", "darkmagenta",
+    "darkolivegreen", "darkorange", "darkorchid", "darkred", "darksalmon", "darkseagreen",
+    "darkslateblue", "darkslategray", "darkslategrey", "darkturquoise", "darkviolet", "deeppink",
+    "deepskyblue", "dimgray", "dimgrey", "dodgerblue", "firebrick", "floralwhite", "forestgreen",
+    "fuchsia", "gainsboro", "ghostwhite", "gold", "goldenrod", "gray", "green", "greenyellow",
+    "grey", "honeydew", "hotpink", "indianred", "indigo", "ivory", "khaki", "lavender",
+    "lavenderblush", "lawngreen", "lemonchiffon", "lightblue", "lightcoral", "lightcyan",
+    "lightgoldenrodyellow", "lightgray", "lightgreen", "lightgrey", "lightpink", "lightsalmon",
+    "lightseagreen", "lightskyblue", "lightslategray", "lightslategrey", "lightsteelblue",
+    "lightyellow", "lime", "limegreen", "linen", "magenta", "maroon", "mediumaquamarine",
+    "mediumblue", "mediumorchid", "mediumpurple", "mediumseagreen", "mediumslateblue",
+    "mediumspringgreen", "mediumturquoise", "mediumvioletred", "midnightblue", "mintcream",
+    "mistyrose", "moccasin", "navajowhite", "navy", "oldlace", "olive", "olivedrab", "orange",
+    "orangered", "orchid", "palegoldenrod", "palegreen", "paleturquoise", "palevioletred",
+    "papayawhip", "peachpuff", "peru", "pink", "plum", "powderblue", "purple", "rebeccapurple",
+    "red", "rosybrown", "royalblue", "saddlebrown", "salmon", "sandybrown", "seagreen", "seashell",
+    "sienna", "silver", "skyblue", "slateblue", "slategray", "slategrey", "snow", "springgreen",
+    "steelblue", "tan", "teal", "thistle", "tomato", "turquoise", "violet", "wheat", "white",
+    "whitesmoke", "yellow", "yellowgreen", "transparent", "currentcolor",
+];
+
+/// CSS color functions accepted by [`is_css_color`].
+const CSS_COLOR_FUNCTIONS: &[&str] = &[
+    "rgb", "rgba", "hsl", "hsla", "hwb", "lab", "lch", "oklab", "oklch", "color",
+];
+
+/// Whether `value` is a CSS color: `#` hex with 3, 4, 6 or 8 digits, a
+/// color function such as `rgb(…)` / `hsl(…)`, or a named color.
+///
+/// Function arguments are only checked for plausible characters (numbers,
+/// units, `%`, `/`, separators), not full CSS grammar.
+fn is_css_color(value: &str) -> bool {
+    let value = value.trim();
+    if let Some(hex) = value.strip_prefix('#') {
+        return matches!(hex.len(), 3 | 4 | 6 | 8) && hex.chars().all(|c| c.is_ascii_hexdigit());
+    }
+    let lower = value.to_ascii_lowercase();
+    if let Some((name, rest)) = lower.split_once('(') {
+        let Some(args) = rest.strip_suffix(')') else {
+            return false;
+        };
+        return CSS_COLOR_FUNCTIONS.contains(&name.trim_end())
+            && !args.trim().is_empty()
+            && args
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c.is_whitespace() || ".,%/+-".contains(c));
+    }
+    CSS_NAMED_COLORS.contains(&lower.as_str())
+}
+
 /// Light-mode color scheme.
 #[derive(Config, Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[config(layer_attr(derive(Clone)))]
@@ -522,7 +1245,8 @@ pub enum FontType {
 /// # Google Fonts (default)
 /// [font]
 /// font = "Noto Sans"
-/// weight = "600"
+/// weight = "600"        # or "400,700" to load several
+/// italic = false
 /// font_type = "sans"
 ///
 /// # Local font (put the file in your assets directory)
@@ -539,9 +1263,14 @@ pub struct FontConfig {
     /// Font family name (Google Fonts family or custom name for local fonts).
     #[config(default = "Noto Sans")]
     pub font: String,
-    /// Font weight to load (e.g. `"600"`).
+    /// Font weight to load (e.g. `"600"`), or a comma-separated list
+    /// (`"400,700"`) to load several. The first one is the site's text
+    /// weight; the rest are available to custom CSS.
     #[config(default = "600")]
     pub weight: String,
+    /// Also load the italic style of every weight (Google Fonts only).
+    #[config(default = false)]
+    pub italic: bool,
     /// Font category: `"sans"` or `"serif"` — determines fallback fonts.
     #[config(default = "sans")]
     pub font_type: FontType,
@@ -550,6 +1279,47 @@ pub struct FontConfig {
     /// instead of loading from Google Fonts. The file should be placed in
     /// the assets directory so it gets copied to the output.
     pub source: Option<String>,
+    /// Metric overrides for the fallback font, to cut layout shift when
+    /// the web font swaps in.
+    #[config(nested)]
+    pub metric_overrides: MetricOverrides,
+}
+
+/// `@font-face` descriptors that reshape the local fallback font to match
+/// the web font's metrics, so text doesn't reflow when the web font swaps
+/// in. Each value is a CSS percentage (e.g. `"105%"`). When any is set, a
+/// `"{font} Fallback"` face is emitted and placed right after the web font
+/// in `--font-family`.
+///
+/// The right values depend on the web font; tools such as Capsize or
+/// `fontaine` compute them from the font files.
+#[derive(Config, Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[config(layer_attr(derive(Clone)))]
+#[config(layer_attr(serde(deny_unknown_fields)))]
+pub struct MetricOverrides {
+    /// Scale applied to the fallback's glyphs (`size-adjust`).
+    pub size_adjust: Option<String>,
+    /// Ascent above the baseline (`ascent-override`).
+    pub ascent_override: Option<String>,
+    /// Descent below the baseline (`descent-override`).
+    pub descent_override: Option<String>,
+    /// Line gap (`line-gap-override`).
+    pub line_gap_override: Option<String>,
+}
+
+impl MetricOverrides {
+    /// Every set descriptor paired with its CSS property name.
+    fn descriptors(&self) -> Vec<(&'static str, &str)> {
+        [
+            ("size-adjust", &self.size_adjust),
+            ("ascent-override", &self.ascent_override),
+            ("descent-override", &self.descent_override),
+            ("line-gap-override", &self.line_gap_override),
+        ]
+        .into_iter()
+        .filter_map(|(name, value)| value.as_deref().map(|v| (name, v)))
+        .collect()
+    }
 }
 
 impl FontConfig {
@@ -558,45 +1328,126 @@ impl FontConfig {
         self.source.is_some()
     }
 
+    /// The first weight listed in `weight` — the one applied to text.
+    pub fn primary_weight(&self) -> &str {
+        self.weight.split(',').next().unwrap_or_default().trim()
+    }
+
     /// Google Fonts stylesheet URL for use in a `<link>` element.
     /// Returns `None` for local fonts.
+    ///
+    /// Every listed weight is requested, in the ascending order the
+    /// css2 API requires. With `italic`, each weight is requested in both
+    /// styles via the `ital,wght` axis pair (`0,400;0,700;1,400;1,700`).
     pub fn stylesheet_url(&self) -> Option<String> {
         if self.is_local() {
             return None;
         }
         let family = self.font.replace(' ', "+");
+        let weights = parse_font_weights(&self.weight).unwrap_or_default();
+        let axes = match (weights.is_empty(), self.italic) {
+            (true, false) => String::new(),
+            (true, true) => ":ital@0;1".to_string(),
+            (false, false) => format!(":wght@{}", join_weights(&weights, None)),
+            (false, true) => format!(
+                ":ital,wght@{};{}",
+                join_weights(&weights, Some(0)),
+                join_weights(&weights, Some(1))
+            ),
+        };
         Some(format!(
-            "https://fonts.googleapis.com/css2?family={}:wght@{}&display=swap",
-            family, self.weight
+            "https://fonts.googleapis.com/css2?family={family}{axes}&display=swap"
         ))
     }
 
-    /// Generate `@font-face` CSS for a local font. Returns `None` for
-    /// Google Fonts.
-    pub fn font_face_css(&self) -> Option<String> {
+    /// Generate `@font-face` CSS for a local font, with the file URL
+    /// rooted at `base_path`. Returns `None` for Google Fonts.
+    pub fn font_face_css(&self, base_path: &str) -> Option<String> {
         let src = self.source.as_ref()?;
         let format = font_format_from_extension(src);
         Some(format!(
             r#"@font-face {{
     font-family: "{}";
-    src: url("/{}") format("{}");
+    src: url("{base_path}{}") format("{}");
     font-weight: {};
     font-display: swap;
 }}"#,
-            self.font, src, format, self.weight
+            self.font,
+            src,
+            format,
+            self.primary_weight()
         ))
     }
 
-    /// CSS `font-family` value with fallbacks based on `font_type`.
+    /// CSS `font-family` value with fallbacks based on `font_type`. With
+    /// metric overrides, the adjusted fallback face comes right after the
+    /// web font.
     pub fn font_family_css(&self) -> String {
         let fallbacks = match self.font_type {
             FontType::Serif => r#"Georgia, "Times New Roman", serif"#,
             FontType::Sans => "Helvetica, Verdana, sans-serif",
         };
-        format!(r#""{}", {}"#, self.font, fallbacks)
+        if self.metric_overrides.descriptors().is_empty() {
+            format!(r#""{}", {}"#, self.font, fallbacks)
+        } else {
+            format!(r#""{0}", "{0} Fallback", {1}"#, self.font, fallbacks)
+        }
+    }
+
+    /// `@font-face` for the metric-adjusted `"{font} Fallback"` face, built
+    /// on the first local fallback font for `font_type`. Returns `None`
+    /// when no overrides are set.
+    pub fn fallback_face_css(&self) -> Option<String> {
+        let descriptors = self.metric_overrides.descriptors();
+        if descriptors.is_empty() {
+            return None;
+        }
+        let local = match self.font_type {
+            FontType::Serif => r#"local("Georgia"), local("Times New Roman")"#,
+            FontType::Sans => r#"local("Helvetica"), local("Arial")"#,
+        };
+        let body: String = descriptors
+            .iter()
+            .map(|(name, value)| format!("\n    {name}: {value};"))
+            .collect();
+        Some(format!(
+            "@font-face {{\n    font-family: \"{} Fallback\";\n    src: {local};{body}\n}}",
+            self.font
+        ))
     }
 }
 
+/// Parse a comma-separated font weight list (`"400, 700"`) into ascending,
+/// de-duplicated weights. `None` if any entry isn't a whole number in
+/// 1–1000 (the CSS `font-weight` range).
+fn parse_font_weights(value: &str) -> Option<Vec<u32>> {
+    let mut weights = value
+        .split(',')
+        .map(|w| {
+            w.trim()
+                .parse::<u32>()
+                .ok()
+                .filter(|w| (1..=1000).contains(w))
+        })
+        .collect::<Option<Vec<_>>>()?;
+    weights.sort_unstable();
+    weights.dedup();
+    Some(weights)
+}
+
+/// Join weights with `;` for a css2 axis tuple list, each prefixed with
+/// `{ital},` when an italic axis value is given.
+fn join_weights(weights: &[u32], ital: Option<u8>) -> String {
+    weights
+        .iter()
+        .map(|w| match ital {
+            Some(ital) => format!("{ital},{w}"),
+            None => w.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(";")
+}
+
 /// Determine the CSS font format string from a file extension.
 fn font_format_from_extension(path: &str) -> &'static str {
     match path.rsplit('.').next().map(|e| e.to_lowercase()).as_deref() {
@@ -621,6 +1472,22 @@ pub struct ProcessingConfig {
     /// When absent, defaults to the number of CPU cores.
     /// Values larger than the core count are clamped down.
     pub max_processes: Option<usize>,
+    /// Workers for the I/O-bound pass before encoding: reading dimensions
+    /// and IPTC metadata and hashing sources for the cache. When absent,
+    /// defaults to twice the number of CPU cores. May exceed the core
+    /// count, since these workers mostly wait on the disk, but is capped
+    /// at [`IO_THREADS_PER_CORE_MAX`] times it.
+    pub io_threads: Option<usize>,
+    /// How the cache notices a changed source: `"hash"` reads and hashes
+    /// every source on every run; `"mtime"` reuses the last hash while the
+    /// file's size and modification time are unchanged.
+    #[config(default = "hash")]
+    pub cache_mode: ChangeDetection,
+    /// Longest source edge (in pixels) decoded at full resolution for every
+    /// output. Larger sources are downscaled once to this size and all
+    /// variants and thumbnails are made from that copy, bounding memory
+    /// for very large panoramas. When absent, there is no limit.
+    pub max_dimension: Option<u32>,
 }
 
 // =============================================================================
@@ -676,90 +1543,481 @@ pub struct AutoIndexingConfig {
     /// Zero-pad numeric prefix to this width. `0` means no padding.
     #[config(default = 3)]
     pub padding: u32,
+    /// `scan` and `check` warn when consecutive image numbers in an album
+    /// are further apart than this (`001` then `900` is usually a typo
+    /// for `009`). `0` turns the warning off.
+    #[config(default = 100)]
+    pub gap_warning: u32,
 }
 
-/// Resolve the effective thread count from config.
+// =============================================================================
+// Naming
+// =============================================================================
+
+/// How display titles are derived from file and directory names.
 ///
-/// - `None` → use all available cores
-/// - `Some(n)` → use `min(n, cores)` (user can constrain down, not up)
-pub fn effective_threads(config: &ProcessingConfig) -> usize {
-    let cores = std::thread::available_parallelism()
-        .map(|n| n.get())
-        .unwrap_or(1);
-    config.max_processes.map(|n| n.min(cores)).unwrap_or(cores)
+/// Only affects titles that fall back to the filename. Titles read from
+/// embedded IPTC metadata are explicit curation and are never rewritten.
+#[derive(Config, Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[config(layer_attr(derive(Clone)))]
+#[config(layer_attr(serde(deny_unknown_fields)))]
+pub struct NamingConfig {
+    /// Turn filename-derived image titles into title case: dashes and
+    /// underscores become spaces and each word is capitalized, so
+    /// `001-golden_gate-bridge.jpg` is titled "Golden Gate Bridge".
+    #[config(default = false)]
+    pub titleize: bool,
 }
 
 // =============================================================================
-// Loading: per-file partial layer + per-directory cascade helpers
+// Output paths
 // =============================================================================
 
-/// Confique-derived layer type for `SiteConfig`.
-///
-/// Every field is `Option<T>` so layers compose by overlay. The scan stage
-/// produces one of these per `config.toml` it finds and folds them together
-/// with [`with_fallback`][confique::Layer::with_fallback].
-pub type SiteConfigLayer = <SiteConfig as Config>::Layer;
+/// How image page directories are named inside an album.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ImagePageNames {
+    /// Position plus escaped title: `/album/02-my-title/`. Changes when an
+    /// image is renamed or reordered.
+    #[default]
+    IndexTitle,
+    /// Position only: `/album/02/`. Stable across title changes.
+    Index,
+    /// The slug from the image filename: `/album/my-title/`. Stable across
+    /// reordering. Images without a slug (`001.jpg`) fall back to `index`.
+    Slug,
+}
 
-/// Load a single sparse `config.toml` from `dir` into a layer.
-///
-/// Returns `Ok(None)` if the file is absent — caller decides whether that's
-/// an error. Returns `Err` for parse failures (with the original text
-/// retained for snippet rendering) and for unknown-key violations enforced
-/// by confique's strict deserializer.
-pub fn load_layer(dir: &Path) -> Result<Option<SiteConfigLayer>, ConfigError> {
-    let config_path = dir.join("config.toml");
-    if !config_path.exists() {
-        return Ok(None);
-    }
-    let content = fs::read_to_string(&config_path)?;
-    let layer: SiteConfigLayer = toml::from_str(&content).map_err(|e| ConfigError::Toml {
-        path: config_path.clone(),
-        source: Box::new(e),
-        source_text: content,
-    })?;
-    Ok(Some(layer))
+/// Which copy wins when a file in `assets_dir` has the same output path
+/// as a processed image (`[output] asset_precedence`). Both always win over
+/// the built-in PWA icons and service worker.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum AssetPrecedence {
+    /// The processed image, so pages show the photo they were built for.
+    #[default]
+    Processed,
+    /// The file from `assets_dir`.
+    Assets,
 }
 
-/// Load `config.toml` from `dir`, merge it onto confique defaults, and
-/// validate.
-///
-/// Used at the root of the cascade and by tests that exercise the full
-/// load → validate flow.
-pub fn load_config(dir: &Path) -> Result<SiteConfig, ConfigError> {
-    let user = load_layer(dir)?.unwrap_or_else(SiteConfigLayer::empty);
-    let merged = user.with_fallback(SiteConfigLayer::default_values());
-    let config = SiteConfig::from_layer(merged)?;
-    config.validate()?;
-    Ok(config)
+/// Settings for the shape of generated output paths.
+#[derive(Config, Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[config(layer_attr(derive(Clone)))]
+#[config(layer_attr(serde(deny_unknown_fields)))]
+pub struct OutputConfig {
+    /// Image page directory naming: `"index-title"` (`02-my-title/`),
+    /// `"index"` (`02/`), or `"slug"` (`my-title/`).
+    #[config(default = "index-title")]
+    pub image_page_names: ImagePageNames,
+    /// Transliterate image page slugs to plain ASCII, so a title like
+    /// "Café Déjà" gives `cafe-deja` rather than `café-déjà`.
+    #[config(default = false)]
+    pub ascii_slugs: bool,
+    /// Which file is kept when an asset and a processed image have the same
+    /// output path: `"processed"` or `"assets"`. The build warns either way.
+    #[config(default = "processed")]
+    pub asset_precedence: AssetPrecedence,
 }
 
-/// Build a `SiteConfig` from a single layer, merging in confique defaults
-/// for any unset fields. Validates before returning. Used by the scan
-/// stage's per-directory cascade after layers have been folded together.
-pub fn finalize_layer(layer: SiteConfigLayer) -> Result<SiteConfig, ConfigError> {
-    let merged = layer.with_fallback(SiteConfigLayer::default_values());
-    let config = SiteConfig::from_layer(merged)?;
-    config.validate()?;
-    Ok(config)
+// =============================================================================
+// PWA
+// =============================================================================
+
+/// How the installed web app is displayed (the manifest `display` member).
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum PwaDisplay {
+    /// Full screen, no browser UI at all.
+    Fullscreen,
+    /// Own window without browser controls, like a native app.
+    #[default]
+    Standalone,
+    /// Own window with a minimal set of navigation controls.
+    MinimalUi,
+    /// A regular browser tab.
+    Browser,
 }
 
-/// Static metadata for the `SiteConfig` schema. Re-exported so the
-/// [`crate`] CLI can hand it to clapfig's schema-emitting subcommand
-/// without having to depend on confique itself.
-pub fn site_config_meta() -> &'static Meta {
-    &<SiteConfig as Config>::META
+/// Installable web app (PWA) settings. The manifest's `scope` and
+/// `start_url` follow `base_path`.
+#[derive(Config, Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[config(layer_attr(derive(Clone)))]
+#[config(layer_attr(serde(deny_unknown_fields)))]
+pub struct PwaConfig {
+    /// Display mode of the installed app: `"standalone"`, `"fullscreen"`,
+    /// `"minimal-ui"` or `"browser"`.
+    #[config(default = "standalone")]
+    pub display: PwaDisplay,
 }
 
-// =============================================================================
-// CSS generators (consumers of resolved config — not config plumbing)
-// =============================================================================
+/// Markdown content page settings.
+#[derive(Config, Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[config(layer_attr(derive(Clone)))]
+#[config(layer_attr(serde(deny_unknown_fields)))]
+pub struct PagesConfig {
+    /// Render a table of contents, linking to each `##`/`###` heading, at
+    /// the top of every content page. A page's `toc` front-matter key
+    /// overrides this.
+    #[config(default = false)]
+    pub toc: bool,
+    /// Syntax-highlight fenced code blocks that name their language
+    /// (```` ```rust ````) at build time, as inline-styled HTML. Needs the
+    /// `highlight` Cargo feature (on by default).
+    #[config(default = false)]
+    pub highlight_code: bool,
+}
 
-/// Generate CSS custom properties from color config.
-///
-/// These `generate_*_css()` functions produce `:root { … }` blocks that are
-/// prepended to the inline `<style>` in every page. The Google Font is
-/// loaded separately via a `<link>` tag (see `FontConfig::stylesheet_url`
-/// and `base_document` in `generate.rs`). Variables defined here are
+/// Album download settings.
+#[derive(Config, Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[config(layer_attr(derive(Clone)))]
+#[config(layer_attr(serde(deny_unknown_fields)))]
+pub struct DownloadConfig {
+    /// Write an `album.zip` of the album's images next to its page and
+    /// link it from the page as "Download album".
+    #[config(default = false)]
+    pub album_zip: bool,
+    /// What goes in the zip: `"web"` for the largest generated AVIF of each
+    /// image, `"originals"` for the source files themselves.
+    #[config(default = "web")]
+    pub contents: ZipContents,
+}
+
+/// Album page settings.
+#[derive(Config, Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[config(layer_attr(derive(Clone)))]
+#[config(layer_attr(serde(deny_unknown_fields)))]
+pub struct AlbumsConfig {
+    /// For an album with no `description.md` / `description.txt`, use the
+    /// IPTC caption of its preview image as the album description.
+    #[config(default = false)]
+    pub description_from_iptc: bool,
+}
+
+/// Title and description set in an album's own `config.toml`, for content
+/// generated by other tools. Album-local: the scan stage never cascades
+/// this section into subdirectories, and ignores it outside albums.
+#[derive(Config, Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[config(layer_attr(derive(Clone)))]
+#[config(layer_attr(serde(deny_unknown_fields)))]
+pub struct AlbumConfig {
+    /// Album title. Overrides the one derived from the directory name.
+    pub title: Option<String>,
+    /// Album description as plain text, formatted like `description.txt`.
+    /// Overrides `description.md` / `description.txt`.
+    pub description: Option<String>,
+}
+
+/// Which metadata source wins for image titles and descriptions. The
+/// other source is still used when the preferred one is empty.
+#[derive(Config, Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[config(layer_attr(derive(Clone)))]
+#[config(layer_attr(serde(deny_unknown_fields)))]
+pub struct MetadataConfig {
+    /// Preferred title source: `"iptc"` (the embedded Object Name) or
+    /// `"filename"` (the title from the `NNN-name` file name).
+    #[config(default = "iptc")]
+    pub title_source: TitleSource,
+    /// Preferred description source: `"sidecar"` (the image's `.txt` file)
+    /// or `"iptc"` (the embedded caption).
+    #[config(default = "sidecar")]
+    pub description_source: DescriptionSource,
+}
+
+/// How a directory's albums and groups are ordered in the navigation and
+/// on its gallery page. Set in a group's `config.toml` to reorder its
+/// children; like every section it cascades into nested groups.
+#[derive(Config, Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[config(layer_attr(derive(Clone)))]
+#[config(layer_attr(serde(deny_unknown_fields)))]
+pub struct OrderingConfig {
+    /// `"number"` orders children by their `NNN-` prefix, unnumbered ones
+    /// last; `"title"` orders them alphabetically by display title.
+    #[config(default = "number")]
+    pub sort: SortOrder,
+}
+
+/// Links to translations of the site maintained as separate builds.
+#[derive(Config, Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[config(layer_attr(derive(Clone)))]
+#[config(layer_attr(serde(deny_unknown_fields)))]
+pub struct I18nConfig {
+    /// Language code (e.g. `"fr"`, `"pt-BR"`) to the absolute base URL of
+    /// the site in that language. Every page links to the same path on each
+    /// with `<link rel="alternate" hreflang>`, so the builds should share
+    /// their album structure. List this site's own language too.
+    #[config(default = {})]
+    pub alternates: BTreeMap<String, String>,
+}
+
+/// Client-side search settings.
+#[derive(Config, Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[config(layer_attr(derive(Clone)))]
+#[config(layer_attr(serde(deny_unknown_fields)))]
+pub struct SearchConfig {
+    /// Write `search-index.json` (titles, descriptions and URLs of every
+    /// listed album and image) and add a search box to the home page that
+    /// filters it in the browser.
+    #[config(default = false)]
+    pub enabled: bool,
+}
+
+/// Order of a directory's children (`[ordering] sort`).
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    /// By numeric prefix.
+    #[default]
+    Number,
+    /// Alphabetically by display title, ignoring case.
+    Title,
+}
+
+/// How the process stage decides whether a source changed since the
+/// last run (`[processing] cache_mode`).
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeDetection {
+    /// Hash the full contents of every source.
+    #[default]
+    Hash,
+    /// Trust an unchanged size and mtime; hash only when either differs.
+    Mtime,
+}
+
+/// Where an image title is taken from first.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TitleSource {
+    /// IPTC Object Name, falling back to the file name.
+    #[default]
+    Iptc,
+    /// The file name, falling back to IPTC Object Name.
+    Filename,
+}
+
+/// Where an image description is taken from first.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum DescriptionSource {
+    /// The sidecar `.txt` file, falling back to the IPTC caption.
+    #[default]
+    Sidecar,
+    /// The IPTC caption, falling back to the sidecar `.txt` file.
+    Iptc,
+}
+
+/// Which files an album zip holds.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ZipContents {
+    /// The largest generated AVIF of each image.
+    #[default]
+    Web,
+    /// The source files, as they are in the content directory.
+    Originals,
+}
+
+/// Resolve the effective thread count from config.
+///
+/// - `None` → use all available cores
+/// - `Some(n)` → use `min(n, cores)` (user can constrain down, not up)
+pub fn effective_threads(config: &ProcessingConfig) -> usize {
+    let cores = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1);
+    config.max_processes.map(|n| n.min(cores)).unwrap_or(cores)
+}
+
+/// Most I/O-pass workers per core. Past a few per core, more workers only
+/// add contention (see the processing docs for measurements).
+pub const IO_THREADS_PER_CORE_MAX: usize = 8;
+
+/// Resolve the thread count of the I/O pass from config.
+///
+/// - `None` → twice the available cores
+/// - `Some(n)` → `n`, at least 1 and at most
+///   [`IO_THREADS_PER_CORE_MAX`] times the available cores
+pub fn effective_io_threads(config: &ProcessingConfig) -> usize {
+    let cores = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1);
+    config
+        .io_threads
+        .unwrap_or(cores * 2)
+        .clamp(1, cores * IO_THREADS_PER_CORE_MAX)
+}
+
+// =============================================================================
+// Loading: per-file partial layer + per-directory cascade helpers
+// =============================================================================
+
+/// Confique-derived layer type for `SiteConfig`.
+///
+/// Every field is `Option<T>` so layers compose by overlay. The scan stage
+/// produces one of these per `config.toml` it finds and folds them together
+/// with [`with_fallback`][confique::Layer::with_fallback].
+pub type SiteConfigLayer = <SiteConfig as Config>::Layer;
+
+/// Load a single sparse `config.toml` from `dir` into a layer.
+///
+/// Returns `Ok(None)` if the file is absent — caller decides whether that's
+/// an error. Returns `Err` for parse failures (with the original text
+/// retained for snippet rendering) and for unknown-key violations enforced
+/// by confique's strict deserializer.
+pub fn load_layer(dir: &Path) -> Result<Option<SiteConfigLayer>, ConfigError> {
+    let config_path = dir.join("config.toml");
+    if !config_path.exists() {
+        return Ok(None);
+    }
+    let content = fs::read_to_string(&config_path)?;
+    let layer: SiteConfigLayer = toml::from_str(&content).map_err(|e| ConfigError::Toml {
+        path: config_path.clone(),
+        source: Box::new(e),
+        source_text: content,
+    })?;
+    Ok(Some(layer))
+}
+
+/// Layer a directory's `local` config over the `inherited` cascade.
+///
+/// Every key overlays as in [`with_fallback`][confique::Layer::with_fallback]
+/// except `[images] exclude_sizes`, which subtracts from the inherited
+/// `sizes` (or this directory's own) instead. The result carries the
+/// reduced `sizes` and no exclusion, so subdirectories inherit the smaller
+/// list and exclusions accumulate down the tree.
+pub fn cascade_layer(
+    mut local: SiteConfigLayer,
+    inherited: &SiteConfigLayer,
+) -> Result<SiteConfigLayer, ConfigError> {
+    if let Some(exclude) = local.images.exclude_sizes.take()
+        && !exclude.is_empty()
+    {
+        let base = local
+            .images
+            .sizes
+            .clone()
+            .or_else(|| inherited.images.sizes.clone())
+            .or_else(|| SiteConfigLayer::default_values().images.sizes)
+            .expect("images.sizes has a default");
+        let ImageSizes::Explicit(mut sizes) = base else {
+            return Err(ConfigError::Validation(
+                "images.exclude_sizes can't be used with images.sizes = \"auto\"".into(),
+            ));
+        };
+        sizes.retain(|size| !exclude.contains(size));
+        if sizes.is_empty() {
+            return Err(ConfigError::Validation(format!(
+                "images.exclude_sizes = {exclude:?} removes every size"
+            )));
+        }
+        local.images.sizes = Some(ImageSizes::Explicit(sizes));
+    }
+    Ok(local.with_fallback(inherited.clone()))
+}
+
+/// Layer built from the `SIMPLE_GAL_*` environment variables that override
+/// root-level string settings at build time, e.g. a per-environment
+/// `base_url` in CI:
+///
+/// | Variable                | Key          |
+/// | ----------------------- | ------------ |
+/// | `SIMPLE_GAL_SITE_TITLE` | `site_title` |
+/// | `SIMPLE_GAL_HOME_LABEL` | `home_label` |
+/// | `SIMPLE_GAL_BASE_URL`   | `base_url`   |
+/// | `SIMPLE_GAL_BASE_PATH`  | `base_path`  |
+///
+/// `lookup` reads one variable; unset and empty variables leave the key
+/// unset so the file cascade decides.
+pub fn env_layer(lookup: impl Fn(&str) -> Option<String>) -> SiteConfigLayer {
+    let var = |name: &str| lookup(name).filter(|value| !value.is_empty());
+    let mut layer = SiteConfigLayer::empty();
+    layer.site_title = var("SIMPLE_GAL_SITE_TITLE");
+    layer.home_label = var("SIMPLE_GAL_HOME_LABEL");
+    layer.base_url = var("SIMPLE_GAL_BASE_URL");
+    layer.base_path = var("SIMPLE_GAL_BASE_PATH");
+    layer
+}
+
+/// Preset applied by `simple-gal build --draft` for fast layout previews:
+/// low AVIF quality, a single 800px responsive size, and no thumbnail
+/// sharpening. Merged like command-line overrides, below the explicit
+/// flags. The changed encoding parameters give every draft output its own
+/// cache key, so a later full build re-encodes instead of reusing them.
+pub fn draft_layer() -> SiteConfigLayer {
+    let mut layer = SiteConfigLayer::empty();
+    layer.images.quality = Some(40);
+    layer.images.sizes = Some(ImageSizes::Explicit(vec![800]));
+    layer.images.default_width = Some(800);
+    layer.thumbnails.sharpen = Some(false);
+    layer
+}
+
+/// A fully-commented `config.toml` listing every option with its stock
+/// default — the same template `simple-gal config gen` writes. Every key
+/// is commented out, so the file changes nothing until an option is
+/// uncommented.
+pub fn stock_config_toml() -> String {
+    confique::toml::template::<SiteConfig>(confique::toml::FormatOptions::default())
+}
+
+/// Load `config.toml` from `dir`, merge it onto confique defaults, and
+/// validate.
+///
+/// Used at the root of the cascade and by tests that exercise the full
+/// load → validate flow.
+pub fn load_config(dir: &Path) -> Result<SiteConfig, ConfigError> {
+    finalize_layer(load_layer(dir)?.unwrap_or_else(SiteConfigLayer::empty))
+}
+
+/// Build a `SiteConfig` from a single layer, merging in confique defaults
+/// for any unset fields. Validates before returning. Used by the scan
+/// stage's per-directory cascade after layers have been folded together.
+///
+/// [Environment overrides](env_layer) sit on top of `layer`, so they beat
+/// every `config.toml` in the cascade.
+pub fn finalize_layer(layer: SiteConfigLayer) -> Result<SiteConfig, ConfigError> {
+    finalize_layer_with(layer, &SiteConfigLayer::empty())
+}
+
+/// [`finalize_layer`] with command-line `overrides` merged last, above the
+/// environment overrides. Validation still runs on the result, so an
+/// out-of-range override is rejected like a bad `config.toml` value.
+///
+/// Both `layer` and `overrides` go through [`cascade_layer`], so an
+/// `exclude_sizes` that was never folded (or one passed with `--set`)
+/// still subtracts from the sizes below it.
+pub fn finalize_layer_with(
+    layer: SiteConfigLayer,
+    overrides: &SiteConfigLayer,
+) -> Result<SiteConfig, ConfigError> {
+    let layer = cascade_layer(layer, &SiteConfigLayer::empty())?;
+    let base = env_layer(|name| std::env::var(name).ok()).with_fallback(layer);
+    let merged =
+        cascade_layer(overrides.clone(), &base)?.with_fallback(SiteConfigLayer::default_values());
+    let mut config = SiteConfig::from_layer(merged)?;
+    config.normalize();
+    config.validate()?;
+    Ok(config)
+}
+
+/// Static metadata for the `SiteConfig` schema. Re-exported so the
+/// [`crate`] CLI can hand it to clapfig's schema-emitting subcommand
+/// without having to depend on confique itself.
+pub fn site_config_meta() -> &'static Meta {
+    &<SiteConfig as Config>::META
+}
+
+// =============================================================================
+// CSS generators (consumers of resolved config — not config plumbing)
+// =============================================================================
+
+/// Generate CSS custom properties from color config.
+///
+/// These `generate_*_css()` functions produce `:root { … }` blocks that are
+/// prepended to the inline `<style>` in every page. The Google Font is
+/// loaded separately via a `<link>` tag (see `FontConfig::stylesheet_url`
+/// and `base_document` in `generate.rs`). Variables defined here are
 /// consumed by `static/style.css`; do not redefine them there.
 pub fn generate_color_css(colors: &ColorConfig) -> String {
     format!(
@@ -803,36 +2061,111 @@ pub fn generate_color_css(colors: &ColorConfig) -> String {
 
 /// Generate CSS custom properties from theme config.
 pub fn generate_theme_css(theme: &ThemeConfig) -> String {
-    format!(
+    let vars = format!(
         r#":root {{
     --mat-x: {mat_x};
     --mat-y: {mat_y};
     --thumbnail-gap: {thumbnail_gap};
     --grid-padding: {grid_padding};
+    --image-max-width: {image_max_width};
+    --frame-background: {frame_background};
+    --frame-border: {frame_border};
+    --frame-shadow: {frame_shadow};{grid_columns}
 }}"#,
         mat_x = theme.mat_x.to_css(),
         mat_y = theme.mat_y.to_css(),
         thumbnail_gap = theme.thumbnail_gap,
         grid_padding = theme.grid_padding,
-    )
+        image_max_width = theme.image_max_width,
+        frame_background = theme.frame.background,
+        frame_border = theme.frame.border,
+        frame_shadow = theme.frame.shadow,
+        // Only set for a fixed count: style.css falls back to each grid's
+        // own responsive auto-fill tracks when the variable is undefined.
+        grid_columns = match theme.grid_columns {
+            GridColumns::Auto => String::new(),
+            GridColumns::Fixed(n) => {
+                format!("\n    --grid-columns: repeat({n}, minmax(0, 1fr));")
+            }
+        },
+    );
+    // Media queries can't read custom properties, so the bar layout is
+    // emitted as rules, wrapped in the breakpoint query for "auto".
+    match theme.nav_style {
+        NavStyle::Hamburger => vars,
+        NavStyle::Bar => format!("{vars}\n\n{NAV_BAR_CSS}"),
+        NavStyle::Auto => {
+            let rules = NAV_BAR_CSS.replace('\n', "\n    ");
+            format!(
+                "{vars}\n\n@media (min-width: {}px) {{\n    {rules}\n}}",
+                theme.nav_breakpoint
+            )
+        }
+    }
 }
 
+/// Rules that turn the slide-in nav panel into a horizontal bar of the
+/// top-level entries (`[theme] nav_style`). Group children stay out of the
+/// bar; each group links to its gallery page. Prefixed with `.site-header`
+/// to outrank the panel rules in `style.css`, which come later.
+const NAV_BAR_CSS: &str = "\
+.site-header .nav-hamburger,
+.site-header .nav-close,
+.site-header .site-nav ul ul {
+    display: none;
+}
+.site-header .nav-panel {
+    position: static;
+    width: auto;
+    max-width: none;
+    height: auto;
+    padding: 0;
+    background: none;
+    border: none;
+    box-shadow: none;
+    transform: none;
+    transition: none;
+    overflow: visible;
+}
+.site-header .nav-panel > ul {
+    display: flex;
+    align-items: center;
+    gap: 1.5rem;
+}
+.site-header .site-nav li {
+    padding: 0;
+}
+.site-header .site-nav .nav-group {
+    margin-bottom: 0;
+}
+.site-header .site-nav .nav-separator {
+    width: 1px;
+    height: 1em;
+    margin: 0;
+}";
+
 /// Generate CSS custom properties from font config.
 ///
-/// For local fonts, also includes the `@font-face` declaration.
-pub fn generate_font_css(font: &FontConfig) -> String {
+/// For local fonts, also includes the `@font-face` declaration; with
+/// metric overrides, the adjusted fallback face too.
+pub fn generate_font_css(font: &FontConfig, base_path: &str) -> String {
     let vars = format!(
         r#":root {{
     --font-family: {family};
     --font-weight: {weight};
 }}"#,
         family = font.font_family_css(),
-        weight = font.weight,
+        weight = font.primary_weight(),
     );
-    match font.font_face_css() {
-        Some(face) => format!("{}\n\n{}", face, vars),
-        None => vars,
-    }
+    [
+        font.font_face_css(base_path),
+        font.fallback_face_css(),
+        Some(vars),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>()
+    .join("\n\n")
 }
 
 #[cfg(test)]
@@ -863,7 +2196,10 @@ mod tests {
     fn default_config_has_image_settings() {
         let config = SiteConfig::default();
         assert_eq!(config.thumbnails.aspect_ratio, [4, 5]);
-        assert_eq!(config.images.sizes, vec![800, 1400, 2080]);
+        assert_eq!(
+            config.images.sizes,
+            ImageSizes::Explicit(vec![800, 1400, 2080])
+        );
         assert_eq!(config.images.quality, 90);
         assert_eq!(config.theme.mat_x.to_css(), "clamp(1rem, 3vw, 2.5rem)");
         assert_eq!(config.theme.mat_y.to_css(), "clamp(2rem, 6vw, 5rem)");
@@ -931,7 +2267,10 @@ background = "#fafafa"
         assert_eq!(config.colors.light.text, "#111111");
         assert_eq!(config.colors.dark.background, "#000000");
         // Unrelated section defaults preserved
-        assert_eq!(config.images.sizes, vec![800, 1400, 2080]);
+        assert_eq!(
+            config.images.sizes,
+            ImageSizes::Explicit(vec![800, 1400, 2080])
+        );
     }
 
     #[test]
@@ -950,11 +2289,35 @@ quality = 85
         );
         let config = load_config(tmp.path()).unwrap();
         assert_eq!(config.thumbnails.aspect_ratio, [1, 1]);
-        assert_eq!(config.images.sizes, vec![400, 800]);
+        assert_eq!(config.images.sizes, ImageSizes::Explicit(vec![400, 800]));
         assert_eq!(config.images.quality, 85);
         assert_eq!(config.colors.light.background, "#ffffff");
     }
 
+    #[test]
+    fn parse_auto_image_sizes() {
+        let tmp = TempDir::new().unwrap();
+        write_config(tmp.path(), "[images]\nsizes = \"auto\"\n");
+        let config = load_config(tmp.path()).unwrap();
+        assert_eq!(config.images.sizes, ImageSizes::Auto);
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn parse_unknown_image_sizes_string_fails() {
+        let tmp = TempDir::new().unwrap();
+        write_config(tmp.path(), "[images]\nsizes = \"large\"\n");
+        assert!(load_config(tmp.path()).is_err());
+    }
+
+    #[test]
+    fn validate_max_dimension_ignores_auto_sizes() {
+        let mut config = SiteConfig::default();
+        config.images.sizes = ImageSizes::Auto;
+        config.processing.max_dimension = Some(1000);
+        assert!(config.validate().is_ok());
+    }
+
     #[test]
     fn parse_full_index_settings() {
         let tmp = TempDir::new().unwrap();
@@ -1078,9 +2441,31 @@ grid_padding = "1rem"
     #[test]
     fn parse_processing_config() {
         let tmp = TempDir::new().unwrap();
-        write_config(tmp.path(), "[processing]\nmax_processes = 4\n");
+        write_config(
+            tmp.path(),
+            "[processing]\nmax_processes = 4\nio_threads = 16\n",
+        );
         let config = load_config(tmp.path()).unwrap();
         assert_eq!(config.processing.max_processes, Some(4));
+        assert_eq!(config.processing.io_threads, Some(16));
+        assert_eq!(config.processing.max_dimension, None);
+        assert_eq!(config.processing.cache_mode, ChangeDetection::Hash);
+    }
+
+    #[test]
+    fn parse_processing_cache_mode() {
+        let tmp = TempDir::new().unwrap();
+        write_config(tmp.path(), "[processing]\ncache_mode = \"mtime\"\n");
+        let config = load_config(tmp.path()).unwrap();
+        assert_eq!(config.processing.cache_mode, ChangeDetection::Mtime);
+    }
+
+    #[test]
+    fn parse_processing_max_dimension() {
+        let tmp = TempDir::new().unwrap();
+        write_config(tmp.path(), "[processing]\nmax_dimension = 8000\n");
+        let config = load_config(tmp.path()).unwrap();
+        assert_eq!(config.processing.max_dimension, Some(8000));
     }
 
     #[test]
@@ -1226,6 +2611,76 @@ text = "#abcdef"
         assert_eq!(config.colors.dark.background, "#000000");
     }
 
+    #[test]
+    fn env_layer_overrides_file_values() {
+        let tmp = TempDir::new().unwrap();
+        write_config(
+            tmp.path(),
+            "site_title = \"From File\"\nbase_url = \"https://file.example\"\n",
+        );
+        let file = load_layer(tmp.path()).unwrap().unwrap();
+        let env = env_layer(|name| match name {
+            "SIMPLE_GAL_SITE_TITLE" => Some("From Env".into()),
+            "SIMPLE_GAL_BASE_PATH" => Some("/staging".into()),
+            _ => None,
+        });
+
+        let config = finalize_layer(env.with_fallback(file)).unwrap();
+        assert_eq!(config.site_title, "From Env");
+        assert_eq!(config.base_path, "/staging/");
+        assert_eq!(config.base_url.as_deref(), Some("https://file.example"));
+    }
+
+    #[test]
+    fn unset_or_empty_env_leaves_file_values() {
+        let tmp = TempDir::new().unwrap();
+        write_config(tmp.path(), "site_title = \"From File\"\n");
+        let file = load_layer(tmp.path()).unwrap().unwrap();
+        let env = env_layer(|name| (name == "SIMPLE_GAL_SITE_TITLE").then(String::new));
+
+        let config = finalize_layer(env.with_fallback(file)).unwrap();
+        assert_eq!(config.site_title, "From File");
+        assert_eq!(config.home_label, None);
+    }
+
+    #[test]
+    fn overrides_beat_file_and_are_validated() {
+        let tmp = TempDir::new().unwrap();
+        write_config(tmp.path(), "[images]\nquality = 85\n");
+        let file = load_layer(tmp.path()).unwrap().unwrap();
+
+        let mut overrides = SiteConfigLayer::empty();
+        overrides.images.quality = Some(50);
+        let config = finalize_layer_with(file.clone(), &overrides).unwrap();
+        assert_eq!(config.images.quality, 50);
+
+        overrides.images.quality = Some(150);
+        assert!(matches!(
+            finalize_layer_with(file, &overrides),
+            Err(ConfigError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn draft_layer_reduces_quality_and_sizes() {
+        let tmp = TempDir::new().unwrap();
+        write_config(tmp.path(), "[images]\nsizes = [1200, 2400]\nquality = 95\n");
+        let file = load_layer(tmp.path()).unwrap().unwrap();
+
+        let config = finalize_layer_with(file, &draft_layer()).unwrap();
+        assert_eq!(config.images.quality, 40);
+        assert_eq!(config.images.sizes, ImageSizes::Explicit(vec![800]));
+        assert_eq!(config.images.default_width, 800);
+        assert!(!config.thumbnails.sharpen);
+    }
+
+    #[test]
+    fn stock_config_toml_loads_as_defaults() {
+        let tmp = TempDir::new().unwrap();
+        write_config(tmp.path(), &stock_config_toml());
+        assert_eq!(load_config(tmp.path()).unwrap(), SiteConfig::default());
+    }
+
     #[test]
     fn load_config_invalid_toml_is_error() {
         let tmp = TempDir::new().unwrap();
@@ -1253,28 +2708,297 @@ text = "#abcdef"
     // ----- validate() unit checks -----
 
     #[test]
-    fn validate_quality_boundary_ok() {
+    fn validate_highlight_code_matches_the_highlight_feature() {
         let mut config = SiteConfig::default();
-        config.images.quality = 100;
-        assert!(config.validate().is_ok());
-        config.images.quality = 0;
-        assert!(config.validate().is_ok());
+        config.pages.highlight_code = true;
+        assert_eq!(config.validate().is_ok(), cfg!(feature = "highlight"));
     }
 
     #[test]
-    fn validate_quality_too_high() {
+    fn validate_base_path() {
         let mut config = SiteConfig::default();
-        config.images.quality = 101;
-        let err = config.validate().unwrap_err();
-        assert!(err.to_string().contains("quality"));
+        assert_eq!(config.base_path, "/");
+        for ok in ["/", "/gallery/", "/photos/2024_trip/"] {
+            config.base_path = ok.to_string();
+            assert!(config.validate().is_ok(), "{ok:?} should be valid");
+        }
+        for bad in ["", "gallery/", "/gallery", "//", "/my gallery/", "/a'b/"] {
+            config.base_path = bad.to_string();
+            assert!(config.validate().is_err(), "{bad:?} should be rejected");
+        }
     }
 
     #[test]
-    fn validate_aspect_ratio_zero() {
+    fn i18n_alternates_load_and_must_be_absolute() {
+        let tmp = TempDir::new().unwrap();
+        write_config(
+            tmp.path(),
+            "[i18n]\nalternates = { en = \"https://example.com/\", fr = \"https://example.com/fr/\" }\n",
+        );
+        let config = load_config(tmp.path()).unwrap();
+        assert_eq!(
+            config.i18n.alternates.get("fr").map(String::as_str),
+            Some("https://example.com/fr/")
+        );
+        assert!(SiteConfig::default().i18n.alternates.is_empty());
+
         let mut config = SiteConfig::default();
-        config.thumbnails.aspect_ratio = [0, 5];
+        config
+            .i18n
+            .alternates
+            .insert("fr".to_string(), "/fr/".to_string());
         assert!(config.validate().is_err());
-        config.thumbnails.aspect_ratio = [4, 0];
+    }
+
+    #[test]
+    fn site_url_joins_base_url_and_base_path() {
+        let site_url = |base_url: Option<&str>, base_path: &str| {
+            SiteConfig {
+                base_url: base_url.map(str::to_string),
+                base_path: base_path.to_string(),
+                ..SiteConfig::default()
+            }
+            .site_url()
+        };
+        assert_eq!(site_url(None, "/portfolio/"), None);
+        assert_eq!(
+            site_url(Some("https://example.com/"), "/"),
+            Some("https://example.com".to_string())
+        );
+        assert_eq!(
+            site_url(Some("https://user.github.io"), "/portfolio/"),
+            Some("https://user.github.io/portfolio".to_string())
+        );
+        assert_eq!(
+            site_url(Some("https://user.github.io/portfolio/"), "/portfolio/"),
+            Some("https://user.github.io/portfolio".to_string())
+        );
+    }
+
+    #[test]
+    fn load_config_adds_base_path_trailing_slash() {
+        let tmp = TempDir::new().unwrap();
+        write_config(tmp.path(), "base_path = \"/portfolio\"\n");
+        let config = load_config(tmp.path()).unwrap();
+        assert_eq!(config.base_path, "/portfolio/");
+    }
+
+    #[test]
+    fn album_file_extensions() {
+        let mut config = SiteConfig::default();
+        assert!(config.is_album_file("prices.pdf"));
+        assert!(config.is_album_file("PRICES.PDF"));
+        assert!(!config.is_album_file("notes.txt"));
+        assert!(!config.is_album_file("pdf"));
+        config.album_file_extensions = vec!["zip".into(), "txt".into()];
+        assert!(config.validate().is_ok());
+        assert!(config.is_album_file("notes.txt"));
+        for bad in ["", ".pdf", "tar.gz"] {
+            config.album_file_extensions = vec![bad.into()];
+            assert!(config.validate().is_err(), "{bad:?} should be rejected");
+        }
+    }
+
+    #[test]
+    fn parse_pwa_display() {
+        let tmp = TempDir::new().unwrap();
+        write_config(tmp.path(), "[pwa]\ndisplay = \"minimal-ui\"\n");
+        let config = load_config(tmp.path()).unwrap();
+        assert_eq!(config.pwa.display, PwaDisplay::MinimalUi);
+        assert_eq!(SiteConfig::default().pwa.display, PwaDisplay::Standalone);
+    }
+
+    #[test]
+    fn validate_default_width_non_zero() {
+        let mut config = SiteConfig::default();
+        assert_eq!(config.images.default_width, 1400);
+        config.images.default_width = 0;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn validate_max_dimension_below_largest_size() {
+        let mut config = SiteConfig::default();
+        config.processing.max_dimension = Some(2080);
+        assert!(config.validate().is_ok());
+        config.processing.max_dimension = Some(2079);
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("processing.max_dimension"), "{err}");
+    }
+
+    #[test]
+    fn validate_max_dimension_checks_thumbnail_long_edge() {
+        let mut config = SiteConfig::default();
+        config.images.sizes = ImageSizes::Explicit(vec![400]);
+        config.thumbnails.size = 400;
+        // 4:5 at a 400px short edge is 400x500.
+        config.processing.max_dimension = Some(500);
+        assert!(config.validate().is_ok());
+        config.processing.max_dimension = Some(499);
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("(500)"), "{err}");
+
+        config.thumbnails.aspect_ratio = [16, 9];
+        config.processing.max_dimension = Some(710);
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("(711)"), "{err}");
+    }
+
+    #[test]
+    fn css_color_accepts_valid_forms() {
+        for color in [
+            "#fff",
+            "#ffff",
+            "#1a2B3c",
+            "#1a2b3c80",
+            "rgb(10, 20, 30)",
+            "rgba(10 20 30 / 50%)",
+            "hsl(210deg 40% 50%)",
+            "HSLA(210, 40%, 50%, 0.5)",
+            "oklch(0.7 0.1 250)",
+            "rebeccapurple",
+            "White",
+            "transparent",
+        ] {
+            assert!(is_css_color(color), "{color}");
+        }
+    }
+
+    #[test]
+    fn css_color_rejects_invalid_forms() {
+        for color in [
+            "",
+            "#ff",
+            "#fffff",
+            "#ggg",
+            "fff",
+            "blu",
+            "rgb()",
+            "rgb(1, 2, 3",
+            "rgb(1, 2, 3); color: red",
+            "url(evil)",
+            "red;",
+        ] {
+            assert!(!is_css_color(color), "{color}");
+        }
+    }
+
+    #[test]
+    fn validate_rejects_invalid_color_naming_key() {
+        let mut config = SiteConfig::default();
+        config.colors.dark.link_hover = "blu".to_string();
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("colors.dark.link_hover"), "{err}");
+        assert!(err.contains("\"blu\""), "{err}");
+        assert!(err.contains("not a valid CSS color"), "{err}");
+
+        config.colors.dark.link_hover = "#ff".to_string();
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("colors.dark.link_hover"), "{err}");
+    }
+
+    #[test]
+    fn validate_accepts_named_and_functional_colors() {
+        let mut config = SiteConfig::default();
+        config.colors.light.background = "ivory".to_string();
+        config.colors.light.link = "rgb(20 40 200)".to_string();
+        config.colors.dark.text = "hsl(0 0% 98%)".to_string();
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn is_css_length_accepts_units_and_math_functions() {
+        for length in [
+            "0",
+            "0.2rem",
+            "3vw",
+            "10px",
+            "50%",
+            ".5em",
+            "-1rem",
+            "2.5REM",
+            " 4dvh ",
+            "calc(100% - 2rem)",
+            "clamp(1rem, 3vw, 2.5rem)",
+            "min(2rem, calc(1vw * 4))",
+        ] {
+            assert!(is_css_length(length), "{length}");
+        }
+    }
+
+    #[test]
+    fn is_css_length_rejects_malformed_values() {
+        for length in [
+            "",
+            "3v",
+            "1reem",
+            "rem",
+            "2",
+            "1.2.3rem",
+            "1 rem",
+            "calc()",
+            "calc(1rem",
+            "calc(1rem))",
+            "calc(1rem); color: red",
+            "url(evil)",
+        ] {
+            assert!(!is_css_length(length), "{length}");
+        }
+    }
+
+    #[test]
+    fn validate_rejects_invalid_length_naming_key() {
+        let mut config = SiteConfig::default();
+        config.theme.mat_x.size = "3v".to_string();
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("theme.mat_x.size"), "{err}");
+        assert!(err.contains("\"3v\""), "{err}");
+        assert!(err.contains("not a valid CSS length"), "{err}");
+
+        let mut config = SiteConfig::default();
+        config.theme.grid_padding = "1reem".to_string();
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("theme.grid_padding"), "{err}");
+
+        let mut config = SiteConfig::default();
+        config.full_index.thumb_gap = "gap".to_string();
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("full_index.thumb_gap"), "{err}");
+    }
+
+    #[test]
+    fn validate_accepts_lengths_and_calc() {
+        let mut config = SiteConfig::default();
+        config.theme.mat_y.min = "0".to_string();
+        config.theme.mat_y.size = "calc(2vh + 1rem)".to_string();
+        config.theme.thumbnail_gap = "4px".to_string();
+        config.theme.grid_padding = "5%".to_string();
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn validate_quality_boundary_ok() {
+        let mut config = SiteConfig::default();
+        config.images.quality = 100;
+        assert!(config.validate().is_ok());
+        config.images.quality = 0;
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn validate_quality_too_high() {
+        let mut config = SiteConfig::default();
+        config.images.quality = 101;
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("quality"));
+    }
+
+    #[test]
+    fn validate_aspect_ratio_zero() {
+        let mut config = SiteConfig::default();
+        config.thumbnails.aspect_ratio = [0, 5];
+        assert!(config.validate().is_err());
+        config.thumbnails.aspect_ratio = [4, 0];
         assert!(config.validate().is_err());
     }
 
@@ -1288,7 +3012,7 @@ text = "#abcdef"
     #[test]
     fn validate_sizes_empty() {
         let mut config = SiteConfig::default();
-        config.images.sizes = vec![];
+        config.images.sizes = ImageSizes::Explicit(vec![]);
         assert!(config.validate().is_err());
     }
 
@@ -1298,12 +3022,95 @@ text = "#abcdef"
         assert!(config.validate().is_ok());
     }
 
+    // ----- exclude_sizes -----
+
+    fn layer(toml: &str) -> SiteConfigLayer {
+        toml::from_str(toml).unwrap()
+    }
+
+    #[test]
+    fn exclude_sizes_subtracts_from_inherited_sizes() {
+        let root = layer("[images]\nsizes = [600, 1200, 1800, 2400]\n");
+        let album = cascade_layer(layer("[images]\nexclude_sizes = [2400]\n"), &root).unwrap();
+        let config = finalize_layer(album).unwrap();
+        assert_eq!(
+            config.images.sizes,
+            ImageSizes::Explicit(vec![600, 1200, 1800])
+        );
+        assert!(config.images.exclude_sizes.is_empty());
+    }
+
+    #[test]
+    fn exclude_sizes_accumulate_down_the_cascade() {
+        let root = cascade_layer(
+            layer("[images]\nexclude_sizes = [2080]\n"),
+            &SiteConfigLayer::empty(),
+        )
+        .unwrap();
+        let group = cascade_layer(layer("[images]\nquality = 80\n"), &root).unwrap();
+        let album = cascade_layer(layer("[images]\nexclude_sizes = [800]\n"), &group).unwrap();
+        let config = finalize_layer(album).unwrap();
+        assert_eq!(config.images.sizes, ImageSizes::Explicit(vec![1400]));
+        assert_eq!(config.images.quality, 80);
+    }
+
+    #[test]
+    fn exclude_sizes_applies_to_sizes_set_alongside() {
+        let album = cascade_layer(
+            layer("[images]\nsizes = [400, 800]\nexclude_sizes = [800, 2080]\n"),
+            &SiteConfigLayer::empty(),
+        )
+        .unwrap();
+        assert_eq!(
+            finalize_layer(album).unwrap().images.sizes,
+            ImageSizes::Explicit(vec![400])
+        );
+    }
+
+    #[test]
+    fn exclude_sizes_rejects_auto_and_removing_everything() {
+        let auto = layer("[images]\nsizes = \"auto\"\n");
+        let err = cascade_layer(layer("[images]\nexclude_sizes = [800]\n"), &auto).unwrap_err();
+        assert!(err.to_string().contains("auto"));
+        let err = cascade_layer(
+            layer("[images]\nexclude_sizes = [800, 1400, 2080]\n"),
+            &SiteConfigLayer::empty(),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("removes every size"));
+    }
+
+    #[test]
+    fn exclude_sizes_applies_when_finalizing() {
+        let root = layer("[images]\nsizes = [600, 1200, 1800]\n");
+        let config = finalize_layer(layer(
+            "[images]\nsizes = [600, 1200]\nexclude_sizes = [1200]\n",
+        ))
+        .unwrap();
+        assert_eq!(config.images.sizes, ImageSizes::Explicit(vec![600]));
+
+        let overrides = layer("[images]\nexclude_sizes = [1800]\n");
+        let config = finalize_layer_with(root, &overrides).unwrap();
+        assert_eq!(config.images.sizes, ImageSizes::Explicit(vec![600, 1200]));
+        assert!(config.images.exclude_sizes.is_empty());
+
+        let err = finalize_layer_with(
+            layer("[images]\nsizes = \"auto\"\n"),
+            &layer("[images]\nexclude_sizes = [800]\n"),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("auto"));
+    }
+
     // ----- effective_threads -----
 
     #[test]
     fn effective_threads_auto() {
         let config = ProcessingConfig {
             max_processes: None,
+            io_threads: None,
+            max_dimension: None,
+            cache_mode: ChangeDetection::Hash,
         };
         let threads = effective_threads(&config);
         let cores = std::thread::available_parallelism()
@@ -1316,6 +3123,9 @@ text = "#abcdef"
     fn effective_threads_clamped_to_cores() {
         let config = ProcessingConfig {
             max_processes: Some(99999),
+            io_threads: None,
+            max_dimension: None,
+            cache_mode: ChangeDetection::Hash,
         };
         let threads = effective_threads(&config);
         let cores = std::thread::available_parallelism()
@@ -1328,10 +3138,60 @@ text = "#abcdef"
     fn effective_threads_user_constrains_down() {
         let config = ProcessingConfig {
             max_processes: Some(1),
+            io_threads: None,
+            max_dimension: None,
+            cache_mode: ChangeDetection::Hash,
         };
         assert_eq!(effective_threads(&config), 1);
     }
 
+    #[test]
+    fn effective_io_threads_auto_oversubscribes() {
+        let config = ProcessingConfig {
+            max_processes: Some(1),
+            io_threads: None,
+            max_dimension: None,
+            cache_mode: ChangeDetection::Hash,
+        };
+        let cores = std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1);
+        assert_eq!(effective_io_threads(&config), cores * 2);
+        assert_eq!(effective_threads(&config), 1);
+    }
+
+    #[test]
+    fn effective_io_threads_may_exceed_cores_up_to_the_cap() {
+        let cores = std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1);
+        let mut config = ProcessingConfig {
+            max_processes: None,
+            io_threads: Some(cores * 4),
+            max_dimension: None,
+            cache_mode: ChangeDetection::Hash,
+        };
+        assert_eq!(effective_io_threads(&config), cores * 4);
+        assert_eq!(effective_threads(&config), cores);
+
+        config.io_threads = Some(99999);
+        assert_eq!(
+            effective_io_threads(&config),
+            cores * IO_THREADS_PER_CORE_MAX
+        );
+    }
+
+    #[test]
+    fn effective_io_threads_at_least_one() {
+        let config = ProcessingConfig {
+            max_processes: None,
+            io_threads: Some(0),
+            max_dimension: None,
+            cache_mode: ChangeDetection::Hash,
+        };
+        assert_eq!(effective_io_threads(&config), 1);
+    }
+
     // ----- CSS generation -----
 
     #[test]
@@ -1377,6 +3237,136 @@ text = "#abcdef"
         assert!(css.contains("--mat-y: clamp(2rem, 6vw, 5rem)"));
         assert!(css.contains("--thumbnail-gap: 0.2rem"));
         assert!(css.contains("--grid-padding: 2rem"));
+        assert!(css.contains("--image-max-width: none"));
+    }
+
+    #[test]
+    fn generate_theme_css_includes_frame_variables() {
+        let mut config = SiteConfig::default();
+        let css = generate_theme_css(&config.theme);
+        assert!(css.contains("--frame-background: transparent;"), "{css}");
+        assert!(css.contains("--frame-border: none;"), "{css}");
+        assert!(css.contains("--frame-shadow: none;"), "{css}");
+
+        config.theme.frame = FrameConfig {
+            background: "#fdfcf8".to_string(),
+            border: "1px solid #d8d4cc".to_string(),
+            shadow: "0 2px 12px rgb(0 0 0 / 0.15)".to_string(),
+        };
+        let css = generate_theme_css(&config.theme);
+        assert!(css.contains("--frame-background: #fdfcf8;"), "{css}");
+        assert!(css.contains("--frame-border: 1px solid #d8d4cc;"), "{css}");
+        assert!(
+            css.contains("--frame-shadow: 0 2px 12px rgb(0 0 0 / 0.15);"),
+            "{css}"
+        );
+    }
+
+    #[test]
+    fn parse_theme_frame() {
+        let tmp = TempDir::new().unwrap();
+        write_config(tmp.path(), "[theme.frame]\nborder = \"2px solid white\"\n");
+        let config = load_config(tmp.path()).unwrap();
+        assert_eq!(config.theme.frame.border, "2px solid white");
+        assert_eq!(config.theme.frame.background, "transparent");
+    }
+
+    #[test]
+    fn generate_theme_css_grid_columns() {
+        let mut config = SiteConfig::default();
+        assert_eq!(config.theme.grid_columns, GridColumns::Auto);
+        assert!(!generate_theme_css(&config.theme).contains("--grid-columns"));
+
+        config.theme.grid_columns = GridColumns::Fixed(3);
+        let css = generate_theme_css(&config.theme);
+        assert!(
+            css.contains("--grid-columns: repeat(3, minmax(0, 1fr));"),
+            "{css}"
+        );
+    }
+
+    #[test]
+    fn generate_theme_css_nav_style() {
+        let mut config = SiteConfig::default();
+        assert_eq!(config.theme.nav_style, NavStyle::Hamburger);
+        assert!(!generate_theme_css(&config.theme).contains(".nav-panel"));
+
+        config.theme.nav_style = NavStyle::Bar;
+        let css = generate_theme_css(&config.theme);
+        assert!(css.contains(".site-header .nav-hamburger,"), "{css}");
+        assert!(!css.contains("@media"), "{css}");
+
+        config.theme.nav_style = NavStyle::Auto;
+        config.theme.nav_breakpoint = 900;
+        let css = generate_theme_css(&config.theme);
+        let query = css.find("@media (min-width: 900px) {").expect(&css);
+        assert!(
+            css[query..].contains("    .site-header .nav-panel {"),
+            "{css}"
+        );
+        assert!(css.ends_with("}\n}"), "{css}");
+    }
+
+    #[test]
+    fn parse_nav_style() {
+        let tmp = TempDir::new().unwrap();
+        write_config(
+            tmp.path(),
+            "[theme]\nnav_style = \"auto\"\nnav_breakpoint = 800\n",
+        );
+        let config = load_config(tmp.path()).unwrap();
+        assert_eq!(config.theme.nav_style, NavStyle::Auto);
+        assert_eq!(config.theme.nav_breakpoint, 800);
+
+        write_config(tmp.path(), "[theme]\nnav_style = \"sidebar\"\n");
+        assert!(load_config(tmp.path()).is_err());
+    }
+
+    #[test]
+    fn parse_grid_columns() {
+        let tmp = TempDir::new().unwrap();
+        write_config(tmp.path(), "[theme]\ngrid_columns = 3\n");
+        let config = load_config(tmp.path()).unwrap();
+        assert_eq!(config.theme.grid_columns, GridColumns::Fixed(3));
+
+        write_config(tmp.path(), "[theme]\ngrid_columns = \"auto\"\n");
+        let config = load_config(tmp.path()).unwrap();
+        assert_eq!(config.theme.grid_columns, GridColumns::Auto);
+
+        for bad in ["0", "-2", "\"wide\""] {
+            write_config(tmp.path(), &format!("[theme]\ngrid_columns = {bad}\n"));
+            assert!(load_config(tmp.path()).is_err(), "{bad}");
+        }
+    }
+
+    #[test]
+    fn generate_theme_css_includes_configured_image_max_width() {
+        let mut config = SiteConfig::default();
+        config.theme.image_max_width = "1600px".to_string();
+        let css = generate_theme_css(&config.theme);
+        assert!(css.contains("--image-max-width: 1600px;"), "{css}");
+    }
+
+    #[test]
+    fn validate_breadcrumb_separator_not_blank() {
+        let mut config = SiteConfig::default();
+        assert_eq!(config.theme.breadcrumb_separator, "›");
+        config.theme.breadcrumb_separator = "/".to_string();
+        assert!(config.validate().is_ok());
+        config.theme.breadcrumb_separator = " ".to_string();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn validate_image_max_width() {
+        let mut config = SiteConfig::default();
+        for ok in ["none", "90rem", "min(100%, 1600px)"] {
+            config.theme.image_max_width = ok.to_string();
+            assert!(config.validate().is_ok(), "{ok}");
+        }
+        config.theme.image_max_width = "wide".to_string();
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("theme.image_max_width"), "{err}");
     }
 
     // ----- font helpers -----
@@ -1386,7 +3376,102 @@ text = "#abcdef"
         let config = SiteConfig::default();
         assert!(!config.font.is_local());
         assert!(config.font.stylesheet_url().is_some());
-        assert!(config.font.font_face_css().is_none());
+        assert!(config.font.font_face_css("/").is_none());
+    }
+
+    #[test]
+    fn stylesheet_url_single_weight() {
+        let config = SiteConfig::default();
+        assert_eq!(
+            config.font.stylesheet_url().unwrap(),
+            "https://fonts.googleapis.com/css2?family=Noto+Sans:wght@600&display=swap"
+        );
+    }
+
+    #[test]
+    fn stylesheet_url_multiple_weights_ascending() {
+        let mut config = SiteConfig::default();
+        config.font.weight = "700, 400,700".to_string();
+        assert_eq!(
+            config.font.stylesheet_url().unwrap(),
+            "https://fonts.googleapis.com/css2?family=Noto+Sans:wght@400;700&display=swap"
+        );
+        assert_eq!(config.font.primary_weight(), "700");
+    }
+
+    #[test]
+    fn stylesheet_url_italic_axis() {
+        let mut config = SiteConfig::default();
+        config.font.font = "EB Garamond".to_string();
+        config.font.weight = "400,700".to_string();
+        config.font.italic = true;
+        assert_eq!(
+            config.font.stylesheet_url().unwrap(),
+            "https://fonts.googleapis.com/css2?family=EB+Garamond:ital,wght@0,400;0,700;1,400;1,700&display=swap"
+        );
+    }
+
+    #[test]
+    fn generate_font_css_uses_primary_weight() {
+        let mut config = SiteConfig::default();
+        config.font.weight = "300,700".to_string();
+        let css = generate_font_css(&config.font, "/");
+        assert!(css.contains("--font-weight: 300;"));
+    }
+
+    #[test]
+    fn validate_font_weight_list() {
+        let mut config = SiteConfig::default();
+        for ok in ["400", "400,700", " 300 , 900 "] {
+            config.font.weight = ok.to_string();
+            assert!(config.validate().is_ok(), "{ok:?} should be valid");
+        }
+        for bad in ["", "bold", "400,", "0", "1001", "400;700"] {
+            config.font.weight = bad.to_string();
+            assert!(config.validate().is_err(), "{bad:?} should be rejected");
+        }
+    }
+
+    #[test]
+    fn generate_font_css_emits_fallback_metric_overrides() {
+        let mut config = SiteConfig::default();
+        assert!(!generate_font_css(&config.font, "/").contains("Fallback"));
+
+        config.font.metric_overrides.size_adjust = Some("104.5%".to_string());
+        config.font.metric_overrides.ascent_override = Some("92%".to_string());
+        let css = generate_font_css(&config.font, "/");
+        assert!(css.contains(
+            "@font-face {\n    font-family: \"Noto Sans Fallback\";\n    \
+             src: local(\"Helvetica\"), local(\"Arial\");\n    \
+             size-adjust: 104.5%;\n    ascent-override: 92%;\n}"
+        ));
+        assert!(css.contains(r#"--font-family: "Noto Sans", "Noto Sans Fallback", Helvetica"#));
+    }
+
+    #[test]
+    fn parse_font_metric_overrides() {
+        let tmp = TempDir::new().unwrap();
+        write_config(
+            tmp.path(),
+            "[font.metric_overrides]\ndescent_override = \"24%\"\nline_gap_override = \"0%\"\n",
+        );
+        let config = load_config(tmp.path()).unwrap();
+        let overrides = &config.font.metric_overrides;
+        assert_eq!(overrides.descent_override.as_deref(), Some("24%"));
+        assert_eq!(overrides.line_gap_override.as_deref(), Some("0%"));
+        assert!(overrides.size_adjust.is_none());
+    }
+
+    #[test]
+    fn validate_metric_overrides_are_percentages() {
+        let mut config = SiteConfig::default();
+        config.font.metric_overrides.size_adjust = Some("105".to_string());
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("font.metric_overrides.size_adjust"));
+        config.font.metric_overrides.size_adjust = Some("-5%".to_string());
+        assert!(config.validate().is_err());
+        config.font.metric_overrides.size_adjust = Some("105%".to_string());
+        assert!(config.validate().is_ok());
     }
 
     #[test]
@@ -1404,11 +3489,13 @@ text = "#abcdef"
         config.font.weight = "400".to_string();
         config.font.font_type = FontType::Sans;
         config.font.source = Some("fonts/MyFont.woff2".to_string());
-        let css = config.font.font_face_css().unwrap();
+        let css = config.font.font_face_css("/").unwrap();
         assert!(css.contains("@font-face"));
         assert!(css.contains(r#"font-family: "My Custom Font""#));
         assert!(css.contains(r#"url("/fonts/MyFont.woff2")"#));
         assert!(css.contains(r#"format("woff2")"#));
+        let nested = config.font.font_face_css("/gallery/").unwrap();
+        assert!(nested.contains(r#"url("/gallery/fonts/MyFont.woff2")"#));
         assert!(css.contains("font-weight: 400"));
         assert!(css.contains("font-display: swap"));
     }
@@ -1463,7 +3550,7 @@ source = "fonts/custom.woff2"
         config.font.weight = "700".to_string();
         config.font.font_type = FontType::Serif;
         config.font.source = Some("fonts/local.woff2".to_string());
-        let css = generate_font_css(&config.font);
+        let css = generate_font_css(&config.font, "/");
         assert!(css.contains("@font-face"));
         assert!(css.contains("--font-family:"));
         assert!(css.contains("--font-weight: 700"));
@@ -1472,7 +3559,7 @@ source = "fonts/custom.woff2"
     #[test]
     fn generate_font_css_no_font_face_for_google() {
         let config = SiteConfig::default();
-        let css = generate_font_css(&config.font);
+        let css = generate_font_css(&config.font, "/");
         assert!(!css.contains("@font-face"));
         assert!(css.contains("--font-family:"));
     }
@@ -1495,6 +3582,75 @@ qualty = 90
 
     // ----- auto-indexing -----
 
+    #[test]
+    fn default_naming_does_not_titleize() {
+        assert!(!SiteConfig::default().naming.titleize);
+    }
+
+    #[test]
+    fn parse_naming_titleize() {
+        let tmp = TempDir::new().unwrap();
+        write_config(
+            tmp.path(),
+            r#"
+[naming]
+titleize = true
+"#,
+        );
+        let config = load_config(tmp.path()).unwrap();
+        assert!(config.naming.titleize);
+    }
+
+    #[test]
+    fn parse_theme_index_layout() {
+        let tmp = TempDir::new().unwrap();
+        write_config(
+            tmp.path(),
+            r#"
+[theme]
+index_layout = "masonry"
+"#,
+        );
+        let config = load_config(tmp.path()).unwrap();
+        assert_eq!(config.theme.index_layout, IndexLayout::Masonry);
+        assert_eq!(SiteConfig::default().theme.index_layout, IndexLayout::Grid);
+    }
+
+    #[test]
+    fn default_image_page_names_is_index_title() {
+        assert_eq!(
+            SiteConfig::default().output.image_page_names,
+            ImagePageNames::IndexTitle
+        );
+    }
+
+    #[test]
+    fn parse_output_image_page_names() {
+        let tmp = TempDir::new().unwrap();
+        write_config(
+            tmp.path(),
+            r#"
+[output]
+image_page_names = "slug"
+"#,
+        );
+        let config = load_config(tmp.path()).unwrap();
+        assert_eq!(config.output.image_page_names, ImagePageNames::Slug);
+    }
+
+    #[test]
+    fn unknown_image_page_names_rejected() {
+        let tmp = TempDir::new().unwrap();
+        write_config(
+            tmp.path(),
+            r#"
+[output]
+image_page_names = "title"
+"#,
+        );
+        assert!(load_config(tmp.path()).is_err());
+    }
+
     #[test]
     fn default_auto_indexing_is_off() {
         let config = SiteConfig::default();
@@ -1591,7 +3747,10 @@ spacing = 0
         assert_eq!(config.auto_indexing.spacing, 0);
         assert!(!config.auto_indexing.sync_source_files);
         assert_eq!(config.auto_indexing.padding, 3);
-        assert_eq!(config.images.sizes, vec![800, 1400, 2080]);
+        assert_eq!(
+            config.images.sizes,
+            ImageSizes::Explicit(vec![800, 1400, 2080])
+        );
         assert_eq!(config.colors.light.background, "#ffffff");
     }
 
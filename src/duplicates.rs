@@ -0,0 +1,147 @@
+//! Near-duplicate photos (`check --duplicates`).
+//!
+//! Every image in the scan manifest is decoded and reduced to a 64-bit
+//! perceptual hash ([`perceptual_hash`]). Pairs whose hashes differ in at
+//! most [`MAX_DISTANCE`] bits are reported, within and across albums — the
+//! same shot exported twice under different numbers, or re-saved at another
+//! size. It is a reviewing aid: nothing here fails a build.
+
+use crate::imaging::perceptual_hash;
+use crate::scan::{Image, Manifest};
+use rayon::prelude::*;
+use serde::Serialize;
+use std::path::Path;
+
+/// Largest Hamming distance between two hashes still reported as a pair.
+pub const MAX_DISTANCE: u32 = 4;
+
+/// Two images that look the same.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DuplicatePair {
+    /// Source paths relative to the content root, in manifest order.
+    pub first: String,
+    pub second: String,
+    /// Differing hash bits; `0` for visually identical images.
+    pub distance: u32,
+}
+
+/// Every near-duplicate pair among `manifest`'s images, in manifest order.
+///
+/// Images that fail to decode are skipped; the build reports those. The
+/// same file placed in several albums on purpose (scan gives the copies
+/// one `canonical_id`) is not reported; two copies in one album are.
+pub fn find_duplicates(manifest: &Manifest, source_root: &Path) -> Vec<DuplicatePair> {
+    let images: Vec<(&str, &Image)> = manifest
+        .albums
+        .iter()
+        .flat_map(|album| {
+            album
+                .images
+                .iter()
+                .map(|image| (album.path.as_str(), image))
+        })
+        .collect();
+    let hashes: Vec<(&str, &Image, u64)> = images
+        .par_iter()
+        .filter_map(|&(album, image)| {
+            let hash = perceptual_hash(&source_root.join(&image.source_path)).ok()?;
+            Some((album, image, hash))
+        })
+        .collect();
+
+    let mut pairs = Vec::new();
+    for (i, (album_a, a, hash_a)) in hashes.iter().enumerate() {
+        for (album_b, b, hash_b) in &hashes[i + 1..] {
+            let shared =
+                album_a != album_b && a.canonical_id.is_some() && a.canonical_id == b.canonical_id;
+            let distance = (hash_a ^ hash_b).count_ones();
+            if !shared && distance <= MAX_DISTANCE {
+                pairs.push(DuplicatePair {
+                    first: a.source_path.clone(),
+                    second: b.source_path.clone(),
+                    distance,
+                });
+            }
+        }
+    }
+    pairs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scan;
+    use image::{Rgb, RgbImage};
+    use std::fs;
+    use tempfile::TempDir;
+
+    /// A diagonal gradient, or its mirror image with `flipped`.
+    fn gradient(width: u32, height: u32, flipped: bool) -> RgbImage {
+        RgbImage::from_fn(width, height, |x, y| {
+            let x = if flipped { width - 1 - x } else { x };
+            let v = ((x * 255 / width + y * 64 / height) % 256) as u8;
+            Rgb([v, v / 2, 255 - v])
+        })
+    }
+
+    #[test]
+    fn reports_the_same_shot_twice_but_not_a_different_one() {
+        let tmp = TempDir::new().unwrap();
+        let album = tmp.path().join("010-Landscapes");
+        fs::create_dir_all(&album).unwrap();
+        gradient(320, 240, false)
+            .save(album.join("001-dawn.png"))
+            .unwrap();
+        // The same picture, smaller and re-encoded as JPEG.
+        gradient(160, 120, false)
+            .save(album.join("002-dawn-copy.jpg"))
+            .unwrap();
+        gradient(320, 240, true)
+            .save(album.join("003-dusk.png"))
+            .unwrap();
+
+        let manifest = scan::scan(tmp.path()).unwrap();
+        let pairs = find_duplicates(&manifest, tmp.path());
+
+        assert_eq!(pairs.len(), 1, "{pairs:?}");
+        assert_eq!(pairs[0].first, "010-Landscapes/001-dawn.png");
+        assert_eq!(pairs[0].second, "010-Landscapes/002-dawn-copy.jpg");
+        assert!(pairs[0].distance <= MAX_DISTANCE);
+    }
+
+    #[test]
+    fn finds_duplicates_across_albums() {
+        let tmp = TempDir::new().unwrap();
+        for album in ["010-Landscapes", "020-Travel"] {
+            fs::create_dir_all(tmp.path().join(album)).unwrap();
+        }
+        gradient(200, 200, false)
+            .save(tmp.path().join("010-Landscapes/001-dawn.png"))
+            .unwrap();
+        gradient(200, 200, false)
+            .save(tmp.path().join("020-Travel/001-dawn.jpg"))
+            .unwrap();
+
+        let manifest = scan::scan(tmp.path()).unwrap();
+        let pairs = find_duplicates(&manifest, tmp.path());
+
+        assert_eq!(pairs.len(), 1, "{pairs:?}");
+        assert_eq!(pairs[0].first, "010-Landscapes/001-dawn.png");
+        assert_eq!(pairs[0].second, "020-Travel/001-dawn.jpg");
+    }
+
+    #[test]
+    fn one_file_shared_by_two_albums_is_not_a_duplicate() {
+        let tmp = TempDir::new().unwrap();
+        for album in ["010-Landscapes", "020-Favorites"] {
+            fs::create_dir_all(tmp.path().join(album)).unwrap();
+            gradient(200, 200, false)
+                .save(tmp.path().join(album).join("001-dawn.png"))
+                .unwrap();
+        }
+
+        let manifest = scan::scan(tmp.path()).unwrap();
+
+        assert!(find_duplicates(&manifest, tmp.path()).is_empty());
+    }
+}
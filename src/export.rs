@@ -0,0 +1,227 @@
+//! Spreadsheet export of the processed manifest.
+//!
+//! `simple-gal export-csv` projects `{temp_dir}/processed/manifest.json`
+//! into one CSV row per image, for print ordering and inventory sheets:
+//!
+//! ```text
+//! album,index,title,slug,width,height,url,description
+//! Landscapes,1,Dawn,dawn,5472,3648,/Landscapes/001-dawn-2080.avif,First light over the ridge
+//! ```
+//!
+//! - `index` is the 1-based position within the album, matching the
+//!   numbers shown on the site.
+//! - `width` / `height` are the source image's dimensions.
+//! - `url` points at the largest generated variant, under `base_path`. It
+//!   is absolute when `base_url` is configured, root-relative otherwise.
+//!
+//! Fields are quoted per RFC 4180 only when they contain a comma, quote,
+//! or line break; embedded quotes are doubled. Rows end with `\r\n`.
+
+use crate::generate::{Image, Manifest};
+use std::borrow::Cow;
+
+/// Column names, in output order.
+pub const CSV_HEADER: [&str; 8] = [
+    "album",
+    "index",
+    "title",
+    "slug",
+    "width",
+    "height",
+    "url",
+    "description",
+];
+
+/// Render every image of every album in `manifest` as CSV, header first.
+///
+/// Returns the CSV text and the number of data rows.
+pub fn manifest_csv(manifest: &Manifest) -> (String, usize) {
+    let base_url = manifest
+        .config
+        .site_url()
+        .unwrap_or_else(|| manifest.config.base_path.trim_end_matches('/').to_string());
+    let mut out = String::new();
+    push_row(&mut out, CSV_HEADER.iter().map(|h| Cow::Borrowed(*h)));
+    let mut rows = 0;
+    for album in &manifest.albums {
+        for (idx, image) in album.images.iter().enumerate() {
+            let (width, height) = image.dimensions;
+            push_row(
+                &mut out,
+                [
+                    Cow::Borrowed(album.path.as_str()),
+                    Cow::Owned((idx + 1).to_string()),
+                    Cow::Borrowed(image.title.as_deref().unwrap_or("")),
+                    Cow::Borrowed(image.slug.as_str()),
+                    Cow::Owned(width.to_string()),
+                    Cow::Owned(height.to_string()),
+                    Cow::Owned(largest_variant_url(image, &base_url)),
+                    Cow::Borrowed(image.description.as_deref().unwrap_or("")),
+                ],
+            );
+            rows += 1;
+        }
+    }
+    (out, rows)
+}
+
+/// URL of the widest generated variant, or empty if there is none.
+fn largest_variant_url(image: &Image, base_url: &str) -> String {
+    image
+        .generated
+        .values()
+        .max_by_key(|v| v.width)
+        .map(|v| format!("{}/{}", base_url, v.avif))
+        .unwrap_or_default()
+}
+
+fn push_row<'a>(out: &mut String, fields: impl IntoIterator<Item = Cow<'a, str>>) {
+    for (i, field) in fields.into_iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        out.push_str(&csv_field(&field));
+    }
+    out.push_str("\r\n");
+}
+
+/// Quote a field if it contains a delimiter, quote, or line break.
+fn csv_field(field: &str) -> Cow<'_, str> {
+    if field.contains([',', '"', '\n', '\r']) {
+        Cow::Owned(format!("\"{}\"", field.replace('"', "\"\"")))
+    } else {
+        Cow::Borrowed(field)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::SiteConfig;
+    use crate::generate::{Album, GeneratedVariant};
+
+    #[test]
+    fn csv_field_plain_is_unquoted() {
+        assert_eq!(csv_field("Dawn"), "Dawn");
+        assert_eq!(csv_field(""), "");
+    }
+
+    #[test]
+    fn csv_field_quotes_commas_quotes_and_newlines() {
+        assert_eq!(csv_field("Rome, Italy"), r#""Rome, Italy""#);
+        assert_eq!(csv_field(r#"The "Big" One"#), r#""The ""Big"" One""#);
+        assert_eq!(csv_field("line one\nline two"), "\"line one\nline two\"");
+        assert_eq!(csv_field("a\r\nb"), "\"a\r\nb\"");
+    }
+
+    fn image(
+        number: u32,
+        slug: &str,
+        title: Option<&str>,
+        description: Option<&str>,
+        variants: &[u32],
+    ) -> Image {
+        let stem = match slug {
+            "" => format!("Travel/Rome/{number:03}"),
+            _ => format!("Travel/Rome/{number:03}-{slug}"),
+        };
+        Image {
+            number,
+            source_path: format!("{stem}.jpg"),
+            slug: slug.to_string(),
+            title: title.map(str::to_string),
+            description: description.map(str::to_string),
+            dimensions: (6000, 4000),
+            generated: variants
+                .iter()
+                .map(|&w| {
+                    (
+                        w.to_string(),
+                        GeneratedVariant {
+                            avif: format!("{stem}-{w}.avif"),
+                            width: w,
+                            height: w * 2 / 3,
+                        },
+                    )
+                })
+                .collect(),
+            thumbnail: format!("{stem}-thumb.avif"),
+            full_index_thumbnail: None,
+            canonical_id: None,
+        }
+    }
+
+    fn fixture_manifest(base_url: Option<&str>) -> Manifest {
+        let config = SiteConfig {
+            base_url: base_url.map(str::to_string),
+            ..Default::default()
+        };
+        Manifest {
+            navigation: vec![],
+            albums: vec![Album {
+                path: "Travel/Rome".to_string(),
+                title: "Rome".to_string(),
+                description: None,
+                description_text: None,
+                thumbnail: "Travel/Rome/001-forum-thumb.avif".to_string(),
+                images: vec![
+                    // "800" sorts after "2080" as a string key; the widest
+                    // variant must still win.
+                    image(
+                        1,
+                        "forum",
+                        Some("Forum, at dusk"),
+                        Some("Shot from the \"good\" side.\nSecond line."),
+                        &[800, 2080],
+                    ),
+                    image(2, "", None, None, &[800]),
+                ],
+                in_nav: true,
+                config: SiteConfig::default(),
+                support_files: vec![],
+                meta: None,
+            }],
+            pages: vec![],
+            description: None,
+            config,
+            canonical_images: vec![],
+        }
+    }
+
+    #[test]
+    fn manifest_csv_rows_are_escaped() {
+        let (csv, rows) = manifest_csv(&fixture_manifest(None));
+        assert_eq!(rows, 2);
+        assert_eq!(
+            csv,
+            "album,index,title,slug,width,height,url,description\r\n\
+             Travel/Rome,1,\"Forum, at dusk\",forum,6000,4000,/Travel/Rome/001-forum-2080.avif,\
+             \"Shot from the \"\"good\"\" side.\nSecond line.\"\r\n\
+             Travel/Rome,2,,,6000,4000,/Travel/Rome/002-800.avif,\r\n"
+        );
+    }
+
+    #[test]
+    fn manifest_csv_uses_base_url() {
+        let (csv, _) = manifest_csv(&fixture_manifest(Some("https://example.com")));
+        assert!(csv.contains(",https://example.com/Travel/Rome/001-forum-2080.avif,"));
+    }
+
+    #[test]
+    fn manifest_csv_urls_include_base_path() {
+        let mut manifest = fixture_manifest(Some("https://user.github.io"));
+        manifest.config.base_path = "/portfolio/".to_string();
+        let (csv, _) = manifest_csv(&manifest);
+        assert!(csv.contains(",https://user.github.io/portfolio/Travel/Rome/001-forum-2080.avif,"));
+
+        manifest.config.base_url = None;
+        let (csv, _) = manifest_csv(&manifest);
+        assert!(csv.contains(",/portfolio/Travel/Rome/001-forum-2080.avif,"));
+    }
+
+    #[test]
+    fn manifest_csv_trims_trailing_slash_from_base_url() {
+        let (csv, _) = manifest_csv(&fixture_manifest(Some("https://example.com/")));
+        assert!(csv.contains(",https://example.com/Travel/Rome/001-forum-2080.avif,"));
+    }
+}
@@ -40,6 +40,14 @@
 //!         └── ...
 //! ```
 //!
+//! ## Replacing the output
+//!
+//! The site is written to a hidden sibling of the output directory
+//! (`.dist.staging/` for `dist/`) and moved into place only once every page
+//! is written, so a failed build leaves the previous site as it was. Files
+//! in the old output that the build doesn't write (a `CNAME`, a deploy
+//! `.git`) are carried over. See [`generate`].
+//!
 //! ## CSS and JavaScript
 //!
 //! Static assets are embedded at compile time:
@@ -58,14 +66,33 @@
 //! Uses [maud](https://maud.lambda.xyz/) for compile-time HTML templating.
 //! Templates are type-safe Rust code with automatic XSS escaping.
 
-use crate::config::{self, SiteConfig};
-use crate::types::{NavItem, Page};
+use crate::config::{
+    self, AssetPrecedence, DescriptionPosition, ImagePageNames, ImagesConfig, IndexLayout,
+    SiteConfig, ZipContents,
+};
+use crate::imaging::exif_scrub::{self, Scrub};
+use crate::imaging::params::ThumbnailParams;
+use crate::imaging::{AspectMode, BackendError, ImageBackend, Quality, RustBackend};
+use crate::links;
+use crate::metadata::{sanitize_slug, slug_text};
+use crate::text;
+use crate::types::{AlbumMeta, NavItem, Page};
+use crate::typography;
 use maud::{DOCTYPE, Markup, PreEscaped, html};
-use pulldown_cmark::{Parser, html as md_html};
-use serde::Deserialize;
-use std::collections::BTreeMap;
+use pulldown_cmark::{CodeBlockKind, Event, Parser, Tag, TagEnd, html as md_html};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
 use std::fs;
-use std::path::Path;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+#[cfg(feature = "highlight")]
+use std::sync::LazyLock;
+#[cfg(feature = "highlight")]
+use syntect::{
+    highlighting::{Theme, ThemeSet},
+    html::highlighted_html_for_string,
+    parsing::SyntaxSet,
+};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -74,6 +101,12 @@ pub enum GenerateError {
     Io(#[from] std::io::Error),
     #[error("JSON error: {0}")]
     Json(#[from] serde_json::Error),
+    #[error("Icon generation failed: {0}")]
+    Icon(#[from] BackendError),
+    #[error("Album zip failed: {0}")]
+    Zip(#[from] zip::result::ZipError),
+    #[error("Output directory {0} contains {1}, which generate won't replace")]
+    OutputContainsInput(PathBuf, PathBuf),
 }
 
 /// Processed manifest from stage 2
@@ -128,15 +161,33 @@ pub struct Album {
     pub path: String,
     pub title: String,
     pub description: Option<String>,
+    /// Plain-text rendering of `description`. Absent from manifests written
+    /// before it was added; see [`Album::plain_description`].
+    #[serde(default)]
+    pub description_text: Option<String>,
     pub thumbnail: String,
+    /// The preview image's double-size thumbnail, with `[thumbnails] hidpi`.
+    #[serde(default)]
+    pub thumbnail_2x: Option<String>,
+    /// Dedicated preview for album cards, with `[thumbnails] card_size`.
+    #[serde(default)]
+    pub card_thumbnail: Option<String>,
+    #[serde(default)]
+    pub card_thumbnail_bytes: Option<u64>,
+    /// The album card's thumbnail as a `data:` URI, filled in by
+    /// [`inline_small_thumbnails`].
+    #[serde(skip)]
+    pub card_inline: Option<String>,
     pub images: Vec<Image>,
     pub in_nav: bool,
-    /// Resolved config for this album (available for future per-album theming).
-    #[allow(dead_code)]
+    /// Resolved config for this album (per-album theming, e.g. caption length).
     pub config: SiteConfig,
     #[serde(default)]
     #[allow(dead_code)]
     pub support_files: Vec<String>,
+    /// Location/date/camera details from the album's `meta.toml`.
+    #[serde(default)]
+    pub meta: Option<AlbumMeta>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -144,6 +195,10 @@ pub struct Image {
     pub number: u32,
     #[allow(dead_code)]
     pub source_path: String,
+    /// URL-safe name from the filename. Used for image page directories
+    /// when `[output] image_page_names = "slug"`.
+    #[serde(default)]
+    pub slug: String,
     #[serde(default)]
     pub title: Option<String>,
     #[serde(default)]
@@ -152,6 +207,15 @@ pub struct Image {
     pub generated: BTreeMap<String, GeneratedVariant>,
     pub thumbnail: String,
     #[serde(default)]
+    pub thumbnail_bytes: Option<u64>,
+    /// The thumbnail as a `data:` URI, filled in by
+    /// [`inline_small_thumbnails`].
+    #[serde(skip)]
+    pub thumbnail_inline: Option<String>,
+    /// Double-size thumbnail, with `[thumbnails] hidpi`.
+    #[serde(default)]
+    pub thumbnail_2x: Option<String>,
+    #[serde(default)]
     pub full_index_thumbnail: Option<String>,
     /// Pointer into [`Manifest::canonical_images`]. Used by the All
     /// Photos dedup path: when two `Image` records share a
@@ -172,6 +236,7 @@ pub struct GeneratedVariant {
 
 const CSS_STATIC: &str = include_str!("../static/style.css");
 const JS: &str = include_str!("../static/nav.js");
+const SEARCH_JS: &str = include_str!("../static/search.js");
 const SW_JS_TEMPLATE: &str = include_str!("../static/sw.js");
 // We embed default icons so every installation is a valid PWA out of the box.
 // Users can override these by placing files in their assets/ directory.
@@ -180,6 +245,26 @@ const ICON_512: &[u8] = include_bytes!("../static/icon-512.png");
 const APPLE_TOUCH_ICON: &[u8] = include_bytes!("../static/apple-touch-icon.png");
 const FAVICON_PNG: &[u8] = include_bytes!("../static/favicon.png");
 
+/// Icons produced from `icon_source`: filename and square edge in pixels.
+const ICON_SIZES: &[(&str, u32)] = &[
+    ("favicon.png", 32),
+    ("icon-192.png", 192),
+    ("icon-512.png", 512),
+    ("apple-touch-icon.png", 180),
+];
+
+/// The variant whose width is closest to `target`; ties go to the larger
+/// variant so the fallback errs on the sharp side.
+fn nearest_variant<'a>(
+    variants: &[&'a GeneratedVariant],
+    target: u32,
+) -> Option<&'a GeneratedVariant> {
+    variants
+        .iter()
+        .copied()
+        .min_by_key(|v| (v.width.abs_diff(target), std::cmp::Reverse(v.width)))
+}
+
 /// Compute the `sizes` attribute for a responsive image based on its aspect ratio
 /// and the maximum generated width. The image frame CSS constrains display to
 /// `min(container-width, container-height * aspect-ratio)`, so for portrait images
@@ -204,32 +289,137 @@ struct GalleryEntry {
     title: String,
     path: String,
     thumbnail: Option<String>,
+    /// Double-size thumbnail for `srcset`, with `[thumbnails] hidpi`.
+    thumbnail_2x: Option<String>,
+    /// The thumbnail as a `data:` URI, with `[thumbnails] inline_below_bytes`.
+    thumbnail_inline: Option<String>,
+    /// Plain-text description excerpt, shown in the `list` index layout.
+    excerpt: Option<String>,
 }
 
-/// Find a thumbnail for a nav item by walking into its first child recursively.
-fn find_nav_thumbnail(item: &NavItem, albums: &[Album]) -> Option<String> {
-    if item.children.is_empty() {
-        // Leaf: find the matching album
+/// Longest description excerpt shown next to an album in the `list` layout.
+const EXCERPT_MAX_CHARS: usize = 200;
+
+/// Plain-text excerpt of a nav item's description: the album's own
+/// description for leaves, the container's `description` otherwise.
+fn find_nav_excerpt(item: &NavItem, albums: &[Album]) -> Option<String> {
+    let plain = if item.children.is_empty() {
         albums
             .iter()
             .find(|a| a.path == item.path)
-            .map(|a| a.thumbnail.clone())
+            .and_then(Album::plain_description)
+    } else {
+        item.description.as_deref().map(text::strip_html_tags)
+    }?;
+    let excerpt = text::one_line(&plain);
+    (!excerpt.is_empty()).then(|| text::truncate_desc(&excerpt, EXCERPT_MAX_CHARS))
+}
+
+/// Find the album whose preview stands for a nav item on its card, by
+/// walking into its first child recursively.
+fn find_nav_album<'a>(item: &NavItem, albums: &'a [Album]) -> Option<&'a Album> {
+    if item.children.is_empty() {
+        // Leaf: find the matching album
+        albums.iter().find(|a| a.path == item.path)
     } else {
         // Container: recurse into first child
         item.children
             .first()
-            .and_then(|c| find_nav_thumbnail(c, albums))
+            .and_then(|c| find_nav_album(c, albums))
+    }
+}
+
+/// `srcset` offering `thumb` at `1x` and `thumb_2x` at `2x`, when there is
+/// a double-size variant. Both paths are prefixed with `root`.
+fn density_srcset(root: &str, thumb: &str, thumb_2x: Option<&str>) -> Option<String> {
+    thumb_2x.map(|thumb_2x| format!("{root}{thumb} 1x, {root}{thumb_2x} 2x"))
+}
+
+/// Read each album's thumbnails under its `[thumbnails] inline_below_bytes`
+/// from `processed_dir` and keep them as `data:` URIs, so pages embed them
+/// instead of linking the file. Sizes come from the process stage.
+///
+/// Thumbnails with a `hidpi` double-size variant stay linked: a `data:` URI
+/// can't carry the `2x` `srcset`, and high-density screens would get the
+/// blurry `1x` file.
+fn inline_small_thumbnails(albums: &mut [Album], processed_dir: &Path) -> std::io::Result<()> {
+    for album in albums {
+        let Some(limit) = album.config.thumbnails.inline_below_bytes else {
+            continue;
+        };
+        let inline = |path: &str, bytes: Option<u64>| -> std::io::Result<Option<String>> {
+            match bytes {
+                Some(bytes) if bytes < limit => {
+                    let data = fs::read(processed_dir.join(path))?;
+                    Ok(Some(format!(
+                        "data:image/avif;base64,{}",
+                        base64_encode(&data)
+                    )))
+                }
+                _ => Ok(None),
+            }
+        };
+        for image in &mut album.images {
+            image.thumbnail_inline = match image.thumbnail_2x {
+                Some(_) => None,
+                None => inline(&image.thumbnail, image.thumbnail_bytes)?,
+            };
+        }
+        let card_inline = match &album.card_thumbnail {
+            Some(card) => inline(card, album.card_thumbnail_bytes)?,
+            None => album
+                .images
+                .iter()
+                .find(|image| image.thumbnail == album.thumbnail)
+                .and_then(|image| image.thumbnail_inline.clone()),
+        };
+        album.card_inline = card_inline;
+    }
+    Ok(())
+}
+
+/// Standard padded base64, for `data:` URIs.
+fn base64_encode(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, &b)| n | ((b as u32) << (16 - 8 * i)));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[((n >> (18 - 6 * i)) & 63) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
     }
+    out
 }
 
 /// Build gallery entries from nav children for a gallery-list page.
 fn collect_gallery_entries(children: &[NavItem], albums: &[Album]) -> Vec<GalleryEntry> {
     children
         .iter()
-        .map(|item| GalleryEntry {
-            title: item.title.clone(),
-            path: item.path.clone(),
-            thumbnail: find_nav_thumbnail(item, albums),
+        .map(|item| {
+            let album = find_nav_album(item, albums);
+            // A `[thumbnails] card_size` preview is used on its own.
+            let (thumbnail, thumbnail_2x) = match album {
+                Some(a) => match &a.card_thumbnail {
+                    Some(card) => (Some(card.clone()), None),
+                    None => (Some(a.thumbnail.clone()), a.thumbnail_2x.clone()),
+                },
+                None => (None, None),
+            };
+            GalleryEntry {
+                title: item.title.clone(),
+                path: item.path.clone(),
+                thumbnail,
+                thumbnail_2x,
+                thumbnail_inline: album.and_then(|a| a.card_inline.clone()),
+                excerpt: find_nav_excerpt(item, albums),
+            }
         })
         .collect()
 }
@@ -266,7 +456,8 @@ fn path_to_breadcrumb_segments<'a>(
     segments
 }
 
-/// User-provided snippets discovered via convention files in the assets directory.
+/// User-provided snippets discovered via convention files in the assets directory,
+/// plus the inline `head_html` / `body_end_html` strings from `config.toml`.
 ///
 /// Drop any of these files into your `assets/` directory to inject custom content:
 /// - `custom.css` → `<link rel="stylesheet">` after the main `<style>` block
@@ -280,6 +471,8 @@ struct CustomSnippets {
     head_html: Option<String>,
     /// Raw HTML to inject before `</body>`.
     body_end_html: Option<String>,
+    /// Render [`site_footer`], from `show_attribution`.
+    attribution: bool,
 }
 
 /// Detect convention-based custom snippet files in the output directory.
@@ -290,24 +483,44 @@ fn detect_custom_snippets(output_dir: &Path) -> CustomSnippets {
         has_custom_css: output_dir.join("custom.css").exists(),
         head_html: fs::read_to_string(output_dir.join("head.html")).ok(),
         body_end_html: fs::read_to_string(output_dir.join("body-end.html")).ok(),
+        attribution: false,
+    }
+}
+
+impl CustomSnippets {
+    /// Append the inline `head_html` / `body_end_html` config strings after
+    /// any snippet loaded from a convention file, and take the
+    /// `show_attribution` flag.
+    fn with_config(self, config: &SiteConfig) -> Self {
+        CustomSnippets {
+            has_custom_css: self.has_custom_css,
+            head_html: join_snippets(self.head_html, config.head_html.as_deref()),
+            body_end_html: join_snippets(self.body_end_html, config.body_end_html.as_deref()),
+            attribution: config.show_attribution,
+        }
+    }
+}
+
+/// Concatenate a file snippet and a config snippet, newline-separated.
+fn join_snippets(file: Option<String>, config: Option<&str>) -> Option<String> {
+    match (file, config) {
+        (Some(file), Some(config)) => Some(format!("{file}\n{config}")),
+        (file, config) => file.or_else(|| config.map(str::to_string)),
     }
 }
 
 /// Zero-padding width for image indices, based on album size.
+///
+/// The number of decimal digits in `total` (minimum 1), so every position
+/// in the album gets the same width: 1500 images → `0001` … `1500`.
 pub(crate) fn index_width(total: usize) -> usize {
-    match total {
-        0..=9 => 1,
-        10..=99 => 2,
-        100..=999 => 3,
-        _ => 4,
-    }
+    total.max(1).ilog10() as usize + 1
 }
 
 /// Build an image page directory name like `"02-My-Title/"` or `"02/"` (when no title).
 ///
 /// The directory name mirrors the display label shown in the header/breadcrumb
-/// (`"02. My Title"`) but URL-escaped: dots and spaces become hyphens, consecutive
-/// hyphens are collapsed.
+/// (`"02. My Title"`) with the title made a slug by [`sanitize_slug`].
 ///
 /// Image pages are directories with an `index.html` inside, so that static
 /// servers can serve them without requiring `.html` in the URL.
@@ -315,42 +528,176 @@ pub(crate) fn image_page_url(position: usize, total: usize, title: Option<&str>)
     let width = index_width(total);
     match title {
         Some(t) => {
-            let escaped = escape_for_url(t);
+            let escaped = sanitize_slug(t);
             format!("{:0>width$}-{}/", position, escaped)
         }
         None => format!("{:0>width$}/", position),
     }
 }
 
-/// Escape a display title for use in URL paths.
-///
-/// Lowercases, replaces spaces/dots/underscores with hyphens, and collapses consecutive hyphens.
-fn escape_for_url(title: &str) -> String {
-    let mut result = String::with_capacity(title.len());
-    let mut prev_dash = false;
-    for c in title.chars() {
-        if c == ' ' || c == '.' || c == '_' {
-            if !prev_dash {
-                result.push('-');
+impl Album {
+    /// The description as plain text: `description_text`, or the rendered
+    /// HTML with its tags stripped when the manifest predates that field.
+    fn plain_description(&self) -> Option<String> {
+        self.description_text
+            .clone()
+            .or_else(|| self.description.as_deref().map(text::strip_html_tags))
+    }
+
+    /// Image page directory (with trailing slash) for the image at 0-based
+    /// `idx`, following this album's `[output] image_page_names` mode.
+    ///
+    /// Every link to an image page — album grid, prev/next, nav dots, All
+    /// Photos — goes through here so they agree with the directory that
+    /// was actually written.
+    pub(crate) fn image_page_dir(&self, idx: usize) -> String {
+        let total = self.images.len();
+        let image = &self.images[idx];
+        let ascii = self.config.output.ascii_slugs;
+        match self.page_names() {
+            ImagePageNames::IndexTitle => {
+                let title = image.title.as_deref().map(|t| slug_text(t, ascii));
+                image_page_url(idx + 1, total, title.as_deref())
             }
-            prev_dash = true;
-        } else {
-            result.extend(c.to_lowercase());
-            prev_dash = false;
+            ImagePageNames::Index => image_page_url(idx + 1, total, None),
+            ImagePageNames::Slug if !image.slug.is_empty() => {
+                format!("{}/", sanitize_slug(&slug_text(&image.slug, ascii)))
+            }
+            ImagePageNames::Slug => image_page_url(idx + 1, total, None),
+        }
+    }
+
+    /// The effective `[output] image_page_names` mode. With
+    /// `[theme] show_index = false` the default index-title names drop
+    /// their index, which leaves the name alone: the `"slug"` form.
+    fn page_names(&self) -> ImagePageNames {
+        match self.config.output.image_page_names {
+            ImagePageNames::IndexTitle if !self.config.theme.show_index => ImagePageNames::Slug,
+            mode => mode,
+        }
+    }
+
+    /// Display label for the image at 0-based `idx`, for breadcrumbs and
+    /// page titles: see [`format_image_label`]. With `[theme] show_index =
+    /// false` a titled image is labelled by its title alone.
+    fn image_label(&self, idx: usize) -> String {
+        match self.images[idx].title.as_deref() {
+            Some(title) if !self.config.theme.show_index => title.to_string(),
+            title => format_image_label(idx + 1, self.images.len(), title),
         }
     }
-    result.trim_matches('-').to_string()
-}
 
-const SHORT_CAPTION_MAX_LEN: usize = 160;
+    /// Make `"slug"`-mode image page directories unique within the album.
+    ///
+    /// Filename slugs can repeat (`001-Sunset.jpg`, `005-Sunset.jpg`) or
+    /// collapse together once escaped or ASCII-folded (`A.B` and `A B`).
+    /// The first image in album order keeps the plain slug; each later one
+    /// gets its number appended (`sunset-5`). So the suffixes aren't stable:
+    /// adding an earlier image with the same slug, or reordering the album,
+    /// moves which page gets the plain URL. Images without a slug use their
+    /// position (`3/`), which a slugged image can't take. The other modes
+    /// lead with the unique position and never collide.
+    fn disambiguate_slugs(&mut self) {
+        if self.page_names() != ImagePageNames::Slug {
+            return;
+        }
+        let total = self.images.len();
+        let ascii = self.config.output.ascii_slugs;
+        let names: Vec<String> = self
+            .images
+            .iter()
+            .enumerate()
+            .map(|(idx, image)| {
+                if image.slug.is_empty() {
+                    image_page_url(idx + 1, total, None)
+                        .trim_end_matches('/')
+                        .to_string()
+                } else {
+                    sanitize_slug(&slug_text(&image.slug, ascii))
+                }
+            })
+            .collect();
+        // Every name some image would get as-is, so an appended number
+        // never lands on another image's plain slug.
+        let mut taken: HashSet<String> = names.iter().cloned().collect();
+        // Position fallbacks can't move, so they're claimed up front.
+        let mut used: HashSet<String> = self
+            .images
+            .iter()
+            .zip(&names)
+            .filter(|(image, _)| image.slug.is_empty())
+            .map(|(_, name)| name.clone())
+            .collect();
+        for (image, name) in self.images.iter_mut().zip(names) {
+            if image.slug.is_empty() || used.insert(name.clone()) {
+                continue;
+            }
+            let unique = (1..)
+                .map(|n| match n {
+                    1 => format!("{name}-{}", image.number),
+                    _ => format!("{name}-{}-{n}", image.number),
+                })
+                .find(|candidate| !taken.contains(candidate))
+                .expect("unbounded candidates");
+            taken.insert(unique.clone());
+            used.insert(unique.clone());
+            image.slug = unique;
+        }
+    }
+}
 
 /// Whether a description is short enough to display as an inline caption.
 ///
-/// Short captions (≤160 chars, single line) are rendered as centered text
-/// directly beneath the image. Longer or multi-line descriptions get a
-/// scrollable container instead.
-fn is_short_caption(text: &str) -> bool {
-    text.len() <= SHORT_CAPTION_MAX_LEN && !text.contains('\n')
+/// Short captions (≤ `max_len` characters, single line) are rendered as
+/// centered text directly beneath the image. Longer or multi-line
+/// descriptions get a scrollable container instead. `max_len` comes from
+/// `[theme] caption_max_len` and counts Unicode scalar values, not bytes,
+/// so accented or CJK captions aren't penalized for their encoding.
+fn is_short_caption(text: &str, max_len: usize) -> bool {
+    !text.contains('\n') && text.chars().count() <= max_len
+}
+
+/// Render a short caption's inline markdown (emphasis, links, code spans)
+/// to HTML, with the site's opt-in description rewrites applied.
+///
+/// Raw HTML in the caption is escaped, not passed through. A caption that
+/// parses as anything but a single paragraph (`1. Dawn` is an ordered list
+/// to markdown) or that links to a `javascript:` URL is shown as plain
+/// text instead.
+fn render_caption(text: &str, config: &SiteConfig) -> Markup {
+    let mut events = Vec::new();
+    for event in Parser::new(text) {
+        match event {
+            Event::Start(Tag::Paragraph) | Event::End(TagEnd::Paragraph) => {}
+            Event::Html(raw) | Event::InlineHtml(raw) => events.push(Event::Text(raw)),
+            Event::Start(Tag::Link { ref dest_url, .. })
+                if dest_url
+                    .trim()
+                    .to_ascii_lowercase()
+                    .starts_with("javascript:") =>
+            {
+                return html! { (text) };
+            }
+            Event::Start(Tag::Emphasis | Tag::Strong | Tag::Strikethrough | Tag::Link { .. })
+            | Event::End(
+                TagEnd::Emphasis | TagEnd::Strong | TagEnd::Strikethrough | TagEnd::Link,
+            )
+            | Event::Text(_)
+            | Event::Code(_)
+            | Event::SoftBreak
+            | Event::HardBreak => events.push(event),
+            _ => return html! { (text) },
+        }
+    }
+    let mut caption = String::new();
+    md_html::push_html(&mut caption, events.into_iter());
+    if config.smart_typography {
+        caption = typography::smarten(&caption);
+    }
+    if config.external_links_new_tab {
+        caption = links::open_external_in_new_tab(&caption, config.base_url.as_deref());
+    }
+    PreEscaped(caption)
 }
 
 // ============================================================================
@@ -362,10 +709,6 @@ fn is_short_caption(text: &str) -> bool {
 /// a 1200-wide AVIF variant comfortably fits.
 const OG_IMAGE_TARGET_WIDTH: u32 = 1200;
 
-/// Breadcrumb separator used in og:description. Matches the on-page crumb
-/// (`site_header`) so the preview text echoes the site's visible hierarchy.
-const OG_CRUMB_SEP: &str = " › ";
-
 /// Open Graph / Twitter Card metadata for a single page.
 ///
 /// Populated only when `site.base_url` is set in config — that's a hard
@@ -431,20 +774,27 @@ fn absolute_url(base_url: &str, path: &str) -> String {
 }
 
 /// Build an og:description by joining site title + breadcrumb segments + any
-/// trailing labels (album title, image label) with `OG_CRUMB_SEP`.
+/// trailing labels (album title, image label) with `separator`, padded like
+/// the on-page crumb (`site_header`) so the preview text echoes the site's
+/// visible hierarchy.
 ///
 /// Photography sites often have untitled images or captionless albums, so a
 /// generic "Photo from the gallery" would be unhelpful. Echoing the crumb
 /// ("Gallery › NY › Night › 03. City") tells the reader exactly what they're
 /// about to open.
-fn og_description(site_title: &str, segments: &[(&str, &str)], trailing: &[&str]) -> String {
+fn og_description(
+    site_title: &str,
+    separator: &str,
+    segments: &[(&str, &str)],
+    trailing: &[&str],
+) -> String {
     let mut parts: Vec<&str> = Vec::with_capacity(1 + segments.len() + trailing.len());
     parts.push(site_title);
     for (seg_title, _) in segments {
         parts.push(seg_title);
     }
     parts.extend(trailing.iter().copied());
-    parts.join(OG_CRUMB_SEP)
+    parts.join(&format!(" {separator} "))
 }
 
 /// Find the image that corresponds to an album's displayed cover thumbnail
@@ -468,14 +818,16 @@ fn build_og_for_album(
     base_url: &str,
     album: &Album,
     navigation: &[NavItem],
-    site_title: &str,
+    config: &SiteConfig,
 ) -> Option<OgMeta> {
+    let site_title = &config.site_title;
+    let separator = &config.theme.breadcrumb_separator;
     let cover = album_cover_image(album)?;
     let variant = pick_og_variant(cover)?;
     let segments = path_to_breadcrumb_segments(&album.path, navigation);
     Some(OgMeta {
         title: album.title.clone(),
-        description: og_description(site_title, &segments, &[&album.title]),
+        description: og_description(site_title, separator, &segments, &[&album.title]),
         image_url: absolute_url(base_url, &variant.avif),
         page_url: absolute_url(base_url, &format!("{}/", album.path)),
         site_name: site_title.to_string(),
@@ -490,23 +842,25 @@ fn build_og_for_image(
     image: &Image,
     image_idx: usize,
     navigation: &[NavItem],
-    site_title: &str,
+    config: &SiteConfig,
 ) -> Option<OgMeta> {
+    let site_title = &config.site_title;
+    let separator = &config.theme.breadcrumb_separator;
     let variant = pick_og_variant(image)?;
-    let total = album.images.len();
-    let image_label = format_image_label(image_idx + 1, total, image.title.as_deref());
+    let image_label = album.image_label(image_idx);
     let segments = path_to_breadcrumb_segments(&album.path, navigation);
-    let page_url_path = format!(
-        "{}/{}",
-        album.path,
-        image_page_url(image_idx + 1, total, image.title.as_deref())
-    );
+    let page_url_path = format!("{}/{}", album.path, album.image_page_dir(image_idx));
     Some(OgMeta {
         title: image
             .title
             .clone()
-            .unwrap_or_else(|| format!("{} — {}", album.title, image_label)),
-        description: og_description(site_title, &segments, &[&album.title, &image_label]),
+            .unwrap_or_else(|| format!("{} {separator} {image_label}", album.title)),
+        description: og_description(
+            site_title,
+            separator,
+            &segments,
+            &[&album.title, &image_label],
+        ),
         image_url: absolute_url(base_url, &variant.avif),
         page_url: absolute_url(base_url, &page_url_path),
         site_name: site_title.to_string(),
@@ -526,8 +880,9 @@ fn build_og_for_gallery_list(
     nav_scope: &[NavItem],
     navigation: &[NavItem],
     albums: &[Album],
-    site_title: &str,
+    config: &SiteConfig,
 ) -> Option<OgMeta> {
+    let site_title = &config.site_title;
     let cover_album = first_album_in_nav(nav_scope, albums)?;
     let cover_image = album_cover_image(cover_album)?;
     let variant = pick_og_variant(cover_image)?;
@@ -540,7 +895,12 @@ fn build_og_for_gallery_list(
         site_title.to_string()
     } else {
         let segments = path_to_breadcrumb_segments(path, navigation);
-        og_description(site_title, &segments, &[title])
+        og_description(
+            site_title,
+            &config.theme.breadcrumb_separator,
+            &segments,
+            &[title],
+        )
     };
     Some(OgMeta {
         title: title.to_string(),
@@ -551,6 +911,16 @@ fn build_og_for_gallery_list(
     })
 }
 
+/// `<link rel="alternate" hreflang>` tags pointing at `page`, a path
+/// relative to the site root, on every `[i18n] alternates` site.
+fn hreflang_links(alternates: &BTreeMap<String, String>, page: &str) -> Markup {
+    html! {
+        @for (lang, base) in alternates {
+            link rel="alternate" hreflang=(lang) href={ (base.trim_end_matches('/')) "/" (page) };
+        }
+    }
+}
+
 /// Render the `<meta>` tags for an OgMeta into a Markup fragment to be
 /// inlined in `<head>`. Emits Open Graph + Twitter Card "summary_large_image"
 /// (WhatsApp/iMessage/Slack all read OG; Twitter/X needs the twitter:card hint
@@ -570,14 +940,191 @@ fn render_og_tags(og: &OgMeta) -> Markup {
     }
 }
 
+/// Generate the site into `output_dir`, replacing what was there.
+///
+/// Everything is written to a staging directory next to `output_dir` first.
+/// Only when that succeeds is the old output renamed aside, the staging
+/// directory renamed into its place, and the old one deleted. Renaming a
+/// directory over a non-empty one fails on Windows (and on Unix), hence
+/// the two renames: at no point does `output_dir` hold a mix of old and
+/// new files. On failure the staging directory is removed and the previous
+/// output is untouched.
+///
+/// Before the swap, every file in the old output that this build didn't
+/// write (a `CNAME`, `.nojekyll`, a deploy `.git`, pages of a removed
+/// album) is hard-linked or copied into the staging directory, so nothing
+/// `generate` doesn't own is lost. A symlink or mount point can't be
+/// swapped without losing what it points to, and a rename can fail (across
+/// devices, or on a directory held open); then the staged site is copied
+/// over the existing output instead.
+///
+/// An output directory that is, or contains, the source or processed
+/// directory (`--output .` with the default paths) is refused with
+/// [`GenerateError::OutputContainsInput`] before anything is written.
 pub fn generate(
     manifest_path: &Path,
     processed_dir: &Path,
     output_dir: &Path,
     source_dir: &Path,
-) -> Result<(), GenerateError> {
+) -> Result<GenerateOutput, GenerateError> {
+    let output_dir = &resolve_output_dir(output_dir)?;
+    for input in [source_dir, processed_dir] {
+        if contains_dir(output_dir, input)? {
+            return Err(GenerateError::OutputContainsInput(
+                output_dir.clone(),
+                input.to_path_buf(),
+            ));
+        }
+    }
+    let staging = sibling_dir(output_dir, "staging");
+    if staging.exists() {
+        // Left behind by a build that was killed.
+        fs::remove_dir_all(&staging)?;
+    }
+    let generated =
+        write_site(manifest_path, processed_dir, &staging, source_dir).and_then(|generated| {
+            if output_dir.is_dir() && !is_link_or_mount_point(output_dir) {
+                keep_unwritten_files(output_dir, &staging)?;
+            }
+            Ok(generated)
+        });
+    let generated = match generated {
+        Ok(generated) => generated,
+        Err(e) => {
+            let _ = fs::remove_dir_all(&staging);
+            return Err(e);
+        }
+    };
+    if is_link_or_mount_point(output_dir) || replace_dir(&staging, output_dir).is_err() {
+        let copied = copy_dir_into(&staging, output_dir);
+        fs::remove_dir_all(&staging)?;
+        copied?;
+    }
+    Ok(generated)
+}
+
+/// `dir` as an absolute path without `.` or `..` components, so that its
+/// parent is where its siblings go: the staging directory for `.` must not
+/// end up inside the output.
+fn resolve_output_dir(dir: &Path) -> std::io::Result<PathBuf> {
+    // `components()` drops `.`; a trailing `..` needs the file system.
+    let absolute: PathBuf = std::path::absolute(dir)?.components().collect();
+    if absolute.file_name().is_some() {
+        Ok(absolute)
+    } else {
+        fs::canonicalize(&absolute)
+    }
+}
+
+/// Whether `outer` is `inner` or one of its ancestors. Both are compared
+/// with symlinks resolved where they exist.
+fn contains_dir(outer: &Path, inner: &Path) -> std::io::Result<bool> {
+    let resolve = |dir: &Path| fs::canonicalize(dir).or_else(|_| resolve_output_dir(dir));
+    Ok(resolve(inner)?.starts_with(resolve(outer)?))
+}
+
+/// Hard-link (or, across file systems, copy) everything under `old` that
+/// isn't in `new` into `new`, so replacing `old` with `new` only replaces
+/// what the build wrote.
+fn keep_unwritten_files(old: &Path, new: &Path) -> std::io::Result<()> {
+    for entry in fs::read_dir(old)? {
+        let entry = entry?;
+        let dest = new.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            // A file the build wrote in place of the directory wins.
+            if fs::symlink_metadata(&dest).is_ok_and(|m| !m.is_dir()) {
+                continue;
+            }
+            fs::create_dir_all(&dest)?;
+            keep_unwritten_files(&entry.path(), &dest)?;
+        } else if fs::symlink_metadata(&dest).is_err() {
+            fs::hard_link(entry.path(), &dest)
+                .or_else(|_| fs::copy(entry.path(), &dest).map(|_| ()))?;
+        }
+    }
+    Ok(())
+}
+
+/// Whether `dir` is a symlink, or a mount point (on a different device from
+/// its parent), either of which renaming would replace rather than fill.
+fn is_link_or_mount_point(dir: &Path) -> bool {
+    let Ok(meta) = fs::symlink_metadata(dir) else {
+        return false;
+    };
+    if meta.file_type().is_symlink() {
+        return true;
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        if let Some(parent) = dir.parent()
+            && let Ok(parent_meta) = fs::metadata(parent)
+        {
+            return parent_meta.dev() != meta.dev();
+        }
+    }
+    false
+}
+
+/// Copy everything under `from` into `to`, overwriting files of the same
+/// name and leaving any others in `to` alone.
+fn copy_dir_into(from: &Path, to: &Path) -> std::io::Result<()> {
+    for entry in walkdir::WalkDir::new(from) {
+        let entry = entry?;
+        let dest = to.join(entry.path().strip_prefix(from).unwrap_or(entry.path()));
+        if entry.file_type().is_dir() {
+            fs::create_dir_all(&dest)?;
+        } else {
+            fs::copy(entry.path(), &dest)?;
+        }
+    }
+    Ok(())
+}
+
+/// `.dist.<suffix>` next to `dist`: a hidden sibling on the same file
+/// system, so renaming between the two is cheap and atomic.
+fn sibling_dir(dir: &Path, suffix: &str) -> PathBuf {
+    let name = dir
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "output".to_string());
+    dir.with_file_name(format!(".{name}.{suffix}"))
+}
+
+/// Move `new` to `target`, setting any existing `target` aside first and
+/// putting it back if the move fails. On error `new` is still in place.
+fn replace_dir(new: &Path, target: &Path) -> std::io::Result<()> {
+    if !target.exists() {
+        return fs::rename(new, target);
+    }
+    let old = sibling_dir(target, "old");
+    if old.exists() {
+        fs::remove_dir_all(&old)?;
+    }
+    fs::rename(target, &old)?;
+    if let Err(e) = fs::rename(new, target) {
+        fs::rename(&old, target)?;
+        return Err(e);
+    }
+    // The new site is in place; a leftover `old` is cleared by the next build.
+    let _ = fs::remove_dir_all(&old);
+    Ok(())
+}
+
+/// Write the whole site into `output_dir`, which [`generate`] then moves
+/// into place.
+fn write_site(
+    manifest_path: &Path,
+    processed_dir: &Path,
+    output_dir: &Path,
+    source_dir: &Path,
+) -> Result<GenerateOutput, GenerateError> {
     let manifest_content = fs::read_to_string(manifest_path)?;
-    let manifest: Manifest = serde_json::from_str(&manifest_content)?;
+    let mut manifest: Manifest = serde_json::from_str(&manifest_content)?;
+    for album in &mut manifest.albums {
+        album.disambiguate_slugs();
+    }
+    inline_small_thumbnails(&mut manifest.albums, processed_dir)?;
 
     // ── CSS assembly ──────────────────────────────────────────────────
     // The final CSS is built from THREE sources, injected in two places:
@@ -589,7 +1136,8 @@ pub fn generate(
     //
     //   2. Generated CSS vars  → config::generate_{color,theme,font}_css()
     //      Produces :root { --color-*, --mat-*, --font-*, … }
-    //      For local fonts, also includes @font-face declaration.
+    //      For local fonts, also includes @font-face declaration; for
+    //      [theme] nav_style = "bar" / "auto", the nav bar rules.
     //      Prepended to the <style> block so vars are defined before use.
     //
     //   3. Static CSS rules    → static/style.css (compiled in via include_str!)
@@ -602,47 +1150,52 @@ pub fn generate(
     let font_url = manifest.config.font.stylesheet_url();
     let color_css = config::generate_color_css(&manifest.config.colors);
     let theme_css = config::generate_theme_css(&manifest.config.theme);
-    let font_css = config::generate_font_css(&manifest.config.font);
+    let font_css = config::generate_font_css(&manifest.config.font, &manifest.config.base_path);
     let css = format!(
         "{}\n\n{}\n\n{}\n\n{}",
         color_css, theme_css, font_css, CSS_STATIC
     );
 
     fs::create_dir_all(output_dir)?;
+    // Absolute paths of everything written; made relative at the end.
+    let mut written: Vec<PathBuf> = Vec::new();
+    let mut withheld_originals = Vec::new();
 
     // ── PWA assets ────────────────────────────────────────────────────
     // Written *before* copying user assets so the user can override any
     // of them by placing files in their assets/ directory.
     //
-    // IMPORTANT: All PWA paths are absolute from the domain root
-    // (/sw.js, /site.webmanifest, /icon-*.png, scope "/", start_url "/").
-    // The generated site MUST be deployed at the root of its domain.
-    // Subdirectory deployment (e.g. example.com/gallery/) is not supported
-    // because the service worker scope, manifest paths, and cached asset
-    // URLs would all need to be rewritten with the subpath prefix.
+    // IMPORTANT: All PWA paths start at `base_path` ("/" unless the site
+    // is served from a subdirectory such as example.com/gallery/): the
+    // manifest, icons, scope and start_url below, and the service worker
+    // registration in base_document(). sw.js itself uses URLs relative to
+    // its own location, so it needs no rewriting.
     // ────────────────────────────────────────────────────────────────────
+    let root = manifest.config.base_path.as_str();
 
-    // 1. Dynamic Manifest (uses site title)
+    // 1. Dynamic Manifest (uses site title, base path, display mode and
+    //    configured background colors)
+    let theme_colors = ThemeColors::from_config(&manifest.config.colors);
     let manifest_json = serde_json::json!({
         "name": manifest.config.site_title,
         "short_name": manifest.config.site_title,
         "icons": [
             {
-                "src": "/icon-192.png",
+                "src": format!("{root}icon-192.png"),
                 "sizes": "192x192",
                 "type": "image/png"
             },
             {
-                "src": "/icon-512.png",
+                "src": format!("{root}icon-512.png"),
                 "sizes": "512x512",
                 "type": "image/png"
             }
         ],
-        "theme_color": "#ffffff",
-        "background_color": "#ffffff",
-        "display": "standalone",
-        "scope": "/",
-        "start_url": "/"
+        "theme_color": theme_colors.light,
+        "background_color": theme_colors.light,
+        "display": manifest.config.pwa.display,
+        "scope": root,
+        "start_url": root
     });
     fs::write(
         output_dir.join("site.webmanifest"),
@@ -657,29 +1210,65 @@ pub fn generate(
         &format!("const CACHE_NAME = 'simple-gal-v{}';", version),
     );
     fs::write(output_dir.join("sw.js"), sw_content)?;
+    written.push(output_dir.join("site.webmanifest"));
+    written.push(output_dir.join("sw.js"));
+
+    for (name, bytes) in [
+        ("icon-192.png", ICON_192),
+        ("icon-512.png", ICON_512),
+        ("apple-touch-icon.png", APPLE_TOUCH_ICON),
+        ("favicon.png", FAVICON_PNG),
+    ] {
+        fs::write(output_dir.join(name), bytes)?;
+        written.push(output_dir.join(name));
+    }
 
-    fs::write(output_dir.join("icon-192.png"), ICON_192)?;
-    fs::write(output_dir.join("icon-512.png"), ICON_512)?;
-    fs::write(output_dir.join("apple-touch-icon.png"), APPLE_TOUCH_ICON)?;
-    fs::write(output_dir.join("favicon.png"), FAVICON_PNG)?;
-
-    // Copy static assets (favicon, fonts, etc.) to output root
-    let assets_path = source_dir.join(&manifest.config.assets_dir);
-    if assets_path.is_dir() {
-        copy_dir_recursive(&assets_path, output_dir)?;
+    // 3. Icons resized from `icon_source` replace the embedded defaults.
+    if let Some(icon_source) = &manifest.config.icon_source {
+        generate_icons(&source_dir.join(icon_source), output_dir)?;
     }
 
-    // Copy processed images to output
-    copy_dir_recursive(processed_dir, output_dir)?;
+    // User assets and processed images, the `[output] asset_precedence`
+    // winner copied last. Either replaces a PWA default of the same name
+    // silently (that is how the defaults are customised); a clash between
+    // the two is reported in `overwritten`.
+    let order = match manifest.config.output.asset_precedence {
+        AssetPrecedence::Processed => [FileOrigin::Asset, FileOrigin::Processed],
+        AssetPrecedence::Assets => [FileOrigin::Processed, FileOrigin::Asset],
+    };
+    let mut origins: BTreeMap<PathBuf, FileOrigin> = BTreeMap::new();
+    let mut overwritten = Vec::new();
+    for origin in order {
+        let copied = match origin {
+            FileOrigin::Asset => copy_user_assets(&manifest, source_dir, output_dir)?,
+            FileOrigin::Processed => copy_dir_recursive(processed_dir, output_dir)?,
+        };
+        for path in copied {
+            if let Some(replaced) = origins.insert(path.clone(), origin)
+                && replaced != origin
+            {
+                overwritten.push(Overwrite {
+                    path: path.strip_prefix(output_dir).unwrap_or(&path).to_path_buf(),
+                    kept: origin,
+                    replaced,
+                });
+            }
+            written.push(path);
+        }
+    }
 
     // Detect favicon in output directory for <link rel="icon"> injection
-    let favicon_href = detect_favicon(output_dir);
+    let favicon_href = detect_favicon(output_dir, root);
 
-    // Detect convention-based custom snippets (custom.css, head.html, body-end.html)
-    let snippets = detect_custom_snippets(output_dir);
+    // Detect convention-based custom snippets (custom.css, head.html, body-end.html),
+    // then layer the inline config snippets on top
+    let snippets = detect_custom_snippets(output_dir).with_config(&manifest.config);
+
+    // Open Graph URLs are absolute: `base_url` plus `base_path`.
+    let site_url = manifest.config.site_url();
 
     // Generate index page
-    let index_og = manifest.config.base_url.as_deref().and_then(|base| {
+    let index_og = site_url.as_deref().and_then(|base| {
         build_og_for_gallery_list(
             base,
             &manifest.config.site_title,
@@ -687,7 +1276,7 @@ pub fn generate(
             &manifest.navigation,
             &manifest.navigation,
             &manifest.albums,
-            &manifest.config.site_title,
+            &manifest.config,
         )
     });
     let index_html = render_index(
@@ -699,68 +1288,57 @@ pub fn generate(
         index_og.as_ref(),
     );
     fs::write(output_dir.join("index.html"), index_html.into_string())?;
+    written.push(output_dir.join("index.html"));
 
-    let show_all_photos = show_all_photos_link(&manifest.config);
+    let ctx = PageContext::new(
+        &manifest,
+        &css,
+        font_url.as_deref(),
+        favicon_href.as_deref(),
+        &snippets,
+    );
 
     // Generate pages (content pages only, not link pages)
     for page in manifest.pages.iter().filter(|p| !p.is_link) {
-        let page_html = render_page(
-            page,
-            &manifest.navigation,
-            &manifest.pages,
-            &css,
-            font_url.as_deref(),
-            &manifest.config.site_title,
-            favicon_href.as_deref(),
-            &snippets,
-            show_all_photos,
-        );
+        let page_html = render_page(&ctx, page, &manifest.config);
         let filename = format!("{}.html", page.slug);
         fs::write(output_dir.join(&filename), page_html.into_string())?;
+        written.push(output_dir.join(&filename));
     }
 
     // Generate gallery-list pages for container directories
-    generate_gallery_list_pages(
+    written.extend(generate_gallery_list_pages(
+        &ctx,
         &manifest.navigation,
         &manifest.albums,
-        &manifest.navigation,
-        &manifest.pages,
-        &css,
-        font_url.as_deref(),
-        &manifest.config.site_title,
-        favicon_href.as_deref(),
-        &snippets,
-        show_all_photos,
-        manifest.config.base_url.as_deref(),
+        &manifest.config,
+        manifest.config.theme.index_layout,
+        site_url.as_deref(),
+        &manifest.config.i18n.alternates,
         output_dir,
-    )?;
+    )?);
 
     // Generate album pages
     for album in &manifest.albums {
         let album_dir = output_dir.join(&album.path);
         fs::create_dir_all(&album_dir)?;
 
-        let album_og = manifest.config.base_url.as_deref().and_then(|base| {
-            build_og_for_album(
-                base,
-                album,
-                &manifest.navigation,
-                &manifest.config.site_title,
-            )
+        let album_og = site_url.as_deref().and_then(|base| {
+            build_og_for_album(base, album, &manifest.navigation, &manifest.config)
         });
-        let album_html = render_album_page(
-            album,
-            &manifest.navigation,
-            &manifest.pages,
-            &css,
-            font_url.as_deref(),
-            &manifest.config.site_title,
-            favicon_href.as_deref(),
-            &snippets,
-            show_all_photos,
-            album_og.as_ref(),
-        );
+        let album_html = render_album_page(&ctx, album, album_og.as_ref());
         fs::write(album_dir.join("index.html"), album_html.into_string())?;
+        written.push(album_dir.join("index.html"));
+
+        if album.config.download.album_zip {
+            withheld_originals.extend(write_album_zip(
+                album,
+                processed_dir,
+                source_dir,
+                &album_dir,
+            )?);
+            written.push(album_dir.join(ALBUM_ZIP));
+        }
 
         // Generate image pages
         for (idx, image) in album.images.iter().enumerate() {
@@ -771,36 +1349,22 @@ pub fn generate(
             };
             let next = album.images.get(idx + 1);
 
-            let image_og = manifest.config.base_url.as_deref().and_then(|base| {
+            let image_og = site_url.as_deref().and_then(|base| {
                 build_og_for_image(
                     base,
                     album,
                     image,
                     idx,
                     &manifest.navigation,
-                    &manifest.config.site_title,
+                    &manifest.config,
                 )
             });
-            let image_html = render_image_page(
-                album,
-                image,
-                prev,
-                next,
-                &manifest.navigation,
-                &manifest.pages,
-                &css,
-                font_url.as_deref(),
-                &manifest.config.site_title,
-                favicon_href.as_deref(),
-                &snippets,
-                show_all_photos,
-                image_og.as_ref(),
-            );
-            let image_dir_name =
-                image_page_url(idx + 1, album.images.len(), image.title.as_deref());
+            let image_html = render_image_page(&ctx, album, image, prev, next, image_og.as_ref());
+            let image_dir_name = album.image_page_dir(idx);
             let image_dir = album_dir.join(&image_dir_name);
             fs::create_dir_all(&image_dir)?;
             fs::write(image_dir.join("index.html"), image_html.into_string())?;
+            written.push(image_dir.join("index.html"));
         }
     }
 
@@ -819,25 +1383,185 @@ pub fn generate(
             all_photos_dir.join("index.html"),
             all_photos_html.into_string(),
         )?;
+        written.push(all_photos_dir.join("index.html"));
     }
 
-    Ok(())
+    // Client-side search index (opt-in via [search] enabled = true)
+    if manifest.config.search.enabled {
+        let index = search_index(&manifest);
+        fs::write(
+            output_dir.join(SEARCH_INDEX),
+            serde_json::to_string(&index)?,
+        )?;
+        written.push(output_dir.join(SEARCH_INDEX));
+    }
+
+    // A user asset can overwrite a PWA default; count each path once.
+    let mut files: Vec<PathBuf> = written
+        .iter()
+        .map(|p| p.strip_prefix(output_dir).unwrap_or(p).to_path_buf())
+        .collect();
+    files.sort();
+    files.dedup();
+    Ok(GenerateOutput {
+        summary: GenerateSummary::from_manifest(&manifest, files.len()),
+        manifest,
+        files,
+        overwritten,
+        withheld_originals,
+    })
+}
+
+/// Copy the site's `assets_dir` to the output root, then the images that
+/// content pages embed from next to their markdown. Pages live at the
+/// output root, so their relative `src` resolves as-is.
+fn copy_user_assets(
+    manifest: &Manifest,
+    source_dir: &Path,
+    output_dir: &Path,
+) -> std::io::Result<Vec<PathBuf>> {
+    let mut copied = Vec::new();
+    let assets_path = source_dir.join(&manifest.config.assets_dir);
+    if assets_path.is_dir() {
+        copied.extend(copy_dir_recursive(&assets_path, output_dir)?);
+    }
+    let page_assets: BTreeSet<&str> = manifest
+        .pages
+        .iter()
+        .flat_map(|p| p.assets.iter().map(String::as_str))
+        .collect();
+    for name in page_assets {
+        fs::copy(source_dir.join(name), output_dir.join(name))?;
+        copied.push(output_dir.join(name));
+    }
+    Ok(copied)
+}
+
+/// Where a file copied into the output came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FileOrigin {
+    /// The site's `assets_dir`, or an image embedded by a content page.
+    Asset,
+    /// A processed image or thumbnail.
+    Processed,
+}
+
+impl std::fmt::Display for FileOrigin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            FileOrigin::Asset => "asset",
+            FileOrigin::Processed => "processed image",
+        })
+    }
+}
+
+/// An output path written by both an asset and a processed image. `kept`
+/// is the copy left in place, per `[output] asset_precedence`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Overwrite {
+    /// Relative to the output directory.
+    pub path: PathBuf,
+    pub kept: FileOrigin,
+    pub replaced: FileOrigin,
+}
+
+/// Everything [`generate`] produced, so callers don't have to re-read the
+/// manifest or walk the output directory.
+#[derive(Debug)]
+pub struct GenerateOutput {
+    /// The manifest the site was rendered from (slugs disambiguated).
+    pub manifest: Manifest,
+    pub summary: GenerateSummary,
+    /// Every file written, relative to the output directory, sorted.
+    pub files: Vec<PathBuf>,
+    /// Output paths an asset and a processed image both wrote to.
+    pub overwritten: Vec<Overwrite>,
+    /// Source paths of originals left out of album downloads because
+    /// their metadata couldn't be removed.
+    pub withheld_originals: Vec<String>,
+}
+
+/// Counts of what [`generate`] produced, for the CLI summary line and
+/// `--format json` payloads.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct GenerateSummary {
+    /// Album pages (one per album).
+    pub albums: usize,
+    /// Per-image pages across all albums.
+    pub image_pages: usize,
+    /// Markdown pages rendered to HTML (link pages excluded).
+    pub content_pages: usize,
+    /// Pages that are only external links in the navigation.
+    pub link_pages: usize,
+    /// Every file written to the output directory: HTML, PWA assets,
+    /// copied user assets and processed images.
+    pub files_written: usize,
+}
+
+impl GenerateSummary {
+    /// Page counts from the manifest; `files_written` is supplied by the caller.
+    pub fn from_manifest(manifest: &Manifest, files_written: usize) -> Self {
+        let link_pages = manifest.pages.iter().filter(|p| p.is_link).count();
+        Self {
+            albums: manifest.albums.len(),
+            image_pages: manifest.albums.iter().map(|a| a.images.len()).sum(),
+            content_pages: manifest.pages.len() - link_pages,
+            link_pages,
+            files_written,
+        }
+    }
+}
+
+impl std::fmt::Display for GenerateSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Generated {} albums, {} image pages, {} pages ({} files)",
+            self.albums, self.image_pages, self.content_pages, self.files_written
+        )
+    }
 }
 
 /// Check the output directory for common favicon files and return the href if found.
-fn detect_favicon(output_dir: &Path) -> Option<String> {
+fn detect_favicon(output_dir: &Path, root: &str) -> Option<String> {
     for (filename, _mime) in &[
         ("favicon.svg", "image/svg+xml"),
         ("favicon.ico", "image/x-icon"),
         ("favicon.png", "image/png"),
     ] {
         if output_dir.join(filename).exists() {
-            return Some(format!("/{}", filename));
+            return Some(format!("{}{}", root, filename));
         }
     }
     None
 }
 
+/// Resize the configured icon source into every entry of [`ICON_SIZES`],
+/// overwriting the embedded defaults in `output_dir`.
+fn generate_icons(source: &Path, output_dir: &Path) -> Result<(), GenerateError> {
+    if !source.is_file() {
+        return Err(BackendError::ProcessingFailed(format!(
+            "icon_source {} does not exist",
+            source.display()
+        ))
+        .into());
+    }
+    let backend = RustBackend::new();
+    for &(filename, size) in ICON_SIZES {
+        backend.thumbnail(&ThumbnailParams {
+            source: source.to_path_buf(),
+            output: output_dir.join(filename),
+            crop_width: size,
+            crop_height: size,
+            quality: Quality::default(),
+            sharpening: None,
+            focal: None,
+        })?;
+    }
+    Ok(())
+}
+
 /// Determine the MIME type for a favicon based on its extension.
 fn favicon_type(href: &str) -> &'static str {
     if href.ends_with(".svg") {
@@ -849,58 +1573,149 @@ fn favicon_type(href: &str) -> &'static str {
     }
 }
 
-fn copy_dir_recursive(src: &Path, dst: &Path) -> std::io::Result<()> {
+/// Copy `src` into `dst`, returning the paths of the copied files.
+fn copy_dir_recursive(src: &Path, dst: &Path) -> std::io::Result<Vec<PathBuf>> {
+    let mut copied = Vec::new();
     for entry in fs::read_dir(src)? {
         let entry = entry?;
         let src_path = entry.path();
         let dst_path = dst.join(entry.file_name());
 
         if src_path.is_dir() {
+            // Symlinked files and directories are copied as what they point
+            // to; a link back up the tree would copy forever.
+            if let Some(ancestor) = crate::scan::symlink_cycle(&src_path)? {
+                return Err(std::io::Error::other(format!(
+                    "symlink cycle: {} leads back to {}",
+                    src_path.display(),
+                    ancestor.display()
+                )));
+            }
             fs::create_dir_all(&dst_path)?;
-            copy_dir_recursive(&src_path, &dst_path)?;
+            copied.extend(copy_dir_recursive(&src_path, &dst_path)?);
         } else if src_path.extension().map(|e| e != "json").unwrap_or(true) {
             // Skip manifest.json, copy everything else
             fs::copy(&src_path, &dst_path)?;
+            copied.push(dst_path);
         }
     }
-    Ok(())
+    Ok(copied)
 }
 
-// ============================================================================
-// HTML Components
-// ============================================================================
+/// File name of an album's download, next to its `index.html`.
+const ALBUM_ZIP: &str = "album.zip";
 
-/// Renders the base HTML document structure.
+/// How much metadata to remove from originals copied into a download,
+/// from `[images] strip_gps` / `preserve_exif`.
+fn original_scrub(images: &ImagesConfig) -> Option<Scrub> {
+    match (images.strip_gps, images.preserve_exif) {
+        (false, _) => None,
+        (true, true) => Some(Scrub::Sensitive),
+        (true, false) => Some(Scrub::All),
+    }
+}
+
+/// Write the album's [`ALBUM_ZIP`] into `album_dir` (`[download] album_zip`).
 ///
-/// Font loading: for Google Fonts, loaded via a `<link>` tag, NOT via
-/// `@import` inside `<style>`. Browsers ignore or delay `@import` in
-/// inline `<style>` blocks. For local fonts, `@font-face` is in the CSS
-/// and `font_url` is `None`. See the CSS assembly comment in `generate()`.
-#[allow(clippy::too_many_arguments)]
+/// Holds the largest generated AVIF of each image, or with `contents =
+/// "originals"` the source files, with their location metadata removed
+/// per [`original_scrub`]. An original whose metadata can't be removed is
+/// left out, and its source path returned. Each file is written to the
+/// archive on its own, so memory use doesn't grow with the album. Photos
+/// are already compressed, so entries are stored rather than deflated.
+fn write_album_zip(
+    album: &Album,
+    processed_dir: &Path,
+    source_dir: &Path,
+    album_dir: &Path,
+) -> Result<Vec<String>, GenerateError> {
+    let file = fs::File::create(album_dir.join(ALBUM_ZIP))?;
+    let mut zip = zip::ZipWriter::new(std::io::BufWriter::new(file));
+    let options =
+        zip::write::SimpleFileOptions::default().compression_method(zip::CompressionMethod::Stored);
+    let scrub = match album.config.download.contents {
+        ZipContents::Originals => original_scrub(&album.config.images),
+        ZipContents::Web => None,
+    };
+    let mut withheld = Vec::new();
+    for image in &album.images {
+        let path = match album.config.download.contents {
+            ZipContents::Web => match image.generated.values().max_by_key(|v| v.width) {
+                Some(variant) => processed_dir.join(&variant.avif),
+                None => continue,
+            },
+            ZipContents::Originals => source_dir.join(&image.source_path),
+        };
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        match scrub {
+            Some(scrub) => {
+                let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+                let Some(data) = exif_scrub::scrub_metadata(&fs::read(&path)?, ext, scrub) else {
+                    withheld.push(image.source_path.clone());
+                    continue;
+                };
+                zip.start_file(name, options)?;
+                zip.write_all(&data)?;
+            }
+            None => {
+                zip.start_file(name, options)?;
+                std::io::copy(&mut fs::File::open(&path)?, &mut zip)?;
+            }
+        }
+    }
+    zip.finish()?;
+    Ok(withheld)
+}
+
+// ============================================================================
+// HTML Components
+// ============================================================================
+
+/// `id` of every page's `<main>` element, the target of the skip link that
+/// [`base_document`] puts first in `<body>`.
+const MAIN_ID: &str = "main-content";
+
+/// Renders the base HTML document structure.
+///
+/// Font loading: for Google Fonts, loaded via a `<link>` tag, NOT via
+/// `@import` inside `<style>`. Browsers ignore or delay `@import` in
+/// inline `<style>` blocks. For local fonts, `@font-face` is in the CSS
+/// and `font_url` is `None`. See the CSS assembly comment in `generate()`.
 fn base_document(
+    ctx: &PageContext,
     title: &str,
-    css: &str,
-    font_url: Option<&str>,
     body_class: Option<&str>,
     head_extra: Option<Markup>,
-    favicon_href: Option<&str>,
-    snippets: &CustomSnippets,
     og: Option<&OgMeta>,
     content: Markup,
 ) -> Markup {
+    let PageContext {
+        css,
+        font_url,
+        favicon_href,
+        snippets,
+        theme_colors,
+        ..
+    } = *ctx;
+    let root = ctx.crumbs.root;
     html! {
         (DOCTYPE)
         html lang="en" {
             head {
                 meta charset="UTF-8";
                 meta name="viewport" content="width=device-width, initial-scale=1.0";
+                meta name="theme-color" content=(theme_colors.light) media="(prefers-color-scheme: light)";
+                meta name="theme-color" content=(theme_colors.dark) media="(prefers-color-scheme: dark)";
                 title { (title) }
                 @if let Some(og) = og {
                     (render_og_tags(og))
                 }
                 // PWA links — absolute paths, requires root deployment (see PWA comment in generate())
-                link rel="manifest" href="/site.webmanifest";
-                link rel="apple-touch-icon" href="/apple-touch-icon.png";
+                link rel="manifest" href={ (root) "site.webmanifest" };
+                link rel="apple-touch-icon" href={ (root) "apple-touch-icon.png" };
                 @if let Some(href) = favicon_href {
                     link rel="icon" type=(favicon_type(href)) href=(href);
                 }
@@ -913,27 +1728,33 @@ fn base_document(
                 style { (PreEscaped(css)) }
                 // Custom CSS loaded after main styles so overrides win at equal specificity.
                 @if snippets.has_custom_css {
-                    link rel="stylesheet" href="/custom.css";
+                    link rel="stylesheet" href={ (root) "custom.css" };
                 }
                 @if let Some(extra) = head_extra {
                     (extra)
                 }
                 script {
-                    (PreEscaped(r#"
-                        if ('serviceWorker' in navigator && location.protocol !== 'file:') {
-                            window.addEventListener('load', () => {
-                                navigator.serviceWorker.register('/sw.js');
-                            });
-                        }
+                    // `root` is validated to plain URL-path characters, so it
+                    // can't break out of the string literal.
+                    (PreEscaped(format!(r#"
+                        if ('serviceWorker' in navigator && location.protocol !== 'file:') {{
+                            window.addEventListener('load', () => {{
+                                navigator.serviceWorker.register('{root}sw.js');
+                            }});
+                        }}
                         window.addEventListener('beforeinstallprompt', e => e.preventDefault());
-                    "#))
+                    "#)))
                 }
                 @if let Some(ref html) = snippets.head_html {
                     (PreEscaped(html))
                 }
             }
             body class=[body_class] {
+                a.skip-link href={ "#" (MAIN_ID) } { "Skip to content" }
                 (content)
+                @if snippets.attribution {
+                    (site_footer())
+                }
                 script { (PreEscaped(JS)) }
                 @if let Some(ref html) = snippets.body_end_html {
                     (PreEscaped(html))
@@ -943,6 +1764,119 @@ fn base_document(
     }
 }
 
+/// Browser-chrome colors for `<meta name="theme-color">` and the web
+/// manifest: the configured light and dark page backgrounds.
+#[derive(Debug, Clone, Copy)]
+struct ThemeColors<'a> {
+    light: &'a str,
+    dark: &'a str,
+}
+
+impl<'a> ThemeColors<'a> {
+    fn from_config(colors: &'a config::ColorConfig) -> Self {
+        ThemeColors {
+            light: &colors.light.background,
+            dark: &colors.dark.background,
+        }
+    }
+}
+
+/// Breadcrumb styling shared by every page: the label of the leading home
+/// link and the separator drawn between segments.
+#[derive(Debug, Clone, Copy)]
+struct Crumbs<'a> {
+    /// The site's `base_path`: the home link target, and the prefix of
+    /// every other root-relative URL on the page.
+    root: &'a str,
+    home: &'a str,
+    /// `logo`, shown in place of the `home` label.
+    logo: Option<&'a str>,
+    separator: &'a str,
+}
+
+impl<'a> Crumbs<'a> {
+    /// `base_path`, `home_label` (falling back to `site_title`), `logo` and
+    /// `theme.breadcrumb_separator`.
+    fn from_config(config: &'a SiteConfig) -> Self {
+        Crumbs {
+            root: &config.base_path,
+            home: config.home_label.as_deref().unwrap_or(&config.site_title),
+            logo: config.logo.as_deref(),
+            separator: &config.theme.breadcrumb_separator,
+        }
+    }
+
+    /// The leading home link: the logo with the label as its `alt`, or the
+    /// label alone.
+    fn home_link(&self) -> Markup {
+        html! {
+            @if let Some(logo) = self.logo {
+                a.site-logo href=(self.root) {
+                    img src={ (self.root) (logo.trim_start_matches('/')) } alt=(self.home);
+                }
+            } @else {
+                a href=(self.root) { (self.home) }
+            }
+        }
+    }
+
+    /// The separator padded with a space on each side.
+    fn sep(&self) -> Markup {
+        html! { " " (self.separator) " " }
+    }
+}
+
+/// Site-wide inputs every page is rendered with: the navigation, the
+/// stylesheet and fonts, breadcrumb styling and the custom snippets.
+#[derive(Debug, Clone, Copy)]
+struct PageContext<'a> {
+    navigation: &'a [NavItem],
+    pages: &'a [Page],
+    css: &'a str,
+    font_url: Option<&'a str>,
+    crumbs: Crumbs<'a>,
+    favicon_href: Option<&'a str>,
+    snippets: &'a CustomSnippets,
+    theme_colors: ThemeColors<'a>,
+    /// Whether the nav menu links the "All Photos" page.
+    show_all_photos: bool,
+}
+
+impl<'a> PageContext<'a> {
+    /// The context of `manifest`'s pages, with the stylesheet, font and
+    /// favicon resolved by [`generate`].
+    fn new(
+        manifest: &'a Manifest,
+        css: &'a str,
+        font_url: Option<&'a str>,
+        favicon_href: Option<&'a str>,
+        snippets: &'a CustomSnippets,
+    ) -> Self {
+        PageContext {
+            navigation: &manifest.navigation,
+            pages: &manifest.pages,
+            css,
+            font_url,
+            crumbs: Crumbs::from_config(&manifest.config),
+            favicon_href,
+            snippets,
+            theme_colors: ThemeColors::from_config(&manifest.config.colors),
+            show_all_photos: show_all_photos_link(&manifest.config),
+        }
+    }
+
+    /// The site navigation with `current_path` marked active.
+    fn nav(&self, current_path: &str) -> Markup {
+        render_nav(
+            self.navigation,
+            current_path,
+            self.pages,
+            self.show_all_photos,
+            self.crumbs.root,
+        )
+    }
+}
+
 /// Renders the site header with breadcrumb and navigation
 fn site_header(breadcrumb: Markup, nav: Markup) -> Markup {
     html! {
@@ -957,6 +1891,16 @@ fn site_header(breadcrumb: Markup, nav: Markup) -> Markup {
     }
 }
 
+/// Renders the `show_attribution` footer crediting Simple Gal.
+fn site_footer() -> Markup {
+    html! {
+        footer.site-footer {
+            "Built with "
+            a href="https://github.com/arthur-debert/simple-gal" { "Simple Gal" }
+        }
+    }
+}
+
 /// Renders the navigation menu (hamburger style, slides from right).
 ///
 /// Albums are listed first, then a separator, then pages (numbered pages only).
@@ -970,6 +1914,7 @@ pub fn render_nav(
     current_path: &str,
     pages: &[Page],
     show_all_photos: bool,
+    root: &str,
 ) -> Markup {
     let nav_pages: Vec<&Page> = pages.iter().filter(|p| p.in_nav).collect();
     let all_photos_current = current_path == "all-photos";
@@ -985,11 +1930,13 @@ pub fn render_nav(
             label.nav-close for="nav-toggle" { "×" }
             ul {
                 @for item in items {
-                    (render_nav_item(item, current_path))
+                    (render_nav_item(item, current_path, root))
                 }
                 @if show_all_photos {
                     li class=[all_photos_current.then_some("current")] {
-                        a href="/all-photos/" { "All Photos" }
+                        a href={ (root) "all-photos/" } aria-current=[all_photos_current.then_some("page")] {
+                            "All Photos"
+                        }
                     }
                 }
                 @if !nav_pages.is_empty() {
@@ -997,14 +1944,16 @@ pub fn render_nav(
                     @for page in &nav_pages {
                         @if page.is_link {
                             li {
-                                a href=(page.body.trim()) target="_blank" rel="noopener" {
+                                a href=(page.body.trim()) target="_blank" rel="noopener noreferrer" {
                                     (page.link_title)
                                 }
                             }
                         } @else {
                             @let is_current = current_path == page.slug;
                             li class=[is_current.then_some("current")] {
-                                a href={ "/" (page.slug) ".html" } { (page.link_title) }
+                                a href={ (root) (page.slug) ".html" } aria-current=[is_current.then_some("page")] {
+                                    (page.link_title)
+                                }
                             }
                         }
                     }
@@ -1015,19 +1964,28 @@ pub fn render_nav(
 }
 
 /// Renders a single navigation item (may have children)
-fn render_nav_item(item: &NavItem, current_path: &str) -> Markup {
-    let is_current =
-        item.path == current_path || current_path.starts_with(&format!("{}/", item.path));
+///
+/// The item for the current page gets `aria-current="page"`; a group that
+/// merely contains it gets `aria-current="true"`, so screen readers announce
+/// the trail without calling the group itself the current page.
+fn render_nav_item(item: &NavItem, current_path: &str, root: &str) -> Markup {
+    let is_page = item.path == current_path;
+    let is_current = is_page || current_path.starts_with(&format!("{}/", item.path));
+    let aria_current = match (is_page, is_current) {
+        (true, _) => Some("page"),
+        (false, true) => Some("true"),
+        _ => None,
+    };
 
     html! {
         li class=[is_current.then_some("current")] {
             @if item.children.is_empty() {
-                a href={ "/" (item.path) "/" } { (item.title) }
+                a href={ (root) (item.path) "/" } aria-current=[aria_current] { (item.title) }
             } @else {
-                a.nav-group href={ "/" (item.path) "/" } { (item.title) }
+                a.nav-group href={ (root) (item.path) "/" } aria-current=[aria_current] { (item.title) }
                 ul {
                     @for child in &item.children {
-                        (render_nav_item(child, current_path))
+                        (render_nav_item(child, current_path, root))
                     }
                 }
             }
@@ -1052,22 +2010,69 @@ fn render_index(
     og: Option<&OgMeta>,
 ) -> Markup {
     render_gallery_list_page(
+        &PageContext::new(manifest, css, font_url, favicon_href, snippets),
         &manifest.config.site_title,
         "",
         &collect_gallery_entries(&manifest.navigation, &manifest.albums),
         manifest.description.as_deref(),
-        &manifest.navigation,
-        &manifest.pages,
-        css,
-        font_url,
-        &manifest.config.site_title,
-        favicon_href,
-        snippets,
-        show_all_photos_link(&manifest.config),
+        manifest.config.theme.index_layout,
+        &manifest.config.i18n.alternates,
+        manifest
+            .config
+            .search
+            .enabled
+            .then(|| format!("{}{SEARCH_INDEX}", manifest.config.base_path))
+            .as_deref(),
         og,
     )
 }
 
+/// File name of the client-side search index, at the site root.
+const SEARCH_INDEX: &str = "search-index.json";
+
+/// One entry of [`SEARCH_INDEX`]: an album or an image page.
+#[derive(Debug, Serialize)]
+struct SearchEntry {
+    /// Page URL, starting at `base_path`.
+    url: String,
+    title: String,
+    /// Title of the album an image belongs to; absent on album entries.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    album: Option<String>,
+    /// Description as plain text on one line.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+}
+
+/// Project the manifest onto the search index: every listed album, then
+/// its images. Albums hidden from the navigation are left out, as on the
+/// All Photos page.
+fn search_index(manifest: &Manifest) -> Vec<SearchEntry> {
+    let root = manifest.config.base_path.as_str();
+    let one_line = |s: &str| Some(text::one_line(s)).filter(|line| !line.is_empty());
+    let mut entries = Vec::new();
+    for album in manifest.albums.iter().filter(|a| a.in_nav) {
+        entries.push(SearchEntry {
+            url: format!("{root}{}/", album.path),
+            title: album.title.clone(),
+            album: None,
+            description: album.plain_description().and_then(|plain| one_line(&plain)),
+        });
+        for (idx, image) in album.images.iter().enumerate() {
+            entries.push(SearchEntry {
+                url: format!("{root}{}/{}", album.path, album.image_page_dir(idx)),
+                title: image
+                    .title
+                    .clone()
+                    .unwrap_or_else(|| format!("Image {}", idx + 1)),
+                album: Some(album.title.clone()),
+                description: image.description.as_deref().and_then(one_line),
+            });
+        }
+    }
+    entries
+}
+
 /// Whether the nav menu should include the "All Photos" entry. Requires both
 /// `full_index.generates` and `full_index.show_link`, since a link without a
 /// generated target would be broken.
@@ -1076,29 +2081,18 @@ fn show_all_photos_link(config: &SiteConfig) -> bool {
 }
 
 /// Renders an album page with thumbnail grid
-#[allow(clippy::too_many_arguments)]
-fn render_album_page(
-    album: &Album,
-    navigation: &[NavItem],
-    pages: &[Page],
-    css: &str,
-    font_url: Option<&str>,
-    site_title: &str,
-    favicon_href: Option<&str>,
-    snippets: &CustomSnippets,
-    show_all_photos: bool,
-    og: Option<&OgMeta>,
-) -> Markup {
-    let nav = render_nav(navigation, &album.path, pages, show_all_photos);
+fn render_album_page(ctx: &PageContext, album: &Album, og: Option<&OgMeta>) -> Markup {
+    let crumbs = ctx.crumbs;
+    let nav = ctx.nav(&album.path);
 
-    let segments = path_to_breadcrumb_segments(&album.path, navigation);
+    let segments = path_to_breadcrumb_segments(&album.path, ctx.navigation);
     let breadcrumb = html! {
-        a href="/" { (site_title) }
+        (crumbs.home_link())
         @for (seg_title, seg_path) in &segments {
-            " › "
-            a href={ "/" (seg_path) "/" } { (seg_title) }
+            (crumbs.sep())
+            a href={ (crumbs.root) (seg_path) "/" } { (seg_title) }
         }
-        " › "
+        (crumbs.sep())
         (album.title)
     };
 
@@ -1109,43 +2103,83 @@ fn render_album_page(
     let album_prefix = format!("{}/", album.path);
     let strip_prefix =
         |path: &str| -> String { path.strip_prefix(&album_prefix).unwrap_or(path).to_string() };
+    let thumb_srcset = |image: &Image| {
+        let thumb_2x = image.thumbnail_2x.as_deref().map(strip_prefix);
+        density_srcset("", &strip_prefix(&image.thumbnail), thumb_2x.as_deref())
+    };
 
-    let has_desc = album.description.is_some();
+    // The collapsible description block. Its toggle, text and label stay
+    // siblings wherever it is placed, which the "Read more" CSS relies on.
+    let description = album.description.as_ref().map(|desc| {
+        html! {
+            input.desc-toggle type="checkbox" id="desc-toggle";
+            div.album-description { (PreEscaped(desc)) }
+            label.desc-expand for="desc-toggle" {
+                span.expand-more { "Read more" }
+                span.expand-less { "Show less" }
+            }
+        }
+    });
+    let (top_desc, bottom_desc) = match album.config.theme.description_position {
+        DescriptionPosition::Top => (description, None),
+        DescriptionPosition::Bottom => (None, description),
+    };
+    // `has-description` switches wide screens to the sidebar layout, which
+    // only makes sense with the description in the header.
+    let has_desc = top_desc.is_some();
+    // Thumbnails of differing shapes are letterboxed rather than cropped.
+    let variable_aspect = album.config.thumbnails.aspect_mode != AspectMode::Fixed;
     let content = html! {
         (site_header(breadcrumb, nav))
-        main.album-page.has-description[has_desc] {
+        main.album-page.has-description[has_desc] id=(MAIN_ID) {
             header.album-header {
                 h1 { (album.title) }
-                @if let Some(desc) = &album.description {
-                    input.desc-toggle type="checkbox" id="desc-toggle";
-                    div.album-description { (PreEscaped(desc)) }
-                    label.desc-expand for="desc-toggle" {
-                        span.expand-more { "Read more" }
-                        span.expand-less { "Show less" }
+                @if let Some(meta) = &album.meta {
+                    dl.album-meta {
+                        @for (label, value) in meta.entries() {
+                            div { dt { (label) } dd { (value) } }
+                        }
                     }
                 }
+                @if album.config.download.album_zip {
+                    a.album-download href=(ALBUM_ZIP) download { "Download album" }
+                }
+                @if let Some(desc) = top_desc {
+                    (desc)
+                }
             }
-            div.thumbnail-grid {
+            div.thumbnail-grid.variable-aspect[variable_aspect] {
                 @for (idx, image) in album.images.iter().enumerate() {
-                    a.thumb-link href=(image_page_url(idx + 1, album.images.len(), image.title.as_deref())) {
-                        img src=(strip_prefix(&image.thumbnail)) alt={ "Image " (idx + 1) } loading="lazy";
+                    a.thumb-link href=(album.image_page_dir(idx)) {
+                        @if let Some(ref data) = image.thumbnail_inline {
+                            img src=(data) alt={ "Image " (idx + 1) };
+                        } @else {
+                            img src=(strip_prefix(&image.thumbnail))
+                                srcset=[thumb_srcset(image)]
+                                alt={ "Image " (idx + 1) } loading="lazy";
+                        }
                     }
                 }
             }
+            @if let Some(desc) = bottom_desc {
+                footer.album-footer { (desc) }
+            }
         }
     };
 
-    base_document(
-        &album.title,
-        css,
-        font_url,
-        None,
-        None,
-        favicon_href,
-        snippets,
-        og,
-        content,
-    )
+    // The first thumbnails are usually above the fold but lazy-loaded like
+    // the rest; preloading them from the head lets the grid paint sooner.
+    let preload_count = album.config.thumbnails.preload as usize;
+    let head_extra = html! {
+        (hreflang_links(&album.config.i18n.alternates, &album_prefix))
+        // Inlined thumbnails arrive with the page itself.
+        @for image in album.images.iter().take(preload_count).filter(|i| i.thumbnail_inline.is_none()) {
+            link rel="preload" as="image" type="image/avif" href=(strip_prefix(&image.thumbnail))
+                imagesrcset=[thumb_srcset(image)];
+        }
+    };
+
+    base_document(ctx, &album.title, None, Some(head_extra), og, content)
 }
 
 /// Format an image's display label for breadcrumbs and page titles.
@@ -1170,23 +2204,16 @@ fn format_image_label(position: usize, total: usize, title: Option<&str>) -> Str
 }
 
 /// Renders an image viewer page
-#[allow(clippy::too_many_arguments)]
 fn render_image_page(
+    ctx: &PageContext,
     album: &Album,
     image: &Image,
     prev: Option<&Image>,
     next: Option<&Image>,
-    navigation: &[NavItem],
-    pages: &[Page],
-    css: &str,
-    font_url: Option<&str>,
-    site_title: &str,
-    favicon_href: Option<&str>,
-    snippets: &CustomSnippets,
-    show_all_photos: bool,
     og: Option<&OgMeta>,
 ) -> Markup {
-    let nav = render_nav(navigation, &album.path, pages, show_all_photos);
+    let crumbs = ctx.crumbs;
+    let nav = ctx.nav(&album.path);
 
     // Image pages live at `/{album.path}/{image_slug}/`, one level below the
     // album directory. Process-stage image paths are full root-relative
@@ -1220,21 +2247,20 @@ fn render_image_page(
 
     let srcset_avif: String = avif_srcset_for(image);
 
-    // Use middle size as default
-    let default_src = variants
-        .get(variants.len() / 2)
+    // Use the size nearest the configured default width as the `src` fallback
+    let default_width = album.config.images.default_width;
+    let default_src = nearest_variant(&variants, default_width)
         .map(|v| strip_prefix(&v.avif))
         .unwrap_or_default();
 
-    // Pick a single middle-size AVIF URL for adjacent image prefetch
-    let mid_avif = |img: &Image| -> String {
-        let v = sorted_variants(img);
-        v.get(v.len() / 2)
+    // Pick a single default-width AVIF URL for adjacent image prefetch
+    let default_avif = |img: &Image| -> String {
+        nearest_variant(&sorted_variants(img), default_width)
             .map(|variant| strip_prefix(&variant.avif))
             .unwrap_or_default()
     };
-    let prev_prefetch = prev.map(&mid_avif);
-    let next_prefetch = next.map(&mid_avif);
+    let prev_prefetch = prev.map(&default_avif);
+    let next_prefetch = next.map(&default_avif);
 
     // Calculate aspect ratio
     let (width, height) = image.dimensions;
@@ -1247,37 +2273,30 @@ fn render_image_page(
         .position(|i| i.number == image.number)
         .unwrap();
 
-    let total = album.images.len();
     let prev_url = match prev {
-        Some(p) => format!(
-            "../{}",
-            image_page_url(image_idx, total, p.title.as_deref())
-        ), // image_idx is 0-based = prev's 1-based
+        Some(_) => format!("../{}", album.image_page_dir(image_idx - 1)),
         None => "../".to_string(),
     };
 
     let next_url = match next {
-        Some(n) => format!(
-            "../{}",
-            image_page_url(image_idx + 2, total, n.title.as_deref())
-        ),
+        Some(_) => format!("../{}", album.image_page_dir(image_idx + 1)),
         None => "../".to_string(),
     };
 
     let display_idx = image_idx + 1;
-    let image_label = format_image_label(display_idx, album.images.len(), image.title.as_deref());
+    let image_label = album.image_label(image_idx);
     let page_title = format!("{} - {}", album.title, image_label);
 
-    let segments = path_to_breadcrumb_segments(&album.path, navigation);
+    let segments = path_to_breadcrumb_segments(&album.path, ctx.navigation);
     let breadcrumb = html! {
-        a href="/" { (site_title) }
+        (crumbs.home_link())
         @for (seg_title, seg_path) in &segments {
-            " › "
-            a href={ "/" (seg_path) "/" } { (seg_title) }
+            (crumbs.sep())
+            a href={ (crumbs.root) (seg_path) "/" } { (seg_title) }
         }
-        " › "
+        (crumbs.sep())
         a href="../" { (album.title) }
-        " › "
+        (crumbs.sep())
         (image_label)
     };
 
@@ -1300,26 +2319,25 @@ fn render_image_page(
         .images
         .iter()
         .enumerate()
-        .map(|(idx, img)| {
-            format!(
-                "../{}",
-                image_page_url(idx + 1, total, img.title.as_deref())
-            )
-        })
+        .map(|(idx, _)| format!("../{}", album.image_page_dir(idx)))
         .collect();
 
     let description = image.description.as_deref().filter(|d| !d.is_empty());
-    let caption_text = description.filter(|d| is_short_caption(d));
-    let description_text = description.filter(|d| !is_short_caption(d));
+    let caption_max_len = album.config.theme.caption_max_len as usize;
+    let caption_text = description.filter(|d| is_short_caption(d, caption_max_len));
+    let description_text = description.filter(|d| !is_short_caption(d, caption_max_len));
 
     let body_class = match description {
-        Some(desc) if is_short_caption(desc) => "image-view has-caption",
+        Some(desc) if is_short_caption(desc, caption_max_len) => "image-view has-caption",
         Some(_) => "image-view has-description",
         None => "image-view",
     };
 
-    // Build <head> extras: render-blocking link + adjacent image prefetches
+    // Build <head> extras: translations, render-blocking link + adjacent
+    // image prefetches
+    let page_path = format!("{album_prefix}{}", album.image_page_dir(image_idx));
     let head_extra = html! {
+        (hreflang_links(&album.config.i18n.alternates, &page_path))
         link rel="expect" href="#main-image" blocking="render";
         @if let Some(ref href) = prev_prefetch {
             link rel="prefetch" as="image" href=(href);
@@ -1331,16 +2349,19 @@ fn render_image_page(
 
     let content = html! {
         (site_header(breadcrumb, nav))
-        main style=(aspect_style) {
+        main id=(MAIN_ID) style=(aspect_style) {
             div.image-page {
                 figure.image-frame {
-                    img #main-image src=(default_src) srcset=(srcset_avif) sizes=(sizes_attr) alt=(alt_text);
+                    img #main-image class=[album.config.theme.image_fade.then_some("image-fade")]
+                        src=(default_src) srcset=(srcset_avif) sizes=(sizes_attr) alt=(alt_text);
                 }
-                p.print-credit {
-                    (album.title) " › " (image_label)
+                @if album.config.theme.print_credit {
+                    p.print-credit {
+                        (album.title) (crumbs.sep()) (image_label)
+                    }
                 }
                 @if let Some(text) = caption_text {
-                    p.image-caption { (text) }
+                    p.image-caption { (render_caption(text, &album.config)) }
                 }
             }
             @if let Some(text) = description_text {
@@ -1359,51 +2380,194 @@ fn render_image_page(
             }
             a.nav-prev href=(prev_url) aria-label="Previous image" {}
             a.nav-next href=(next_url) aria-label="Next image" {}
+            // Always-visible way back to the grid; nav.js maps Escape to the same href.
+            a.back-to-album href="../" aria-label={ "Back to " (album.title) } title={ "Back to " (album.title) } {
+                svg viewBox="0 0 16 16" aria-hidden="true" {
+                    rect x="1" y="1" width="6" height="6" {}
+                    rect x="9" y="1" width="6" height="6" {}
+                    rect x="1" y="9" width="6" height="6" {}
+                    rect x="9" y="9" width="6" height="6" {}
+                }
+            }
         }
     };
 
     base_document(
+        ctx,
         &page_title,
-        css,
-        font_url,
         Some(body_class),
         Some(head_extra),
-        favicon_href,
-        snippets,
         og,
         content,
     )
 }
 
+/// A `##`/`###` heading listed in a content page's table of contents.
+struct TocEntry {
+    level: u8,
+    id: String,
+    text: String,
+}
+
+/// Give every heading in a markdown event stream an `id` (derived from its
+/// text, made unique with the first free `-2`, `-3`, … suffix) and collect
+/// the `##` and `###` headings, in document order, for the table of
+/// contents. Suffixed ids are checked against every id already given, so
+/// "Shooting 2" followed by a second "Shooting" can't share `shooting-2`.
+fn with_heading_ids(mut events: Vec<Event<'_>>) -> (Vec<Event<'_>>, Vec<TocEntry>) {
+    let mut entries = Vec::new();
+    let mut used: HashSet<String> = HashSet::new();
+    let mut i = 0;
+    while i < events.len() {
+        let Event::Start(Tag::Heading { level, .. }) = &events[i] else {
+            i += 1;
+            continue;
+        };
+        let level = *level as u8;
+        let mut text = String::new();
+        let mut end = i + 1;
+        while !matches!(events[end], Event::End(TagEnd::Heading(_))) {
+            if let Event::Text(t) | Event::Code(t) = &events[end] {
+                text.push_str(t);
+            }
+            end += 1;
+        }
+        let base = heading_id(&text);
+        let id = std::iter::once(base.clone())
+            .chain((2..).map(|n| format!("{base}-{n}")))
+            .find(|id| !used.contains(id))
+            .expect("an unused suffix exists");
+        used.insert(id.clone());
+        if let Event::Start(Tag::Heading { id: slot, .. }) = &mut events[i] {
+            *slot = Some(id.clone().into());
+        }
+        if (2..=3).contains(&level) {
+            entries.push(TocEntry { level, id, text });
+        }
+        i = end + 1;
+    }
+    (events, entries)
+}
+
+/// Anchor id for a heading: lowercase letters and digits, with every run of
+/// anything else collapsed to one `-`.
+fn heading_id(text: &str) -> String {
+    let mut id = String::with_capacity(text.len());
+    for c in text.chars() {
+        if c.is_alphanumeric() {
+            id.extend(c.to_lowercase());
+        } else if !id.is_empty() && !id.ends_with('-') {
+            id.push('-');
+        }
+    }
+    let id = id.trim_end_matches('-');
+    if id.is_empty() {
+        "section".to_string()
+    } else {
+        id.to_string()
+    }
+}
+
+/// Syntect's bundled syntaxes and themes, loaded on first use so sites
+/// without `[pages] highlight_code` never pay for them.
+#[cfg(feature = "highlight")]
+static SYNTAXES: LazyLock<SyntaxSet> = LazyLock::new(SyntaxSet::load_defaults_newlines);
+#[cfg(feature = "highlight")]
+static HIGHLIGHT_THEME: LazyLock<Theme> = LazyLock::new(|| {
+    ThemeSet::load_defaults()
+        .themes
+        .remove("base16-ocean.dark")
+        .expect("syntect bundles base16-ocean.dark")
+});
+
+/// Replace fenced code blocks whose language syntect knows with
+/// inline-styled highlighted HTML. Blocks without a language, with an
+/// unknown one, or indented code blocks are left as plain `<pre><code>`.
+#[cfg(feature = "highlight")]
+fn highlight_code_blocks(events: Vec<Event<'_>>) -> Vec<Event<'_>> {
+    let mut out = Vec::with_capacity(events.len());
+    let mut events = events.into_iter();
+    while let Some(event) = events.next() {
+        let syntax = match &event {
+            Event::Start(Tag::CodeBlock(CodeBlockKind::Fenced(lang))) => lang
+                .split_whitespace()
+                .next()
+                .and_then(|token| SYNTAXES.find_syntax_by_token(token)),
+            _ => None,
+        };
+        let Some(syntax) = syntax else {
+            out.push(event);
+            continue;
+        };
+        let mut code = String::new();
+        for inner in events.by_ref() {
+            match inner {
+                Event::Text(text) => code.push_str(&text),
+                Event::End(TagEnd::CodeBlock) => break,
+                _ => {}
+            }
+        }
+        match highlighted_html_for_string(&code, &SYNTAXES, syntax, &HIGHLIGHT_THEME) {
+            Ok(html) => out.push(Event::Html(html.into())),
+            Err(_) => {
+                out.push(event);
+                out.push(Event::Text(code.into()));
+                out.push(Event::End(TagEnd::CodeBlock));
+            }
+        }
+    }
+    out
+}
+
 /// Renders a content page from markdown
-#[allow(clippy::too_many_arguments)]
-fn render_page(
-    page: &Page,
-    navigation: &[NavItem],
-    pages: &[Page],
-    css: &str,
-    font_url: Option<&str>,
-    site_title: &str,
-    favicon_href: Option<&str>,
-    snippets: &CustomSnippets,
-    show_all_photos: bool,
-) -> Markup {
-    let nav = render_nav(navigation, &page.slug, pages, show_all_photos);
+fn render_page(ctx: &PageContext, page: &Page, config: &SiteConfig) -> Markup {
+    let settings = &config.pages;
+    let crumbs = ctx.crumbs;
+    let nav = ctx.nav(&page.slug);
 
     // Convert markdown to HTML
-    let parser = Parser::new(&page.body);
+    let mut events: Vec<Event> = Parser::new(&page.body).collect();
+    // Without the `highlight` feature, config validation rejects the option.
+    #[cfg(feature = "highlight")]
+    if settings.highlight_code {
+        events = highlight_code_blocks(events);
+    }
+    let toc_entries = if page.toc.unwrap_or(settings.toc) {
+        let (with_ids, entries) = with_heading_ids(events);
+        events = with_ids;
+        entries
+    } else {
+        Vec::new()
+    };
     let mut body_html = String::new();
-    md_html::push_html(&mut body_html, parser);
+    md_html::push_html(&mut body_html, events.into_iter());
+    if config.smart_typography {
+        body_html = typography::smarten(&body_html);
+    }
+    if config.external_links_new_tab {
+        body_html = links::open_external_in_new_tab(&body_html, config.base_url.as_deref());
+    }
 
     let breadcrumb = html! {
-        a href="/" { (site_title) }
-        " › "
+        (crumbs.home_link())
+        (crumbs.sep())
         (page.title)
     };
 
     let content = html! {
         (site_header(breadcrumb, nav))
-        main.page {
+        main.page id=(MAIN_ID) {
+            @if !toc_entries.is_empty() {
+                nav.page-toc aria-label="Contents" {
+                    ol {
+                        @for entry in &toc_entries {
+                            li class={ "toc-level-" (entry.level) } {
+                                a href={ "#" (entry.id) } { (entry.text) }
+                            }
+                        }
+                    }
+                }
+            }
             article.page-content {
                 (PreEscaped(body_html))
             }
@@ -1411,13 +2575,13 @@ fn render_page(
     };
 
     base_document(
+        ctx,
         &page.title,
-        css,
-        font_url,
-        None,
         None,
-        favicon_href,
-        snippets,
+        Some(hreflang_links(
+            &config.i18n.alternates,
+            &format!("{}.html", page.slug),
+        )),
         None,
         content,
     )
@@ -1425,36 +2589,33 @@ fn render_page(
 
 /// Renders a gallery-list page for a container directory (e.g. /NY/).
 ///
-/// Structurally identical to the index page but parameterized: shows a grid
-/// of album cards for the container's children.
+/// Structurally identical to the index page but parameterized: shows the
+/// container's children as album cards in the configured `layout`.
 #[allow(clippy::too_many_arguments)]
 fn render_gallery_list_page(
+    ctx: &PageContext,
     title: &str,
     path: &str,
     entries: &[GalleryEntry],
     description: Option<&str>,
-    navigation: &[NavItem],
-    pages: &[Page],
-    css: &str,
-    font_url: Option<&str>,
-    site_title: &str,
-    favicon_href: Option<&str>,
-    snippets: &CustomSnippets,
-    show_all_photos: bool,
+    layout: IndexLayout,
+    alternates: &BTreeMap<String, String>,
+    search_index: Option<&str>,
     og: Option<&OgMeta>,
 ) -> Markup {
-    let nav = render_nav(navigation, path, pages, show_all_photos);
+    let crumbs = ctx.crumbs;
+    let nav = ctx.nav(path);
 
     let is_root = path.is_empty();
-    let segments = path_to_breadcrumb_segments(path, navigation);
+    let segments = path_to_breadcrumb_segments(path, ctx.navigation);
     let breadcrumb = html! {
-        a href="/" { (site_title) }
+        (crumbs.home_link())
         @if !is_root {
             @for (seg_title, seg_path) in &segments {
-                " › "
-                a href={ "/" (seg_path) "/" } { (seg_title) }
+                (crumbs.sep())
+                a href={ (crumbs.root) (seg_path) "/" } { (seg_title) }
             }
-            " › "
+            (crumbs.sep())
             (title)
         }
     };
@@ -1465,7 +2626,15 @@ fn render_gallery_list_page(
     };
     let content = html! {
         (site_header(breadcrumb, nav))
-        main class=(main_class) {
+        main id=(MAIN_ID) class=(main_class) {
+            @if let Some(index) = search_index {
+                form.site-search role="search" data-index=(index) {
+                    input type="search" name="q" placeholder="Search"
+                        aria-label="Search albums and photos" autocomplete="off";
+                    ol.search-results hidden {}
+                }
+                script { (PreEscaped(SEARCH_JS)) }
+            }
             @if let Some(desc) = description {
                 header.index-header {
                     h1 { (title) }
@@ -1477,32 +2646,59 @@ fn render_gallery_list_page(
                     }
                 }
             }
-            div.album-grid {
-                @for entry in entries {
-                    a.album-card href={ "/" (entry.path) "/" } {
-                        @if let Some(ref thumb) = entry.thumbnail {
-                            img src={ "/" (thumb) } alt=(entry.title) loading="lazy";
-                        }
-                        span.album-title { (entry.title) }
-                    }
-                }
+            @match layout {
+                IndexLayout::Grid => div.album-grid { (album_cards(entries, false, crumbs.root)) },
+                IndexLayout::Masonry => div.album-masonry { (album_cards(entries, false, crumbs.root)) },
+                IndexLayout::List => div.album-list { (album_cards(entries, true, crumbs.root)) },
             }
         }
     };
+    let page = if is_root {
+        String::new()
+    } else {
+        format!("{path}/")
+    };
 
     base_document(
+        ctx,
         title,
-        css,
-        font_url,
         None,
-        None,
-        favicon_href,
-        snippets,
+        Some(hreflang_links(alternates, &page)),
         og,
         content,
     )
 }
 
+/// Album cards for a gallery-list page. With `with_excerpt`, each card
+/// wraps its title and description excerpt in an `album-text` block so
+/// the list layout can place them beside the thumbnail. Links start at
+/// `root`, the site's `base_path`.
+fn album_cards(entries: &[GalleryEntry], with_excerpt: bool, root: &str) -> Markup {
+    html! {
+        @for entry in entries {
+            a.album-card href={ (root) (entry.path) "/" } {
+                @if let Some(ref data) = entry.thumbnail_inline {
+                    img src=(data) alt=(entry.title);
+                } @else if let Some(ref thumb) = entry.thumbnail {
+                    img src={ (root) (thumb) }
+                        srcset=[density_srcset(root, thumb, entry.thumbnail_2x.as_deref())]
+                        alt=(entry.title) loading="lazy";
+                }
+                @if with_excerpt {
+                    div.album-text {
+                        span.album-title { (entry.title) }
+                        @if let Some(ref excerpt) = entry.excerpt {
+                            p.album-excerpt { (excerpt) }
+                        }
+                    }
+                } @else {
+                    span.album-title { (entry.title) }
+                }
+            }
+        }
+    }
+}
+
 /// Renders the site-wide "All Photos" page — a single thumbnail grid containing
 /// every image from every public (numbered) album. Uses the full-index thumbnails
 /// generated in the process stage, with gap and aspect ratio controlled by
@@ -1518,16 +2714,13 @@ fn render_full_index_page(
     let path = "all-photos";
     let fi = &manifest.config.full_index;
 
-    let nav = render_nav(
-        &manifest.navigation,
-        path,
-        &manifest.pages,
-        show_all_photos_link(&manifest.config),
-    );
+    let ctx = PageContext::new(manifest, css, font_url, favicon_href, snippets);
+    let crumbs = ctx.crumbs;
+    let nav = ctx.nav(path);
 
     let breadcrumb = html! {
-        a href="/" { (manifest.config.site_title) }
-        " › "
+        (crumbs.home_link())
+        (crumbs.sep())
         (title)
     };
 
@@ -1555,7 +2748,6 @@ fn render_full_index_page(
         if !album.in_nav {
             continue;
         }
-        let total = album.images.len();
         for (idx, image) in album.images.iter().enumerate() {
             let Some(ref thumb) = image.full_index_thumbnail else {
                 continue;
@@ -1567,14 +2759,14 @@ fn render_full_index_page(
                 // album; skip the duplicate.
                 continue;
             }
-            let image_dir = image_page_url(idx + 1, total, image.title.as_deref());
-            let link = format!("/{}/{}", album.path, image_dir);
+            let image_dir = album.image_page_dir(idx);
+            let link = format!("{}{}/{}", crumbs.root, album.path, image_dir);
             let alt = match &image.title {
                 Some(t) => format!("{} - {}", album.title, t),
                 None => format!("{} - Image {}", album.title, idx + 1),
             };
             entries.push(FullIndexEntry {
-                thumbnail: format!("/{}", thumb),
+                thumbnail: format!("{}{}", crumbs.root, thumb),
                 link,
                 alt,
                 album_title: &album.title,
@@ -1607,7 +2799,7 @@ fn render_full_index_page(
 
     let content = html! {
         (site_header(breadcrumb, nav))
-        main.album-page.full-index-page style=(main_style) {
+        main.album-page.full-index-page id=(MAIN_ID) style=(main_style) {
             header.album-header {
                 h1 { (title) }
             }
@@ -1622,34 +2814,32 @@ fn render_full_index_page(
     };
 
     base_document(
+        &ctx,
         title,
-        css,
-        font_url,
         None,
-        None,
-        favicon_href,
-        snippets,
+        Some(hreflang_links(
+            &manifest.config.i18n.alternates,
+            &format!("{path}/"),
+        )),
         None,
         content,
     )
 }
 
-/// Walk the navigation tree and generate gallery-list pages for every container.
+/// Walk the navigation tree and generate gallery-list pages for every
+/// container. Returns the paths of the pages written.
 #[allow(clippy::too_many_arguments)]
 fn generate_gallery_list_pages(
+    ctx: &PageContext,
     items: &[NavItem],
     albums: &[Album],
-    navigation: &[NavItem],
-    pages: &[Page],
-    css: &str,
-    font_url: Option<&str>,
-    site_title: &str,
-    favicon_href: Option<&str>,
-    snippets: &CustomSnippets,
-    show_all_photos: bool,
+    config: &SiteConfig,
+    layout: IndexLayout,
     base_url: Option<&str>,
+    alternates: &BTreeMap<String, String>,
     output_dir: &Path,
-) -> Result<(), GenerateError> {
+) -> Result<Vec<PathBuf>, GenerateError> {
+    let mut written = Vec::new();
     for item in items {
         if !item.children.is_empty() {
             let entries = collect_gallery_entries(&item.children, albums);
@@ -1659,48 +2849,41 @@ fn generate_gallery_list_pages(
                     &item.title,
                     &item.path,
                     &item.children,
-                    navigation,
+                    ctx.navigation,
                     albums,
-                    site_title,
+                    config,
                 )
             });
             let page_html = render_gallery_list_page(
+                ctx,
                 &item.title,
                 &item.path,
                 &entries,
                 item.description.as_deref(),
-                navigation,
-                pages,
-                css,
-                font_url,
-                site_title,
-                favicon_href,
-                snippets,
-                show_all_photos,
+                layout,
+                alternates,
+                None,
                 og.as_ref(),
             );
             let dir = output_dir.join(&item.path);
             fs::create_dir_all(&dir)?;
             fs::write(dir.join("index.html"), page_html.into_string())?;
+            written.push(dir.join("index.html"));
 
             // Recurse into children
-            generate_gallery_list_pages(
+            written.extend(generate_gallery_list_pages(
+                ctx,
                 &item.children,
                 albums,
-                navigation,
-                pages,
-                css,
-                font_url,
-                site_title,
-                favicon_href,
-                snippets,
-                show_all_photos,
+                config,
+                layout,
                 base_url,
+                alternates,
                 output_dir,
-            )?;
+            )?);
         }
     }
-    Ok(())
+    Ok(written)
 }
 
 // ============================================================================
@@ -1715,6 +2898,44 @@ mod tests {
         CustomSnippets::default()
     }
 
+    fn theme_colors() -> ThemeColors<'static> {
+        ThemeColors {
+            light: "#ffffff",
+            dark: "#000000",
+        }
+    }
+
+    fn crumbs(home: &str) -> Crumbs<'_> {
+        Crumbs {
+            root: "/",
+            home,
+            logo: None,
+            separator: "›",
+        }
+    }
+
+    static NO_SNIPPETS: CustomSnippets = CustomSnippets {
+        has_custom_css: false,
+        head_html: None,
+        body_end_html: None,
+        attribution: false,
+    };
+
+    /// Context of a page on a site with no navigation, stylesheet or snippets.
+    fn page_ctx() -> PageContext<'static> {
+        PageContext {
+            navigation: &[],
+            pages: &[],
+            css: "",
+            font_url: None,
+            crumbs: crumbs("Gallery"),
+            favicon_href: None,
+            snippets: &NO_SNIPPETS,
+            theme_colors: theme_colors(),
+            show_all_photos: false,
+        }
+    }
+
     fn make_page(slug: &str, link_title: &str, in_nav: bool, is_link: bool) -> Page {
         Page {
             title: link_title.to_string(),
@@ -1728,6 +2949,8 @@ mod tests {
             in_nav,
             sort_key: if in_nav { 40 } else { u32::MAX },
             is_link,
+            assets: vec![],
+            toc: None,
         }
     }
 
@@ -1740,7 +2963,7 @@ mod tests {
             description: None,
             children: vec![],
         }];
-        let html = render_nav(&items, "", &[], false).into_string();
+        let html = render_nav(&items, "", &[], false, "/").into_string();
         assert!(html.contains("Album One"));
         assert!(html.contains("/010-one/"));
     }
@@ -1748,7 +2971,7 @@ mod tests {
     #[test]
     fn nav_includes_pages() {
         let pages = vec![make_page("about", "About", true, false)];
-        let html = render_nav(&[], "", &pages, false).into_string();
+        let html = render_nav(&[], "", &pages, false, "/").into_string();
         assert!(html.contains("About"));
         assert!(html.contains("/about.html"));
     }
@@ -1756,7 +2979,7 @@ mod tests {
     #[test]
     fn nav_hides_unnumbered_pages() {
         let pages = vec![make_page("notes", "Notes", false, false)];
-        let html = render_nav(&[], "", &pages, false).into_string();
+        let html = render_nav(&[], "", &pages, false, "/").into_string();
         assert!(!html.contains("Notes"));
         // No separator either when no nav pages
         assert!(!html.contains("nav-separator"));
@@ -1765,7 +2988,7 @@ mod tests {
     #[test]
     fn nav_renders_link_page_as_external() {
         let pages = vec![make_page("github", "GitHub", true, true)];
-        let html = render_nav(&[], "", &pages, false).into_string();
+        let html = render_nav(&[], "", &pages, false, "/").into_string();
         assert!(html.contains("GitHub"));
         assert!(html.contains("https://example.com"));
         assert!(html.contains("target=\"_blank\""));
@@ -1789,16 +3012,44 @@ mod tests {
                 children: vec![],
             },
         ];
-        let html = render_nav(&items, "020-second", &[], false).into_string();
+        let html = render_nav(&items, "020-second", &[], false, "/").into_string();
         // The second item should have the current class
         assert!(html.contains(r#"class="current"#));
+        assert!(html.contains(r#"<a href="/020-second/" aria-current="page">"#));
+        assert!(html.contains(r#"<a href="/010-first/">"#));
+        assert_eq!(html.matches("aria-current").count(), 1);
     }
 
     #[test]
     fn nav_marks_current_page() {
-        let pages = vec![make_page("about", "About", true, false)];
-        let html = render_nav(&[], "about", &pages, false).into_string();
+        let pages = vec![
+            make_page("about", "About", true, false),
+            make_page("contact", "Contact", true, false),
+        ];
+        let html = render_nav(&[], "about", &pages, false, "/").into_string();
         assert!(html.contains(r#"class="current"#));
+        assert!(html.contains(r#"<a href="/about.html" aria-current="page">"#));
+        assert!(html.contains(r#"<a href="/contact.html">"#));
+    }
+
+    #[test]
+    fn nav_group_containing_current_page_is_not_the_page() {
+        let items = vec![NavItem {
+            title: "Parent".to_string(),
+            path: "010-parent".to_string(),
+            source_dir: String::new(),
+            description: None,
+            children: vec![NavItem {
+                title: "Child".to_string(),
+                path: "010-parent/010-child".to_string(),
+                source_dir: String::new(),
+                description: None,
+                children: vec![],
+            }],
+        }];
+        let html = render_nav(&items, "010-parent/010-child", &[], false, "/").into_string();
+        assert!(html.contains(r#"href="/010-parent/" aria-current="true""#));
+        assert!(html.contains(r#"<a href="/010-parent/010-child/" aria-current="page">"#));
     }
 
     #[test]
@@ -1816,7 +3067,7 @@ mod tests {
                 children: vec![],
             }],
         }];
-        let html = render_nav(&items, "", &[], false).into_string();
+        let html = render_nav(&items, "", &[], false, "/").into_string();
         assert!(html.contains("Parent"));
         assert!(html.contains("Child"));
         assert!(html.contains("nav-group")); // Parent should have nav-group class
@@ -1825,12 +3076,12 @@ mod tests {
     #[test]
     fn nav_separator_only_when_pages() {
         // No pages = no separator
-        let html_no_pages = render_nav(&[], "", &[], false).into_string();
+        let html_no_pages = render_nav(&[], "", &[], false, "/").into_string();
         assert!(!html_no_pages.contains("nav-separator"));
 
         // With nav pages = separator
         let pages = vec![make_page("about", "About", true, false)];
-        let html_with_pages = render_nav(&[], "", &pages, false).into_string();
+        let html_with_pages = render_nav(&[], "", &pages, false, "/").into_string();
         assert!(html_with_pages.contains("nav-separator"));
     }
 
@@ -1838,13 +3089,13 @@ mod tests {
     fn base_document_includes_doctype() {
         let content = html! { p { "test" } };
         let doc = base_document(
+            &PageContext {
+                css: "body {}",
+                ..page_ctx()
+            },
             "Test",
-            "body {}",
-            None,
-            None,
             None,
             None,
-            &no_snippets(),
             None,
             content,
         )
@@ -1855,18 +3106,8 @@ mod tests {
     #[test]
     fn base_document_applies_body_class() {
         let content = html! { p { "test" } };
-        let doc = base_document(
-            "Test",
-            "",
-            None,
-            Some("image-view"),
-            None,
-            None,
-            &no_snippets(),
-            None,
-            content,
-        )
-        .into_string();
+        let doc = base_document(&page_ctx(), "Test", Some("image-view"), None, None, content)
+            .into_string();
         assert!(html_contains_body_class(&doc, "image-view"));
     }
 
@@ -1897,11 +3138,17 @@ mod tests {
             path: "test".to_string(),
             title: "Test Album".to_string(),
             description: Some("<p>A test album description</p>".to_string()),
+            description_text: None,
             thumbnail: "test/001-image-thumb.avif".to_string(),
+            thumbnail_2x: None,
+            card_thumbnail: None,
+            card_thumbnail_bytes: None,
+            card_inline: None,
             images: vec![
                 Image {
                     number: 1,
                     source_path: "test/001-dawn.jpg".to_string(),
+                    slug: "dawn".to_string(),
                     title: Some("Dawn".to_string()),
                     description: None,
                     dimensions: (1600, 1200),
@@ -1926,12 +3173,16 @@ mod tests {
                         map
                     },
                     thumbnail: "test/001-dawn-thumb.avif".to_string(),
+                    thumbnail_2x: None,
+                    thumbnail_bytes: None,
+                    thumbnail_inline: None,
                     full_index_thumbnail: None,
                     canonical_id: None,
                 },
                 Image {
                     number: 2,
                     source_path: "test/002-night.jpg".to_string(),
+                    slug: "night".to_string(),
                     title: None,
                     description: None,
                     dimensions: (1200, 1600),
@@ -1948,6 +3199,9 @@ mod tests {
                         map
                     },
                     thumbnail: "test/002-night-thumb.avif".to_string(),
+                    thumbnail_2x: None,
+                    thumbnail_bytes: None,
+                    thumbnail_inline: None,
                     full_index_thumbnail: None,
                     canonical_id: None,
                 },
@@ -1955,6 +3209,7 @@ mod tests {
             in_nav: true,
             config: SiteConfig::default(),
             support_files: vec![],
+            meta: None,
         }
     }
 
@@ -1967,10 +3222,16 @@ mod tests {
             path: "NY/Night".to_string(),
             title: "Night".to_string(),
             description: None,
+            description_text: None,
             thumbnail: "NY/Night/001-city-thumb.avif".to_string(),
+            thumbnail_2x: None,
+            card_thumbnail: None,
+            card_thumbnail_bytes: None,
+            card_inline: None,
             images: vec![Image {
                 number: 1,
                 source_path: "NY/Night/001-city.jpg".to_string(),
+                slug: "city".to_string(),
                 title: Some("City".to_string()),
                 description: None,
                 dimensions: (1600, 1200),
@@ -1995,31 +3256,23 @@ mod tests {
                     map
                 },
                 thumbnail: "NY/Night/001-city-thumb.avif".to_string(),
+                thumbnail_2x: None,
+                thumbnail_bytes: None,
+                thumbnail_inline: None,
                 full_index_thumbnail: None,
                 canonical_id: None,
             }],
             in_nav: true,
             config: SiteConfig::default(),
             support_files: vec![],
+            meta: None,
         }
     }
 
     #[test]
     fn nested_album_thumbnail_paths_are_relative_to_album_dir() {
         let album = create_nested_test_album();
-        let html = render_album_page(
-            &album,
-            &[],
-            &[],
-            "",
-            None,
-            "Gallery",
-            None,
-            &no_snippets(),
-            false,
-            None,
-        )
-        .into_string();
+        let html = render_album_page(&page_ctx(), &album, None).into_string();
 
         // The album page is served from `/NY/Night/`, so `<img src>` must be
         // a bare filename. Any `NY/` or `NY/Night/` prefix in `src=` would
@@ -2032,22 +3285,7 @@ mod tests {
     fn nested_album_image_page_srcset_paths_are_relative() {
         let album = create_nested_test_album();
         let image = &album.images[0];
-        let html = render_image_page(
-            &album,
-            image,
-            None,
-            None,
-            &[],
-            &[],
-            "",
-            None,
-            "Gallery",
-            None,
-            &no_snippets(),
-            false,
-            None,
-        )
-        .into_string();
+        let html = render_image_page(&page_ctx(), &album, image, None, None, None).into_string();
 
         // Image page is at `/NY/Night/1-city/`, so srcset paths use `../` to
         // reach the album directory. Must be bare filenames after `../` — any
@@ -2058,20 +3296,45 @@ mod tests {
         assert!(!html.contains("../Night/"));
     }
 
+    #[test]
+    fn print_rules_hide_navigation_and_show_the_credit() {
+        let start = CSS_STATIC.find("@media print {").unwrap();
+        let print = &CSS_STATIC[start..];
+        let hidden = &print[..print.find("display: none;").unwrap()];
+        for selector in [
+            ".site-header",
+            ".site-nav",
+            ".nav-prev",
+            ".nav-next",
+            ".image-nav",
+        ] {
+            assert!(hidden.contains(selector), "{selector} not hidden in print");
+        }
+        assert!(print.contains(".print-credit {\n        display: block;"));
+    }
+
+    #[test]
+    fn print_credit_can_be_turned_off() {
+        let mut album = create_test_album();
+        let render = |album: &Album| {
+            render_image_page(&page_ctx(), album, &album.images[0], None, None, None).into_string()
+        };
+        assert!(render(&album).contains(r#"<p class="print-credit">"#));
+
+        album.config.theme.print_credit = false;
+        assert!(!render(&album).contains("print-credit"));
+    }
+
     #[test]
     fn render_album_page_includes_title() {
         let album = create_test_album();
         let nav = vec![];
         let html = render_album_page(
+            &PageContext {
+                navigation: &nav,
+                ..page_ctx()
+            },
             &album,
-            &nav,
-            &[],
-            "",
-            None,
-            "Gallery",
-            None,
-            &no_snippets(),
-            false,
             None,
         )
         .into_string();
@@ -2080,20 +3343,71 @@ mod tests {
         assert!(html.contains("<h1>"));
     }
 
+    #[test]
+    fn render_album_page_letterboxes_variable_aspect_thumbnails() {
+        let mut album = create_test_album();
+        let render = |album: &Album| render_album_page(&page_ctx(), album, None).into_string();
+
+        assert!(render(&album).contains(r#"<div class="thumbnail-grid">"#));
+        for mode in [AspectMode::Original, AspectMode::Adaptive] {
+            album.config.thumbnails.aspect_mode = mode;
+            assert!(render(&album).contains(r#"<div class="thumbnail-grid variable-aspect">"#));
+        }
+    }
+
+    #[test]
+    fn render_album_page_preloads_first_thumbnails() {
+        let mut album = create_test_album();
+        album.config.thumbnails.preload = 1;
+        let html = render_album_page(&page_ctx(), &album, None).into_string();
+
+        let head = &html[..html.find("</head>").unwrap()];
+        let first = album.images[0].thumbnail.strip_prefix("test/").unwrap();
+        let second = album.images[1].thumbnail.strip_prefix("test/").unwrap();
+        assert!(head.contains(&format!(
+            r#"<link rel="preload" as="image" type="image/avif" href="{first}">"#
+        )));
+        assert!(!head.contains(second));
+        assert_eq!(head.matches(r#"rel="preload""#).count(), 1);
+
+        album.config.thumbnails.preload = 0;
+        let html = render_album_page(&page_ctx(), &album, None).into_string();
+        assert!(!html.contains(r#"rel="preload""#));
+    }
+
+    #[test]
+    fn render_album_page_includes_meta_block() {
+        let mut album = create_test_album();
+        album.meta = Some(AlbumMeta {
+            location: Some("Kyoto, Japan".to_string()),
+            date: None,
+            camera: Some("Leica M6 <film>".to_string()),
+        });
+        let html = render_album_page(&page_ctx(), &album, None).into_string();
+
+        assert!(html.contains(r#"<dl class="album-meta">"#));
+        assert!(html.contains("<dt>Location</dt><dd>Kyoto, Japan</dd>"));
+        assert!(html.contains("<dt>Camera</dt><dd>Leica M6 &lt;film&gt;</dd>"));
+        assert!(!html.contains("<dt>Date</dt>"));
+    }
+
+    #[test]
+    fn render_album_page_without_meta_has_no_block() {
+        let album = create_test_album();
+        let html = render_album_page(&page_ctx(), &album, None).into_string();
+        assert!(!html.contains("album-meta"));
+    }
+
     #[test]
     fn render_album_page_includes_description() {
         let album = create_test_album();
         let nav = vec![];
         let html = render_album_page(
+            &PageContext {
+                navigation: &nav,
+                ..page_ctx()
+            },
             &album,
-            &nav,
-            &[],
-            "",
-            None,
-            "Gallery",
-            None,
-            &no_snippets(),
-            false,
             None,
         )
         .into_string();
@@ -2102,20 +3416,36 @@ mod tests {
         assert!(html.contains("album-description"));
     }
 
+    #[test]
+    fn album_description_position_top_or_bottom() {
+        let mut album = create_test_album();
+        let render = |album: &Album| render_album_page(&page_ctx(), album, None).into_string();
+
+        let html = render(&album);
+        let desc = html.find("A test album description").unwrap();
+        assert!(desc < html.find("thumbnail-grid").unwrap());
+        assert!(html.contains("has-description"));
+
+        album.config.theme.description_position = DescriptionPosition::Bottom;
+        let html = render(&album);
+        let desc = html.find("A test album description").unwrap();
+        assert!(desc > html.find("thumbnail-grid").unwrap());
+        assert!(html.contains(r#"<footer class="album-footer"><input class="desc-toggle""#));
+        assert!(html.contains("Read more"));
+        // The sidebar layout is for header descriptions only.
+        assert!(!html.contains("has-description"));
+    }
+
     #[test]
     fn render_album_page_thumbnail_links() {
         let album = create_test_album();
         let nav = vec![];
         let html = render_album_page(
+            &PageContext {
+                navigation: &nav,
+                ..page_ctx()
+            },
             &album,
-            &nav,
-            &[],
-            "",
-            None,
-            "Gallery",
-            None,
-            &no_snippets(),
-            false,
             None,
         )
         .into_string();
@@ -2132,15 +3462,11 @@ mod tests {
         let album = create_test_album();
         let nav = vec![];
         let html = render_album_page(
+            &PageContext {
+                navigation: &nav,
+                ..page_ctx()
+            },
             &album,
-            &nav,
-            &[],
-            "",
-            None,
-            "Gallery",
-            None,
-            &no_snippets(),
-            false,
             None,
         )
         .into_string();
@@ -2156,18 +3482,14 @@ mod tests {
         let image = &album.images[0];
         let nav = vec![];
         let html = render_image_page(
+            &PageContext {
+                navigation: &nav,
+                ..page_ctx()
+            },
             &album,
             image,
             None,
             Some(&album.images[1]),
-            &nav,
-            &[],
-            "",
-            None,
-            "Gallery",
-            None,
-            &no_snippets(),
-            false,
             None,
         )
         .into_string();
@@ -2184,18 +3506,14 @@ mod tests {
         let image = &album.images[0];
         let nav = vec![];
         let html = render_image_page(
+            &PageContext {
+                navigation: &nav,
+                ..page_ctx()
+            },
             &album,
             image,
             None,
             Some(&album.images[1]),
-            &nav,
-            &[],
-            "",
-            None,
-            "Gallery",
-            None,
-            &no_snippets(),
-            false,
             None,
         )
         .into_string();
@@ -2212,18 +3530,14 @@ mod tests {
         let image = &album.images[0];
         let nav = vec![];
         let html = render_image_page(
+            &PageContext {
+                navigation: &nav,
+                ..page_ctx()
+            },
             &album,
             image,
             None,
             Some(&album.images[1]),
-            &nav,
-            &[],
-            "",
-            None,
-            "Gallery",
-            None,
-            &no_snippets(),
-            false,
             None,
         )
         .into_string();
@@ -2234,6 +3548,46 @@ mod tests {
         assert!(html.contains(r#"aria-label="Next image""#));
     }
 
+    #[test]
+    fn render_image_page_back_to_album_control() {
+        let album = create_test_album();
+        let html = render_image_page(
+            &page_ctx(),
+            &album,
+            &album.images[1],
+            Some(&album.images[0]),
+            None,
+            None,
+        )
+        .into_string();
+
+        assert!(html.contains(
+            r#"<a class="back-to-album" href="../" aria-label="Back to Test Album" title="Back to Test Album">"#
+        ));
+    }
+
+    #[test]
+    fn nav_js_parent_url_prefers_back_to_album_control() {
+        // Escape/ArrowUp go to `parentUrl`; the control outranks the
+        // breadcrumb. Behavior is covered by the `browser_layout` tests.
+        assert!(JS.contains("var back = document.querySelector('.back-to-album');"));
+        assert!(JS.contains("var parentLink = back || lastCrumb;"));
+    }
+
+    #[test]
+    fn image_fade_marks_the_main_image() {
+        let mut album = create_test_album();
+        let render = |album: &Album| {
+            render_image_page(&page_ctx(), album, &album.images[0], None, None, None).into_string()
+        };
+        assert!(!render(&album).contains("image-fade"));
+
+        album.config.theme.image_fade = true;
+        assert!(render(&album).contains(r#"<img id="main-image" class="image-fade""#));
+        assert!(JS.contains("img.image-fade"));
+        assert!(CSS_STATIC.contains("img.image-fade.is-loading"));
+    }
+
     #[test]
     fn render_image_page_prev_next_urls() {
         let album = create_test_album();
@@ -2241,18 +3595,14 @@ mod tests {
 
         // First image - no prev, has next
         let html1 = render_image_page(
+            &PageContext {
+                navigation: &nav,
+                ..page_ctx()
+            },
             &album,
             &album.images[0],
             None,
             Some(&album.images[1]),
-            &nav,
-            &[],
-            "",
-            None,
-            "Gallery",
-            None,
-            &no_snippets(),
-            false,
             None,
         )
         .into_string();
@@ -2261,18 +3611,14 @@ mod tests {
 
         // Second image - has prev, no next (image[1] has no title)
         let html2 = render_image_page(
+            &PageContext {
+                navigation: &nav,
+                ..page_ctx()
+            },
             &album,
             &album.images[1],
             Some(&album.images[0]),
             None,
-            &nav,
-            &[],
-            "",
-            None,
-            "Gallery",
-            None,
-            &no_snippets(),
-            false,
             None,
         )
         .into_string();
@@ -2286,18 +3632,14 @@ mod tests {
         let image = &album.images[0]; // 1600x1200 = 1.333...
         let nav = vec![];
         let html = render_image_page(
+            &PageContext {
+                navigation: &nav,
+                ..page_ctx()
+            },
             &album,
             image,
             None,
             None,
-            &nav,
-            &[],
-            "",
-            None,
-            "Gallery",
-            None,
-            &no_snippets(),
-            false,
             None,
         )
         .into_string();
@@ -2316,19 +3658,10 @@ mod tests {
             in_nav: true,
             sort_key: 40,
             is_link: false,
+            assets: vec![],
+            toc: None,
         };
-        let html = render_page(
-            &page,
-            &[],
-            &[],
-            "",
-            None,
-            "Gallery",
-            None,
-            &no_snippets(),
-            false,
-        )
-        .into_string();
+        let html = render_page(&page_ctx(), &page, &SiteConfig::default()).into_string();
 
         // Markdown should be converted to HTML
         assert!(html.contains("<strong>bold</strong>"));
@@ -2345,22 +3678,131 @@ mod tests {
             in_nav: true,
             sort_key: 40,
             is_link: false,
+            assets: vec![],
+            toc: None,
+        };
+        let html = render_page(&page_ctx(), &page, &SiteConfig::default()).into_string();
+
+        assert!(html.contains("<title>About Me</title>"));
+        assert!(html.contains("class=\"page\""));
+    }
+
+    #[test]
+    fn render_page_toc_lists_headings_in_order() {
+        let page = Page {
+            title: "Process".to_string(),
+            link_title: "process".to_string(),
+            slug: "process".to_string(),
+            body:
+                "# Process\n\n## Shooting\n\nText.\n\n### Film *stock*\n\n## Editing\n\n## Shooting"
+                    .to_string(),
+            in_nav: true,
+            sort_key: 40,
+            is_link: false,
+            assets: vec![],
+            toc: Some(true),
         };
+        let html = render_page(&page_ctx(), &page, &SiteConfig::default()).into_string();
+
+        assert!(html.contains(r#"<h1 id="process">Process</h1>"#));
+        assert!(html.contains(r#"<h2 id="shooting">Shooting</h2>"#));
+        assert!(html.contains(r#"<h3 id="film-stock">Film <em>stock</em></h3>"#));
+        assert!(html.contains(r#"<h2 id="shooting-2">Shooting</h2>"#));
+
+        let toc_start = html.find(r#"<nav class="page-toc""#).unwrap();
+        let toc = &html[toc_start..html[toc_start..].find("</nav>").unwrap() + toc_start];
+        let links: Vec<&str> = toc
+            .match_indices("href=\"#")
+            .map(|(i, _)| &toc[i + 7..i + 7 + toc[i + 7..].find('"').unwrap()])
+            .collect();
+        assert_eq!(links, ["shooting", "film-stock", "editing", "shooting-2"]);
+        assert!(
+            toc.contains(r##"<li class="toc-level-3"><a href="#film-stock">Film stock</a></li>"##)
+        );
+        assert!(toc_start < html.find("<article").unwrap());
+    }
+
+    #[test]
+    fn render_page_without_toc_adds_no_ids() {
+        let mut page = make_page("about", "About", true, false);
+        page.body = "# About\n\n## Contact".to_string();
+        let html = render_page(&page_ctx(), &page, &SiteConfig::default()).into_string();
+
+        assert!(html.contains("<h2>Contact</h2>"));
+        assert!(!html.contains("page-toc"));
+    }
+
+    #[test]
+    #[cfg(feature = "highlight")]
+    fn render_page_highlights_fenced_code() {
+        let mut page = make_page("notes", "Notes", true, false);
+        page.body = "# Notes\n\n```rust\nfn main() {}\n```\n\n```\nplain <text>\n```".to_string();
+        let mut config = SiteConfig::default();
+        let render = |config: &SiteConfig| render_page(&page_ctx(), &page, config).into_string();
+
+        let plain = render(&config);
+        assert!(plain.contains(r#"<code class="language-rust">fn main() {}"#));
+
+        config.pages.highlight_code = true;
+        let html = render(&config);
+        assert!(!html.contains("language-rust"));
+        assert!(html.contains(r#"<pre style="background-color:"#));
+        assert!(html.contains(r#"<span style="color:"#));
+        assert!(html.contains(">fn</span>"));
+        // Fences without a language stay plain and escaped
+        assert!(html.contains("<pre><code>plain &lt;text&gt;"));
+    }
+
+    #[test]
+    fn render_page_smart_typography() {
+        let mut page = make_page("about", "About", true, false);
+        page.body = "# About\n\nI'm \"here\" -- mostly... `it's`".to_string();
         let html = render_page(
+            &page_ctx(),
             &page,
-            &[],
-            &[],
-            "",
-            None,
-            "Gallery",
-            None,
-            &no_snippets(),
-            false,
+            &SiteConfig {
+                smart_typography: true,
+                ..SiteConfig::default()
+            },
         )
         .into_string();
 
-        assert!(html.contains("<title>About Me</title>"));
-        assert!(html.contains("class=\"page\""));
+        assert!(html.contains("<p>I’m “here” — mostly… <code>it's</code></p>"));
+    }
+
+    #[test]
+    fn render_page_external_links_new_tab() {
+        let mut page = make_page("about", "About", true, false);
+        page.body = "[Shop](https://shop.example.org) · [Home](https://photos.example.com/) · [Contact](contact.html)"
+            .to_string();
+        let config = SiteConfig {
+            base_url: Some("https://photos.example.com".to_string()),
+            external_links_new_tab: true,
+            ..SiteConfig::default()
+        };
+        let html = render_page(&page_ctx(), &page, &config).into_string();
+
+        assert!(html.contains(
+            r#"<a href="https://shop.example.org" target="_blank" rel="noopener noreferrer">Shop</a>"#
+        ));
+        assert!(html.contains(r#"<a href="https://photos.example.com/">Home</a>"#));
+        assert!(html.contains(r#"<a href="contact.html">Contact</a>"#));
+    }
+
+    #[test]
+    fn heading_ids_stay_unique_across_suffixes() {
+        let events: Vec<Event> =
+            Parser::new("## Shooting\n## Shooting 2\n## Shooting\n## Shooting\n").collect();
+        let (_, entries) = with_heading_ids(events);
+        let ids: Vec<&str> = entries.iter().map(|e| e.id.as_str()).collect();
+        assert_eq!(ids, ["shooting", "shooting-2", "shooting-3", "shooting-4"]);
+    }
+
+    #[test]
+    fn heading_id_slugifies_text() {
+        assert_eq!(heading_id("Film & Lenses: 2024"), "film-lenses-2024");
+        assert_eq!(heading_id("Über uns"), "über-uns");
+        assert_eq!(heading_id("!!!"), "section");
     }
 
     // =========================================================================
@@ -2389,6 +3831,23 @@ mod tests {
         assert_eq!(format_image_label(7, 120, None), "007");
     }
 
+    #[test]
+    fn format_label_zero_pads_for_1000_plus() {
+        assert_eq!(format_image_label(7, 1500, Some("X")), "0007. X");
+        assert_eq!(format_image_label(1500, 1500, None), "1500");
+        assert_eq!(image_page_url(7, 1500, Some("X")), "0007-x/");
+    }
+
+    #[test]
+    fn index_width_grows_with_album_size() {
+        assert_eq!(index_width(0), 1);
+        assert_eq!(index_width(9), 1);
+        assert_eq!(index_width(10), 2);
+        assert_eq!(index_width(999), 3);
+        assert_eq!(index_width(1500), 4);
+        assert_eq!(index_width(10_000), 5);
+    }
+
     #[test]
     fn format_label_no_padding_under_10() {
         assert_eq!(format_image_label(3, 9, Some("Y")), "3. Y");
@@ -2400,18 +3859,14 @@ mod tests {
         let image = &album.images[0]; // has title "Dawn"
         let nav = vec![];
         let html = render_image_page(
+            &PageContext {
+                navigation: &nav,
+                ..page_ctx()
+            },
             &album,
             image,
             None,
             Some(&album.images[1]),
-            &nav,
-            &[],
-            "",
-            None,
-            "Gallery",
-            None,
-            &no_snippets(),
-            false,
             None,
         )
         .into_string();
@@ -2427,18 +3882,14 @@ mod tests {
         let image = &album.images[1]; // no title
         let nav = vec![];
         let html = render_image_page(
+            &PageContext {
+                navigation: &nav,
+                ..page_ctx()
+            },
             &album,
             image,
             Some(&album.images[0]),
             None,
-            &nav,
-            &[],
-            "",
-            None,
-            "Gallery",
-            None,
-            &no_snippets(),
-            false,
             None,
         )
         .into_string();
@@ -2455,18 +3906,14 @@ mod tests {
         let image = &album.images[0];
         let nav = vec![];
         let html = render_image_page(
+            &PageContext {
+                navigation: &nav,
+                ..page_ctx()
+            },
             &album,
             image,
             None,
             Some(&album.images[1]),
-            &nav,
-            &[],
-            "",
-            None,
-            "Gallery",
-            None,
-            &no_snippets(),
-            false,
             None,
         )
         .into_string();
@@ -2480,18 +3927,14 @@ mod tests {
         let image = &album.images[0]; // has title "Dawn"
         let nav = vec![];
         let html = render_image_page(
+            &PageContext {
+                navigation: &nav,
+                ..page_ctx()
+            },
             &album,
             image,
             None,
             Some(&album.images[1]),
-            &nav,
-            &[],
-            "",
-            None,
-            "Gallery",
-            None,
-            &no_snippets(),
-            false,
             None,
         )
         .into_string();
@@ -2505,49 +3948,99 @@ mod tests {
 
     #[test]
     fn is_short_caption_short_text() {
-        assert!(is_short_caption("A beautiful sunset"));
+        assert!(is_short_caption("A beautiful sunset", 160));
     }
 
     #[test]
     fn is_short_caption_exactly_at_limit() {
-        let text = "a".repeat(SHORT_CAPTION_MAX_LEN);
-        assert!(is_short_caption(&text));
+        let text = "a".repeat(160);
+        assert!(is_short_caption(&text, 160));
     }
 
     #[test]
     fn is_short_caption_over_limit() {
-        let text = "a".repeat(SHORT_CAPTION_MAX_LEN + 1);
-        assert!(!is_short_caption(&text));
+        let text = "a".repeat(161);
+        assert!(!is_short_caption(&text, 160));
     }
 
     #[test]
     fn is_short_caption_with_newline() {
-        assert!(!is_short_caption("Line one\nLine two"));
+        assert!(!is_short_caption("Line one\nLine two", 160));
+    }
+
+    #[test]
+    fn is_short_caption_for_crlf_sidecar() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let image = tmp.path().join("001-bay.jpg");
+        fs::write(
+            tmp.path().join("001-bay.txt"),
+            "---\r\nquality: 95\r\n---\r\nSunset over the bay\r\n",
+        )
+        .unwrap();
+
+        let sidecar = crate::metadata::read_sidecar(&image).unwrap();
+        let caption = sidecar.description.unwrap();
+        assert_eq!(caption, "Sunset over the bay");
+        assert!(is_short_caption(&caption, 160));
     }
 
     #[test]
     fn is_short_caption_empty_string() {
-        assert!(is_short_caption(""));
+        assert!(is_short_caption("", 160));
     }
 
     #[test]
-    fn render_image_page_short_caption() {
+    fn is_short_caption_custom_threshold() {
+        assert!(is_short_caption("twelve chars", 12));
+        assert!(!is_short_caption("thirteen char", 12));
+    }
+
+    #[test]
+    fn is_short_caption_counts_chars_not_bytes() {
+        // 160 two-byte characters = 320 bytes, still within a 160-char limit.
+        let at_limit = "é".repeat(160);
+        assert!(is_short_caption(&at_limit, 160));
+        let over = "é".repeat(161);
+        assert!(!is_short_caption(&over, 160));
+        // Three-byte CJK characters at the boundary.
+        assert!(is_short_caption(&"山".repeat(5), 5));
+        assert!(!is_short_caption(&"山".repeat(6), 5));
+    }
+
+    #[test]
+    fn render_image_page_respects_caption_max_len() {
         let mut album = create_test_album();
-        album.images[0].description = Some("A beautiful sunrise over the mountains".to_string());
+        album.config.theme.caption_max_len = 10;
+        album.images[0].description = Some("A beautiful sunrise".to_string());
         let image = &album.images[0];
         let html = render_image_page(
+            &page_ctx(),
             &album,
             image,
             None,
             Some(&album.images[1]),
-            &[],
-            &[],
-            "",
             None,
-            "Gallery",
+        )
+        .into_string();
+
+        assert!(!html.contains("image-caption"));
+        assert!(html_contains_body_class(
+            &html,
+            "image-view has-description"
+        ));
+    }
+
+    #[test]
+    fn render_image_page_short_caption() {
+        let mut album = create_test_album();
+        album.images[0].description = Some("A beautiful sunrise over the mountains".to_string());
+        let image = &album.images[0];
+        let html = render_image_page(
+            &page_ctx(),
+            &album,
+            image,
             None,
-            &no_snippets(),
-            false,
+            Some(&album.images[1]),
             None,
         )
         .into_string();
@@ -2557,6 +4050,58 @@ mod tests {
         assert!(html_contains_body_class(&html, "image-view has-caption"));
     }
 
+    fn caption_html(text: &str) -> String {
+        render_caption(text, &SiteConfig::default()).into_string()
+    }
+
+    #[test]
+    fn caption_renders_inline_markdown() {
+        assert_eq!(
+            caption_html("Dawn over the *Alps*, **cold**"),
+            "Dawn over the <em>Alps</em>, <strong>cold</strong>"
+        );
+        assert_eq!(
+            caption_html("By [Ana](https://ana.example)"),
+            r#"By <a href="https://ana.example">Ana</a>"#
+        );
+    }
+
+    #[test]
+    fn caption_escapes_html() {
+        let html = caption_html("Hi <script>alert(1)</script> <img src=x onerror=alert(1)>");
+        assert!(!html.contains("<script>"), "{html}");
+        assert!(!html.contains("<img"), "{html}");
+        assert!(
+            html.contains("&lt;script&gt;alert(1)&lt;/script&gt;"),
+            "{html}"
+        );
+
+        let html = caption_html("[click](javascript:alert(1))");
+        assert!(!html.contains("<a"), "{html}");
+    }
+
+    #[test]
+    fn caption_block_syntax_stays_plain_text() {
+        assert_eq!(caption_html("1. Dawn"), "1. Dawn");
+        assert_eq!(caption_html("# Not a heading"), "# Not a heading");
+    }
+
+    #[test]
+    fn render_image_page_caption_markdown() {
+        let mut album = create_test_album();
+        album.images[0].description = Some("Light on *water*".to_string());
+        let html = render_image_page(
+            &page_ctx(),
+            &album,
+            &album.images[0],
+            None,
+            Some(&album.images[1]),
+            None,
+        )
+        .into_string();
+        assert!(html.contains(r#"<p class="image-caption">Light on <em>water</em></p>"#));
+    }
+
     #[test]
     fn render_image_page_long_description() {
         let mut album = create_test_album();
@@ -2564,18 +4109,11 @@ mod tests {
         album.images[0].description = Some(long_text.clone());
         let image = &album.images[0];
         let html = render_image_page(
+            &page_ctx(),
             &album,
             image,
             None,
             Some(&album.images[1]),
-            &[],
-            &[],
-            "",
-            None,
-            "Gallery",
-            None,
-            &no_snippets(),
-            false,
             None,
         )
         .into_string();
@@ -2594,18 +4132,11 @@ mod tests {
         album.images[0].description = Some("Line one\nLine two".to_string());
         let image = &album.images[0];
         let html = render_image_page(
+            &page_ctx(),
             &album,
             image,
             None,
             Some(&album.images[1]),
-            &[],
-            &[],
-            "",
-            None,
-            "Gallery",
-            None,
-            &no_snippets(),
-            false,
             None,
         )
         .into_string();
@@ -2623,18 +4154,11 @@ mod tests {
         let album = create_test_album();
         let image = &album.images[1]; // description: None
         let html = render_image_page(
+            &page_ctx(),
             &album,
             image,
             Some(&album.images[0]),
             None,
-            &[],
-            &[],
-            "",
-            None,
-            "Gallery",
-            None,
-            &no_snippets(),
-            false,
             None,
         )
         .into_string();
@@ -2650,18 +4174,11 @@ mod tests {
         album.images[0].description = Some("Short caption".to_string());
         let image = &album.images[0];
         let html = render_image_page(
+            &page_ctx(),
             &album,
             image,
             None,
             Some(&album.images[1]),
-            &[],
-            &[],
-            "",
-            None,
-            "Gallery",
-            None,
-            &no_snippets(),
-            false,
             None,
         )
         .into_string();
@@ -2683,7 +4200,7 @@ mod tests {
             description: None,
             children: vec![],
         }];
-        let html = render_nav(&items, "", &[], false).into_string();
+        let html = render_nav(&items, "", &[], false, "/").into_string();
 
         // Should be escaped, not raw script tag
         assert!(!html.contains("<script>alert"));
@@ -2691,57 +4208,221 @@ mod tests {
     }
 
     // =========================================================================
-    // escape_for_url tests
+    // Image page URL tests
     // =========================================================================
 
     #[test]
-    fn escape_for_url_spaces_become_dashes() {
-        assert_eq!(escape_for_url("My Title"), "my-title");
+    fn image_page_urls_use_the_image_slug() {
+        for title in [
+            "Rock & Roll",
+            "Tom's Diner",
+            "Before / After",
+            "St. Louis",
+            "My_Title",
+            "Café Déjà",
+        ] {
+            let text = slug_text(title, true);
+            let slug = sanitize_slug(&text);
+            assert!(
+                !slug.contains(['&', '\'', '/', '.', '_', ' ']),
+                "{title}: {slug}"
+            );
+            assert_eq!(image_page_url(1, 5, Some(&text)), format!("1-{slug}/"));
+        }
+    }
+
+    #[test]
+    fn image_page_url_with_title() {
+        assert_eq!(image_page_url(3, 15, Some("Dawn")), "03-dawn/");
     }
 
     #[test]
-    fn escape_for_url_dots_become_dashes() {
-        assert_eq!(escape_for_url("St. Louis"), "st-louis");
+    fn image_page_url_without_title() {
+        assert_eq!(image_page_url(3, 15, None), "03/");
     }
 
     #[test]
-    fn escape_for_url_collapses_consecutive() {
-        assert_eq!(escape_for_url("A.  B"), "a-b");
+    fn image_page_url_title_with_spaces() {
+        assert_eq!(image_page_url(1, 5, Some("My Museum")), "1-my-museum/");
     }
 
     #[test]
-    fn escape_for_url_strips_leading_trailing() {
-        assert_eq!(escape_for_url(". Title ."), "title");
+    fn image_page_url_title_with_dot() {
+        assert_eq!(image_page_url(1, 5, Some("St. Louis")), "1-st-louis/");
+    }
+
+    fn album_with_page_names(mode: ImagePageNames) -> Album {
+        let mut album = create_test_album();
+        album.config.output.image_page_names = mode;
+        album
     }
 
     #[test]
-    fn escape_for_url_preserves_dashes() {
-        assert_eq!(escape_for_url("My-Title"), "my-title");
+    fn image_page_dir_index_title_mode() {
+        let album = album_with_page_names(ImagePageNames::IndexTitle);
+        assert_eq!(album.image_page_dir(0), "1-dawn/");
+        assert_eq!(album.image_page_dir(1), "2/");
     }
 
     #[test]
-    fn escape_for_url_underscores_become_dashes() {
-        assert_eq!(escape_for_url("My_Title"), "my-title");
+    fn image_page_dir_index_mode_ignores_title() {
+        let album = album_with_page_names(ImagePageNames::Index);
+        assert_eq!(album.image_page_dir(0), "1/");
+        assert_eq!(album.image_page_dir(1), "2/");
     }
 
     #[test]
-    fn image_page_url_with_title() {
-        assert_eq!(image_page_url(3, 15, Some("Dawn")), "03-dawn/");
+    fn image_page_dir_slug_mode_uses_filename_slug() {
+        let album = album_with_page_names(ImagePageNames::Slug);
+        assert_eq!(album.image_page_dir(0), "dawn/");
+        // Untitled image still gets its slug from the filename.
+        assert_eq!(album.image_page_dir(1), "night/");
     }
 
     #[test]
-    fn image_page_url_without_title() {
-        assert_eq!(image_page_url(3, 15, None), "03/");
+    fn image_page_dir_slug_mode_falls_back_to_index() {
+        let mut album = album_with_page_names(ImagePageNames::Slug);
+        album.images[1].slug = String::new();
+        assert_eq!(album.image_page_dir(1), "2/");
     }
 
     #[test]
-    fn image_page_url_title_with_spaces() {
-        assert_eq!(image_page_url(1, 5, Some("My Museum")), "1-my-museum/");
+    fn colliding_slugs_get_unique_dirs() {
+        let mut album = album_with_page_names(ImagePageNames::Slug);
+        album.images = [
+            (1, "A.B"),
+            (2, "A B"),
+            (5, "a-b"),
+            (7, "Sunset"),
+            (9, "a-b-5"),
+        ]
+        .into_iter()
+        .map(|(number, slug)| {
+            let mut image = create_test_album().images.remove(1);
+            image.number = number;
+            image.slug = slug.to_string();
+            image
+        })
+        .collect();
+
+        album.disambiguate_slugs();
+        let dirs: Vec<String> = (0..album.images.len())
+            .map(|i| album.image_page_dir(i))
+            .collect();
+        assert_eq!(
+            dirs,
+            ["a-b/", "a-b-2/", "a-b-5-2/", "sunset/", "a-b-5/"],
+            "first keeps the plain slug, later ones append their number"
+        );
     }
 
     #[test]
-    fn image_page_url_title_with_dot() {
-        assert_eq!(image_page_url(1, 5, Some("St. Louis")), "1-st-louis/");
+    fn slugs_yield_to_position_fallbacks() {
+        let mut album = album_with_page_names(ImagePageNames::Slug);
+        // Image 1's slug is the directory image 2 falls back to.
+        album.images[0].slug = "2".to_string();
+        album.images[1].slug = String::new();
+
+        album.disambiguate_slugs();
+
+        assert_eq!(album.image_page_dir(0), "2-1/");
+        assert_eq!(album.image_page_dir(1), "2/");
+    }
+
+    #[test]
+    fn disambiguation_leaves_other_modes_alone() {
+        let mut album = album_with_page_names(ImagePageNames::IndexTitle);
+        album.images[1].slug = "dawn".to_string();
+        album.disambiguate_slugs();
+        assert_eq!(album.images[1].slug, "dawn");
+        assert_eq!(album.image_page_dir(1), "2/");
+    }
+
+    #[test]
+    fn hidden_index_drops_position_from_urls_and_labels() {
+        let mut album = create_test_album();
+        album.config.theme.show_index = false;
+        assert_eq!(album.image_page_dir(0), "dawn/");
+        assert_eq!(album.image_page_dir(1), "night/");
+        assert_eq!(album.image_label(0), "Dawn");
+        // Untitled images have nothing else to show.
+        assert_eq!(album.image_label(1), "2");
+
+        // An explicit "index" mode still names pages by position.
+        album.config.output.image_page_names = ImagePageNames::Index;
+        assert_eq!(album.image_page_dir(0), "1/");
+    }
+
+    #[test]
+    fn hidden_index_keeps_prev_next_by_position() {
+        let mut album = create_test_album();
+        album.config.theme.show_index = false;
+        let html = render_image_page(
+            &page_ctx(),
+            &album,
+            &album.images[0],
+            None,
+            album.images.get(1),
+            None,
+        )
+        .into_string();
+        assert!(html.contains(r#"class="nav-next" href="../night/""#));
+        assert!(html.contains("<title>Test Album - Dawn</title>"));
+        assert!(!html.contains("1. Dawn"));
+    }
+
+    #[test]
+    fn image_page_links_consistent_within_each_mode() {
+        for mode in [
+            ImagePageNames::IndexTitle,
+            ImagePageNames::Index,
+            ImagePageNames::Slug,
+        ] {
+            let album = album_with_page_names(mode);
+            let dirs: Vec<String> = (0..album.images.len())
+                .map(|i| album.image_page_dir(i))
+                .collect();
+
+            let album_html = render_album_page(&page_ctx(), &album, None).into_string();
+            for dir in &dirs {
+                assert!(
+                    album_html.contains(&format!(r#"href="{dir}""#)),
+                    "{mode:?}: album grid missing link to {dir}"
+                );
+            }
+
+            let render = |idx: usize| {
+                render_image_page(
+                    &page_ctx(),
+                    &album,
+                    &album.images[idx],
+                    idx.checked_sub(1).map(|p| &album.images[p]),
+                    album.images.get(idx + 1),
+                    None,
+                )
+                .into_string()
+            };
+            let first = render(0);
+            let second = render(1);
+            assert!(
+                first.contains(&format!(r#"class="nav-next" href="../{}""#, dirs[1])),
+                "{mode:?}: next link does not match {}",
+                dirs[1]
+            );
+            assert!(
+                second.contains(&format!(r#"class="nav-prev" href="../{}""#, dirs[0])),
+                "{mode:?}: prev link does not match {}",
+                dirs[0]
+            );
+            for html in [&first, &second] {
+                for dir in &dirs {
+                    assert!(
+                        html.contains(&format!(r#"href="../{dir}""#)),
+                        "{mode:?}: nav dots missing {dir}"
+                    );
+                }
+            }
+        }
     }
 
     // =========================================================================
@@ -2753,18 +4434,11 @@ mod tests {
         let album = create_test_album();
         let image = &album.images[0];
         let html = render_image_page(
+            &page_ctx(),
             &album,
             image,
             None,
             Some(&album.images[1]),
-            &[],
-            &[],
-            "",
-            None,
-            "Gallery",
-            None,
-            &no_snippets(),
-            false,
             None,
         )
         .into_string();
@@ -2777,18 +4451,11 @@ mod tests {
         let album = create_test_album();
         let image = &album.images[0];
         let html = render_image_page(
+            &page_ctx(),
             &album,
             image,
             None,
             Some(&album.images[1]),
-            &[],
-            &[],
-            "",
-            None,
-            "Gallery",
-            None,
-            &no_snippets(),
-            false,
             None,
         )
         .into_string();
@@ -2803,18 +4470,11 @@ mod tests {
         let album = create_test_album();
         let image = &album.images[0];
         let html = render_image_page(
+            &page_ctx(),
             &album,
             image,
             None,
             Some(&album.images[1]),
-            &[],
-            &[],
-            "",
-            None,
-            "Gallery",
-            None,
-            &no_snippets(),
-            false,
             None,
         )
         .into_string();
@@ -2830,18 +4490,11 @@ mod tests {
         let album = create_test_album();
         let image = &album.images[1];
         let html = render_image_page(
+            &page_ctx(),
             &album,
             image,
             Some(&album.images[0]),
             None,
-            &[],
-            &[],
-            "",
-            None,
-            "Gallery",
-            None,
-            &no_snippets(),
-            false,
             None,
         )
         .into_string();
@@ -2859,22 +4512,7 @@ mod tests {
         let album = create_test_album();
         let image = &album.images[0];
         // No prev, no next
-        let html = render_image_page(
-            &album,
-            image,
-            None,
-            None,
-            &[],
-            &[],
-            "",
-            None,
-            "Gallery",
-            None,
-            &no_snippets(),
-            false,
-            None,
-        )
-        .into_string();
+        let html = render_image_page(&page_ctx(), &album, image, None, None, None).into_string();
 
         // Should still have the render-blocking link
         assert!(html.contains(r#"rel="expect""#));
@@ -2894,20 +4532,16 @@ mod tests {
 
         let color_css = crate::config::generate_color_css(&config.colors);
         let theme_css = crate::config::generate_theme_css(&config.theme);
-        let font_css = crate::config::generate_font_css(&config.font);
+        let font_css = crate::config::generate_font_css(&config.font, "/");
         let css = format!("{}\n{}\n{}", color_css, theme_css, font_css);
 
         let album = create_test_album();
         let html = render_album_page(
+            &PageContext {
+                css: &css,
+                ..page_ctx()
+            },
             &album,
-            &[],
-            &[],
-            &css,
-            None,
-            "Gallery",
-            None,
-            &no_snippets(),
-            false,
             None,
         )
         .into_string();
@@ -2930,15 +4564,11 @@ mod tests {
         let theme_css = crate::config::generate_theme_css(&config.theme);
         let album = create_test_album();
         let html = render_album_page(
+            &PageContext {
+                css: &theme_css,
+                ..page_ctx()
+            },
             &album,
-            &[],
-            &[],
-            &theme_css,
-            None,
-            "Gallery",
-            None,
-            &no_snippets(),
-            false,
             None,
         )
         .into_string();
@@ -2956,20 +4586,17 @@ mod tests {
         config.font.weight = "300".to_string();
         config.font.font_type = crate::config::FontType::Serif;
 
-        let font_css = crate::config::generate_font_css(&config.font);
+        let font_css = crate::config::generate_font_css(&config.font, "/");
         let font_url = config.font.stylesheet_url();
 
         let album = create_test_album();
         let html = render_album_page(
+            &PageContext {
+                css: &font_css,
+                font_url: font_url.as_deref(),
+                ..page_ctx()
+            },
             &album,
-            &[],
-            &[],
-            &font_css,
-            font_url.as_deref(),
-            "Gallery",
-            None,
-            &no_snippets(),
-            false,
             None,
         )
         .into_string();
@@ -2984,6 +4611,99 @@ mod tests {
     // Index page edge cases
     // =========================================================================
 
+    fn layout_test_manifest(layout: IndexLayout) -> Manifest {
+        let mut config = SiteConfig::default();
+        config.theme.index_layout = layout;
+        Manifest {
+            navigation: vec![NavItem {
+                title: "Coast".to_string(),
+                path: "coast".to_string(),
+                source_dir: String::new(),
+                description: None,
+                children: vec![],
+            }],
+            albums: vec![Album {
+                path: "coast".to_string(),
+                title: "Coast".to_string(),
+                description: Some("<p>Cliffs &amp; coves,\nshot at <em>dawn</em>.</p>".to_string()),
+                description_text: None,
+                thumbnail: "coast/thumb.avif".to_string(),
+                thumbnail_2x: None,
+                card_thumbnail: None,
+                card_thumbnail_bytes: None,
+                card_inline: None,
+                images: vec![],
+                in_nav: true,
+                config: SiteConfig::default(),
+                support_files: vec![],
+                meta: None,
+            }],
+            pages: vec![],
+            description: None,
+            config,
+            canonical_images: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn index_layout_grid_is_default() {
+        let manifest = layout_test_manifest(IndexLayout::default());
+        let html = render_index(&manifest, "", None, None, &no_snippets(), None).into_string();
+        assert!(html.contains(r#"<div class="album-grid">"#));
+        assert!(!html.contains("album-excerpt"));
+    }
+
+    #[test]
+    fn index_layout_masonry_class() {
+        let manifest = layout_test_manifest(IndexLayout::Masonry);
+        let html = render_index(&manifest, "", None, None, &no_snippets(), None).into_string();
+        assert!(html.contains(r#"<div class="album-masonry">"#));
+        assert!(!html.contains("album-grid"));
+        assert!(html.contains(r#"href="/coast/""#));
+    }
+
+    #[test]
+    fn index_layout_list_shows_description_excerpt() {
+        let manifest = layout_test_manifest(IndexLayout::List);
+        let html = render_index(&manifest, "", None, None, &no_snippets(), None).into_string();
+        assert!(html.contains(r#"<div class="album-list">"#));
+        assert!(!html.contains("album-grid"));
+        // Plain text: tags stripped, entities decoded (then re-escaped by
+        // maud), whitespace collapsed.
+        assert!(html.contains(r#"<p class="album-excerpt">Cliffs &amp; coves, shot at dawn.</p>"#));
+    }
+
+    #[test]
+    fn index_layout_list_prefers_plain_text_description() {
+        let mut manifest = layout_test_manifest(IndexLayout::List);
+        manifest.albums[0].description_text = Some("Sea cliffs\n\nat first light.".to_string());
+        let html = render_index(&manifest, "", None, None, &no_snippets(), None).into_string();
+        assert!(html.contains(r#"<p class="album-excerpt">Sea cliffs at first light.</p>"#));
+    }
+
+    #[test]
+    fn index_layout_list_truncates_long_descriptions() {
+        let mut manifest = layout_test_manifest(IndexLayout::List);
+        manifest.albums[0].description = Some(format!("<p>{}</p>", "word ".repeat(100)));
+        let html = render_index(&manifest, "", None, None, &no_snippets(), None).into_string();
+        let excerpt = html
+            .split(r#"<p class="album-excerpt">"#)
+            .nth(1)
+            .and_then(|rest| rest.split("</p>").next())
+            .unwrap();
+        assert!(excerpt.ends_with("..."));
+        assert_eq!(excerpt.chars().count(), EXCERPT_MAX_CHARS + 3);
+    }
+
+    #[test]
+    fn index_layout_list_without_description_has_no_excerpt() {
+        let mut manifest = layout_test_manifest(IndexLayout::List);
+        manifest.albums[0].description = None;
+        let html = render_index(&manifest, "", None, None, &no_snippets(), None).into_string();
+        assert!(html.contains("album-text"));
+        assert!(!html.contains("album-excerpt"));
+    }
+
     #[test]
     fn index_page_excludes_non_nav_albums() {
         let manifest = Manifest {
@@ -2999,21 +4719,33 @@ mod tests {
                     path: "visible".to_string(),
                     title: "Visible".to_string(),
                     description: None,
+                    description_text: None,
                     thumbnail: "visible/thumb.avif".to_string(),
+                    thumbnail_2x: None,
+                    card_thumbnail: None,
+                    card_thumbnail_bytes: None,
+                    card_inline: None,
                     images: vec![],
                     in_nav: true,
                     config: SiteConfig::default(),
                     support_files: vec![],
+                    meta: None,
                 },
                 Album {
                     path: "hidden".to_string(),
                     title: "Hidden".to_string(),
                     description: None,
+                    description_text: None,
                     thumbnail: "hidden/thumb.avif".to_string(),
+                    thumbnail_2x: None,
+                    card_thumbnail: None,
+                    card_thumbnail_bytes: None,
+                    card_inline: None,
                     images: vec![],
                     in_nav: false,
                     config: SiteConfig::default(),
                     support_files: vec![],
+                    meta: None,
                 },
             ],
             pages: vec![],
@@ -3045,6 +4777,7 @@ mod tests {
         let make_image = |album: &str, n: u32, slug: &str, title: &str| Image {
             number: n,
             source_path: format!("{}/00{}-{}.jpg", album, n, slug),
+            slug: slug.to_string(),
             title: Some(title.to_string()),
             description: None,
             dimensions: (1600, 1200),
@@ -3061,6 +4794,9 @@ mod tests {
                 map
             },
             thumbnail: format!("{}/00{}-{}-thumb.avif", album, n, slug),
+            thumbnail_2x: None,
+            thumbnail_bytes: None,
+            thumbnail_inline: None,
             full_index_thumbnail: Some(format!("{}/00{}-{}-fi-thumb.avif", album, n, slug)),
             canonical_id: None,
         };
@@ -3087,21 +4823,33 @@ mod tests {
                     path: "alpha".to_string(),
                     title: "Alpha".to_string(),
                     description: None,
+                    description_text: None,
                     thumbnail: "alpha/001-dawn-thumb.avif".to_string(),
+                    thumbnail_2x: None,
+                    card_thumbnail: None,
+                    card_thumbnail_bytes: None,
+                    card_inline: None,
                     images: vec![make_image("alpha", 1, "dawn", "Dawn")],
                     in_nav: true,
                     config: cfg.clone(),
                     support_files: vec![],
+                    meta: None,
                 },
                 Album {
                     path: "beta".to_string(),
                     title: "Beta".to_string(),
                     description: None,
+                    description_text: None,
                     thumbnail: "beta/001-dusk-thumb.avif".to_string(),
+                    thumbnail_2x: None,
+                    card_thumbnail: None,
+                    card_thumbnail_bytes: None,
+                    card_inline: None,
                     images: vec![make_image("beta", 1, "dusk", "Dusk")],
                     in_nav: true,
                     config: cfg.clone(),
                     support_files: vec![],
+                    meta: None,
                 },
             ],
             pages: vec![],
@@ -3213,21 +4961,31 @@ mod tests {
             path: "hidden".to_string(),
             title: "Hidden".to_string(),
             description: None,
+            description_text: None,
             thumbnail: "hidden/001-secret-thumb.avif".to_string(),
+            thumbnail_2x: None,
+            card_thumbnail: None,
+            card_thumbnail_bytes: None,
+            card_inline: None,
             images: vec![Image {
                 number: 1,
                 source_path: "hidden/001-secret.jpg".to_string(),
+                slug: "secret".to_string(),
                 title: Some("Secret".to_string()),
                 description: None,
                 dimensions: (1600, 1200),
                 generated: BTreeMap::new(),
                 thumbnail: "hidden/001-secret-thumb.avif".to_string(),
+                thumbnail_2x: None,
+                thumbnail_bytes: None,
+                thumbnail_inline: None,
                 full_index_thumbnail: Some("hidden/001-secret-fi-thumb.avif".to_string()),
                 canonical_id: None,
             }],
             in_nav: false,
             config: manifest.config.clone(),
             support_files: vec![],
+            meta: None,
         };
         manifest.albums.push(hidden);
 
@@ -3242,7 +5000,7 @@ mod tests {
         let mut cfg = SiteConfig::default();
         cfg.full_index.generates = true;
         cfg.full_index.show_link = true;
-        let html = render_nav(&[], "", &[], show_all_photos_link(&cfg)).into_string();
+        let html = render_nav(&[], "", &[], show_all_photos_link(&cfg), "/").into_string();
         assert!(html.contains("All Photos"));
         assert!(html.contains(r#"href="/all-photos/""#));
     }
@@ -3250,7 +5008,7 @@ mod tests {
     #[test]
     fn all_photos_nav_link_absent_by_default() {
         let cfg = SiteConfig::default();
-        let html = render_nav(&[], "", &[], show_all_photos_link(&cfg)).into_string();
+        let html = render_nav(&[], "", &[], show_all_photos_link(&cfg), "/").into_string();
         assert!(!html.contains("All Photos"));
     }
 
@@ -3265,8 +5023,9 @@ mod tests {
 
     #[test]
     fn all_photos_nav_link_marked_current_on_page() {
-        let html = render_nav(&[], "all-photos", &[], true).into_string();
+        let html = render_nav(&[], "all-photos", &[], true, "/").into_string();
         assert!(html.contains(r#"class="current""#));
+        assert!(html.contains(r#"href="/all-photos/" aria-current="page""#));
         assert!(html.contains("All Photos"));
     }
 
@@ -3339,10 +5098,16 @@ mod tests {
             path: "solo".to_string(),
             title: "Solo Album".to_string(),
             description: None,
+            description_text: None,
             thumbnail: "solo/001-thumb.avif".to_string(),
+            thumbnail_2x: None,
+            card_thumbnail: None,
+            card_thumbnail_bytes: None,
+            card_inline: None,
             images: vec![Image {
                 number: 1,
                 source_path: "solo/001-photo.jpg".to_string(),
+                slug: "photo".to_string(),
                 title: Some("Photo".to_string()),
                 description: None,
                 dimensions: (1600, 1200),
@@ -3359,31 +5124,20 @@ mod tests {
                     map
                 },
                 thumbnail: "solo/001-photo-thumb.avif".to_string(),
+                thumbnail_2x: None,
+                thumbnail_bytes: None,
+                thumbnail_inline: None,
                 full_index_thumbnail: None,
                 canonical_id: None,
             }],
             in_nav: true,
             config: SiteConfig::default(),
             support_files: vec![],
+            meta: None,
         };
 
         let image = &album.images[0];
-        let html = render_image_page(
-            &album,
-            image,
-            None,
-            None,
-            &[],
-            &[],
-            "",
-            None,
-            "Gallery",
-            None,
-            &no_snippets(),
-            false,
-            None,
-        )
-        .into_string();
+        let html = render_image_page(&page_ctx(), &album, image, None, None, None).into_string();
 
         // Both prev and next should go back to album
         assert!(html.contains(r#"class="nav-prev" href="../""#));
@@ -3394,19 +5148,7 @@ mod tests {
     fn album_page_no_description() {
         let mut album = create_test_album();
         album.description = None;
-        let html = render_album_page(
-            &album,
-            &[],
-            &[],
-            "",
-            None,
-            "Gallery",
-            None,
-            &no_snippets(),
-            false,
-            None,
-        )
-        .into_string();
+        let html = render_album_page(&page_ctx(), &album, None).into_string();
 
         assert!(!html.contains("album-description"));
         assert!(html.contains("Test Album"));
@@ -3417,18 +5159,11 @@ mod tests {
         let album = create_test_album();
         let image = &album.images[0];
         let html = render_image_page(
+            &page_ctx(),
             &album,
             image,
             None,
             Some(&album.images[1]),
-            &[],
-            &[],
-            "",
-            None,
-            "Gallery",
-            None,
-            &no_snippets(),
-            false,
             None,
         )
         .into_string();
@@ -3447,18 +5182,11 @@ mod tests {
         let album = create_test_album();
         // Render second image page
         let html = render_image_page(
+            &page_ctx(),
             &album,
             &album.images[1],
             Some(&album.images[0]),
             None,
-            &[],
-            &[],
-            "",
-            None,
-            "Gallery",
-            None,
-            &no_snippets(),
-            false,
             None,
         )
         .into_string();
@@ -3501,15 +5229,11 @@ mod tests {
     fn album_page_breadcrumb_uses_custom_site_title() {
         let album = create_test_album();
         let html = render_album_page(
+            &PageContext {
+                crumbs: crumbs("My Portfolio"),
+                ..page_ctx()
+            },
             &album,
-            &[],
-            &[],
-            "",
-            None,
-            "My Portfolio",
-            None,
-            &no_snippets(),
-            false,
             None,
         )
         .into_string();
@@ -3523,18 +5247,14 @@ mod tests {
         let album = create_test_album();
         let image = &album.images[0];
         let html = render_image_page(
+            &PageContext {
+                crumbs: crumbs("My Portfolio"),
+                ..page_ctx()
+            },
             &album,
             image,
             None,
             Some(&album.images[1]),
-            &[],
-            &[],
-            "",
-            None,
-            "My Portfolio",
-            None,
-            &no_snippets(),
-            false,
             None,
         )
         .into_string();
@@ -3553,17 +5273,16 @@ mod tests {
             in_nav: true,
             sort_key: 40,
             is_link: false,
+            assets: vec![],
+            toc: None,
         };
         let html = render_page(
+            &PageContext {
+                crumbs: crumbs("My Portfolio"),
+                ..page_ctx()
+            },
             &page,
-            &[],
-            &[],
-            "",
-            None,
-            "My Portfolio",
-            None,
-            &no_snippets(),
-            false,
+            &SiteConfig::default(),
         )
         .into_string();
 
@@ -3597,18 +5316,7 @@ mod tests {
     #[test]
     fn no_custom_css_link_by_default() {
         let content = html! { p { "test" } };
-        let doc = base_document(
-            "Test",
-            "",
-            None,
-            None,
-            None,
-            None,
-            &no_snippets(),
-            None,
-            content,
-        )
-        .into_string();
+        let doc = base_document(&page_ctx(), "Test", None, None, None, content).into_string();
         assert!(!doc.contains("custom.css"));
     }
 
@@ -3619,8 +5327,18 @@ mod tests {
             ..Default::default()
         };
         let content = html! { p { "test" } };
-        let doc = base_document("Test", "", None, None, None, None, &snippets, None, content)
-            .into_string();
+        let doc = base_document(
+            &PageContext {
+                snippets: &snippets,
+                ..page_ctx()
+            },
+            "Test",
+            None,
+            None,
+            None,
+            content,
+        )
+        .into_string();
         assert!(doc.contains(r#"<link rel="stylesheet" href="/custom.css">"#));
     }
 
@@ -3632,7 +5350,16 @@ mod tests {
         };
         let content = html! { p { "test" } };
         let doc = base_document(
-            "Test", "body{}", None, None, None, None, &snippets, None, content,
+            &PageContext {
+                css: "body{}",
+                snippets: &snippets,
+                ..page_ctx()
+            },
+            "Test",
+            None,
+            None,
+            None,
+            content,
         )
         .into_string();
         let style_pos = doc.find("</style>").unwrap();
@@ -3650,8 +5377,18 @@ mod tests {
             ..Default::default()
         };
         let content = html! { p { "test" } };
-        let doc = base_document("Test", "", None, None, None, None, &snippets, None, content)
-            .into_string();
+        let doc = base_document(
+            &PageContext {
+                snippets: &snippets,
+                ..page_ctx()
+            },
+            "Test",
+            None,
+            None,
+            None,
+            content,
+        )
+        .into_string();
         assert!(doc.contains(r#"<script>console.log("analytics")</script>"#));
     }
 
@@ -3662,8 +5399,18 @@ mod tests {
             ..Default::default()
         };
         let content = html! { p { "test" } };
-        let doc = base_document("Test", "", None, None, None, None, &snippets, None, content)
-            .into_string();
+        let doc = base_document(
+            &PageContext {
+                snippets: &snippets,
+                ..page_ctx()
+            },
+            "Test",
+            None,
+            None,
+            None,
+            content,
+        )
+        .into_string();
         let head_end = doc.find("</head>").unwrap();
         let snippet_pos = doc.find("<!-- custom head -->").unwrap();
         assert!(
@@ -3675,18 +5422,7 @@ mod tests {
     #[test]
     fn no_head_html_by_default() {
         let content = html! { p { "test" } };
-        let doc = base_document(
-            "Test",
-            "",
-            None,
-            None,
-            None,
-            None,
-            &no_snippets(),
-            None,
-            content,
-        )
-        .into_string();
+        let doc = base_document(&page_ctx(), "Test", None, None, None, content).into_string();
         // Only the standard head content should be present
         assert!(!doc.contains("<!-- custom"));
     }
@@ -3698,8 +5434,18 @@ mod tests {
             ..Default::default()
         };
         let content = html! { p { "test" } };
-        let doc = base_document("Test", "", None, None, None, None, &snippets, None, content)
-            .into_string();
+        let doc = base_document(
+            &PageContext {
+                snippets: &snippets,
+                ..page_ctx()
+            },
+            "Test",
+            None,
+            None,
+            None,
+            content,
+        )
+        .into_string();
         assert!(doc.contains(r#"<script src="/tracking.js"></script>"#));
     }
 
@@ -3710,8 +5456,18 @@ mod tests {
             ..Default::default()
         };
         let content = html! { p { "test" } };
-        let doc = base_document("Test", "", None, None, None, None, &snippets, None, content)
-            .into_string();
+        let doc = base_document(
+            &PageContext {
+                snippets: &snippets,
+                ..page_ctx()
+            },
+            "Test",
+            None,
+            None,
+            None,
+            content,
+        )
+        .into_string();
         let body_end = doc.find("</body>").unwrap();
         let snippet_pos = doc.find("<!-- body end -->").unwrap();
         assert!(
@@ -3727,8 +5483,18 @@ mod tests {
             ..Default::default()
         };
         let content = html! { p { "main content" } };
-        let doc = base_document("Test", "", None, None, None, None, &snippets, None, content)
-            .into_string();
+        let doc = base_document(
+            &PageContext {
+                snippets: &snippets,
+                ..page_ctx()
+            },
+            "Test",
+            None,
+            None,
+            None,
+            content,
+        )
+        .into_string();
         let content_pos = doc.find("main content").unwrap();
         let snippet_pos = doc.find("<!-- body end -->").unwrap();
         assert!(
@@ -3745,8 +5511,18 @@ mod tests {
             body_end_html: Some("<!-- body snippet -->".to_string()),
         };
         let content = html! { p { "test" } };
-        let doc = base_document("Test", "", None, None, None, None, &snippets, None, content)
-            .into_string();
+        let doc = base_document(
+            &PageContext {
+                snippets: &snippets,
+                ..page_ctx()
+            },
+            "Test",
+            None,
+            None,
+            None,
+            content,
+        )
+        .into_string();
         assert!(doc.contains(r#"href="/custom.css""#));
         assert!(doc.contains("<!-- head snippet -->"));
         assert!(doc.contains("<!-- body snippet -->"));
@@ -3777,15 +5553,11 @@ mod tests {
         // Album page
         let album = create_test_album();
         let html = render_album_page(
+            &PageContext {
+                snippets: &snippets,
+                ..page_ctx()
+            },
             &album,
-            &[],
-            &[],
-            "",
-            None,
-            "Gallery",
-            None,
-            &snippets,
-            false,
             None,
         )
         .into_string();
@@ -3795,26 +5567,29 @@ mod tests {
 
         // Content page
         let page = make_page("about", "About", true, false);
-        let html =
-            render_page(&page, &[], &[], "", None, "Gallery", None, &snippets, false).into_string();
+        let html = render_page(
+            &PageContext {
+                snippets: &snippets,
+                ..page_ctx()
+            },
+            &page,
+            &SiteConfig::default(),
+        )
+        .into_string();
         assert!(html.contains("custom.css"));
         assert!(html.contains("<!-- head -->"));
         assert!(html.contains("<!-- body -->"));
 
         // Image page
         let html = render_image_page(
+            &PageContext {
+                snippets: &snippets,
+                ..page_ctx()
+            },
             &album,
             &album.images[0],
             None,
             Some(&album.images[1]),
-            &[],
-            &[],
-            "",
-            None,
-            "Gallery",
-            None,
-            &snippets,
-            false,
             None,
         )
         .into_string();
@@ -3860,6 +5635,105 @@ mod tests {
         );
     }
 
+    #[test]
+    fn config_snippets_follow_file_snippets() {
+        let config = SiteConfig {
+            head_html: Some("<meta name=\"config\">".to_string()),
+            body_end_html: Some("<script>cfg()</script>".to_string()),
+            ..Default::default()
+        };
+
+        let only_config = CustomSnippets::default().with_config(&config);
+        assert_eq!(
+            only_config.head_html.as_deref(),
+            Some("<meta name=\"config\">")
+        );
+        assert_eq!(
+            only_config.body_end_html.as_deref(),
+            Some("<script>cfg()</script>")
+        );
+
+        let both = CustomSnippets {
+            has_custom_css: true,
+            head_html: Some("<meta name=\"file\">".to_string()),
+            body_end_html: None,
+            attribution: false,
+        }
+        .with_config(&config);
+        assert!(both.has_custom_css);
+        assert_eq!(
+            both.head_html.as_deref(),
+            Some("<meta name=\"file\">\n<meta name=\"config\">")
+        );
+        assert_eq!(
+            both.body_end_html.as_deref(),
+            Some("<script>cfg()</script>")
+        );
+
+        let neither = CustomSnippets::default().with_config(&SiteConfig::default());
+        assert!(neither.head_html.is_none());
+        assert!(neither.body_end_html.is_none());
+    }
+
+    // =========================================================================
+    // nearest_variant tests
+    // =========================================================================
+
+    fn ladder(widths: &[u32]) -> Vec<GeneratedVariant> {
+        widths
+            .iter()
+            .map(|&width| GeneratedVariant {
+                avif: format!("{width}.avif"),
+                width,
+                height: width * 3 / 4,
+            })
+            .collect()
+    }
+
+    fn nearest_width(widths: &[u32], target: u32) -> Option<u32> {
+        let variants = ladder(widths);
+        let refs: Vec<_> = variants.iter().collect();
+        nearest_variant(&refs, target).map(|v| v.width)
+    }
+
+    #[test]
+    fn nearest_variant_picks_closest_width() {
+        assert_eq!(nearest_width(&[800, 1400, 2080], 1400), Some(1400));
+        assert_eq!(nearest_width(&[400, 800, 1600, 3200], 1400), Some(1600));
+        assert_eq!(
+            nearest_width(&[640, 1200, 1920, 2560, 3840], 1400),
+            Some(1200)
+        );
+        // Positional middle would be 2080 here
+        assert_eq!(nearest_width(&[600, 1300, 2080, 2600], 1400), Some(1300));
+        assert_eq!(nearest_width(&[3000, 4000], 1400), Some(3000));
+        assert_eq!(nearest_width(&[320], 1400), Some(320));
+        assert_eq!(nearest_width(&[], 1400), None);
+    }
+
+    #[test]
+    fn nearest_variant_tie_prefers_larger() {
+        assert_eq!(nearest_width(&[1200, 1600], 1400), Some(1600));
+    }
+
+    #[test]
+    fn render_image_page_default_src_nearest_configured_width() {
+        let mut album = create_test_album();
+        album.config.images.default_width = 800;
+        let html = render_image_page(
+            &page_ctx(),
+            &album,
+            &album.images[0],
+            None,
+            Some(&album.images[1]),
+            None,
+        )
+        .into_string();
+
+        assert!(html.contains(r#"src="../001-dawn-800.avif""#));
+        assert!(html.contains(r#"<link rel="prefetch" as="image" href="../002-night-800.avif">"#));
+    }
+
     // =========================================================================
     // image_sizes_attr tests
     // =========================================================================
@@ -3939,18 +5813,14 @@ mod tests {
         let image = &album.images[1]; // portrait 1200x1600, generated width=600
         let nav = vec![];
         let html = render_image_page(
+            &PageContext {
+                navigation: &nav,
+                ..page_ctx()
+            },
             &album,
             image,
             Some(&album.images[0]),
             None,
-            &nav,
-            &[],
-            "",
-            None,
-            "Gallery",
-            None,
-            &no_snippets(),
-            false,
             None,
         )
         .into_string();
@@ -3972,18 +5842,14 @@ mod tests {
         let image = &album.images[0]; // landscape 1600x1200, generated widths 800 and 1400
         let nav = vec![];
         let html = render_image_page(
+            &PageContext {
+                navigation: &nav,
+                ..page_ctx()
+            },
             &album,
             image,
             None,
             Some(&album.images[1]),
-            &nav,
-            &[],
-            "",
-            None,
-            "Gallery",
-            None,
-            &no_snippets(),
-            false,
             None,
         )
         .into_string();
@@ -4064,8 +5930,10 @@ mod tests {
     #[test]
     fn og_description_joins_site_title_segments_and_trailing() {
         let segments = vec![("NY", "NY"), ("Night", "NY/Night")];
-        let out = og_description("Gallery", &segments, &["City"]);
+        let out = og_description("Gallery", "›", &segments, &["City"]);
         assert_eq!(out, "Gallery › NY › Night › City");
+        let out = og_description("Gallery", "/", &segments, &["City"]);
+        assert_eq!(out, "Gallery / NY / Night / City");
     }
 
     fn ny_navigation() -> Vec<NavItem> {
@@ -4088,7 +5956,13 @@ mod tests {
     fn build_og_for_nested_album_yields_absolute_paths_and_breadcrumb() {
         let album = create_nested_test_album();
         let navigation = ny_navigation();
-        let og = build_og_for_album("https://example.com", &album, &navigation, "Gallery").unwrap();
+        let og = build_og_for_album(
+            "https://example.com",
+            &album,
+            &navigation,
+            &SiteConfig::default(),
+        )
+        .unwrap();
 
         assert_eq!(og.title, "Night");
         assert_eq!(og.page_url, "https://example.com/NY/Night/");
@@ -4111,7 +5985,7 @@ mod tests {
             image,
             0,
             &navigation,
-            "Gallery",
+            &SiteConfig::default(),
         )
         .unwrap();
         // Single-image album → 1-wide index, so label is "1. City".
@@ -4127,21 +6001,9 @@ mod tests {
     #[test]
     fn render_album_page_emits_og_tags_when_og_is_some() {
         let album = create_test_album();
-        let og = build_og_for_album("https://example.com", &album, &[], "Gallery")
+        let og = build_og_for_album("https://example.com", &album, &[], &SiteConfig::default())
             .expect("album has images");
-        let html = render_album_page(
-            &album,
-            &[],
-            &[],
-            "",
-            None,
-            "Gallery",
-            None,
-            &no_snippets(),
-            false,
-            Some(&og),
-        )
-        .into_string();
+        let html = render_album_page(&page_ctx(), &album, Some(&og)).into_string();
 
         assert!(html.contains(r#"<meta property="og:title" content="Test Album">"#));
         assert!(html.contains(r#"<meta property="og:type" content="website">"#));
@@ -4154,19 +6016,7 @@ mod tests {
     #[test]
     fn render_album_page_emits_no_og_tags_when_og_is_none() {
         let album = create_test_album();
-        let html = render_album_page(
-            &album,
-            &[],
-            &[],
-            "",
-            None,
-            "Gallery",
-            None,
-            &no_snippets(),
-            false,
-            None,
-        )
-        .into_string();
+        let html = render_album_page(&page_ctx(), &album, None).into_string();
 
         assert!(!html.contains("og:title"));
         assert!(!html.contains("og:image"));
@@ -4177,21 +6027,21 @@ mod tests {
     fn render_image_page_og_description_is_full_breadcrumb() {
         let album = create_test_album();
         let image = &album.images[0];
-        let og = build_og_for_image("https://example.com", &album, image, 0, &[], "Gallery")
-            .expect("image has variants");
+        let og = build_og_for_image(
+            "https://example.com",
+            &album,
+            image,
+            0,
+            &[],
+            &SiteConfig::default(),
+        )
+        .expect("image has variants");
         let html = render_image_page(
+            &page_ctx(),
             &album,
             image,
             None,
             Some(&album.images[1]),
-            &[],
-            &[],
-            "",
-            None,
-            "Gallery",
-            None,
-            &no_snippets(),
-            false,
             Some(&og),
         )
         .into_string();
@@ -4200,4 +6050,1155 @@ mod tests {
         // (no nav segments because the test album has no navigation tree).
         assert!(html.contains(r#"content="Gallery › Test Album › 1. Dawn""#));
     }
+
+    // =========================================================================
+    // generate() summary
+    // =========================================================================
+
+    #[test]
+    fn generate_returns_summary_counts() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let processed = tmp.path().join("processed");
+        let output = tmp.path().join("dist");
+        for file in [
+            "Landscapes/001-dawn-800.avif",
+            "Landscapes/002-dusk-800.avif",
+            "Travel/Japan/001-tokyo-800.avif",
+        ] {
+            let path = processed.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "avif").unwrap();
+        }
+
+        let image = |album: &str, n: u32, stem: &str| {
+            format!(
+                r#"{{"number": {n}, "source_path": "{album}/{stem}.jpg",
+                    "dimensions": [800, 600],
+                    "generated": {{"800": {{"avif": "{album}/{stem}-800.avif", "width": 800, "height": 600}}}},
+                    "thumbnail": "{album}/{stem}-thumb.avif"}}"#
+            )
+        };
+        let album = |path: &str, title: &str, images: Vec<String>| {
+            format!(
+                r#"{{"path": "{path}", "title": "{title}", "description": null,
+                    "thumbnail": "{path}/001-thumb.avif", "images": [{}],
+                    "in_nav": true, "config": {{}}}}"#,
+                images.join(", ")
+            )
+        };
+        let page = |slug: &str, is_link: bool| {
+            format!(
+                r#"{{"title": "{slug}", "link_title": "{slug}", "slug": "{slug}", "body": "x",
+                    "in_nav": true, "sort_key": 1, "is_link": {is_link}}}"#
+            )
+        };
+        let manifest = format!(
+            r#"{{
+                "navigation": [
+                    {{"title": "Landscapes", "path": "Landscapes"}},
+                    {{"title": "Travel", "path": "Travel",
+                      "children": [{{"title": "Japan", "path": "Travel/Japan"}}]}}
+                ],
+                "albums": [{}, {}],
+                "pages": [{}, {}],
+                "config": {{}}
+            }}"#,
+            album(
+                "Landscapes",
+                "Landscapes",
+                vec![
+                    image("Landscapes", 1, "001-dawn"),
+                    image("Landscapes", 2, "002-dusk")
+                ]
+            ),
+            album(
+                "Travel/Japan",
+                "Japan",
+                vec![image("Travel/Japan", 1, "001-tokyo")]
+            ),
+            page("about", false),
+            page("github", true),
+        );
+        let manifest_path = processed.join("manifest.json");
+        fs::write(&manifest_path, manifest).unwrap();
+
+        let result = generate(&manifest_path, &processed, &output, tmp.path()).unwrap();
+        let summary = result.summary;
+
+        assert_eq!(
+            summary,
+            GenerateSummary {
+                albums: 2,
+                image_pages: 3,
+                content_pages: 1,
+                link_pages: 1,
+                // 6 PWA files + 3 copied images + home + about + Travel
+                // gallery list + 2 album pages + 3 image pages
+                files_written: 17,
+            }
+        );
+        assert!(output.join("Travel/index.html").exists());
+        assert_eq!(
+            summary.to_string(),
+            "Generated 2 albums, 3 image pages, 1 pages (17 files)"
+        );
+        let expected: Vec<std::path::PathBuf> = [
+            "Landscapes/001-dawn-800.avif",
+            "Landscapes/002-dusk-800.avif",
+            "Landscapes/1/index.html",
+            "Landscapes/2/index.html",
+            "Landscapes/index.html",
+            "Travel/Japan/001-tokyo-800.avif",
+            "Travel/Japan/1/index.html",
+            "Travel/Japan/index.html",
+            "Travel/index.html",
+            "about.html",
+            "apple-touch-icon.png",
+            "favicon.png",
+            "icon-192.png",
+            "icon-512.png",
+            "index.html",
+            "site.webmanifest",
+            "sw.js",
+        ]
+        .into_iter()
+        .map(Into::into)
+        .collect();
+        assert_eq!(result.files, expected);
+        assert!(result.files.iter().all(|f| output.join(f).is_file()));
+        assert_eq!(result.manifest.albums.len(), 2);
+    }
+
+    /// Write a one-album, one-image, one-page manifest with the given `config`
+    /// JSON object into `processed`, returning the manifest path.
+    fn write_minimal_site(processed: &Path, config: &str) -> std::path::PathBuf {
+        let image_path = processed.join("Landscapes/001-dawn-800.avif");
+        fs::create_dir_all(image_path.parent().unwrap()).unwrap();
+        fs::write(image_path, "avif").unwrap();
+
+        let manifest = format!(
+            r#"{{
+                "navigation": [{{"title": "Landscapes", "path": "Landscapes"}}],
+                "albums": [{{
+                    "path": "Landscapes", "title": "Landscapes", "description": null,
+                    "thumbnail": "Landscapes/001-dawn-thumb.avif",
+                    "images": [{{
+                        "number": 1, "source_path": "Landscapes/001-dawn.jpg",
+                        "dimensions": [800, 600],
+                        "generated": {{"800": {{"avif": "Landscapes/001-dawn-800.avif", "width": 800, "height": 600}}}},
+                        "thumbnail": "Landscapes/001-dawn-thumb.avif"
+                    }}],
+                    "in_nav": true, "config": {{}}
+                }}],
+                "pages": [{{"title": "About", "link_title": "About", "slug": "about", "body": "x",
+                           "in_nav": true, "sort_key": 1, "is_link": false}}],
+                "config": {config}
+            }}"#
+        );
+        let manifest_path = processed.join("manifest.json");
+        fs::write(&manifest_path, manifest).unwrap();
+        manifest_path
+    }
+
+    #[test]
+    fn failed_generate_leaves_previous_output_intact() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let processed = tmp.path().join("processed");
+        let output = tmp.path().join("dist");
+        let manifest_path = write_minimal_site(&processed, "{}");
+        generate(&manifest_path, &processed, &output, tmp.path()).unwrap();
+        let index = fs::read_to_string(output.join("index.html")).unwrap();
+
+        // Fails after the PWA files are written, before any page.
+        let manifest_path =
+            write_minimal_site(&processed, r#"{"icon_source": "assets/missing.png"}"#);
+        generate(&manifest_path, &processed, &output, tmp.path()).unwrap_err();
+
+        assert_eq!(
+            fs::read_to_string(output.join("index.html")).unwrap(),
+            index
+        );
+        assert!(output.join("Landscapes/1/index.html").exists());
+        assert!(!tmp.path().join(".dist.staging").exists());
+        assert!(!tmp.path().join(".dist.old").exists());
+    }
+
+    #[test]
+    fn generate_replaces_previous_output() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let processed = tmp.path().join("processed");
+        let output = tmp.path().join("dist");
+        let manifest_path = write_minimal_site(&processed, "{}");
+        generate(&manifest_path, &processed, &output, tmp.path()).unwrap();
+        fs::write(output.join("stale.html"), "old page").unwrap();
+
+        let manifest_path = write_minimal_site(&processed, r#"{"site_title": "New"}"#);
+        generate(&manifest_path, &processed, &output, tmp.path()).unwrap();
+
+        // Not written by this build, so carried over.
+        assert!(output.join("stale.html").exists());
+        let index = fs::read_to_string(output.join("index.html")).unwrap();
+        assert!(index.contains("<title>New</title>"), "{index}");
+        let leftovers: Vec<_> = fs::read_dir(tmp.path())
+            .unwrap()
+            .map(|e| e.unwrap().file_name())
+            .filter(|n| n.to_string_lossy().starts_with(".dist"))
+            .collect();
+        assert!(leftovers.is_empty(), "{leftovers:?}");
+    }
+
+    #[test]
+    fn generate_keeps_files_it_did_not_write() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let processed = tmp.path().join("processed");
+        let source = tmp.path().join("content");
+        let output = tmp.path().join("dist");
+        fs::create_dir_all(output.join(".git/refs")).unwrap();
+        fs::write(output.join("CNAME"), "photos.example.com").unwrap();
+        fs::write(output.join(".git/HEAD"), "ref: refs/heads/gh-pages").unwrap();
+        fs::write(output.join("index.html"), "old index").unwrap();
+        let manifest_path = write_minimal_site(&processed, "{}");
+
+        generate(&manifest_path, &processed, &output, &source).unwrap();
+
+        assert_ne!(
+            fs::read_to_string(output.join("index.html")).unwrap(),
+            "old index"
+        );
+        assert_eq!(
+            fs::read_to_string(output.join("CNAME")).unwrap(),
+            "photos.example.com"
+        );
+        assert_eq!(
+            fs::read_to_string(output.join(".git/HEAD")).unwrap(),
+            "ref: refs/heads/gh-pages"
+        );
+        assert!(output.join(".git/refs").is_dir());
+    }
+
+    #[test]
+    fn generate_refuses_an_output_containing_its_inputs() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let source = tmp.path().join("content");
+        let processed = tmp.path().join(".simple-gal-temp/processed");
+        fs::create_dir_all(&source).unwrap();
+        fs::write(source.join("config.toml"), "").unwrap();
+        let manifest_path = write_minimal_site(&processed, "{}");
+
+        let temp = tmp.path().join(".simple-gal-temp");
+        for output in [tmp.path(), source.as_path(), temp.as_path()] {
+            let err = generate(&manifest_path, &processed, output, &source).unwrap_err();
+            assert!(
+                matches!(err, GenerateError::OutputContainsInput(..)),
+                "{output:?}: {err}"
+            );
+        }
+        assert!(source.join("config.toml").exists());
+        assert!(manifest_path.exists());
+        assert!(!tmp.path().join("index.html").exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn generate_into_a_symlink_fills_its_target() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let processed = tmp.path().join("processed");
+        let real = tmp.path().join("site");
+        let output = tmp.path().join("dist");
+        fs::create_dir_all(&real).unwrap();
+        fs::write(real.join("CNAME"), "photos.example.com").unwrap();
+        std::os::unix::fs::symlink(&real, &output).unwrap();
+        let manifest_path = write_minimal_site(&processed, "{}");
+
+        generate(&manifest_path, &processed, &output, tmp.path()).unwrap();
+
+        assert!(
+            fs::symlink_metadata(&output)
+                .unwrap()
+                .file_type()
+                .is_symlink()
+        );
+        assert!(real.join("index.html").exists());
+        assert!(real.join("Landscapes/1/index.html").exists());
+        // Written in place, so the link's other files stay.
+        assert!(real.join("CNAME").exists());
+        assert!(!tmp.path().join(".dist.staging").exists());
+    }
+
+    #[test]
+    fn output_dir_siblings_are_outside_it() {
+        let cwd = std::env::current_dir().unwrap();
+        let dot = resolve_output_dir(Path::new(".")).unwrap();
+        assert_eq!(dot, cwd);
+        assert!(!sibling_dir(&dot, "staging").starts_with(&cwd));
+
+        let tmp = tempfile::TempDir::new().unwrap();
+        fs::create_dir_all(tmp.path().join("dist")).unwrap();
+        let up = resolve_output_dir(&tmp.path().join("dist/..")).unwrap();
+        assert_eq!(up, fs::canonicalize(tmp.path()).unwrap());
+        assert_eq!(
+            resolve_output_dir(&tmp.path().join("./dist/.")).unwrap(),
+            tmp.path().join("dist")
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn copy_dir_recursive_follows_symlinks_but_stops_at_a_cycle() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let shared = tmp.path().join("shared");
+        let assets = tmp.path().join("assets");
+        fs::create_dir_all(&shared).unwrap();
+        fs::create_dir_all(assets.join("fonts")).unwrap();
+        fs::write(shared.join("logo.svg"), "<svg/>").unwrap();
+        std::os::unix::fs::symlink(&shared, assets.join("brand")).unwrap();
+        std::os::unix::fs::symlink(shared.join("logo.svg"), assets.join("logo.svg")).unwrap();
+
+        let out = tmp.path().join("out");
+        copy_dir_recursive(&assets, &out).unwrap();
+        assert_eq!(
+            fs::read_to_string(out.join("brand/logo.svg")).unwrap(),
+            "<svg/>"
+        );
+        assert_eq!(fs::read_to_string(out.join("logo.svg")).unwrap(), "<svg/>");
+        assert!(!out.join("logo.svg").is_symlink());
+
+        std::os::unix::fs::symlink(&assets, assets.join("fonts/loop")).unwrap();
+        let err = copy_dir_recursive(&assets, &tmp.path().join("out2")).unwrap_err();
+        assert!(err.to_string().contains("symlink cycle"), "{err}");
+    }
+
+    #[test]
+    fn asset_clashing_with_processed_image_follows_precedence() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let processed = tmp.path().join("processed");
+        let output = tmp.path().join("dist");
+        let assets = tmp.path().join("assets");
+        fs::create_dir_all(assets.join("Landscapes")).unwrap();
+        fs::write(assets.join("Landscapes/001-dawn-800.avif"), "asset").unwrap();
+        // Replacing a PWA default is how it is customised, not a clash.
+        fs::write(assets.join("favicon.png"), "custom icon").unwrap();
+        let clash = |kept, replaced| Overwrite {
+            path: PathBuf::from("Landscapes/001-dawn-800.avif"),
+            kept,
+            replaced,
+        };
+
+        let manifest_path = write_minimal_site(&processed, "{}");
+        let result = generate(&manifest_path, &processed, &output, tmp.path()).unwrap();
+        assert_eq!(
+            fs::read_to_string(output.join("Landscapes/001-dawn-800.avif")).unwrap(),
+            "avif"
+        );
+        assert_eq!(
+            result.overwritten,
+            vec![clash(FileOrigin::Processed, FileOrigin::Asset)]
+        );
+
+        let manifest_path =
+            write_minimal_site(&processed, r#"{"output": {"asset_precedence": "assets"}}"#);
+        let result = generate(&manifest_path, &processed, &output, tmp.path()).unwrap();
+        assert_eq!(
+            fs::read_to_string(output.join("Landscapes/001-dawn-800.avif")).unwrap(),
+            "asset"
+        );
+        assert_eq!(
+            result.overwritten,
+            vec![clash(FileOrigin::Asset, FileOrigin::Processed)]
+        );
+        assert_eq!(
+            fs::read_to_string(output.join("favicon.png")).unwrap(),
+            "custom icon"
+        );
+    }
+
+    #[test]
+    fn page_sibling_image_copied_and_resolves() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let processed = tmp.path().join("processed");
+        let output = tmp.path().join("dist");
+        let manifest_path = write_minimal_site(&processed, "{}");
+        let mut manifest: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&manifest_path).unwrap()).unwrap();
+        manifest["pages"][0]["body"] = "# About\n\n![Me](portrait.jpg)".into();
+        manifest["pages"][0]["assets"] = serde_json::json!(["portrait.jpg"]);
+        fs::write(&manifest_path, manifest.to_string()).unwrap();
+        fs::write(tmp.path().join("portrait.jpg"), "jpeg").unwrap();
+
+        generate(&manifest_path, &processed, &output, tmp.path()).unwrap();
+
+        assert_eq!(
+            fs::read_to_string(output.join("portrait.jpg")).unwrap(),
+            "jpeg"
+        );
+        let html = fs::read_to_string(output.join("about.html")).unwrap();
+        assert!(html.contains(r#"<img src="portrait.jpg" alt="Me""#));
+    }
+
+    #[test]
+    fn icon_source_resized_into_icon_set() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let processed = tmp.path().join("processed");
+        let output = tmp.path().join("dist");
+        let manifest_path =
+            write_minimal_site(&processed, r#"{"icon_source": "assets/icon-source.png"}"#);
+        fs::create_dir_all(tmp.path().join("assets")).unwrap();
+        image::RgbImage::from_pixel(600, 400, image::Rgb([200, 40, 40]))
+            .save(tmp.path().join("assets/icon-source.png"))
+            .unwrap();
+
+        generate(&manifest_path, &processed, &output, tmp.path()).unwrap();
+
+        for &(filename, size) in ICON_SIZES {
+            assert_eq!(
+                image::image_dimensions(output.join(filename)).unwrap(),
+                (size, size),
+                "{filename}"
+            );
+        }
+        let webmanifest: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(output.join("site.webmanifest")).unwrap())
+                .unwrap();
+        assert_eq!(webmanifest["icons"][0]["src"], "/icon-192.png");
+        assert_eq!(webmanifest["icons"][1]["src"], "/icon-512.png");
+        let html = fs::read_to_string(output.join("index.html")).unwrap();
+        assert!(html.contains(r#"<link rel="icon" type="image/png" href="/favicon.png">"#));
+        assert!(html.contains(r#"<link rel="apple-touch-icon" href="/apple-touch-icon.png">"#));
+    }
+
+    #[test]
+    fn missing_icon_source_is_an_error() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let processed = tmp.path().join("processed");
+        let output = tmp.path().join("dist");
+        let manifest_path = write_minimal_site(&processed, r#"{"icon_source": "nope.png"}"#);
+
+        let err = generate(&manifest_path, &processed, &output, tmp.path()).unwrap_err();
+        assert!(err.to_string().contains("nope.png"), "{err}");
+    }
+
+    /// Set the album's own `[download]` config (the cascade is resolved
+    /// before generate, so the site config alone doesn't reach it).
+    fn set_album_download(manifest_path: &Path, download: serde_json::Value) {
+        let mut manifest: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(manifest_path).unwrap()).unwrap();
+        manifest["albums"][0]["config"]["download"] = download;
+        fs::write(manifest_path, manifest.to_string()).unwrap();
+    }
+
+    fn album_zip_entries(output: &Path) -> Vec<String> {
+        let file = fs::File::open(output.join("Landscapes").join(ALBUM_ZIP)).unwrap();
+        let archive = zip::ZipArchive::new(file).unwrap();
+        archive.file_names().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn album_zip_holds_web_sizes_and_is_linked() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let processed = tmp.path().join("processed");
+        let output = tmp.path().join("dist");
+        let manifest_path = write_minimal_site(&processed, "{}");
+        set_album_download(&manifest_path, serde_json::json!({"album_zip": true}));
+
+        generate(&manifest_path, &processed, &output, tmp.path()).unwrap();
+
+        assert_eq!(album_zip_entries(&output), ["001-dawn-800.avif"]);
+        let page = fs::read_to_string(output.join("Landscapes/index.html")).unwrap();
+        assert!(page.contains(r#"<a class="album-download" href="album.zip" download>"#));
+    }
+
+    #[test]
+    fn album_zip_with_originals() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let processed = tmp.path().join("processed");
+        let output = tmp.path().join("dist");
+        let manifest_path = write_minimal_site(&processed, "{}");
+        set_album_download(
+            &manifest_path,
+            serde_json::json!({"album_zip": true, "contents": "originals"}),
+        );
+        // Unscrubbed, so the bytes go in exactly as they are.
+        let mut manifest: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&manifest_path).unwrap()).unwrap();
+        manifest["albums"][0]["config"]["images"] = serde_json::json!({"strip_gps": false});
+        fs::write(&manifest_path, manifest.to_string()).unwrap();
+        fs::create_dir_all(tmp.path().join("Landscapes")).unwrap();
+        fs::write(tmp.path().join("Landscapes/001-dawn.jpg"), "original bytes").unwrap();
+
+        generate(&manifest_path, &processed, &output, tmp.path()).unwrap();
+
+        assert_eq!(album_zip_entries(&output), ["001-dawn.jpg"]);
+        let mut archive =
+            zip::ZipArchive::new(fs::File::open(output.join("Landscapes/album.zip")).unwrap())
+                .unwrap();
+        let mut entry = archive.by_name("001-dawn.jpg").unwrap();
+        let mut body = String::new();
+        std::io::Read::read_to_string(&mut entry, &mut body).unwrap();
+        assert_eq!(body, "original bytes");
+    }
+
+    #[test]
+    fn album_zip_withholds_originals_that_cant_be_scrubbed() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let processed = tmp.path().join("processed");
+        let output = tmp.path().join("dist");
+        let manifest_path = write_minimal_site(&processed, "{}");
+        set_album_download(
+            &manifest_path,
+            serde_json::json!({"album_zip": true, "contents": "originals"}),
+        );
+        fs::create_dir_all(tmp.path().join("Landscapes")).unwrap();
+        fs::write(tmp.path().join("Landscapes/001-dawn.jpg"), "not a jpeg").unwrap();
+
+        let result = generate(&manifest_path, &processed, &output, tmp.path()).unwrap();
+
+        assert!(album_zip_entries(&output).is_empty());
+        assert_eq!(result.withheld_originals, ["Landscapes/001-dawn.jpg"]);
+    }
+
+    /// Zip the [`exif_scrub::jpeg_with_gps`] fixture as an original with the
+    /// given `[images]` settings and return the archived bytes.
+    fn zipped_original_with_gps(images: serde_json::Value) -> Vec<u8> {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let processed = tmp.path().join("processed");
+        let output = tmp.path().join("dist");
+        let manifest_path = write_minimal_site(&processed, "{}");
+        set_album_download(
+            &manifest_path,
+            serde_json::json!({"album_zip": true, "contents": "originals"}),
+        );
+        let mut manifest: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&manifest_path).unwrap()).unwrap();
+        manifest["albums"][0]["config"]["images"] = images;
+        fs::write(&manifest_path, manifest.to_string()).unwrap();
+        fs::create_dir_all(tmp.path().join("Landscapes")).unwrap();
+        fs::write(
+            tmp.path().join("Landscapes/001-dawn.jpg"),
+            exif_scrub::jpeg_with_gps(),
+        )
+        .unwrap();
+
+        generate(&manifest_path, &processed, &output, tmp.path()).unwrap();
+
+        let mut archive =
+            zip::ZipArchive::new(fs::File::open(output.join("Landscapes/album.zip")).unwrap())
+                .unwrap();
+        let mut body = Vec::new();
+        std::io::Read::read_to_end(&mut archive.by_name("001-dawn.jpg").unwrap(), &mut body)
+            .unwrap();
+        body
+    }
+
+    fn has_gps_latitude(jpeg: &[u8]) -> bool {
+        let latitude: Vec<u8> = [51u32, 1, 30, 1]
+            .iter()
+            .flat_map(|v| v.to_le_bytes())
+            .collect();
+        jpeg.windows(latitude.len()).any(|w| w == latitude)
+    }
+
+    #[test]
+    fn album_zip_originals_have_gps_stripped_by_default() {
+        let body = zipped_original_with_gps(serde_json::json!({}));
+        assert!(!has_gps_latitude(&body));
+        assert!(!body.windows(7).any(|w| w == b"Camera1"));
+    }
+
+    #[test]
+    fn album_zip_originals_preserve_exif_keeps_camera_but_not_gps() {
+        let body = zipped_original_with_gps(serde_json::json!({"preserve_exif": true}));
+        assert!(!has_gps_latitude(&body));
+        assert!(body.windows(7).any(|w| w == b"Camera1"));
+    }
+
+    #[test]
+    fn album_zip_originals_untouched_without_strip_gps() {
+        let body = zipped_original_with_gps(serde_json::json!({"strip_gps": false}));
+        assert_eq!(body, exif_scrub::jpeg_with_gps());
+    }
+
+    #[test]
+    fn hidpi_thumbnails_get_a_density_srcset() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let processed = tmp.path().join("processed");
+        let output = tmp.path().join("dist");
+        let manifest_path = write_minimal_site(&processed, "{}");
+        let mut manifest: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&manifest_path).unwrap()).unwrap();
+        manifest["albums"][0]["thumbnail_2x"] = "Landscapes/001-dawn-thumb-2x.avif".into();
+        manifest["albums"][0]["images"][0]["thumbnail_2x"] =
+            "Landscapes/001-dawn-thumb-2x.avif".into();
+        fs::write(&manifest_path, manifest.to_string()).unwrap();
+
+        generate(&manifest_path, &processed, &output, tmp.path()).unwrap();
+
+        let album = fs::read_to_string(output.join("Landscapes/index.html")).unwrap();
+        assert!(album.contains(
+            r#"<img src="001-dawn-thumb.avif" srcset="001-dawn-thumb.avif 1x, 001-dawn-thumb-2x.avif 2x""#
+        ));
+        assert!(
+            album.contains(r#"imagesrcset="001-dawn-thumb.avif 1x, 001-dawn-thumb-2x.avif 2x""#)
+        );
+        let index = fs::read_to_string(output.join("index.html")).unwrap();
+        assert!(index.contains(
+            r#"srcset="/Landscapes/001-dawn-thumb.avif 1x, /Landscapes/001-dawn-thumb-2x.avif 2x""#
+        ));
+    }
+
+    #[test]
+    fn index_card_uses_card_thumbnail_when_processed() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let processed = tmp.path().join("processed");
+        let output = tmp.path().join("dist");
+        let manifest_path = write_minimal_site(&processed, "{}");
+
+        generate(&manifest_path, &processed, &output, tmp.path()).unwrap();
+        let index = fs::read_to_string(output.join("index.html")).unwrap();
+        assert!(index.contains(r#"src="/Landscapes/001-dawn-thumb.avif""#));
+
+        let mut manifest: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&manifest_path).unwrap()).unwrap();
+        manifest["albums"][0]["card_thumbnail"] = "Landscapes/001-dawn-card-thumb.avif".into();
+        fs::write(&manifest_path, manifest.to_string()).unwrap();
+
+        generate(&manifest_path, &processed, &output, tmp.path()).unwrap();
+        let index = fs::read_to_string(output.join("index.html")).unwrap();
+        assert!(index.contains(r#"src="/Landscapes/001-dawn-card-thumb.avif""#));
+        assert!(!index.contains("001-dawn-thumb.avif"));
+    }
+
+    #[test]
+    fn base64_encode_pads_to_whole_quads() {
+        assert_eq!(base64_encode(b""), "");
+        assert_eq!(base64_encode(b"f"), "Zg==");
+        assert_eq!(base64_encode(b"fo"), "Zm8=");
+        assert_eq!(base64_encode(b"foo"), "Zm9v");
+        assert_eq!(base64_encode(b"foobar"), "Zm9vYmFy");
+        assert_eq!(base64_encode(&[0xff, 0xfe]), "//4=");
+    }
+
+    #[test]
+    fn small_thumbnails_inline_and_large_ones_link_the_file() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let processed = tmp.path().join("processed");
+        let output = tmp.path().join("dist");
+        let manifest_path = write_minimal_site(&processed, "{}");
+        fs::write(processed.join("Landscapes/001-dawn-thumb.avif"), "tiny").unwrap();
+        fs::write(
+            processed.join("Landscapes/002-dusk-thumb.avif"),
+            "x".repeat(500),
+        )
+        .unwrap();
+        let mut manifest: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&manifest_path).unwrap()).unwrap();
+        let album = &mut manifest["albums"][0];
+        album["config"] = serde_json::json!({"thumbnails": {"inline_below_bytes": 100}});
+        album["images"][0]["thumbnail_bytes"] = 4.into();
+        let mut dusk = album["images"][0].clone();
+        dusk["number"] = 2.into();
+        dusk["source_path"] = "Landscapes/002-dusk.jpg".into();
+        dusk["thumbnail"] = "Landscapes/002-dusk-thumb.avif".into();
+        dusk["thumbnail_bytes"] = 500.into();
+        album["images"].as_array_mut().unwrap().push(dusk);
+        fs::write(&manifest_path, manifest.to_string()).unwrap();
+
+        generate(&manifest_path, &processed, &output, tmp.path()).unwrap();
+
+        let page = fs::read_to_string(output.join("Landscapes/index.html")).unwrap();
+        assert!(page.contains(r#"<img src="data:image/avif;base64,dGlueQ==" alt="Image 1">"#));
+        assert!(page.contains(r#"src="002-dusk-thumb.avif""#));
+        assert!(!page.contains(r#"href="001-dawn-thumb.avif""#));
+        let index = fs::read_to_string(output.join("index.html")).unwrap();
+        assert!(index.contains(r#"src="data:image/avif;base64,dGlueQ==""#));
+        assert!(!index.contains("001-dawn-thumb.avif"));
+    }
+
+    #[test]
+    fn hidpi_thumbnails_are_not_inlined() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let processed = tmp.path().join("processed");
+        let output = tmp.path().join("dist");
+        let manifest_path = write_minimal_site(&processed, "{}");
+        fs::write(processed.join("Landscapes/001-dawn-thumb.avif"), "tiny").unwrap();
+        let mut manifest: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&manifest_path).unwrap()).unwrap();
+        let album = &mut manifest["albums"][0];
+        album["config"] =
+            serde_json::json!({"thumbnails": {"hidpi": true, "inline_below_bytes": 100}});
+        album["thumbnail_2x"] = "Landscapes/001-dawn-thumb-2x.avif".into();
+        album["images"][0]["thumbnail_bytes"] = 4.into();
+        album["images"][0]["thumbnail_2x"] = "Landscapes/001-dawn-thumb-2x.avif".into();
+        fs::write(&manifest_path, manifest.to_string()).unwrap();
+
+        generate(&manifest_path, &processed, &output, tmp.path()).unwrap();
+
+        let page = fs::read_to_string(output.join("Landscapes/index.html")).unwrap();
+        assert!(!page.contains("data:image/avif"));
+        assert!(page.contains(
+            r#"<img src="001-dawn-thumb.avif" srcset="001-dawn-thumb.avif 1x, 001-dawn-thumb-2x.avif 2x""#
+        ));
+        let index = fs::read_to_string(output.join("index.html")).unwrap();
+        assert!(!index.contains("data:image/avif"));
+        assert!(index.contains(
+            r#"srcset="/Landscapes/001-dawn-thumb.avif 1x, /Landscapes/001-dawn-thumb-2x.avif 2x""#
+        ));
+    }
+
+    #[test]
+    fn no_album_zip_by_default() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let processed = tmp.path().join("processed");
+        let output = tmp.path().join("dist");
+        let manifest_path = write_minimal_site(&processed, "{}");
+
+        generate(&manifest_path, &processed, &output, tmp.path()).unwrap();
+
+        assert!(!output.join("Landscapes").join(ALBUM_ZIP).exists());
+        let page = fs::read_to_string(output.join("Landscapes/index.html")).unwrap();
+        assert!(!page.contains("Download album"));
+    }
+
+    /// Every generated HTML document under `output` (skipping copied
+    /// `.html` snippet files, which aren't full documents).
+    fn generated_pages(output: &Path) -> Vec<std::path::PathBuf> {
+        walkdir::WalkDir::new(output)
+            .into_iter()
+            .filter_map(Result::ok)
+            .filter(|e| e.path().extension().is_some_and(|ext| ext == "html"))
+            .filter(|e| {
+                fs::read_to_string(e.path()).is_ok_and(|html| html.starts_with("<!DOCTYPE html>"))
+            })
+            .map(|e| e.into_path())
+            .collect()
+    }
+
+    #[test]
+    fn every_page_starts_with_skip_link_to_main() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let processed = tmp.path().join("processed");
+        let output = tmp.path().join("dist");
+        let manifest_path =
+            write_minimal_site(&processed, r#"{"full_index": {"generates": true}}"#);
+
+        generate(&manifest_path, &processed, &output, tmp.path()).unwrap();
+
+        let pages = generated_pages(&output);
+        // home + about + album + image page + All Photos
+        assert_eq!(pages.len(), 5);
+        for page in pages {
+            let html = fs::read_to_string(&page).unwrap();
+            let body = html.find("<body").unwrap();
+            let first_link = html[body..].find("<a ").unwrap() + body;
+            assert!(
+                html[first_link..].starts_with(r##"<a class="skip-link" href="#main-content">"##),
+                "skip link is not the first link in {}",
+                page.display()
+            );
+            let main = html.find("<main").unwrap();
+            let main_tag = &html[main..main + html[main..].find('>').unwrap()];
+            assert!(
+                main_tag.contains(r#"id="main-content""#),
+                "main content target missing in {}",
+                page.display()
+            );
+            assert_eq!(html.matches(r#"id="main-content""#).count(), 1);
+        }
+    }
+
+    #[test]
+    fn config_snippets_injected_into_every_page() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let processed = tmp.path().join("processed");
+        let output = tmp.path().join("dist");
+        let manifest_path = write_minimal_site(
+            &processed,
+            r#"{
+                "head_html": "<meta name=\"from-config\">",
+                "body_end_html": "<script>trackFromConfig()</script>"
+            }"#,
+        );
+
+        generate(&manifest_path, &processed, &output, tmp.path()).unwrap();
+
+        let pages = generated_pages(&output);
+        // home + about + album + image page
+        assert_eq!(pages.len(), 4);
+        for page in pages {
+            let html = fs::read_to_string(&page).unwrap();
+            let head_end = html.find("</head>").unwrap();
+            let snippet = html.find(r#"<meta name="from-config">"#);
+            assert!(
+                snippet.is_some_and(|at| at < head_end),
+                "head snippet missing in {}",
+                page.display()
+            );
+            assert!(
+                html.contains("<script>trackFromConfig()</script></body>"),
+                "body-end snippet missing in {}",
+                page.display()
+            );
+        }
+    }
+
+    #[test]
+    fn assets_convention_files_injected_into_every_page() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let processed = tmp.path().join("processed");
+        let output = tmp.path().join("dist");
+        let manifest_path = write_minimal_site(&processed, "{}");
+        let assets = tmp.path().join("assets");
+        fs::create_dir_all(&assets).unwrap();
+        fs::write(assets.join("custom.css"), "body { color: red; }").unwrap();
+        fs::write(assets.join("head.html"), r#"<meta name="from-file">"#).unwrap();
+        fs::write(assets.join("body-end.html"), "<script>fromFile()</script>").unwrap();
+
+        generate(&manifest_path, &processed, &output, tmp.path()).unwrap();
+
+        assert!(output.join("custom.css").exists());
+        let pages = generated_pages(&output);
+        assert_eq!(pages.len(), 4);
+        for page in pages {
+            let html = fs::read_to_string(&page).unwrap();
+            let style_end = html.find("</style>").unwrap();
+            let head_end = html.find("</head>").unwrap();
+
+            let css_link = html
+                .find(r#"<link rel="stylesheet" href="/custom.css">"#)
+                .unwrap_or_else(|| panic!("custom.css link missing in {}", page.display()));
+            assert!(style_end < css_link && css_link < head_end);
+
+            let head = html
+                .find(r#"<meta name="from-file">"#)
+                .unwrap_or_else(|| panic!("head.html missing in {}", page.display()));
+            assert!(head < head_end);
+
+            assert!(
+                html.contains("<script>fromFile()</script></body>"),
+                "body-end.html missing in {}",
+                page.display()
+            );
+        }
+    }
+
+    #[test]
+    fn custom_breadcrumb_separator_and_home_label_on_every_page() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let processed = tmp.path().join("processed");
+        let output = tmp.path().join("dist");
+        let manifest_path = write_minimal_site(
+            &processed,
+            r#"{
+                "site_title": "Jane Doe Photography",
+                "home_label": "Home",
+                "base_url": "https://example.com",
+                "theme": {"breadcrumb_separator": "/"},
+                "full_index": {"generates": true}
+            }"#,
+        );
+
+        generate(&manifest_path, &processed, &output, tmp.path()).unwrap();
+
+        let pages = generated_pages(&output);
+        // home + about + album + image page + All Photos
+        assert_eq!(pages.len(), 5);
+        for page in pages {
+            let html = fs::read_to_string(&page).unwrap();
+            assert!(
+                html.contains(r#"<a href="/">Home</a>"#),
+                "home label missing in {}",
+                page.display()
+            );
+            assert!(
+                !html.contains(" › "),
+                "default separator in {}",
+                page.display()
+            );
+            if page != output.join("index.html") {
+                assert!(
+                    html.contains(r#"<a href="/">Home</a> / "#),
+                    "custom separator missing in {}",
+                    page.display()
+                );
+            }
+        }
+        let index = fs::read_to_string(output.join("index.html")).unwrap();
+        assert!(index.contains("<title>Jane Doe Photography</title>"));
+        let album = fs::read_to_string(output.join("Landscapes/index.html")).unwrap();
+        assert!(album.contains(
+            r#"<meta property="og:description" content="Jane Doe Photography / Landscapes">"#
+        ));
+    }
+
+    #[test]
+    fn logo_replaces_the_home_label_in_the_header() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let processed = tmp.path().join("processed");
+        let output = tmp.path().join("dist");
+        let manifest_path = write_minimal_site(&processed, r#"{"site_title": "Jane Doe"}"#);
+
+        generate(&manifest_path, &processed, &output, tmp.path()).unwrap();
+        let index = fs::read_to_string(output.join("index.html")).unwrap();
+        assert!(index.contains(r#"<a href="/">Jane Doe</a>"#));
+        assert!(!index.contains("site-logo"));
+
+        let manifest_path = write_minimal_site(
+            &processed,
+            r#"{"site_title": "Jane Doe", "base_path": "/gallery/", "logo": "logo.svg"}"#,
+        );
+        generate(&manifest_path, &processed, &output, tmp.path()).unwrap();
+        for page in generated_pages(&output) {
+            let html = fs::read_to_string(&page).unwrap();
+            assert!(
+                html.contains(
+                    r#"<a class="site-logo" href="/gallery/"><img src="/gallery/logo.svg" alt="Jane Doe"></a>"#
+                ),
+                "logo missing in {}",
+                page.display()
+            );
+            assert!(!html.contains(r#"<a href="/gallery/">Jane Doe</a>"#));
+        }
+    }
+
+    #[test]
+    fn i18n_alternates_emit_hreflang_links_on_every_page() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let processed = tmp.path().join("processed");
+        let output = tmp.path().join("dist");
+        let manifest_path = write_minimal_site(
+            &processed,
+            r#"{"i18n": {"alternates": {
+                "en": "https://example.com",
+                "fr": "https://example.com/fr/"
+            }}}"#,
+        );
+
+        generate(&manifest_path, &processed, &output, tmp.path()).unwrap();
+
+        let read = |page: &str| fs::read_to_string(output.join(page)).unwrap();
+        let index = read("index.html");
+        assert!(
+            index.contains(r#"<link rel="alternate" hreflang="en" href="https://example.com/">"#)
+        );
+        assert!(
+            index
+                .contains(r#"<link rel="alternate" hreflang="fr" href="https://example.com/fr/">"#)
+        );
+        let album = read("Landscapes/index.html");
+        assert!(album.contains(r#"hreflang="en" href="https://example.com/Landscapes/""#));
+        assert!(album.contains(r#"hreflang="fr" href="https://example.com/fr/Landscapes/""#));
+        assert!(
+            read("about.html")
+                .contains(r#"hreflang="fr" href="https://example.com/fr/about.html""#)
+        );
+        let image = read("Landscapes/1/index.html");
+        assert!(
+            image.contains(r#"hreflang="fr" href="https://example.com/fr/Landscapes/1/">"#),
+            "{image}"
+        );
+        for page in generated_pages(&output) {
+            let html = fs::read_to_string(&page).unwrap();
+            assert_eq!(html.matches(r#"rel="alternate" hreflang"#).count(), 2);
+        }
+    }
+
+    #[test]
+    fn no_hreflang_links_without_alternates() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let processed = tmp.path().join("processed");
+        let output = tmp.path().join("dist");
+        let manifest_path = write_minimal_site(&processed, "{}");
+
+        generate(&manifest_path, &processed, &output, tmp.path()).unwrap();
+
+        for page in generated_pages(&output) {
+            assert!(!fs::read_to_string(&page).unwrap().contains("hreflang"));
+        }
+    }
+
+    #[test]
+    fn attribution_footer_only_when_enabled() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let processed = tmp.path().join("processed");
+        let output = tmp.path().join("dist");
+        let manifest_path = write_minimal_site(&processed, "{}");
+        generate(&manifest_path, &processed, &output, tmp.path()).unwrap();
+        for page in generated_pages(&output) {
+            let html = fs::read_to_string(&page).unwrap();
+            assert!(
+                !html.contains("site-footer"),
+                "footer in {}",
+                page.display()
+            );
+        }
+
+        let manifest_path = write_minimal_site(&processed, r#"{"show_attribution": true}"#);
+        generate(&manifest_path, &processed, &output, tmp.path()).unwrap();
+        for page in generated_pages(&output) {
+            let html = fs::read_to_string(&page).unwrap();
+            assert!(
+                html.contains(
+                    r#"<footer class="site-footer">Built with <a href="https://github.com/arthur-debert/simple-gal">Simple Gal</a></footer>"#
+                ),
+                "footer missing in {}",
+                page.display()
+            );
+        }
+    }
+
+    #[test]
+    fn search_index_lists_albums_and_images() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let processed = tmp.path().join("processed");
+        let output = tmp.path().join("dist");
+        let manifest_path = write_minimal_site(&processed, "{}");
+        generate(&manifest_path, &processed, &output, tmp.path()).unwrap();
+        assert!(!output.join(SEARCH_INDEX).exists());
+        let index_html = fs::read_to_string(output.join("index.html")).unwrap();
+        assert!(!index_html.contains("site-search"));
+
+        let manifest_path = write_minimal_site(
+            &processed,
+            r#"{"base_path": "/gallery/", "search": {"enabled": true}}"#,
+        );
+        let mut manifest: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&manifest_path).unwrap()).unwrap();
+        manifest["albums"][0]["description"] = "<p>Early\nlight &amp; fog</p>".into();
+        manifest["albums"][0]["images"][0]["title"] = "Dawn".into();
+        manifest["albums"][0]["images"][0]["description"] = "Over the ridge".into();
+        fs::write(&manifest_path, manifest.to_string()).unwrap();
+        generate(&manifest_path, &processed, &output, tmp.path()).unwrap();
+
+        let index: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(output.join(SEARCH_INDEX)).unwrap()).unwrap();
+        assert_eq!(
+            index,
+            serde_json::json!([
+                {
+                    "url": "/gallery/Landscapes/",
+                    "title": "Landscapes",
+                    "description": "Early light & fog"
+                },
+                {
+                    "url": "/gallery/Landscapes/1-dawn/",
+                    "title": "Dawn",
+                    "album": "Landscapes",
+                    "description": "Over the ridge"
+                }
+            ])
+        );
+        assert!(output.join("Landscapes/1-dawn/index.html").exists());
+        let index_html = fs::read_to_string(output.join("index.html")).unwrap();
+        assert!(index_html.contains(
+            r#"<form class="site-search" role="search" data-index="/gallery/search-index.json">"#
+        ));
+        assert!(index_html.contains("querySelector('.site-search')"));
+        let album_html = fs::read_to_string(output.join("Landscapes/index.html")).unwrap();
+        assert!(!album_html.contains("site-search"));
+    }
+
+    #[test]
+    fn theme_color_follows_configured_backgrounds() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let processed = tmp.path().join("processed");
+        let output = tmp.path().join("dist");
+        let manifest_path = write_minimal_site(
+            &processed,
+            r##"{
+                "colors": {
+                    "light": {"background": "#f4efe6"},
+                    "dark": {"background": "#101418"}
+                }
+            }"##,
+        );
+
+        generate(&manifest_path, &processed, &output, tmp.path()).unwrap();
+
+        let webmanifest: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(output.join("site.webmanifest")).unwrap())
+                .unwrap();
+        assert_eq!(webmanifest["theme_color"], "#f4efe6");
+        assert_eq!(webmanifest["background_color"], "#f4efe6");
+
+        for page in generated_pages(&output) {
+            let html = fs::read_to_string(&page).unwrap();
+            assert!(
+                html.contains(
+                    r##"<meta name="theme-color" content="#f4efe6" media="(prefers-color-scheme: light)">"##
+                ),
+                "light theme-color missing in {}",
+                page.display()
+            );
+            assert!(
+                html.contains(
+                    r##"<meta name="theme-color" content="#101418" media="(prefers-color-scheme: dark)">"##
+                ),
+                "dark theme-color missing in {}",
+                page.display()
+            );
+            assert!(!html.contains(r##"content="#ffffff""##));
+        }
+    }
+
+    #[test]
+    fn subpath_build_roots_manifest_and_urls_at_base_path() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let processed = tmp.path().join("processed");
+        let output = tmp.path().join("dist");
+        let manifest_path = write_minimal_site(
+            &processed,
+            r#"{"base_path": "/gallery/", "pwa": {"display": "minimal-ui"}}"#,
+        );
+        let assets = tmp.path().join("assets");
+        fs::create_dir_all(&assets).unwrap();
+        fs::write(assets.join("favicon.svg"), "<svg/>").unwrap();
+        fs::write(assets.join("custom.css"), "body {}").unwrap();
+
+        generate(&manifest_path, &processed, &output, tmp.path()).unwrap();
+
+        let webmanifest: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(output.join("site.webmanifest")).unwrap())
+                .unwrap();
+        assert_eq!(webmanifest["scope"], "/gallery/");
+        assert_eq!(webmanifest["start_url"], "/gallery/");
+        assert_eq!(webmanifest["display"], "minimal-ui");
+        assert_eq!(webmanifest["icons"][0]["src"], "/gallery/icon-192.png");
+        assert_eq!(webmanifest["icons"][1]["src"], "/gallery/icon-512.png");
+
+        let sw = fs::read_to_string(output.join("sw.js")).unwrap();
+        assert!(sw.contains("'./site.webmanifest'"));
+        assert!(!sw.contains("'/"));
+
+        let pages = generated_pages(&output);
+        assert_eq!(pages.len(), 4);
+        for page in pages {
+            let html = fs::read_to_string(&page).unwrap();
+            for expected in [
+                r#"<link rel="manifest" href="/gallery/site.webmanifest">"#,
+                r#"<link rel="apple-touch-icon" href="/gallery/apple-touch-icon.png">"#,
+                r#"<link rel="icon" type="image/svg+xml" href="/gallery/favicon.svg">"#,
+                r#"<link rel="stylesheet" href="/gallery/custom.css">"#,
+                "navigator.serviceWorker.register('/gallery/sw.js');",
+                r#"<a href="/gallery/">Gallery</a>"#,
+                r#"href="/gallery/Landscapes/""#,
+                r#"href="/gallery/about.html""#,
+            ] {
+                assert!(
+                    html.contains(expected),
+                    "{expected} missing in {}",
+                    page.display()
+                );
+            }
+            // Every root-relative URL starts at the base path
+            for attr in [r#"href="/"#, r#"src="/"#] {
+                for (at, _) in html.match_indices(attr) {
+                    assert!(
+                        html[at + attr.len() - 1..].starts_with("/gallery/"),
+                        "unrooted URL in {}: {}",
+                        page.display(),
+                        &html[at..(at + 40).min(html.len())]
+                    );
+                }
+            }
+        }
+    }
 }
@@ -91,6 +91,7 @@ pub mod tests {
             crop_height: u32,
             quality: u32,
             sharpening: Option<(f32, i32)>,
+            focal: Option<(u32, u32)>,
         },
     }
 
@@ -167,6 +168,7 @@ pub mod tests {
                 crop_height: params.crop_height,
                 quality: params.quality.value(),
                 sharpening: params.sharpening.map(|s| (s.sigma, s.threshold)),
+                focal: params.focal,
             });
             Ok(())
         }
@@ -227,6 +229,7 @@ pub mod tests {
                 crop_height: 500,
                 quality: super::super::params::Quality::new(85),
                 sharpening: Some(Sharpening::light()),
+                focal: None,
             })
             .unwrap();
 
@@ -2,6 +2,8 @@
 //!
 //! All functions here are pure and testable without any I/O or images.
 
+use super::params::{AspectMode, ImageSizes};
+
 /// Calculate thumbnail dimensions from aspect ratio and short edge size.
 ///
 /// # Arguments
@@ -36,6 +38,71 @@ pub fn calculate_thumbnail_dimensions(aspect: (u32, u32), short_edge: u32) -> (u
     }
 }
 
+/// Aspect ratio of one image's thumbnail under `mode`, given the configured
+/// `aspect` and the `source` dimensions, both as `(width, height)`.
+///
+/// # Examples
+/// ```
+/// # use simple_gal::imaging::AspectMode;
+/// # use simple_gal::imaging::calculations::thumbnail_aspect;
+/// // A 3:2 landscape source with a 4:5 configured ratio
+/// assert_eq!(thumbnail_aspect(AspectMode::Fixed, (4, 5), (3000, 2000)), (4, 5));
+/// assert_eq!(thumbnail_aspect(AspectMode::Original, (4, 5), (3000, 2000)), (3000, 2000));
+/// assert_eq!(thumbnail_aspect(AspectMode::Adaptive, (4, 5), (3000, 2000)), (5, 4));
+/// ```
+pub fn thumbnail_aspect(mode: AspectMode, aspect: (u32, u32), source: (u32, u32)) -> (u32, u32) {
+    let (short, long) = (aspect.0.min(aspect.1), aspect.0.max(aspect.1));
+    match mode {
+        AspectMode::Fixed => aspect,
+        AspectMode::Original => source,
+        AspectMode::Adaptive if source.0 > source.1 => (long, short),
+        AspectMode::Adaptive if source.0 < source.1 => (short, long),
+        AspectMode::Adaptive => (1, 1),
+    }
+}
+
+/// Size a `source` image is scaled to so it covers a `crop`-sized window
+/// with nothing left over on at least one axis.
+///
+/// # Examples
+/// ```
+/// # use simple_gal::imaging::calculations::fill_dimensions;
+/// // 3:2 landscape filling a 4:5 thumbnail: height fits, width overflows
+/// assert_eq!(fill_dimensions((1500, 1000), (400, 500)), (750, 500));
+/// ```
+pub fn fill_dimensions(source: (u32, u32), crop: (u32, u32)) -> (u32, u32) {
+    let ratio = f64::max(
+        crop.0 as f64 / source.0 as f64,
+        crop.1 as f64 / source.1 as f64,
+    );
+    let w = (source.0 as f64 * ratio).round() as u32;
+    let h = (source.1 as f64 * ratio).round() as u32;
+    (w.max(crop.0), h.max(crop.1))
+}
+
+/// Top-left corner of the `crop` window inside a `filled` image (see
+/// [`fill_dimensions`]) that puts `focal` — `(x, y)` percentages — as
+/// close to the window's center as the image edges allow.
+///
+/// # Examples
+/// ```
+/// # use simple_gal::imaging::calculations::crop_origin;
+/// // Centered focal point → the same window as a center crop
+/// assert_eq!(crop_origin((750, 500), (400, 500), (50, 50)), (175, 0));
+/// // Focal point near the left edge → window pinned to the left
+/// assert_eq!(crop_origin((750, 500), (400, 500), (10, 50)), (0, 0));
+/// ```
+pub fn crop_origin(filled: (u32, u32), crop: (u32, u32), focal: (u32, u32)) -> (u32, u32) {
+    let axis = |filled: u32, crop: u32, percent: u32| {
+        let center = (filled as f64 * percent.min(100) as f64 / 100.0).round() as i64;
+        (center - crop as i64 / 2).clamp(0, filled.saturating_sub(crop) as i64) as u32
+    };
+    (
+        axis(filled.0, crop.0, focal.0),
+        axis(filled.1, crop.1, focal.1),
+    )
+}
+
 /// Represents a single responsive size to generate.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ResponsiveSize {
@@ -47,23 +114,62 @@ pub struct ResponsiveSize {
     pub height: u32,
 }
 
+/// Largest variant `sizes = "auto"` generates; bigger sources start here.
+pub const AUTO_SIZES_CAP: u32 = 2560;
+
+/// `sizes = "auto"` stops halving before going below this.
+pub const AUTO_SIZES_FLOOR: u32 = 400;
+
+/// Most variants `sizes = "auto"` can produce (2560 → 1280 → 640).
+pub const AUTO_SIZES_MAX_COUNT: usize = 3;
+
+/// Breakpoints for `sizes = "auto"`, ascending.
+///
+/// Starts at the source's longer edge (capped at [`AUTO_SIZES_CAP`]) and
+/// halves down while staying at or above [`AUTO_SIZES_FLOOR`]. Sources
+/// smaller than the floor get a single full-size variant.
+///
+/// # Examples
+/// ```
+/// # use simple_gal::imaging::calculations::auto_sizes;
+/// assert_eq!(auto_sizes((6000, 4000)), vec![640, 1280, 2560]);
+/// assert_eq!(auto_sizes((1000, 1500)), vec![750, 1500]);
+/// assert_eq!(auto_sizes((300, 200)), vec![300]);
+/// ```
+pub fn auto_sizes(original: (u32, u32)) -> Vec<u32> {
+    let mut size = original.0.max(original.1).min(AUTO_SIZES_CAP);
+    let mut sizes = vec![size];
+    while size / 2 >= AUTO_SIZES_FLOOR {
+        size /= 2;
+        sizes.push(size);
+    }
+    sizes.reverse();
+    sizes
+}
+
 /// Calculate which responsive sizes to generate and their dimensions.
 ///
-/// Filters out sizes larger than the original and calculates output dimensions
-/// preserving aspect ratio. If all requested sizes exceed the original,
+/// Explicit sizes larger than the original are capped to it; `auto` derives
+/// the breakpoints from the original via [`auto_sizes`]. Output dimensions
+/// preserve the aspect ratio. If all requested sizes exceed the original,
 /// returns the original size as the only entry.
 ///
 /// # Arguments
 /// * `original` - Original image dimensions (width, height)
-/// * `sizes` - Requested breakpoint sizes (on the longer edge)
+/// * `sizes` - Requested breakpoint sizes (on the longer edge), or `auto`
 ///
 /// # Returns
 /// * Vector of sizes to generate with their dimensions
-pub fn calculate_responsive_sizes(original: (u32, u32), sizes: &[u32]) -> Vec<ResponsiveSize> {
+pub fn calculate_responsive_sizes(original: (u32, u32), sizes: &ImageSizes) -> Vec<ResponsiveSize> {
     let (orig_w, orig_h) = original;
     let longer_edge = orig_w.max(orig_h);
 
-    let mut result: Vec<ResponsiveSize> = sizes
+    let targets = match sizes {
+        ImageSizes::Explicit(sizes) => sizes.clone(),
+        ImageSizes::Auto => auto_sizes(original),
+    };
+
+    let mut result: Vec<ResponsiveSize> = targets
         .iter()
         .map(|&target_size| {
             // Cap at source size — never upscale
@@ -95,14 +201,91 @@ pub fn calculate_responsive_sizes(original: (u32, u32), sizes: &[u32]) -> Vec<Re
     result
 }
 
+/// Requested sizes that exceed the original's longer edge.
+///
+/// These are the breakpoints [`calculate_responsive_sizes`] cannot honor:
+/// they are capped to the source size (and deduplicated) instead, so the
+/// image ends up with fewer variants than configured. Always empty for
+/// `auto`, whose ladder never exceeds the source.
+pub fn oversized_sizes(original: (u32, u32), sizes: &ImageSizes) -> Vec<u32> {
+    let longer_edge = original.0.max(original.1);
+    let sizes = sizes.explicit().unwrap_or_default();
+    sizes.iter().copied().filter(|&s| s > longer_edge).collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn explicit(sizes: &[u32]) -> ImageSizes {
+        ImageSizes::Explicit(sizes.to_vec())
+    }
+
     // =========================================================================
     // calculate_thumbnail_dimensions tests
     // =========================================================================
 
+    // =========================================================================
+    // thumbnail_aspect tests
+    // =========================================================================
+
+    const LANDSCAPE: (u32, u32) = (3000, 2000);
+    const PORTRAIT: (u32, u32) = (2000, 3000);
+
+    /// Thumbnail size for `source` at a 4:5 ratio and 400px short edge.
+    fn thumb_for(mode: AspectMode, source: (u32, u32)) -> (u32, u32) {
+        calculate_thumbnail_dimensions(thumbnail_aspect(mode, (4, 5), source), 400)
+    }
+
+    #[test]
+    fn fixed_mode_crops_every_source_to_the_configured_ratio() {
+        assert_eq!(thumb_for(AspectMode::Fixed, LANDSCAPE), (400, 500));
+        assert_eq!(thumb_for(AspectMode::Fixed, PORTRAIT), (400, 500));
+    }
+
+    #[test]
+    fn original_mode_keeps_each_source_shape() {
+        assert_eq!(thumb_for(AspectMode::Original, LANDSCAPE), (600, 400));
+        assert_eq!(thumb_for(AspectMode::Original, PORTRAIT), (400, 600));
+        assert_eq!(thumb_for(AspectMode::Original, (1000, 1000)), (400, 400));
+    }
+
+    #[test]
+    fn adaptive_mode_turns_the_ratio_to_the_source_orientation() {
+        assert_eq!(thumb_for(AspectMode::Adaptive, LANDSCAPE), (500, 400));
+        assert_eq!(thumb_for(AspectMode::Adaptive, PORTRAIT), (400, 500));
+        assert_eq!(thumb_for(AspectMode::Adaptive, (1000, 1000)), (400, 400));
+        // A landscape configured ratio is turned just the same
+        assert_eq!(
+            thumbnail_aspect(AspectMode::Adaptive, (16, 9), PORTRAIT),
+            (9, 16)
+        );
+    }
+
+    #[test]
+    fn fill_dimensions_covers_the_crop() {
+        assert_eq!(fill_dimensions((1500, 1000), (400, 500)), (750, 500));
+        assert_eq!(fill_dimensions((1000, 3000), (400, 500)), (400, 1200));
+        assert_eq!(fill_dimensions((800, 1000), (400, 500)), (400, 500));
+    }
+
+    #[test]
+    fn crop_origin_follows_the_focal_point() {
+        // Landscape source in a portrait thumbnail: only x can move
+        assert_eq!(crop_origin((750, 500), (400, 500), (50, 50)), (175, 0));
+        assert_eq!(crop_origin((750, 500), (400, 500), (70, 50)), (325, 0));
+        assert_eq!(crop_origin((750, 500), (400, 500), (30, 90)), (25, 0));
+        // Portrait source: only y can move
+        assert_eq!(crop_origin((400, 1200), (400, 500), (50, 30)), (0, 110));
+    }
+
+    #[test]
+    fn crop_origin_clamps_at_the_edges() {
+        assert_eq!(crop_origin((750, 500), (400, 500), (0, 0)), (0, 0));
+        assert_eq!(crop_origin((750, 500), (400, 500), (100, 100)), (350, 0));
+        assert_eq!(crop_origin((400, 1200), (400, 500), (50, 100)), (0, 700));
+    }
+
     #[test]
     fn thumbnail_portrait_aspect() {
         // 4:5 with short edge 400 → 400x500
@@ -140,7 +323,7 @@ mod tests {
     #[test]
     fn responsive_caps_at_source_size() {
         // 1000x800 landscape: 800 fits, 1400 and 2080 cap to 1000 (deduped)
-        let sizes = calculate_responsive_sizes((1000, 800), &[800, 1400, 2080]);
+        let sizes = calculate_responsive_sizes((1000, 800), &explicit(&[800, 1400, 2080]));
         assert_eq!(sizes.len(), 2);
         assert_eq!(sizes[0].target, 800);
         assert_eq!(sizes[1].target, 1000); // capped from 1400/2080
@@ -151,7 +334,7 @@ mod tests {
     #[test]
     fn responsive_calculates_dimensions_landscape() {
         // 2000x1500 landscape, target 1000 on longer edge
-        let sizes = calculate_responsive_sizes((2000, 1500), &[1000]);
+        let sizes = calculate_responsive_sizes((2000, 1500), &explicit(&[1000]));
         assert_eq!(sizes.len(), 1);
         assert_eq!(sizes[0].width, 1000);
         assert_eq!(sizes[0].height, 750); // 1500 * (1000/2000) = 750
@@ -160,7 +343,7 @@ mod tests {
     #[test]
     fn responsive_calculates_dimensions_portrait() {
         // 1500x2000 portrait, target 1000 on longer edge
-        let sizes = calculate_responsive_sizes((1500, 2000), &[1000]);
+        let sizes = calculate_responsive_sizes((1500, 2000), &explicit(&[1000]));
         assert_eq!(sizes.len(), 1);
         assert_eq!(sizes[0].width, 750); // 1500 * (1000/2000) = 750
         assert_eq!(sizes[0].height, 1000);
@@ -169,7 +352,7 @@ mod tests {
     #[test]
     fn responsive_caps_all_when_all_exceed() {
         // 500x400, all sizes exceed — all cap to 500, deduped to one
-        let sizes = calculate_responsive_sizes((500, 400), &[800, 1400, 2080]);
+        let sizes = calculate_responsive_sizes((500, 400), &explicit(&[800, 1400, 2080]));
         assert_eq!(sizes.len(), 1);
         assert_eq!(sizes[0].target, 500);
         assert_eq!(sizes[0].width, 500);
@@ -178,7 +361,7 @@ mod tests {
 
     #[test]
     fn responsive_preserves_order() {
-        let sizes = calculate_responsive_sizes((3000, 2000), &[800, 1400, 2080]);
+        let sizes = calculate_responsive_sizes((3000, 2000), &explicit(&[800, 1400, 2080]));
         assert_eq!(sizes.len(), 3);
         assert_eq!(sizes[0].target, 800);
         assert_eq!(sizes[1].target, 1400);
@@ -187,7 +370,50 @@ mod tests {
 
     #[test]
     fn responsive_empty_sizes_returns_empty() {
-        let sizes = calculate_responsive_sizes((1000, 800), &[]);
+        let sizes = calculate_responsive_sizes((1000, 800), &explicit(&[]));
         assert_eq!(sizes.len(), 0);
     }
+
+    #[test]
+    fn auto_sizes_halves_from_capped_source() {
+        assert_eq!(auto_sizes((6000, 4000)), vec![640, 1280, 2560]);
+        assert_eq!(auto_sizes((2560, 1440)), vec![640, 1280, 2560]);
+        assert_eq!(auto_sizes((1600, 2400)), vec![600, 1200, 2400]);
+        assert_eq!(auto_sizes((1000, 800)), vec![500, 1000]);
+        assert_eq!(auto_sizes((799, 600)), vec![799]);
+        assert_eq!(auto_sizes((300, 200)), vec![300]);
+    }
+
+    #[test]
+    fn auto_sizes_never_exceeds_max_count() {
+        for edge in [100, 400, 800, 1600, 2559, 2560, 5000, 20000] {
+            assert!(
+                auto_sizes((edge, edge)).len() <= AUTO_SIZES_MAX_COUNT,
+                "{edge}"
+            );
+        }
+    }
+
+    #[test]
+    fn responsive_auto_uses_source_ladder() {
+        let sizes = calculate_responsive_sizes((4000, 3000), &ImageSizes::Auto);
+        let targets: Vec<u32> = sizes.iter().map(|s| s.target).collect();
+        assert_eq!(targets, vec![640, 1280, 2560]);
+        assert_eq!((sizes[2].width, sizes[2].height), (2560, 1920));
+        assert_eq!((sizes[0].width, sizes[0].height), (640, 480));
+    }
+
+    #[test]
+    fn oversized_sizes_empty_for_auto() {
+        assert!(oversized_sizes((300, 200), &ImageSizes::Auto).is_empty());
+    }
+
+    #[test]
+    fn oversized_sizes_lists_sizes_above_longer_edge() {
+        assert_eq!(
+            oversized_sizes((800, 1000), &explicit(&[800, 1000, 1400, 2080])),
+            vec![1400, 2080]
+        );
+        assert!(oversized_sizes((3000, 2000), &explicit(&[800, 1400, 2080])).is_empty());
+    }
 }
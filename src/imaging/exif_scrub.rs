@@ -0,0 +1,1078 @@
+//! Removes location and other sensitive metadata from originals before they
+//! are published for download.
+//!
+//! Generated AVIFs are encoded from decoded pixels and never carry EXIF, so
+//! only the source files copied verbatim into album ZIPs need this. Like
+//! [`super::iptc_parser`], it walks the container by hand instead of pulling
+//! in an EXIF crate:
+//!
+//! | Format | [`Scrub::All`] | [`Scrub::Sensitive`] |
+//! |---|---|---|
+//! | JPEG | cut the APP1 EXIF block down to Orientation, drop XMP and APP13 IPTC | remove GPS and serial-number tags from the EXIF block, drop XMP that mentions GPS and APP13 IPTC |
+//! | TIFF | keep only Orientation and the tags describing the image data in IFD0 | remove GPS, serial-number and IPTC tags |
+//! | PNG | cut `eXIf` down to Orientation, drop the XMP `iTXt` chunk | remove GPS and serial-number tags from `eXIf`, drop XMP that mentions GPS |
+//! | WebP | cut `EXIF` down to Orientation, drop `XMP ` | remove GPS and serial-number tags from `EXIF`, drop XMP that mentions GPS |
+//! | AVIF | zero the `Exif` item but for Orientation, zero the XMP `mime` item | remove GPS and serial-number tags from `Exif`, zero XMP that mentions GPS |
+//!
+//! Orientation always survives, so a downloaded photo isn't shown sideways,
+//! and embedded ICC profiles (JPEG APP2, PNG `iCCP`, WebP `ICCP`, AVIF
+//! `colr`) are never touched, so colours stay right.
+//!
+//! Removed IFD entries have their out-of-line values zeroed as well, so the
+//! coordinates don't survive as orphaned bytes. AVIF items are edited where
+//! they lie, so no box offsets move.
+//!
+//! Scrubbing fails closed: a file whose structure can't be walked, or of any
+//! other format, yields `None` and is left out of the download.
+
+/// How much metadata to remove.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scrub {
+    /// All EXIF and XMP.
+    All,
+    /// GPS location and camera/lens serial numbers; camera, exposure and
+    /// date tags are kept.
+    Sensitive,
+}
+
+const TAG_ORIENTATION: u16 = 0x0112;
+const TAG_EXIF_IFD: u16 = 0x8769;
+const TAG_GPS_IFD: u16 = 0x8825;
+/// BodySerialNumber, LensSerialNumber and CameraOwnerName.
+const SENSITIVE_EXIF_TAGS: [u16; 3] = [0xA431, 0xA435, 0xA430];
+/// IPTC-NAA and Photoshop image resources, either of which can carry IPTC
+/// city and sublocation fields.
+const TAG_IPTC: [u16; 2] = [0x83BB, 0x8649];
+/// IFD0 tags describing a TIFF file's image data rather than the photo,
+/// the only ones [`Scrub::All`] keeps besides Orientation: dimensions,
+/// sample layout, strips and tiles, resolution, colour (including the ICC
+/// profile) and JPEG-in-TIFF tables.
+const TIFF_IMAGE_TAGS: [u16; 33] = [
+    0x00FE, 0x00FF, 0x0100, 0x0101, 0x0102, 0x0103, 0x0106, 0x010A, 0x0111, 0x0115, 0x0116, 0x0117,
+    0x011A, 0x011B, 0x011C, 0x0128, 0x012D, 0x013D, 0x013E, 0x013F, 0x0140, 0x0142, 0x0143, 0x0144,
+    0x0145, 0x0152, 0x0153, 0x015B, 0x0211, 0x0212, 0x0213, 0x0214, 0x8773,
+];
+
+const XMP_NAMESPACE: &[u8] = b"http://ns.adobe.com/xap/1.0/\0";
+const XMP_EXTENSION_NAMESPACE: &[u8] = b"http://ns.adobe.com/xmp/extension/\0";
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+/// Return `data` (a file with extension `ext`) with its metadata scrubbed,
+/// or `None` when that can't be done safely.
+///
+/// An EXIF block that can't be parsed inside an otherwise readable JPEG,
+/// WebP or AVIF is removed entirely. A file whose container can't be
+/// parsed, or of an unknown format, is `None` rather than published as is.
+pub fn scrub_metadata(data: &[u8], ext: &str, scrub: Scrub) -> Option<Vec<u8>> {
+    match ext.to_ascii_lowercase().as_str() {
+        "jpg" | "jpeg" => scrub_jpeg(data, scrub),
+        "tif" | "tiff" => {
+            let mut out = data.to_vec();
+            scrub_tiff(&mut out, scrub).map(|_| out)
+        }
+        "png" => scrub_png(data, scrub),
+        "webp" => scrub_webp(data, scrub),
+        "avif" => scrub_avif(data, scrub),
+        _ => None,
+    }
+}
+
+fn scrub_jpeg(data: &[u8], scrub: Scrub) -> Option<Vec<u8>> {
+    if data.len() < 4 || data[0] != 0xFF || data[1] != 0xD8 {
+        return None;
+    }
+    let mut out = Vec::with_capacity(data.len());
+    out.extend_from_slice(&data[..2]);
+    let mut pos = 2;
+    while pos + 4 <= data.len() {
+        if data[pos] != 0xFF {
+            return None;
+        }
+        let marker = data[pos + 1];
+        // Fill bytes between segments.
+        if marker == 0xFF {
+            out.push(0xFF);
+            pos += 1;
+            continue;
+        }
+        // Start of scan: the rest is entropy-coded image data.
+        if marker == 0xDA {
+            break;
+        }
+        let length = u16::from_be_bytes([data[pos + 2], data[pos + 3]]) as usize;
+        let end = pos + 2 + length;
+        if length < 2 || end > data.len() {
+            return None;
+        }
+        let payload = &data[pos + 4..end];
+        // APP13 holds IPTC, which can name the city and sublocation.
+        if marker == 0xED {
+            pos = end;
+            continue;
+        }
+        if marker == 0xE1 {
+            if let Some(tiff) = payload.strip_prefix(b"Exif\0\0") {
+                if let Some(tiff) = scrub_exif_block(tiff, scrub) {
+                    out.extend_from_slice(&[0xFF, 0xE1]);
+                    out.extend_from_slice(&u16::try_from(tiff.len() + 8).ok()?.to_be_bytes());
+                    out.extend_from_slice(b"Exif\0\0");
+                    out.extend_from_slice(&tiff);
+                }
+                pos = end;
+                continue;
+            }
+            if (payload.starts_with(XMP_NAMESPACE) || payload.starts_with(XMP_EXTENSION_NAMESPACE))
+                && (scrub == Scrub::All || xmp_mentions_gps(payload))
+            {
+                pos = end;
+                continue;
+            }
+        }
+        out.extend_from_slice(&data[pos..end]);
+        pos = end;
+    }
+    out.extend_from_slice(&data[pos..]);
+    Some(out)
+}
+
+fn scrub_png(data: &[u8], scrub: Scrub) -> Option<Vec<u8>> {
+    if !data.starts_with(PNG_SIGNATURE) {
+        return None;
+    }
+    let mut out = Vec::with_capacity(data.len());
+    out.extend_from_slice(PNG_SIGNATURE);
+    let mut pos = PNG_SIGNATURE.len();
+    while pos + 8 <= data.len() {
+        let length = u32::from_be_bytes(data[pos..pos + 4].try_into().ok()?) as usize;
+        let end = pos + 12 + length;
+        if end > data.len() {
+            return None;
+        }
+        let kind = &data[pos + 4..pos + 8];
+        let body = &data[pos + 8..pos + 8 + length];
+        let is_xmp = kind == b"iTXt" && body.starts_with(b"XML:com.adobe.xmp\0");
+        if kind == b"eXIf" {
+            if let Some(tiff) = scrub_exif_block(body, scrub) {
+                let mut chunk = b"eXIf".to_vec();
+                chunk.extend_from_slice(&tiff);
+                out.extend_from_slice(&u32::try_from(tiff.len()).ok()?.to_be_bytes());
+                out.extend_from_slice(&chunk);
+                out.extend_from_slice(&crc32(&chunk).to_be_bytes());
+            }
+        } else if !(is_xmp && (scrub == Scrub::All || xmp_mentions_gps(body))) {
+            out.extend_from_slice(&data[pos..end]);
+        }
+        pos = end;
+    }
+    out.extend_from_slice(&data[pos..]);
+    Some(out)
+}
+
+/// VP8X feature flags announcing an `EXIF` and an `XMP ` chunk.
+const WEBP_EXIF_FLAG: u8 = 0x08;
+const WEBP_XMP_FLAG: u8 = 0x04;
+
+fn scrub_webp(data: &[u8], scrub: Scrub) -> Option<Vec<u8>> {
+    if data.len() < 12 || &data[..4] != b"RIFF" || &data[8..12] != b"WEBP" {
+        return None;
+    }
+    let riff_end = 8usize.checked_add(u32::from_le_bytes(data[4..8].try_into().ok()?) as usize)?;
+    if riff_end > data.len() {
+        return None;
+    }
+    let mut out = data[..12].to_vec();
+    let mut vp8x_flags_at = None;
+    let mut pos = 12;
+    while pos < riff_end {
+        let kind = data.get(pos..pos + 4)?;
+        let size = u32::from_le_bytes(data.get(pos + 4..pos + 8)?.try_into().ok()?) as usize;
+        // Chunks are padded to an even length.
+        let end = (pos + 8 + size + (size & 1)).min(riff_end);
+        let body = data.get(pos + 8..pos + 8 + size)?;
+        match kind {
+            b"EXIF" => {
+                // Some writers keep the JPEG-style `Exif\0\0` prefix.
+                let prefix = if body.starts_with(b"Exif\0\0") { 6 } else { 0 };
+                match scrub_exif_block(&body[prefix..], scrub) {
+                    Some(tiff) => {
+                        let size = prefix + tiff.len();
+                        out.extend_from_slice(b"EXIF");
+                        out.extend_from_slice(&u32::try_from(size).ok()?.to_le_bytes());
+                        out.extend_from_slice(&body[..prefix]);
+                        out.extend_from_slice(&tiff);
+                        if size % 2 == 1 {
+                            out.push(0);
+                        }
+                    }
+                    None => {
+                        if let Some(at) = vp8x_flags_at {
+                            *out.get_mut(at)? &= !WEBP_EXIF_FLAG;
+                        }
+                    }
+                }
+            }
+            b"XMP " if scrub == Scrub::All || xmp_mentions_gps(body) => {
+                if let Some(at) = vp8x_flags_at {
+                    *out.get_mut(at)? &= !WEBP_XMP_FLAG;
+                }
+            }
+            _ => {
+                if kind == b"VP8X" {
+                    // The flags are the chunk's first byte.
+                    if body.is_empty() {
+                        return None;
+                    }
+                    vp8x_flags_at = Some(out.len() + 8);
+                }
+                out.extend_from_slice(&data[pos..end]);
+            }
+        }
+        pos = end;
+    }
+    out.extend_from_slice(&data[riff_end..]);
+    let riff_size = u32::try_from(out.len().checked_sub(8 + data.len() - riff_end)?).ok()?;
+    out[4..8].copy_from_slice(&riff_size.to_le_bytes());
+    Some(out)
+}
+
+/// Edit the `Exif` and XMP items of an AVIF (ISO-BMFF) file in place.
+fn scrub_avif(data: &[u8], scrub: Scrub) -> Option<Vec<u8>> {
+    let top = bmff_boxes(data, 0, data.len())?;
+    if top.first().map(|b| &b.kind) != Some(b"ftyp") {
+        return None;
+    }
+    let mut out = data.to_vec();
+    let Some(meta) = top.iter().find(|b| &b.kind == b"meta") else {
+        return Some(out);
+    };
+    // `meta` is a full box: version and flags precede its children.
+    let children = bmff_boxes(data, meta.content + 4, meta.end)?;
+    let child = |kind: &[u8; 4]| children.iter().find(|b| &b.kind == kind);
+    let Some(iinf) = child(b"iinf") else {
+        return Some(out);
+    };
+    let items = avif_items(data, iinf)?;
+    let locations = match child(b"iloc") {
+        Some(iloc) => avif_locations(data, iloc, child(b"idat").map(|b| b.content))?,
+        None => Vec::new(),
+    };
+    for item in items {
+        let is_exif = &item.kind == b"Exif";
+        let is_xmp = &item.kind == b"mime" && item.content_type == "application/rdf+xml";
+        if !is_exif && !is_xmp {
+            continue;
+        }
+        // An item we can't locate might still be in the file.
+        let (_, extents) = locations.iter().find(|(id, _)| *id == item.id)?;
+        let mut payload = Vec::new();
+        for &(offset, len) in extents {
+            payload.extend_from_slice(data.get(offset..offset.checked_add(len)?)?);
+        }
+        if is_exif {
+            // A 4-byte offset to the TIFF header leads the item.
+            let skip = 4 + u32::from_be_bytes(payload.get(..4)?.try_into().ok()?) as usize;
+            let kept = payload
+                .get(skip..)
+                .and_then(|tiff| scrub_exif_block(tiff, scrub));
+            // Zero the TIFF only, so the offset still leads to the kept block.
+            match payload.get_mut(skip..) {
+                Some(tiff) => tiff.fill(0),
+                None => payload.fill(0),
+            }
+            if let Some(kept) = kept
+                && let Some(dest) = payload.get_mut(skip..skip + kept.len())
+            {
+                dest.copy_from_slice(&kept);
+            }
+        } else if scrub == Scrub::All || xmp_mentions_gps(&payload) {
+            payload.fill(0);
+        }
+        let mut rest = payload.as_slice();
+        for &(offset, len) in extents {
+            let (head, tail) = rest.split_at(len);
+            out[offset..offset + len].copy_from_slice(head);
+            rest = tail;
+        }
+    }
+    Some(out)
+}
+
+/// An ISO-BMFF box: its type, where its content starts and where it ends.
+struct BmffBox {
+    kind: [u8; 4],
+    content: usize,
+    end: usize,
+}
+
+/// The boxes laid end to end in `data[start..end]`.
+fn bmff_boxes(data: &[u8], start: usize, end: usize) -> Option<Vec<BmffBox>> {
+    let mut boxes = Vec::new();
+    let mut pos = start;
+    while pos < end {
+        let size = read_be(data, pos, 4)? as usize;
+        let kind: [u8; 4] = data.get(pos + 4..pos + 8)?.try_into().ok()?;
+        let (header, size) = match size {
+            0 => (8, end - pos),
+            1 => (16, read_be(data, pos + 8, 8)? as usize),
+            size => (8, size),
+        };
+        let box_end = pos.checked_add(size)?;
+        if size < header || box_end > end {
+            return None;
+        }
+        boxes.push(BmffBox {
+            kind,
+            content: pos + header,
+            end: box_end,
+        });
+        pos = box_end;
+    }
+    Some(boxes)
+}
+
+/// Big-endian unsigned integer of `len` (0–8) bytes at `at`.
+fn read_be(data: &[u8], at: usize, len: usize) -> Option<u64> {
+    let bytes = data.get(at..at.checked_add(len)?)?;
+    Some(bytes.iter().fold(0, |acc, &b| (acc << 8) | b as u64))
+}
+
+struct AvifItem {
+    id: u32,
+    kind: [u8; 4],
+    content_type: String,
+}
+
+/// The entries of an `iinf` box.
+fn avif_items(data: &[u8], iinf: &BmffBox) -> Option<Vec<AvifItem>> {
+    let version = *data.get(iinf.content)?;
+    let entries_at = iinf.content + 4 + if version == 0 { 2 } else { 4 };
+    let mut items = Vec::new();
+    for infe in bmff_boxes(data, entries_at, iinf.end)? {
+        if &infe.kind != b"infe" {
+            continue;
+        }
+        let version = *data.get(infe.content)?;
+        let mut at = infe.content + 4;
+        let id = match version {
+            2 => read_be(data, at, 2)?,
+            3 => read_be(data, at, 4)?,
+            // Versions 0 and 1 predate item types; AVIF doesn't use them.
+            _ => return None,
+        } as u32;
+        at += (if version == 2 { 2 } else { 4 }) + 2; // item ID, protection index
+        let kind: [u8; 4] = data.get(at..at + 4)?.try_into().ok()?;
+        at += 4;
+        let c_string = |at: usize| -> Option<(String, usize)> {
+            let bytes = data.get(at..infe.end)?;
+            let len = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+            Some((
+                String::from_utf8_lossy(&bytes[..len]).into_owned(),
+                at + len + 1,
+            ))
+        };
+        let (_name, at) = c_string(at)?;
+        let content_type = match &kind {
+            b"mime" => c_string(at)?.0,
+            _ => String::new(),
+        };
+        items.push(AvifItem {
+            id,
+            kind,
+            content_type,
+        });
+    }
+    Some(items)
+}
+
+/// Each item's extents in an `iloc` box, as absolute `(offset, length)`
+/// ranges of the file. `idat` is where the `idat` box's content starts.
+#[allow(clippy::type_complexity)]
+fn avif_locations(
+    data: &[u8],
+    iloc: &BmffBox,
+    idat: Option<usize>,
+) -> Option<Vec<(u32, Vec<(usize, usize)>)>> {
+    let version = *data.get(iloc.content)?;
+    let sizes = read_be(data, iloc.content + 4, 2)? as usize;
+    let (offset_size, length_size) = (sizes >> 12, (sizes >> 8) & 0xF);
+    let (base_offset_size, index_size) = ((sizes >> 4) & 0xF, sizes & 0xF);
+    let wide = if version == 2 { 4 } else { 2 };
+    let mut at = iloc.content + 6;
+    let count = read_be(data, at, wide)?;
+    at += wide;
+    let mut locations = Vec::new();
+    for _ in 0..count {
+        let id = read_be(data, at, wide)? as u32;
+        at += wide;
+        let method = if version == 0 {
+            0
+        } else {
+            at += 2;
+            read_be(data, at - 2, 2)? & 0xF
+        };
+        at += 2; // data reference index
+        let base = read_be(data, at, base_offset_size)? as usize;
+        at += base_offset_size;
+        let extent_count = read_be(data, at, 2)?;
+        at += 2;
+        let origin = match method {
+            0 => 0,
+            1 => idat?,
+            _ => return None,
+        };
+        let mut extents = Vec::new();
+        for _ in 0..extent_count {
+            if version > 0 {
+                at += index_size;
+            }
+            let offset = read_be(data, at, offset_size)? as usize;
+            at += offset_size;
+            let length = read_be(data, at, length_size)? as usize;
+            at += length_size;
+            // A zero length means "to the end of the file".
+            let start = origin.checked_add(base)?.checked_add(offset)?;
+            let length = if length == 0 {
+                data.len().checked_sub(start)?
+            } else {
+                length
+            };
+            extents.push((start, length));
+        }
+        locations.push((id, extents));
+    }
+    Some(locations)
+}
+
+/// The EXIF block (a TIFF structure) to keep in place of `tiff`, or `None`
+/// to drop it. [`Scrub::All`] keeps only the Orientation tag, so the photo
+/// still displays the right way up; so does [`Scrub::Sensitive`] on a block
+/// it can't edit safely.
+fn scrub_exif_block(tiff: &[u8], scrub: Scrub) -> Option<Vec<u8>> {
+    if scrub == Scrub::Sensitive {
+        let mut scrubbed = tiff.to_vec();
+        if scrub_tiff(&mut scrubbed, scrub).is_some() {
+            return Some(scrubbed);
+        }
+    }
+    orientation_only(tiff)
+}
+
+/// A TIFF structure in `tiff`'s byte order whose IFD0 holds nothing but
+/// `tiff`'s Orientation tag, or `None` when it has none.
+fn orientation_only(tiff: &[u8]) -> Option<Vec<u8>> {
+    let mut buf = tiff.to_vec();
+    let tiff = Tiff::new(&mut buf)?;
+    let ifd0 = tiff.read_u32(4)? as usize;
+    tiff.ifd_len(ifd0)?;
+    // A single SHORT, so the value sits inside the entry itself.
+    let entry = (0..tiff.read_u16(ifd0)? as usize)
+        .map(|i| ifd0 + 2 + i * 12)
+        .find(|&entry| tiff.read_u16(entry) == Some(TAG_ORIENTATION))
+        .filter(|&entry| {
+            tiff.read_u16(entry + 2) == Some(3) && tiff.read_u32(entry + 4) == Some(1)
+        })?;
+    let (ifd0_offset, entry_count) = if tiff.big_endian {
+        (8u32.to_be_bytes(), 1u16.to_be_bytes())
+    } else {
+        (8u32.to_le_bytes(), 1u16.to_le_bytes())
+    };
+    let mut out = tiff.buf[..4].to_vec();
+    out.extend_from_slice(&ifd0_offset);
+    out.extend_from_slice(&entry_count);
+    out.extend_from_slice(&tiff.buf[entry..entry + 12]);
+    out.extend_from_slice(&[0; 4]);
+    Some(out)
+}
+
+/// CRC-32 (ISO 3309) as PNG chunks use it, over a chunk's type and data.
+fn crc32(bytes: &[u8]) -> u32 {
+    !bytes.iter().fold(!0u32, |crc, &byte| {
+        (0..8).fold(crc ^ byte as u32, |crc, _| {
+            (crc >> 1) ^ (0xEDB8_8320 & (crc & 1).wrapping_neg())
+        })
+    })
+}
+
+fn xmp_mentions_gps(xmp: &[u8]) -> bool {
+    xmp.windows(8).any(|w| w == b"exif:GPS")
+}
+
+/// Edit the TIFF structure in `buf` (starting at its byte-order mark) in place.
+fn scrub_tiff(buf: &mut [u8], scrub: Scrub) -> Option<()> {
+    let mut tiff = Tiff::new(buf)?;
+    let ifd0 = tiff.read_u32(4)? as usize;
+    match scrub {
+        Scrub::All => {
+            let removed = tiff.remove_entries(ifd0, |tag| {
+                tag != TAG_ORIENTATION && !TIFF_IMAGE_TAGS.contains(&tag)
+            })?;
+            for (tag, value) in removed {
+                if tag == TAG_GPS_IFD || tag == TAG_EXIF_IFD {
+                    tiff.wipe_ifd(value as usize)?;
+                }
+            }
+        }
+        Scrub::Sensitive => {
+            let exif_ifd = tiff.find_entry(ifd0, TAG_EXIF_IFD)?;
+            let removed =
+                tiff.remove_entries(ifd0, |tag| tag == TAG_GPS_IFD || TAG_IPTC.contains(&tag))?;
+            for (tag, value) in removed {
+                if tag == TAG_GPS_IFD {
+                    tiff.wipe_ifd(value as usize)?;
+                }
+            }
+            if let Some(exif_ifd) = exif_ifd {
+                tiff.remove_entries(exif_ifd as usize, |tag| SENSITIVE_EXIF_TAGS.contains(&tag))?;
+            }
+        }
+    }
+    Some(())
+}
+
+/// A TIFF buffer with its byte order resolved. Every accessor returns
+/// `None` when an offset points outside the buffer.
+struct Tiff<'a> {
+    buf: &'a mut [u8],
+    big_endian: bool,
+}
+
+impl<'a> Tiff<'a> {
+    fn new(buf: &'a mut [u8]) -> Option<Self> {
+        let big_endian = match buf.get(..2)? {
+            b"MM" => true,
+            b"II" => false,
+            _ => return None,
+        };
+        let tiff = Self { buf, big_endian };
+        (tiff.read_u16(2)? == 42).then_some(tiff)
+    }
+
+    fn read_u16(&self, at: usize) -> Option<u16> {
+        let bytes: [u8; 2] = self.buf.get(at..at + 2)?.try_into().ok()?;
+        Some(if self.big_endian {
+            u16::from_be_bytes(bytes)
+        } else {
+            u16::from_le_bytes(bytes)
+        })
+    }
+
+    fn read_u32(&self, at: usize) -> Option<u32> {
+        let bytes: [u8; 4] = self.buf.get(at..at + 4)?.try_into().ok()?;
+        Some(if self.big_endian {
+            u32::from_be_bytes(bytes)
+        } else {
+            u32::from_le_bytes(bytes)
+        })
+    }
+
+    fn write_u16(&mut self, at: usize, value: u16) {
+        let bytes = if self.big_endian {
+            value.to_be_bytes()
+        } else {
+            value.to_le_bytes()
+        };
+        self.buf[at..at + 2].copy_from_slice(&bytes);
+    }
+
+    fn zero(&mut self, start: usize, len: usize) -> Option<()> {
+        self.buf.get_mut(start..start.checked_add(len)?)?.fill(0);
+        Some(())
+    }
+
+    /// Byte range of the IFD at `ifd`: count, entries and next-IFD offset.
+    fn ifd_len(&self, ifd: usize) -> Option<usize> {
+        let len = 2 + 12 * self.read_u16(ifd)? as usize + 4;
+        self.buf.get(ifd..ifd + len)?;
+        Some(len)
+    }
+
+    /// Value field of `tag` in the IFD at `ifd`, if present.
+    fn find_entry(&self, ifd: usize, tag: u16) -> Option<Option<u32>> {
+        self.ifd_len(ifd)?;
+        let count = self.read_u16(ifd)? as usize;
+        for entry in (0..count).map(|i| ifd + 2 + i * 12) {
+            if self.read_u16(entry)? == tag {
+                return Some(Some(self.read_u32(entry + 8)?));
+            }
+        }
+        Some(None)
+    }
+
+    /// Zero the value of the entry at `entry` when it's stored out of line
+    /// (more than 4 bytes).
+    fn wipe_value(&mut self, entry: usize) -> Option<()> {
+        let type_size = match self.read_u16(entry + 2)? {
+            1 | 2 | 6 | 7 => 1,   // BYTE, ASCII, SBYTE, UNDEFINED
+            3 | 8 => 2,           // SHORT, SSHORT
+            4 | 9 | 11 | 13 => 4, // LONG, SLONG, FLOAT, IFD
+            5 | 10 | 12 => 8,     // RATIONAL, SRATIONAL, DOUBLE
+            _ => 1,
+        };
+        let size = (self.read_u32(entry + 4)? as usize).checked_mul(type_size)?;
+        if size > 4 {
+            let offset = self.read_u32(entry + 8)? as usize;
+            self.zero(offset, size)?;
+        }
+        Some(())
+    }
+
+    /// Remove the entries whose tag matches `remove` from the IFD at `ifd`,
+    /// wiping their values, and return each removed `(tag, value field)`.
+    fn remove_entries(
+        &mut self,
+        ifd: usize,
+        remove: impl Fn(u16) -> bool,
+    ) -> Option<Vec<(u16, u32)>> {
+        let len = self.ifd_len(ifd)?;
+        let count = self.read_u16(ifd)? as usize;
+        let next_ifd: [u8; 4] = self.buf[ifd + len - 4..ifd + len].try_into().ok()?;
+        let mut kept = Vec::with_capacity(count);
+        let mut removed = Vec::new();
+        for entry in (0..count).map(|i| ifd + 2 + i * 12) {
+            let tag = self.read_u16(entry)?;
+            if remove(tag) {
+                removed.push((tag, self.read_u32(entry + 8)?));
+                self.wipe_value(entry)?;
+            } else {
+                let bytes: [u8; 12] = self.buf[entry..entry + 12].try_into().ok()?;
+                kept.push(bytes);
+            }
+        }
+        if removed.is_empty() {
+            return Some(removed);
+        }
+        // Rewrite the IFD compacted: entries stay in tag order, the
+        // next-IFD offset follows the last kept entry, and the freed tail is
+        // zeroed.
+        self.zero(ifd, len)?;
+        self.write_u16(ifd, kept.len() as u16);
+        for (i, bytes) in kept.iter().enumerate() {
+            let at = ifd + 2 + i * 12;
+            self.buf[at..at + 12].copy_from_slice(bytes);
+        }
+        let at = ifd + 2 + kept.len() * 12;
+        self.buf[at..at + 4].copy_from_slice(&next_ifd);
+        Some(removed)
+    }
+
+    /// Zero a whole sub-IFD and every value it points to.
+    fn wipe_ifd(&mut self, ifd: usize) -> Option<()> {
+        let len = self.ifd_len(ifd)?;
+        let count = self.read_u16(ifd)? as usize;
+        for entry in (0..count).map(|i| ifd + 2 + i * 12) {
+            self.wipe_value(entry)?;
+        }
+        self.zero(ifd, len)
+    }
+}
+
+/// A minimal JPEG whose EXIF block has a camera model, an Orientation of 6,
+/// a GPS position and a body serial number. The GPS latitude reference is `"N"` and the latitude
+/// rationals are 51/1, 30/1, 2634/100.
+#[cfg(test)]
+pub(crate) fn jpeg_with_gps() -> Vec<u8> {
+    let tiff = tiff_with_gps();
+    let mut jpeg = vec![0xFF, 0xD8, 0xFF, 0xE1];
+    jpeg.extend_from_slice(&((tiff.len() + 8) as u16).to_be_bytes());
+    jpeg.extend_from_slice(b"Exif\0\0");
+    jpeg.extend_from_slice(&tiff);
+    // SOS with a few bytes of "scan data", then EOI.
+    jpeg.extend_from_slice(&[0xFF, 0xDA, 0x00, 0x02, 0x12, 0x34, 0xFF, 0xD9]);
+    jpeg
+}
+
+/// A minimal extended-format WebP carrying the [`jpeg_with_gps`] EXIF block
+/// in an `EXIF` chunk, announced in its `VP8X` flags.
+#[cfg(test)]
+pub(crate) fn webp_with_gps() -> Vec<u8> {
+    let chunk = |kind: &[u8], body: &[u8]| {
+        let mut c = kind.to_vec();
+        c.extend_from_slice(&(body.len() as u32).to_le_bytes());
+        c.extend_from_slice(body);
+        if body.len() % 2 == 1 {
+            c.push(0);
+        }
+        c
+    };
+    let mut body = b"WEBP".to_vec();
+    body.extend(chunk(b"VP8X", &[WEBP_EXIF_FLAG, 0, 0, 0, 0, 0, 0, 0, 0, 0]));
+    body.extend(chunk(b"VP8L", &[0x2F, 0, 0, 0, 0]));
+    body.extend(chunk(b"EXIF", &tiff_with_gps()));
+    let mut webp = b"RIFF".to_vec();
+    webp.extend_from_slice(&(body.len() as u32).to_le_bytes());
+    webp.extend(body);
+    webp
+}
+
+/// A minimal AVIF whose `meta` box lists one `Exif` item, stored in `mdat`,
+/// holding the [`jpeg_with_gps`] EXIF block.
+#[cfg(test)]
+pub(crate) fn avif_with_gps() -> Vec<u8> {
+    let bmff = |kind: &[u8], body: &[u8]| {
+        let mut b = ((body.len() + 8) as u32).to_be_bytes().to_vec();
+        b.extend_from_slice(kind);
+        b.extend_from_slice(body);
+        b
+    };
+    let mut exif = 0u32.to_be_bytes().to_vec();
+    exif.extend(tiff_with_gps());
+
+    let ftyp = bmff(b"ftyp", b"avif\0\0\0\0avifmif1");
+    // infe v2: item 1, no protection, type Exif, empty name.
+    let infe = bmff(b"infe", b"\x02\0\0\0\0\x01\0\0Exif\0");
+    let mut iinf_body = vec![0, 0, 0, 0, 0, 1];
+    iinf_body.extend(infe);
+    let iinf = bmff(b"iinf", &iinf_body);
+    let iloc_len = 8 + 4 + 2 + 2 + 2 + 2 + 2 + 4 + 4;
+    let meta_len = 8 + 4 + iinf.len() + iloc_len;
+    let exif_at = (ftyp.len() + meta_len + 8) as u32;
+    // iloc v0: 4-byte offsets and lengths, no base offset, one extent.
+    let mut iloc_body = vec![0, 0, 0, 0, 0x44, 0x00, 0, 1, 0, 1, 0, 0, 0, 1];
+    iloc_body.extend_from_slice(&exif_at.to_be_bytes());
+    iloc_body.extend_from_slice(&(exif.len() as u32).to_be_bytes());
+    let iloc = bmff(b"iloc", &iloc_body);
+    let mut meta_body = vec![0, 0, 0, 0];
+    meta_body.extend(iinf);
+    meta_body.extend(iloc);
+    let meta = bmff(b"meta", &meta_body);
+    assert_eq!(meta.len(), meta_len);
+
+    let mut avif = ftyp;
+    avif.extend(meta);
+    avif.extend(bmff(b"mdat", &exif));
+    avif
+}
+
+/// The EXIF block of [`jpeg_with_gps`]: a TIFF structure starting at its
+/// byte-order mark.
+#[cfg(test)]
+fn tiff_with_gps() -> Vec<u8> {
+    // Little-endian TIFF: header (8), IFD0 at 8 with Model, Orientation,
+    // ExifIFD, GPSIFD.
+    let mut tiff: Vec<u8> = Vec::new();
+    tiff.extend_from_slice(b"II\x2a\x00");
+    tiff.extend_from_slice(&8u32.to_le_bytes());
+    let entry = |tiff: &mut Vec<u8>, tag: u16, typ: u16, count: u32, value: u32| {
+        tiff.extend_from_slice(&tag.to_le_bytes());
+        tiff.extend_from_slice(&typ.to_le_bytes());
+        tiff.extend_from_slice(&count.to_le_bytes());
+        tiff.extend_from_slice(&value.to_le_bytes());
+    };
+    // IFD0: 4 entries, 8 + 2 + 48 + 4 = 62.
+    let model_at = 62u32;
+    let exif_at = model_at + 8;
+    tiff.extend_from_slice(&4u16.to_le_bytes());
+    entry(&mut tiff, 0x0110, 2, 8, model_at);
+    entry(&mut tiff, TAG_ORIENTATION, 3, 1, 6); // Rotate 90° CW
+    entry(&mut tiff, TAG_EXIF_IFD, 4, 1, exif_at);
+    // Exif IFD: 2 entries = 2 + 24 + 4 = 30, then the serial number (8).
+    let serial_at = exif_at + 30;
+    let gps_at = serial_at + 8;
+    entry(&mut tiff, TAG_GPS_IFD, 4, 1, gps_at);
+    tiff.extend_from_slice(&0u32.to_le_bytes());
+    tiff.extend_from_slice(b"Camera1\0");
+    tiff.extend_from_slice(&2u16.to_le_bytes());
+    entry(&mut tiff, 0x9003, 2, 4, u32::from_le_bytes(*b"2024")); // DateTimeOriginal (truncated)
+    entry(&mut tiff, 0xA431, 2, 8, serial_at);
+    tiff.extend_from_slice(&0u32.to_le_bytes());
+    tiff.extend_from_slice(b"SN12345\0");
+    // GPS IFD: 2 entries = 2 + 24 + 4 = 30, then the latitude (24).
+    let latitude_at = gps_at + 30;
+    tiff.extend_from_slice(&2u16.to_le_bytes());
+    entry(&mut tiff, 0x0001, 2, 2, u32::from_le_bytes(*b"N\0\0\0"));
+    entry(&mut tiff, 0x0002, 5, 3, latitude_at);
+    tiff.extend_from_slice(&0u32.to_le_bytes());
+    for (num, den) in [(51u32, 1u32), (30, 1), (2634, 100)] {
+        tiff.extend_from_slice(&num.to_le_bytes());
+        tiff.extend_from_slice(&den.to_le_bytes());
+    }
+    tiff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The TIFF block of a scrubbed JPEG produced by [`jpeg_with_gps`].
+    fn exif_tiff(jpeg: &[u8]) -> Option<Vec<u8>> {
+        jpeg.windows(6)
+            .position(|w| w == b"Exif\0\0")
+            .map(|at| jpeg[at + 6..jpeg.len() - 8].to_vec())
+    }
+
+    fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+        haystack.windows(needle.len()).any(|w| w == needle)
+    }
+
+    fn latitude_bytes() -> Vec<u8> {
+        [51u32, 1, 30, 1]
+            .iter()
+            .flat_map(|v| v.to_le_bytes())
+            .collect()
+    }
+
+    #[test]
+    fn fixture_has_gps_and_serial() {
+        let jpeg = jpeg_with_gps();
+        let mut tiff = exif_tiff(&jpeg).unwrap();
+        let t = Tiff::new(&mut tiff).unwrap();
+        assert!(t.find_entry(8, TAG_GPS_IFD).unwrap().is_some());
+        assert!(contains(&jpeg, &latitude_bytes()));
+        assert!(contains(&jpeg, b"SN12345"));
+    }
+
+    #[test]
+    fn sensitive_removes_gps_and_serial_but_keeps_camera() {
+        let jpeg = jpeg_with_gps();
+        let scrubbed = scrub_metadata(&jpeg, "JPG", Scrub::Sensitive).unwrap();
+
+        assert_eq!(scrubbed.len(), jpeg.len());
+        let mut tiff = exif_tiff(&scrubbed).unwrap();
+        let t = Tiff::new(&mut tiff).unwrap();
+        assert_eq!(t.read_u16(8), Some(3));
+        assert_eq!(t.find_entry(8, TAG_ORIENTATION), Some(Some(6)));
+        assert_eq!(t.find_entry(8, TAG_GPS_IFD), Some(None));
+        let exif_ifd = t.find_entry(8, TAG_EXIF_IFD).unwrap().unwrap() as usize;
+        assert_eq!(t.find_entry(exif_ifd, 0xA431), Some(None));
+        assert!(t.find_entry(exif_ifd, 0x9003).unwrap().is_some());
+        assert!(!contains(&scrubbed, &latitude_bytes()));
+        assert!(!contains(&scrubbed, b"SN12345"));
+        assert!(contains(&scrubbed, b"Camera1"));
+        assert!(scrubbed.ends_with(&[0xFF, 0xDA, 0x00, 0x02, 0x12, 0x34, 0xFF, 0xD9]));
+    }
+
+    /// Check `tiff` holds nothing but the fixture's Orientation.
+    fn assert_orientation_only(mut tiff: Vec<u8>) {
+        assert_eq!(tiff.len(), 26);
+        let t = Tiff::new(&mut tiff).unwrap();
+        assert_eq!(t.read_u16(8), Some(1));
+        assert_eq!(t.find_entry(8, TAG_ORIENTATION), Some(Some(6)));
+        assert_eq!(t.read_u32(22), Some(0));
+    }
+
+    #[test]
+    fn all_keeps_only_orientation() {
+        let scrubbed = scrub_metadata(&jpeg_with_gps(), "jpeg", Scrub::All).unwrap();
+
+        assert_orientation_only(exif_tiff(&scrubbed).unwrap());
+        assert_eq!(&scrubbed[..6], &[0xFF, 0xD8, 0xFF, 0xE1, 0x00, 34]);
+        assert!(!contains(&scrubbed, b"Camera1"));
+        assert!(!contains(&scrubbed, &latitude_bytes()));
+        assert!(scrubbed.ends_with(&[0xFF, 0xDA, 0x00, 0x02, 0x12, 0x34, 0xFF, 0xD9]));
+    }
+
+    #[test]
+    fn all_drops_the_exif_segment_without_orientation() {
+        let mut jpeg = jpeg_with_gps();
+        // Retag Orientation (IFD0's second entry) as StripOffsets.
+        jpeg[12 + 22] = 0x11;
+        let scrubbed = scrub_metadata(&jpeg, "jpeg", Scrub::All).unwrap();
+        assert_eq!(
+            scrubbed,
+            vec![0xFF, 0xD8, 0xFF, 0xDA, 0x00, 0x02, 0x12, 0x34, 0xFF, 0xD9]
+        );
+    }
+
+    #[test]
+    fn unparseable_exif_is_dropped_in_sensitive_mode() {
+        let mut jpeg = jpeg_with_gps();
+        // Corrupt the TIFF byte-order mark.
+        jpeg[12] = 0;
+        let scrubbed = scrub_metadata(&jpeg, "jpg", Scrub::Sensitive).unwrap();
+        assert!(!contains(&scrubbed, b"Exif\0\0"));
+    }
+
+    #[test]
+    fn tiff_all_keeps_only_orientation_and_image_tags() {
+        let mut tiff = tiff_with_gps();
+        // Retag Model (IFD0's first entry) as ImageWidth.
+        let mut with_width = tiff.clone();
+        with_width[10] = 0x00;
+        with_width[11] = 0x01;
+
+        let scrubbed = scrub_metadata(&tiff, "tif", Scrub::All).unwrap();
+        let mut scrubbed_tiff = scrubbed.clone();
+        let t = Tiff::new(&mut scrubbed_tiff).unwrap();
+        assert_eq!(t.read_u16(8), Some(1));
+        assert_eq!(t.find_entry(8, TAG_ORIENTATION), Some(Some(6)));
+        assert_eq!(t.find_entry(8, TAG_EXIF_IFD), Some(None));
+        assert!(!contains(&scrubbed, &latitude_bytes()));
+        assert!(!contains(&scrubbed, b"SN12345"));
+        assert!(!contains(&scrubbed, b"Camera1"));
+
+        scrub_tiff(&mut with_width, Scrub::All).unwrap();
+        let t = Tiff::new(&mut with_width).unwrap();
+        assert_eq!(t.read_u16(8), Some(2));
+        assert!(t.find_entry(8, 0x0100).unwrap().is_some());
+
+        // IPTC goes in Sensitive mode too: retag Model as IPTC-NAA.
+        tiff[10] = 0xBB;
+        tiff[11] = 0x83;
+        let scrubbed = scrub_metadata(&tiff, "tiff", Scrub::Sensitive).unwrap();
+        assert!(!contains(&scrubbed, b"Camera1"));
+    }
+
+    #[test]
+    fn jpeg_iptc_segment_is_removed() {
+        let mut iptc = vec![0xFF, 0xED, 0x00, 0x14];
+        iptc.extend_from_slice(b"Photoshop 3.0\0City");
+        let plain = jpeg_with_gps();
+        let mut jpeg = plain[..2].to_vec();
+        jpeg.extend(iptc);
+        jpeg.extend_from_slice(&plain[2..]);
+
+        for scrub in [Scrub::All, Scrub::Sensitive] {
+            let scrubbed = scrub_metadata(&jpeg, "jpg", scrub).unwrap();
+            assert_eq!(scrubbed, scrub_metadata(&plain, "jpg", scrub).unwrap());
+            assert!(!contains(&scrubbed, b"Photoshop"));
+        }
+    }
+
+    fn png_chunk(kind: &[u8], body: &[u8]) -> Vec<u8> {
+        let mut c = (body.len() as u32).to_be_bytes().to_vec();
+        c.extend_from_slice(kind);
+        c.extend_from_slice(body);
+        c.extend_from_slice(&crc32(&c[4..]).to_be_bytes());
+        c
+    }
+
+    #[test]
+    fn crc32_matches_png() {
+        // The CRC every PNG's IEND chunk carries.
+        assert_eq!(crc32(b"IEND"), 0xAE42_6082);
+    }
+
+    #[test]
+    fn png_drops_unreadable_exif_chunk() {
+        let chunk = png_chunk;
+        let mut png = PNG_SIGNATURE.to_vec();
+        png.extend(chunk(b"IHDR", &[0; 13]));
+        png.extend(chunk(b"eXIf", b"MM\0\x2a"));
+        png.extend(chunk(b"IEND", &[]));
+
+        let scrubbed = scrub_metadata(&png, "png", Scrub::Sensitive).unwrap();
+
+        let mut expected = PNG_SIGNATURE.to_vec();
+        expected.extend(chunk(b"IHDR", &[0; 13]));
+        expected.extend(chunk(b"IEND", &[]));
+        assert_eq!(scrubbed, expected);
+    }
+
+    #[test]
+    fn png_exif_chunk_is_scrubbed_with_a_fresh_crc() {
+        let png_with = |exif: &[u8]| {
+            let mut png = PNG_SIGNATURE.to_vec();
+            png.extend(png_chunk(b"IHDR", &[0; 13]));
+            png.extend(png_chunk(b"eXIf", exif));
+            png.extend(png_chunk(b"IEND", &[]));
+            png
+        };
+        let tiff = tiff_with_gps();
+        let png = png_with(&tiff);
+
+        let mut sensitive_tiff = tiff.clone();
+        scrub_tiff(&mut sensitive_tiff, Scrub::Sensitive).unwrap();
+        let sensitive = scrub_metadata(&png, "png", Scrub::Sensitive).unwrap();
+        assert_eq!(sensitive, png_with(&sensitive_tiff));
+        assert!(contains(&sensitive, b"Camera1"));
+        assert!(!contains(&sensitive, &latitude_bytes()));
+
+        let all = scrub_metadata(&png, "png", Scrub::All).unwrap();
+        assert_eq!(all, png_with(&orientation_only(&tiff).unwrap()));
+        assert_orientation_only(orientation_only(&tiff).unwrap());
+    }
+
+    #[test]
+    fn webp_sensitive_removes_gps_but_keeps_camera() {
+        let webp = webp_with_gps();
+        assert!(contains(&webp, &latitude_bytes()));
+
+        let scrubbed = scrub_metadata(&webp, "webp", Scrub::Sensitive).unwrap();
+
+        assert_eq!(scrubbed.len(), webp.len());
+        assert!(!contains(&scrubbed, &latitude_bytes()));
+        assert!(!contains(&scrubbed, b"SN12345"));
+        assert!(contains(&scrubbed, b"Camera1"));
+        assert!(contains(&scrubbed, b"EXIF"));
+    }
+
+    #[test]
+    fn webp_all_keeps_only_orientation_in_the_exif_chunk() {
+        let webp = webp_with_gps();
+        let scrubbed = scrub_metadata(&webp, "WEBP", Scrub::All).unwrap();
+
+        let at = scrubbed.windows(4).position(|w| w == b"EXIF").unwrap();
+        assert_orientation_only(scrubbed[at + 8..].to_vec());
+        assert!(!contains(&scrubbed, b"Camera1"));
+        // VP8X flags byte, after RIFF header (12) and chunk header (8).
+        assert_eq!(scrubbed[20] & WEBP_EXIF_FLAG, WEBP_EXIF_FLAG);
+        let riff_size = u32::from_le_bytes(scrubbed[4..8].try_into().unwrap()) as usize;
+        assert_eq!(riff_size + 8, scrubbed.len());
+    }
+
+    #[test]
+    fn webp_drops_an_unreadable_exif_chunk_and_its_flag() {
+        let mut webp = webp_with_gps();
+        let at = webp.windows(4).position(|w| w == b"EXIF").unwrap();
+        // Corrupt the TIFF byte-order mark.
+        webp[at + 8] = 0;
+        let scrubbed = scrub_metadata(&webp, "webp", Scrub::Sensitive).unwrap();
+
+        assert!(!contains(&scrubbed, b"EXIF"));
+        assert_eq!(scrubbed[20] & WEBP_EXIF_FLAG, 0);
+        let riff_size = u32::from_le_bytes(scrubbed[4..8].try_into().unwrap()) as usize;
+        assert_eq!(riff_size + 8, scrubbed.len());
+    }
+
+    #[test]
+    fn webp_with_an_empty_vp8x_chunk_is_withheld() {
+        let webp = webp_with_gps();
+        let at = webp.windows(4).position(|w| w == b"VP8X").unwrap();
+        // Cut the VP8X chunk down to an empty one.
+        let mut truncated = webp[..at + 4].to_vec();
+        truncated.extend_from_slice(&0u32.to_le_bytes());
+        truncated.extend_from_slice(&webp[at + 18..]);
+        let riff_size = (truncated.len() - 8) as u32;
+        truncated[4..8].copy_from_slice(&riff_size.to_le_bytes());
+
+        for scrub in [Scrub::All, Scrub::Sensitive] {
+            assert_eq!(scrub_metadata(&truncated, "webp", scrub), None);
+        }
+    }
+
+    #[test]
+    fn avif_exif_item_is_scrubbed_in_place() {
+        let avif = avif_with_gps();
+        assert!(contains(&avif, &latitude_bytes()));
+
+        let sensitive = scrub_metadata(&avif, "avif", Scrub::Sensitive).unwrap();
+        assert_eq!(sensitive.len(), avif.len());
+        assert!(!contains(&sensitive, &latitude_bytes()));
+        assert!(!contains(&sensitive, b"SN12345"));
+        assert!(contains(&sensitive, b"Camera1"));
+
+        let all = scrub_metadata(&avif, "avif", Scrub::All).unwrap();
+        assert_eq!(all.len(), avif.len());
+        assert!(!contains(&all, &latitude_bytes()));
+        assert!(!contains(&all, b"Camera1"));
+        // The Exif item is the whole of `mdat`, at the end of the file.
+        let tiff_at = all.len() - tiff_with_gps().len();
+        assert_orientation_only(all[tiff_at..tiff_at + 26].to_vec());
+    }
+
+    #[test]
+    fn unreadable_or_unknown_files_are_withheld() {
+        let mut truncated_png = PNG_SIGNATURE.to_vec();
+        truncated_png.extend_from_slice(&[0, 0, 1, 0, b'e', b'X', b'I', b'f']);
+        assert_eq!(scrub_metadata(&truncated_png, "png", Scrub::All), None);
+        assert_eq!(scrub_metadata(b"not a tiff", "tiff", Scrub::All), None);
+        assert_eq!(
+            scrub_metadata(b"RIFF\xff\0\0\0WEBP", "webp", Scrub::All),
+            None
+        );
+        let mut avif = avif_with_gps();
+        avif.truncate(avif.len() - 10);
+        assert_eq!(scrub_metadata(&avif, "avif", Scrub::Sensitive), None);
+        assert_eq!(scrub_metadata(&jpeg_with_gps(), "heic", Scrub::All), None);
+    }
+}
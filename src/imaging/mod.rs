@@ -13,7 +13,9 @@
 //! | **Identify** (dimensions) | `image::image_dimensions` |
 //! | **IPTC metadata** | Custom parser (`iptc_parser`) — reads JPEG APP13 + TIFF IFD |
 //! | **Resize → AVIF** | Lanczos3 resampling + rav1e AVIF encoder |
-//! | **Thumbnail** | `resize_to_fill` (center crop) + optional `unsharpen` |
+//! | **Thumbnail** | `resize_to_fill` (center crop, or around a sidecar focal point) + optional `unsharpen` |
+//! | **Metadata scrubbing** (download originals) | Custom rewriter (`exif_scrub`) — JPEG APP1, TIFF IFD, PNG chunks |
+//! | **Perceptual hash** (`check --duplicates`) | 9×8 grayscale `thumbnail_exact` → 64-bit dHash |
 //!
 //! ## Architecture: Backend Trait Pattern
 //!
@@ -30,6 +32,7 @@
 
 pub mod backend;
 pub mod calculations;
+pub(crate) mod exif_scrub;
 pub(crate) mod iptc_parser;
 pub mod operations;
 pub mod params;
@@ -42,5 +45,5 @@ pub use calculations::calculate_thumbnail_dimensions;
 pub use operations::{
     ResponsiveConfig, ThumbnailConfig, create_responsive_images, create_thumbnail, get_dimensions,
 };
-pub use params::{Quality, Sharpening};
-pub use rust_backend::{RustBackend, supported_input_extensions};
+pub use params::{AspectMode, ImageSizes, Quality, Sharpening};
+pub use rust_backend::{RustBackend, perceptual_hash, supported_input_extensions};
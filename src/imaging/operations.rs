@@ -5,9 +5,9 @@
 
 use super::backend::{BackendError, ImageBackend};
 use super::calculations::{
-    ResponsiveSize, calculate_responsive_sizes, calculate_thumbnail_dimensions,
+    ResponsiveSize, calculate_responsive_sizes, calculate_thumbnail_dimensions, thumbnail_aspect,
 };
-use super::params::{Quality, ResizeParams, Sharpening, ThumbnailParams};
+use super::params::{AspectMode, ImageSizes, Quality, ResizeParams, Sharpening, ThumbnailParams};
 use std::path::Path;
 
 /// Result type for image operations.
@@ -31,7 +31,7 @@ pub struct GeneratedVariant {
 /// Configuration for responsive image generation.
 #[derive(Debug, Clone)]
 pub struct ResponsiveConfig {
-    pub sizes: Vec<u32>,
+    pub sizes: ImageSizes,
     pub quality: Quality,
 }
 
@@ -88,31 +88,40 @@ pub fn create_responsive_images(
 #[derive(Debug, Clone)]
 pub struct ThumbnailConfig {
     pub aspect: (u32, u32),
+    /// Whether `aspect` applies as-is or per source (see [`thumbnail_aspect`]).
+    pub aspect_mode: AspectMode,
     pub short_edge: u32,
     pub quality: Quality,
     pub sharpening: Option<Sharpening>,
+    /// Sidecar `focal:` point, as `(x, y)` percentages; `None` center-crops.
+    pub focal: Option<(u32, u32)>,
 }
 
 impl Default for ThumbnailConfig {
     fn default() -> Self {
         Self {
             aspect: (4, 5),
+            aspect_mode: AspectMode::Fixed,
             short_edge: 400,
             quality: Quality::default(),
             sharpening: Some(Sharpening::light()),
+            focal: None,
         }
     }
 }
 
 /// Plan a thumbnail operation without executing it.
 ///
-/// Useful for testing parameter generation.
+/// `original_dims` picks the crop shape under the `original` and
+/// `adaptive` aspect modes. Useful for testing parameter generation.
 pub fn plan_thumbnail(
     source: &Path,
     output_path: &Path,
+    original_dims: (u32, u32),
     config: &ThumbnailConfig,
 ) -> ThumbnailParams {
-    let (crop_w, crop_h) = calculate_thumbnail_dimensions(config.aspect, config.short_edge);
+    let aspect = thumbnail_aspect(config.aspect_mode, config.aspect, original_dims);
+    let (crop_w, crop_h) = calculate_thumbnail_dimensions(aspect, config.short_edge);
 
     ThumbnailParams {
         source: source.to_path_buf(),
@@ -121,23 +130,26 @@ pub fn plan_thumbnail(
         crop_height: crop_h,
         quality: config.quality,
         sharpening: config.sharpening,
+        focal: config.focal,
     }
 }
 
 /// Create a thumbnail image.
 ///
-/// Resizes to fill the target aspect ratio, then center-crops.
+/// Resizes to fill the target aspect ratio, then crops around the focal
+/// point (the center unless the config sets one).
 pub fn create_thumbnail(
     backend: &impl ImageBackend,
     source: &Path,
     output_dir: &Path,
     filename_stem: &str,
+    original_dims: (u32, u32),
     config: &ThumbnailConfig,
 ) -> Result<String> {
     let thumb_name = format!("{}-thumb.avif", filename_stem);
     let thumb_path = output_dir.join(&thumb_name);
 
-    let params = plan_thumbnail(source, &thumb_path, config);
+    let params = plan_thumbnail(source, &thumb_path, original_dims, config);
     backend.thumbnail(&params)?;
 
     let relative_dir = output_dir
@@ -171,6 +183,7 @@ mod tests {
         let params = plan_thumbnail(
             Path::new("/source.jpg"),
             Path::new("/thumb.avif"),
+            (3000, 2000),
             &ThumbnailConfig::default(),
         );
 
@@ -185,12 +198,64 @@ mod tests {
             short_edge: 180,
             ..ThumbnailConfig::default()
         };
-        let params = plan_thumbnail(Path::new("/source.jpg"), Path::new("/thumb.avif"), &config);
+        let params = plan_thumbnail(
+            Path::new("/source.jpg"),
+            Path::new("/thumb.avif"),
+            (3000, 2000),
+            &config,
+        );
 
         assert_eq!(params.crop_width, 320);
         assert_eq!(params.crop_height, 180);
     }
 
+    #[test]
+    fn plan_thumbnail_follows_aspect_mode() {
+        let plan = |aspect_mode, source| {
+            let config = ThumbnailConfig {
+                aspect_mode,
+                ..ThumbnailConfig::default()
+            };
+            let params = plan_thumbnail(Path::new("/s.jpg"), Path::new("/t.avif"), source, &config);
+            (params.crop_width, params.crop_height)
+        };
+        let (landscape, portrait) = ((3000, 2000), (2000, 3000));
+
+        assert_eq!(plan(AspectMode::Fixed, landscape), (400, 500));
+        assert_eq!(plan(AspectMode::Fixed, portrait), (400, 500));
+        assert_eq!(plan(AspectMode::Original, landscape), (600, 400));
+        assert_eq!(plan(AspectMode::Original, portrait), (400, 600));
+        assert_eq!(plan(AspectMode::Adaptive, landscape), (500, 400));
+        assert_eq!(plan(AspectMode::Adaptive, portrait), (400, 500));
+    }
+
+    #[test]
+    fn plan_thumbnail_carries_focal_point() {
+        let config = ThumbnailConfig {
+            focal: Some((20, 70)),
+            ..ThumbnailConfig::default()
+        };
+        let params = plan_thumbnail(
+            Path::new("/source.jpg"),
+            Path::new("/thumb.avif"),
+            (3000, 2000),
+            &config,
+        );
+
+        assert_eq!(params.focal, Some((20, 70)));
+        assert_eq!((params.crop_width, params.crop_height), (400, 500));
+        assert_eq!(
+            plan_thumbnail(
+                Path::new("/source.jpg"),
+                Path::new("/thumb.avif"),
+                (3000, 2000),
+                &ThumbnailConfig::default()
+            )
+            .focal,
+            None
+        );
+    }
+
     #[test]
     fn create_thumbnail_uses_backend() {
         let backend = MockBackend::new();
@@ -201,6 +266,7 @@ mod tests {
             Path::new("/source.jpg"),
             Path::new("/output"),
             "001-test",
+            (3000, 2000),
             &config,
         )
         .unwrap();
@@ -223,7 +289,7 @@ mod tests {
     fn create_responsive_caps_at_source_size() {
         let backend = MockBackend::new();
         let config = ResponsiveConfig {
-            sizes: vec![800, 1400, 2080],
+            sizes: ImageSizes::Explicit(vec![800, 1400, 2080]),
             quality: Quality::default(),
         };
 
@@ -250,7 +316,7 @@ mod tests {
     fn create_responsive_generates_avif() {
         let backend = MockBackend::new();
         let config = ResponsiveConfig {
-            sizes: vec![800],
+            sizes: ImageSizes::Explicit(vec![800]),
             quality: Quality::new(85),
         };
 
@@ -277,7 +343,7 @@ mod tests {
     fn create_responsive_fallback_to_original_size() {
         let backend = MockBackend::new();
         let config = ResponsiveConfig {
-            sizes: vec![800, 1400],
+            sizes: ImageSizes::Explicit(vec![800, 1400]),
             quality: Quality::default(),
         };
 
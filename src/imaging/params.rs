@@ -10,9 +10,14 @@
 //!
 //! - [`Quality`] — Lossy encoding quality (1–100, default 90). Clamped on construction.
 //! - [`Sharpening`] — Unsharp-mask parameters (sigma + threshold) for thumbnail crispness.
+//! - [`ImageSizes`] — Responsive sizes to generate: an explicit list or `auto` (derived per source).
+//! - [`AspectMode`] — Whether thumbnails share one aspect ratio or follow each source's shape.
 //! - [`ResizeParams`] — Full specification for a resize: source, output path, target dimensions, quality.
 //! - [`ThumbnailParams`] — Full specification for a thumbnail: source, output, crop dimensions, quality, optional sharpening.
 
+use serde::de::{self, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
 use std::path::PathBuf;
 
 /// Quality setting for lossy image encoding (1-100).
@@ -35,6 +40,21 @@ impl Default for Quality {
     }
 }
 
+/// How a thumbnail's aspect ratio is chosen (`[thumbnails] aspect_mode`).
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum AspectMode {
+    /// Every thumbnail is cropped to the configured `aspect_ratio`.
+    #[default]
+    Fixed,
+    /// Each thumbnail keeps its source's aspect ratio; nothing is cropped.
+    Original,
+    /// The configured ratio, turned to match each source's orientation:
+    /// portrait sources get the portrait form, landscapes the landscape
+    /// form, and square sources a square.
+    Adaptive,
+}
+
 /// Sharpening parameters for unsharp mask.
 ///
 /// - `sigma`: Standard deviation of the Gaussian blur (higher = more sharpening)
@@ -55,6 +75,79 @@ impl Sharpening {
     }
 }
 
+/// Responsive sizes (longer edge, in pixels) to generate for each image.
+///
+/// In config this is either a TOML array (`sizes = [800, 1400, 2080]`) or
+/// the string `"auto"`, which derives a ladder from each source's own
+/// dimensions (see [`auto_sizes`](super::calculations::auto_sizes)).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImageSizes {
+    /// Fixed breakpoints, shared by every image.
+    Explicit(Vec<u32>),
+    /// Halving ladder computed per image from its source size.
+    Auto,
+}
+
+impl ImageSizes {
+    /// The configured breakpoints, or `None` in auto mode.
+    pub fn explicit(&self) -> Option<&[u32]> {
+        match self {
+            Self::Explicit(sizes) => Some(sizes),
+            Self::Auto => None,
+        }
+    }
+
+    /// Upper bound on the number of variants generated per image, for
+    /// progress totals before source dimensions are known.
+    pub fn max_count(&self) -> usize {
+        match self {
+            Self::Explicit(sizes) => sizes.len(),
+            Self::Auto => super::calculations::AUTO_SIZES_MAX_COUNT,
+        }
+    }
+}
+
+impl Serialize for ImageSizes {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::Explicit(sizes) => sizes.serialize(serializer),
+            Self::Auto => serializer.serialize_str("auto"),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for ImageSizes {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct SizesVisitor;
+
+        impl<'de> Visitor<'de> for SizesVisitor {
+            type Value = ImageSizes;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("an array of pixel sizes or the string \"auto\"")
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<ImageSizes, E> {
+                if value == "auto" {
+                    Ok(ImageSizes::Auto)
+                } else {
+                    Err(E::invalid_value(de::Unexpected::Str(value), &self))
+                }
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<ImageSizes, A::Error> {
+                let mut sizes = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+                while let Some(size) = seq.next_element::<u32>()? {
+                    sizes.push(size);
+                }
+                Ok(ImageSizes::Explicit(sizes))
+            }
+        }
+
+        deserializer.deserialize_any(SizesVisitor)
+    }
+}
+
 /// Parameters for a simple resize operation.
 #[derive(Debug, Clone, PartialEq)]
 pub struct ResizeParams {
@@ -75,6 +168,9 @@ pub struct ThumbnailParams {
     pub crop_height: u32,
     pub quality: Quality,
     pub sharpening: Option<Sharpening>,
+    /// Point kept in frame, as `(x, y)` percentages of the source; `None`
+    /// crops around the center.
+    pub focal: Option<(u32, u32)>,
 }
 
 #[cfg(test)]
@@ -93,6 +189,28 @@ mod tests {
         assert_eq!(Quality::default().value(), 90);
     }
 
+    #[test]
+    fn image_sizes_deserialize_array_or_auto() {
+        let sizes: ImageSizes = serde_json::from_str("[800, 1400]").unwrap();
+        assert_eq!(sizes, ImageSizes::Explicit(vec![800, 1400]));
+        let sizes: ImageSizes = serde_json::from_str("\"auto\"").unwrap();
+        assert_eq!(sizes, ImageSizes::Auto);
+        assert!(serde_json::from_str::<ImageSizes>("\"large\"").is_err());
+        assert!(serde_json::from_str::<ImageSizes>("800").is_err());
+    }
+
+    #[test]
+    fn image_sizes_serialize_round_trips() {
+        for sizes in [ImageSizes::Auto, ImageSizes::Explicit(vec![400, 800])] {
+            let json = serde_json::to_string(&sizes).unwrap();
+            assert_eq!(serde_json::from_str::<ImageSizes>(&json).unwrap(), sizes);
+        }
+        assert_eq!(
+            serde_json::to_string(&ImageSizes::Auto).unwrap(),
+            "\"auto\""
+        );
+    }
+
     #[test]
     fn sharpening_light_values() {
         let s = Sharpening::light();
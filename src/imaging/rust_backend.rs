@@ -8,6 +8,7 @@
 //! |---|---|
 //! | Decode (JPEG, PNG, TIFF, WebP) | `image` crate (pure Rust decoders) |
 //! | Decode (AVIF) | `avif-parse` (container) + `rav1d` (AV1 decode) + custom YUV→RGB |
+//! | ICC → sRGB | `moxcms` (embedded profiles of JPEG/PNG/TIFF/WebP sources) |
 //! | Resize | `image::imageops::resize` with `Lanczos3` filter |
 //! | Encode → AVIF | `image::codecs::avif::AvifEncoder` (rav1e, speed 6) |
 //! | Thumbnail crop | `image::DynamicImage::resize_to_fill` |
@@ -15,9 +16,10 @@
 //! | IPTC metadata | custom `iptc_parser` (JPEG APP13 + TIFF IFD) |
 
 use super::backend::{BackendError, Dimensions, ImageBackend, ImageMetadata};
+use super::calculations::{crop_origin, fill_dimensions};
 use super::params::{ResizeParams, ThumbnailParams};
 use image::imageops::FilterType;
-use image::{DynamicImage, ImageFormat, ImageReader};
+use image::{DynamicImage, ImageBuffer, ImageDecoder, ImageFormat, ImageReader, Pixel};
 use std::path::Path;
 use std::sync::LazyLock;
 
@@ -76,16 +78,113 @@ fn is_avif(path: &Path) -> bool {
 }
 
 /// Load and decode an image from disk.
+///
+/// Pixels are returned in sRGB: an embedded ICC profile is applied via
+/// [`convert_to_srgb`]. AVIF sources are assumed to be sRGB already.
 fn load_image(path: &Path) -> Result<DynamicImage, BackendError> {
     if is_avif(path) {
         return decode_avif(path);
     }
-    ImageReader::open(path)
+    let decode_err = |e: image::ImageError| {
+        BackendError::ProcessingFailed(format!("Failed to decode {}: {}", path.display(), e))
+    };
+    let mut decoder = ImageReader::open(path)
         .map_err(BackendError::Io)?
-        .decode()
-        .map_err(|e| {
-            BackendError::ProcessingFailed(format!("Failed to decode {}: {}", path.display(), e))
-        })
+        .into_decoder()
+        .map_err(decode_err)?;
+    let icc = decoder.icc_profile().map_err(decode_err)?;
+    let img = DynamicImage::from_decoder(decoder).map_err(decode_err)?;
+    Ok(match icc {
+        Some(icc) => convert_to_srgb(img, &icc),
+        None => img,
+    })
+}
+
+/// Perceptual hash of the image at `path`, for spotting the same shot
+/// saved twice (`check --duplicates`). See [`dhash`].
+pub fn perceptual_hash(path: &Path) -> Result<u64, BackendError> {
+    Ok(dhash(&load_image(path)?))
+}
+
+/// 64-bit difference hash: shrink to 9×8 grayscale and set one bit per
+/// pixel that is brighter than its right-hand neighbor. Re-encoding,
+/// resizing and light edits flip a few bits; unrelated pictures differ in
+/// about half of them.
+fn dhash(img: &DynamicImage) -> u64 {
+    let small = img.thumbnail_exact(9, 8).to_luma8();
+    let mut hash = 0u64;
+    for y in 0..8 {
+        for x in 0..8 {
+            hash <<= 1;
+            if small.get_pixel(x, y)[0] > small.get_pixel(x + 1, y)[0] {
+                hash |= 1;
+            }
+        }
+    }
+    hash
+}
+
+/// Convert pixels described by an ICC profile to sRGB.
+///
+/// The AVIF encoder writes untagged files, which browsers display as sRGB,
+/// so wide-gamut sources (Display P3, Adobe RGB) would otherwise come out
+/// desaturated. Colors outside the sRGB gamut are clipped. Non-RGB profiles
+/// (grayscale, CMYK) and profiles that fail to parse leave the pixels as-is.
+fn convert_to_srgb(img: DynamicImage, icc: &[u8]) -> DynamicImage {
+    use moxcms::{ColorProfile, DataColorSpace, Layout};
+
+    let Ok(profile) = ColorProfile::new_from_slice(icc) else {
+        return img;
+    };
+    if profile.color_space != DataColorSpace::Rgb {
+        return img;
+    }
+
+    let color = img.color();
+    let high_bit_depth = color.bytes_per_pixel() > color.channel_count();
+    let converted = match (high_bit_depth, color.has_alpha()) {
+        (false, false) => {
+            transform_8bit(&profile, Layout::Rgb, img.to_rgb8()).map(DynamicImage::ImageRgb8)
+        }
+        (false, true) => {
+            transform_8bit(&profile, Layout::Rgba, img.to_rgba8()).map(DynamicImage::ImageRgba8)
+        }
+        (true, false) => {
+            transform_16bit(&profile, Layout::Rgb, img.to_rgb16()).map(DynamicImage::ImageRgb16)
+        }
+        (true, true) => {
+            transform_16bit(&profile, Layout::Rgba, img.to_rgba16()).map(DynamicImage::ImageRgba16)
+        }
+    };
+    converted.unwrap_or(img)
+}
+
+fn transform_8bit<P: Pixel<Subpixel = u8>>(
+    profile: &moxcms::ColorProfile,
+    layout: moxcms::Layout,
+    mut buf: ImageBuffer<P, Vec<u8>>,
+) -> Option<ImageBuffer<P, Vec<u8>>> {
+    let srgb = moxcms::ColorProfile::new_srgb();
+    let transform = profile
+        .create_transform_8bit(layout, &srgb, layout, moxcms::TransformOptions::default())
+        .ok()?;
+    let src = buf.clone();
+    transform.transform(&src, &mut buf).ok()?;
+    Some(buf)
+}
+
+fn transform_16bit<P: Pixel<Subpixel = u16>>(
+    profile: &moxcms::ColorProfile,
+    layout: moxcms::Layout,
+    mut buf: ImageBuffer<P, Vec<u16>>,
+) -> Option<ImageBuffer<P, Vec<u16>>> {
+    let srgb = moxcms::ColorProfile::new_srgb();
+    let transform = profile
+        .create_transform_16bit(layout, &srgb, layout, moxcms::TransformOptions::default())
+        .ok()?;
+    let src = buf.clone();
+    transform.transform(&src, &mut buf).ok()?;
+    Some(buf)
 }
 
 /// Read an AVIF file and parse its ISOBMFF container.
@@ -380,6 +479,10 @@ fn save_image(img: &DynamicImage, path: &Path, quality: u32) -> Result<(), Backe
 
     match ext.as_str() {
         "avif" => save_avif(img, path, quality),
+        // Lossless intermediate for oversized sources; `quality` is ignored.
+        "png" => img
+            .save_with_format(path, ImageFormat::Png)
+            .map_err(|e| BackendError::ProcessingFailed(format!("PNG encode failed: {}", e))),
         other => Err(BackendError::ProcessingFailed(format!(
             "Unsupported output format: {}",
             other
@@ -418,17 +521,27 @@ impl ImageBackend for RustBackend {
     }
 
     fn resize(&self, params: &ResizeParams) -> Result<(), BackendError> {
-        let img = load_image(&params.source)?;
-        let resized = img.resize(params.width, params.height, FilterType::Lanczos3);
+        // The full-size decode is a temporary, dropped before encoding so
+        // it never coexists with the encoder's buffers.
+        let resized =
+            load_image(&params.source)?.resize(params.width, params.height, FilterType::Lanczos3);
         save_image(&resized, &params.output, params.quality.value())
     }
 
     fn thumbnail(&self, params: &ThumbnailParams) -> Result<(), BackendError> {
+        // Fill-resize then crop to exact dimensions: around the focal point
+        // when one is set, otherwise centered.
         let img = load_image(&params.source)?;
-
-        // Fill-resize then center-crop to exact dimensions
-        let filled =
-            img.resize_to_fill(params.crop_width, params.crop_height, FilterType::Lanczos3);
+        let crop = (params.crop_width, params.crop_height);
+        let filled = match params.focal {
+            Some(focal) => {
+                let (w, h) = fill_dimensions((img.width(), img.height()), crop);
+                let resized = img.resize_exact(w, h, FilterType::Lanczos3);
+                let (x, y) = crop_origin((w, h), crop, focal);
+                resized.crop_imm(x, y, crop.0, crop.1)
+            }
+            None => img.resize_to_fill(crop.0, crop.1, FilterType::Lanczos3),
+        };
 
         // Apply sharpening if requested
         let final_img = if let Some(sharpening) = params.sharpening {
@@ -474,6 +587,70 @@ mod tests {
             .unwrap();
     }
 
+    /// Display P3 matrix/TRC profile (`fixtures/icc/display-p3.icc`).
+    const DISPLAY_P3_ICC: &[u8] = include_bytes!("../../fixtures/icc/display-p3.icc");
+
+    /// Create a flat-colored PNG, optionally tagged with an ICC profile.
+    fn create_test_png(path: &Path, rgb: [u8; 3], icc: Option<&[u8]>) {
+        let img = RgbImage::from_pixel(64, 64, image::Rgb(rgb));
+        let file = std::fs::File::create(path).unwrap();
+        let mut encoder = image::codecs::png::PngEncoder::new(std::io::BufWriter::new(file));
+        if let Some(icc) = icc {
+            encoder.set_icc_profile(icc.to_vec()).unwrap();
+        }
+        encoder
+            .write_image(img.as_raw(), 64, 64, image::ExtendedColorType::Rgb8)
+            .unwrap();
+    }
+
+    #[test]
+    fn load_image_converts_display_p3_to_srgb() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("p3.png");
+        create_test_png(&path, [200, 100, 50], Some(DISPLAY_P3_ICC));
+
+        // P3 (200, 100, 50) is sRGB ~(215, 93, 31): more saturated than the
+        // same numbers read as sRGB.
+        let rgb = load_image(&path).unwrap().to_rgb8().get_pixel(32, 32).0;
+        assert!((213..=217).contains(&rgb[0]), "{rgb:?}");
+        assert!((90..=96).contains(&rgb[1]), "{rgb:?}");
+        assert!((28..=34).contains(&rgb[2]), "{rgb:?}");
+    }
+
+    #[test]
+    fn load_image_keeps_untagged_pixels() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("plain.png");
+        create_test_png(&path, [200, 100, 50], None);
+
+        let rgb = load_image(&path).unwrap().to_rgb8().get_pixel(32, 32).0;
+        assert_eq!(rgb, [200, 100, 50]);
+    }
+
+    #[test]
+    fn resize_display_p3_source_outputs_srgb_avif() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let source = tmp.path().join("p3.png");
+        create_test_png(&source, [200, 100, 50], Some(DISPLAY_P3_ICC));
+
+        let output = tmp.path().join("out.avif");
+        RustBackend::new()
+            .resize(&ResizeParams {
+                source,
+                output: output.clone(),
+                width: 32,
+                height: 32,
+                quality: Quality::new(90),
+            })
+            .unwrap();
+
+        // Untagged AVIF is displayed as sRGB, so the encoded pixels must be
+        // the converted values, not the raw P3 numbers.
+        let rgb = decode_avif(&output).unwrap().to_rgb8().get_pixel(16, 16).0;
+        assert!(rgb[0] >= 208, "{rgb:?}");
+        assert!(rgb[2] <= 40, "{rgb:?}");
+    }
+
     #[test]
     fn identify_synthetic_jpeg() {
         let tmp = tempfile::TempDir::new().unwrap();
@@ -569,6 +746,7 @@ mod tests {
                 crop_height: 500,
                 quality: Quality::new(85),
                 sharpening: Some(Sharpening::light()),
+                focal: None,
             })
             .unwrap();
 
@@ -592,6 +770,7 @@ mod tests {
                 crop_height: 500,
                 quality: Quality::new(85),
                 sharpening: Some(Sharpening::light()),
+                focal: None,
             })
             .unwrap();
 
@@ -615,6 +794,7 @@ mod tests {
                 crop_height: 200,
                 quality: Quality::new(85),
                 sharpening: None,
+                focal: None,
             })
             .unwrap();
 
@@ -0,0 +1,159 @@
+//! Starter content for new galleries.
+//!
+//! `simple-gal init` writes a small content tree that already follows every
+//! convention, so a new user can build it straight away and then replace
+//! the samples with their own work:
+//!
+//! ```text
+//! content/
+//! ├── config.toml              # Every option, commented out
+//! ├── site.md                  # Index page description
+//! ├── 020-about.md             # Content page (numbered = in nav)
+//! └── 010-Sample-Album/
+//!     ├── description.md       # Album description
+//!     ├── 001-dawn.jpg         # Placeholder images
+//!     └── 002-dusk.jpg
+//! ```
+//!
+//! The placeholder images are gradients rendered at init time, so the
+//! binary carries no sample photos.
+
+use crate::config;
+use image::{ImageError, Rgb, RgbImage};
+use std::fs;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+const SITE_MD: &str = "\
+Welcome to my portfolio.
+
+This text comes from `site.md` and appears on the index page.
+";
+
+const ABOUT_MD: &str = "\
+# About
+
+This page comes from `020-about.md`. The `020-` prefix puts it in the
+navigation, after the sample album; drop the prefix to hide it from the
+menu while keeping the page reachable.
+";
+
+const ALBUM_DESCRIPTION_MD: &str = "\
+A sample album. Replace these images with your own: files are shown in the
+order of their number prefix, and the lowest-numbered image is the album's
+thumbnail.
+";
+
+/// Directory of the sample album, relative to the content root.
+const SAMPLE_ALBUM: &str = "010-Sample-Album";
+
+/// Placeholder images: file name and the gradient's start and end colors.
+const SAMPLE_IMAGES: &[(&str, [u8; 3], [u8; 3])] = &[
+    ("001-dawn.jpg", [236, 178, 120], [64, 92, 140]),
+    ("002-dusk.jpg", [40, 44, 80], [200, 96, 88]),
+];
+
+#[derive(Error, Debug)]
+pub enum InitError {
+    #[error("`{0}` is not empty; pass --force to write the starter files into it anyway")]
+    NotEmpty(PathBuf),
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("failed to write placeholder image: {0}")]
+    Image(#[from] ImageError),
+}
+
+/// Write the starter content tree into `dir`, creating it if needed.
+///
+/// An existing non-empty `dir` is refused unless `force` is set, in which
+/// case the starter files overwrite same-named files and everything else
+/// is left alone. Returns the written files, relative to `dir`.
+pub fn init(dir: &Path, force: bool) -> Result<Vec<PathBuf>, InitError> {
+    if !force && dir.is_dir() && fs::read_dir(dir)?.next().is_some() {
+        return Err(InitError::NotEmpty(dir.to_path_buf()));
+    }
+    let album = Path::new(SAMPLE_ALBUM);
+    fs::create_dir_all(dir.join(album))?;
+
+    let mut written = Vec::new();
+    for (path, text) in [
+        (PathBuf::from("config.toml"), config::stock_config_toml()),
+        (PathBuf::from("site.md"), SITE_MD.to_string()),
+        (PathBuf::from("020-about.md"), ABOUT_MD.to_string()),
+        (
+            album.join("description.md"),
+            ALBUM_DESCRIPTION_MD.to_string(),
+        ),
+    ] {
+        fs::write(dir.join(&path), text)?;
+        written.push(path);
+    }
+    for &(name, from, to) in SAMPLE_IMAGES {
+        let path = album.join(name);
+        placeholder_image(from, to).save(dir.join(&path))?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+/// A 1200×800 diagonal gradient from `from` (top left) to `to`.
+fn placeholder_image(from: [u8; 3], to: [u8; 3]) -> RgbImage {
+    let (width, height) = (1200, 800);
+    RgbImage::from_fn(width, height, |x, y| {
+        let t = (x + y) as f32 / (width + height - 2) as f32;
+        Rgb(std::array::from_fn(|i| {
+            (from[i] as f32 + (to[i] as f32 - from[i] as f32) * t).round() as u8
+        }))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scan;
+    use tempfile::TempDir;
+
+    #[test]
+    fn scaffold_scans_cleanly() {
+        let tmp = TempDir::new().unwrap();
+        let content = tmp.path().join("content");
+
+        let written = init(&content, false).unwrap();
+        assert!(written.contains(&PathBuf::from("config.toml")));
+
+        let manifest = scan::scan(&content).unwrap();
+        assert_eq!(manifest.albums.len(), 1);
+        assert_eq!(manifest.albums[0].title, "Sample Album");
+        assert_eq!(manifest.albums[0].images.len(), 2);
+        assert!(manifest.albums[0].description.is_some());
+        assert!(manifest.description.is_some());
+        assert_eq!(manifest.pages.len(), 1);
+        assert_eq!(manifest.pages[0].slug, "about");
+    }
+
+    #[test]
+    fn refuses_non_empty_dir_without_force() {
+        let tmp = TempDir::new().unwrap();
+        fs::write(tmp.path().join("notes.txt"), "keep me").unwrap();
+
+        assert!(matches!(
+            init(tmp.path(), false),
+            Err(InitError::NotEmpty(_))
+        ));
+        assert!(!tmp.path().join("config.toml").exists());
+
+        init(tmp.path(), true).unwrap();
+        assert!(tmp.path().join("config.toml").exists());
+        assert_eq!(
+            fs::read_to_string(tmp.path().join("notes.txt")).unwrap(),
+            "keep me"
+        );
+    }
+
+    #[test]
+    fn empty_existing_dir_is_fine() {
+        let tmp = TempDir::new().unwrap();
+        init(tmp.path(), false).unwrap();
+        assert!(tmp.path().join("site.md").exists());
+    }
+}
@@ -13,7 +13,10 @@
 
 use crate::cache::CacheStats;
 use crate::config::ConfigError;
+use crate::duplicates;
 use crate::generate;
+use crate::pipeline::BuildTimings;
+use crate::process::SizeStats;
 use crate::scan;
 use serde::Serialize;
 use std::path::{Path, PathBuf};
@@ -201,6 +204,12 @@ pub struct ScanPayload<'a> {
     pub manifest: &'a scan::Manifest,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub saved_manifest_path: Option<PathBuf>,
+    /// Suspiciously large jumps in album image numbering (warnings only).
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub numbering_gaps: Vec<scan::NumberingGap>,
+    /// Photos skipped for being in an unsupported format (warnings only).
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub unsupported_images: Vec<scan::UnsupportedImages>,
 }
 
 impl<'a> ScanPayload<'a> {
@@ -219,6 +228,8 @@ impl<'a> ScanPayload<'a> {
             },
             manifest,
             saved_manifest_path,
+            numbering_gaps: scan::numbering_gaps(manifest),
+            unsupported_images: manifest.unsupported_images.clone(),
         }
     }
 }
@@ -244,11 +255,30 @@ impl From<&CacheStats> for CacheStatsPayload {
     }
 }
 
+#[derive(Debug, Serialize)]
+pub struct SizeStatsPayload {
+    pub source_bytes: u64,
+    pub output_bytes: u64,
+    /// `output_bytes / source_bytes`; `null` when there are no sources.
+    pub ratio: Option<f64>,
+}
+
+impl From<&SizeStats> for SizeStatsPayload {
+    fn from(s: &SizeStats) -> Self {
+        Self {
+            source_bytes: s.source_bytes,
+            output_bytes: s.output_bytes,
+            ratio: s.ratio(),
+        }
+    }
+}
+
 #[derive(Debug, Serialize)]
 pub struct ProcessPayload {
     pub processed_dir: PathBuf,
     pub manifest_path: PathBuf,
     pub cache: CacheStatsPayload,
+    pub sizes: SizeStatsPayload,
 }
 
 // ----- generate -----
@@ -259,13 +289,35 @@ pub struct GeneratePayload<'a> {
     pub counts: GenerateCounts,
     pub albums: Vec<GeneratedAlbum>,
     pub pages: Vec<GeneratedPage>,
+    /// Output paths an asset and a processed image both wrote to.
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    pub overwritten: &'a [generate::Overwrite],
+    /// Originals left out of album downloads: their metadata couldn't be
+    /// removed.
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    pub withheld_originals: &'a [String],
 }
 
 #[derive(Debug, Serialize)]
 pub struct GenerateCounts {
     pub albums: usize,
     pub image_pages: usize,
+    /// Content pages (link pages are counted separately).
     pub pages: usize,
+    pub link_pages: usize,
+    pub files_written: usize,
+}
+
+impl From<&generate::GenerateSummary> for GenerateCounts {
+    fn from(s: &generate::GenerateSummary) -> Self {
+        Self {
+            albums: s.albums,
+            image_pages: s.image_pages,
+            pages: s.content_pages,
+            link_pages: s.link_pages,
+            files_written: s.files_written,
+        }
+    }
 }
 
 #[derive(Debug, Serialize)]
@@ -284,9 +336,13 @@ pub struct GeneratedPage {
 }
 
 impl<'a> GeneratePayload<'a> {
-    pub fn new(manifest: &'a generate::Manifest, output: &'a Path) -> Self {
-        let image_pages = manifest.albums.iter().map(|a| a.images.len()).sum();
-        let pages_count = manifest.pages.iter().filter(|p| !p.is_link).count();
+    pub fn new(
+        manifest: &'a generate::Manifest,
+        summary: &generate::GenerateSummary,
+        output: &'a Path,
+        overwritten: &'a [generate::Overwrite],
+        withheld_originals: &'a [String],
+    ) -> Self {
         let albums = manifest
             .albums
             .iter()
@@ -308,13 +364,11 @@ impl<'a> GeneratePayload<'a> {
             .collect();
         Self {
             output,
-            counts: GenerateCounts {
-                albums: manifest.albums.len(),
-                image_pages,
-                pages: pages_count,
-            },
+            counts: GenerateCounts::from(summary),
             albums,
             pages,
+            overwritten,
+            withheld_originals,
         }
     }
 }
@@ -327,6 +381,51 @@ pub struct BuildPayload<'a> {
     pub output: &'a Path,
     pub counts: GenerateCounts,
     pub cache: CacheStatsPayload,
+    pub sizes: SizeStatsPayload,
+    pub timings: TimingsPayload,
+    /// Output paths an asset and a processed image both wrote to.
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    pub overwritten: &'a [generate::Overwrite],
+    /// Originals left out of album downloads: their metadata couldn't be
+    /// removed.
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    pub withheld_originals: &'a [String],
+}
+
+/// Wall-clock time per stage and per album, in whole milliseconds.
+#[derive(Debug, Serialize)]
+pub struct TimingsPayload {
+    pub scan_ms: u64,
+    pub process_ms: u64,
+    pub generate_ms: u64,
+    pub total_ms: u64,
+    pub albums: Vec<AlbumTimingPayload>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AlbumTimingPayload {
+    pub path: String,
+    pub ms: u64,
+}
+
+impl From<&BuildTimings> for TimingsPayload {
+    fn from(t: &BuildTimings) -> Self {
+        let ms = |d: std::time::Duration| d.as_millis() as u64;
+        Self {
+            scan_ms: ms(t.scan),
+            process_ms: ms(t.process),
+            generate_ms: ms(t.generate),
+            total_ms: ms(t.total()),
+            albums: t
+                .albums
+                .iter()
+                .map(|(path, elapsed)| AlbumTimingPayload {
+                    path: path.clone(),
+                    ms: ms(*elapsed),
+                })
+                .collect(),
+        }
+    }
 }
 
 // ----- check -----
@@ -336,6 +435,35 @@ pub struct CheckPayload<'a> {
     pub valid: bool,
     pub source: &'a Path,
     pub counts: Counts,
+    /// Suspiciously large jumps in album image numbering. They don't make
+    /// the content invalid.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub numbering_gaps: Vec<scan::NumberingGap>,
+    /// Photos skipped for being in an unsupported format. They don't make
+    /// the content invalid either.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub unsupported_images: Vec<scan::UnsupportedImages>,
+    /// Near-duplicate image pairs, with `--duplicates`. A reviewing aid,
+    /// not an error.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub duplicates: Vec<duplicates::DuplicatePair>,
+}
+
+// ----- init -----
+
+/// Starter files written by `simple-gal init`, relative to `path`.
+#[derive(Debug, Serialize)]
+pub struct InitPayload<'a> {
+    pub path: &'a Path,
+    pub files: &'a [PathBuf],
+}
+
+// ----- export-csv -----
+
+#[derive(Debug, Serialize)]
+pub struct ExportCsvPayload<'a> {
+    pub path: &'a Path,
+    pub rows: usize,
 }
 
 // ----- reindex -----
@@ -745,6 +873,28 @@ mod tests {
         assert!(!codes.contains(&0), "0 is reserved for success");
     }
 
+    #[test]
+    fn timings_payload_is_in_milliseconds() {
+        use std::time::Duration;
+        let timings = BuildTimings {
+            scan: Duration::from_millis(12),
+            process: Duration::from_micros(1_500_900),
+            generate: Duration::from_millis(300),
+            albums: vec![("Travel".to_string(), Duration::from_millis(1500))],
+        };
+        let json = serde_json::to_value(TimingsPayload::from(&timings)).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "scan_ms": 12,
+                "process_ms": 1500,
+                "generate_ms": 300,
+                "total_ms": 1812,
+                "albums": [{"path": "Travel", "ms": 1500}],
+            })
+        );
+    }
+
     #[test]
     fn error_envelope_collects_causes() {
         use std::io;
@@ -30,6 +30,7 @@
 //! | [`scan`] | Stage 1 — walks the content directory, extracts metadata, produces the scan manifest |
 //! | [`process`] | Stage 2 — generates responsive AVIF images and thumbnails from the scan manifest |
 //! | [`generate`] | Stage 3 — renders the final HTML site from the process manifest using Maud |
+//! | [`pipeline`] | [`build`] — all three stages in one call, for embedding in other tools |
 //! | [`cache`] | Incremental build cache — skips AVIF encoding when source and params are unchanged |
 //! | [`config`] | Hierarchical `config.toml` loading, validation, merging, and CSS generation |
 //! | [`types`] | Shared types serialized between stages (`NavItem`, `Page`) |
@@ -37,7 +38,10 @@
 //! | [`metadata`] | Image metadata resolution: IPTC tags, sidecar files, filename fallback |
 //! | [`imaging`] | Pure-Rust image operations: resize, thumbnail, IPTC parsing |
 //! | [`output`] | CLI output formatting — tree-based display of pipeline results |
+//! | [`text`] | Plain text from rendered descriptions, shared by CLI output and generate |
 //! | [`reindex`] | Normalizes `NNN-` prefixes: pure `plan_reindex` + two-phase `apply_plan` |
+//! | [`export`] | `export-csv` — one spreadsheet row per image from the process manifest |
+//! | [`duplicates`] | `check --duplicates` — near-duplicate photos by perceptual hash |
 //!
 //! # Design Decisions
 //!
@@ -106,16 +110,25 @@
 
 pub mod cache;
 pub mod config;
+pub mod duplicates;
+pub mod export;
 pub mod generate;
 pub mod imaging;
+pub mod init;
 pub mod json_output;
+pub mod links;
 pub mod metadata;
 pub mod naming;
 pub mod output;
+pub mod pipeline;
 pub mod process;
 pub mod reindex;
 pub mod scan;
+pub mod text;
 pub mod types;
+pub mod typography;
+
+pub use pipeline::{BuildEvent, BuildOptions, BuildSummary, build};
 
 #[cfg(test)]
 pub(crate) mod test_helpers;
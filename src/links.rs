@@ -0,0 +1,98 @@
+//! Opening external links in a new tab (`external_links_new_tab`).
+//!
+//! [`open_external_in_new_tab`] adds `target="_blank" rel="noopener noreferrer"`
+//! to every anchor in an HTML fragment whose `href` is an absolute
+//! `http(s)://` URL on a different host than the site's own `base_url`.
+//! Relative links, links to the site itself, and anchors that already set a
+//! `target` are left alone.
+//!
+//! Like [`crate::typography`], it runs on HTML simple-gal produced itself
+//! (markdown output and linkified plain-text descriptions), where every
+//! attribute value is double-quoted.
+
+const NEW_TAB: &str = r#" target="_blank" rel="noopener noreferrer""#;
+
+/// Add new-tab attributes to the external links in `html`. `base_url` is
+/// the site's public origin; without it every absolute URL is external.
+pub fn open_external_in_new_tab(html: &str, base_url: Option<&str>) -> String {
+    let own_host = base_url.and_then(host);
+    let mut out = String::with_capacity(html.len());
+    let mut rest = html;
+    while let Some(start) = rest.find("<a ") {
+        let Some(len) = rest[start..].find('>') else {
+            break;
+        };
+        let tag = &rest[start..start + len];
+        out.push_str(&rest[..start + len]);
+        let external = attr(tag, "href")
+            .and_then(host)
+            .is_some_and(|h| own_host.is_none_or(|own| !own.eq_ignore_ascii_case(h)));
+        if external && attr(tag, "target").is_none() {
+            if attr(tag, "rel").is_some() {
+                out.push_str(r#" target="_blank""#);
+            } else {
+                out.push_str(NEW_TAB);
+            }
+        }
+        rest = &rest[start + len..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Value of a double-quoted attribute in an opening tag.
+fn attr<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+    let needle = format!(" {name}=\"");
+    let start = tag.find(&needle)? + needle.len();
+    let len = tag[start..].find('"')?;
+    Some(&tag[start..start + len])
+}
+
+/// Host of an absolute `http(s)://` URL.
+fn host(url: &str) -> Option<&str> {
+    let after = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))?;
+    let end = after.find(['/', ':', '?', '#']).unwrap_or(after.len());
+    Some(&after[..end])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn external_links_open_in_new_tab() {
+        let html = r#"<p><a href="https://example.com/x">ext</a> and <a href="http://other.org">b</a></p>"#;
+        assert_eq!(
+            open_external_in_new_tab(html, None),
+            r#"<p><a href="https://example.com/x" target="_blank" rel="noopener noreferrer">ext</a> and <a href="http://other.org" target="_blank" rel="noopener noreferrer">b</a></p>"#
+        );
+    }
+
+    #[test]
+    fn internal_links_untouched() {
+        let html = r##"<a href="/about.html">a</a><a href="photo.jpg">b</a><a href="#top">c</a><a href="https://Photos.Example.com/ny/">d</a>"##;
+        assert_eq!(
+            open_external_in_new_tab(html, Some("https://photos.example.com")),
+            html
+        );
+    }
+
+    #[test]
+    fn existing_target_or_rel_respected() {
+        let html =
+            r#"<a href="https://x.org" target="_self">a</a><a href="https://y.org" rel="me">b</a>"#;
+        assert_eq!(
+            open_external_in_new_tab(html, None),
+            r#"<a href="https://x.org" target="_self">a</a><a href="https://y.org" rel="me" target="_blank">b</a>"#
+        );
+    }
+
+    #[test]
+    fn host_ignores_port_path_and_query() {
+        assert_eq!(host("https://a.com:8080/x"), Some("a.com"));
+        assert_eq!(host("http://a.com?q"), Some("a.com"));
+        assert_eq!(host("mailto:me@a.com"), None);
+    }
+}
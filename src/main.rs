@@ -1,12 +1,17 @@
 use clap::{Parser, Subcommand, ValueEnum};
 use clapfig::{Clapfig, ConfigAction, ConfigArgs, ConfigSubcommand, SearchPath};
+use confique::Layer;
 use serde::Serialize;
 use simple_gal::config::SiteConfig;
 use simple_gal::json_output::{
     self, BuildPayload, CacheStatsPayload, CheckPayload, ConfigOpPayload, Counts, ErrorEnvelope,
-    ErrorKind, GeneratePayload, OkEnvelope, ProcessPayload, ReindexPayload, ScanPayload,
+    ErrorKind, ExportCsvPayload, GeneratePayload, InitPayload, OkEnvelope, ProcessPayload,
+    ReindexPayload, ScanPayload, SizeStatsPayload, TimingsPayload,
+};
+use simple_gal::output::{Logger, Verbosity};
+use simple_gal::{
+    config, duplicates, export, generate, init, output, pipeline, process, reindex, scan,
 };
-use simple_gal::{config, generate, output, process, reindex, scan};
 use std::io::{IsTerminal, Write};
 use std::path::{Path, PathBuf};
 
@@ -16,12 +21,57 @@ struct CacheArgs {
     /// Disable the processing cache — force re-encoding of all images
     #[arg(long)]
     no_cache: bool,
+    /// Re-hash cached outputs and re-encode any that no longer match what
+    /// was written (edited or damaged files)
+    #[arg(long, conflicts_with = "no_cache")]
+    verify_cache: bool,
     /// Wipe `<temp-dir>/processed/` before loading the cache, if its
     /// schema version doesn't match this binary. Matches the nuke-on-
     /// mismatch policy in `docs/dev/data-model-refactor.md` §5.2.
     /// Without this flag, a version mismatch aborts with a clear error.
     #[arg(long)]
     auto_reset_cache: bool,
+    /// After processing, also delete processed images the cache manifest
+    /// never tracked (left by older builds or interrupted runs)
+    #[arg(long)]
+    prune_untracked: bool,
+    /// Read and write the cache manifest at this path instead of
+    /// `<temp-dir>/processed/.cache-manifest.json`, so it can be persisted
+    /// separately from the processed images
+    #[arg(long, value_name = "PATH")]
+    cache_file: Option<PathBuf>,
+}
+
+impl CacheArgs {
+    /// The cache mode `--no-cache` / `--verify-cache` select.
+    fn mode(&self) -> simple_gal::cache::CacheMode {
+        use simple_gal::cache::CacheMode;
+        if self.no_cache {
+            CacheMode::Disabled
+        } else if self.verify_cache {
+            CacheMode::Verify
+        } else {
+            CacheMode::Enabled
+        }
+    }
+
+    /// Where the cache manifest for `processed_dir` lives.
+    fn cache_file(&self, processed_dir: &Path) -> PathBuf {
+        self.cache_file
+            .clone()
+            .unwrap_or_else(|| simple_gal::cache::manifest_path(processed_dir))
+    }
+}
+
+/// Arguments for the `build` command.
+#[derive(clap::Args, Clone)]
+struct BuildArgs {
+    #[command(flatten)]
+    cache: CacheArgs,
+    /// Fast preview build: low quality, a single 800px size and no
+    /// thumbnail sharpening. Explicit `--quality` still wins.
+    #[arg(long)]
+    draft: bool,
 }
 
 /// Output format for all commands.
@@ -51,6 +101,15 @@ struct ScanArgs {
     save_manifest: Option<PathBuf>,
 }
 
+/// Arguments for the `check` command.
+#[derive(clap::Args, Clone)]
+struct CheckArgs {
+    /// Also decode every image and report pairs that look the same, within
+    /// and across albums. Slower: reads every photo in full.
+    #[arg(long)]
+    duplicates: bool,
+}
+
 /// Arguments for the `reindex` command.
 ///
 /// `spacing` and `padding` default to the `[auto_indexing]` values in the
@@ -79,6 +138,25 @@ struct ReindexArgs {
     yes: bool,
 }
 
+/// Arguments for the `init` command.
+#[derive(clap::Args, Clone)]
+struct InitArgs {
+    /// Directory to create. Defaults to the content source (`--source`).
+    path: Option<PathBuf>,
+    /// Write the starter files into a non-empty directory, overwriting
+    /// any files with the same names.
+    #[arg(long)]
+    force: bool,
+}
+
+/// Arguments for the `export-csv` command.
+#[derive(clap::Args, Clone)]
+struct ExportCsvArgs {
+    /// CSV file to write.
+    #[arg(default_value = "images.csv")]
+    path: PathBuf,
+}
+
 fn version_string() -> &'static str {
     let on_tag = env!("ON_RELEASE_TAG");
     if on_tag == "true" {
@@ -129,7 +207,8 @@ Metadata resolution (first available wins):
   Description: sidecar .txt → IPTC caption
   Gallery:     directory name; description from description.md or .txt
 
-Run 'simple-gal config gen' to generate a documented config.toml,
+Run 'simple-gal init' to create a starter content directory, or
+'simple-gal config gen' to generate a documented config.toml,
 or 'simple-gal config schema' to emit a JSON Schema for tooling.")]
 #[command(version = version_string())]
 struct Cli {
@@ -156,16 +235,48 @@ struct Cli {
     #[arg(long, global = true, value_enum)]
     format: Option<OutputFormat>,
 
-    /// Suppress non-error output in text mode. No effect on JSON mode.
-    #[arg(long, global = true)]
+    /// Only print errors and the final result in text mode. No effect on JSON mode.
+    #[arg(long, short = 'q', global = true)]
     quiet: bool,
 
+    /// Print per-file detail (source paths, per-variant cache decisions)
+    /// in text mode. No effect on JSON mode.
+    #[arg(long, short = 'v', global = true, conflicts_with = "quiet")]
+    verbose: bool,
+
+    /// Override `site_title` for this run
+    #[arg(long, global = true)]
+    site_title: Option<String>,
+
+    /// Override `images.quality` (0-100) for this run, e.g. a low value
+    /// for fast draft previews
+    #[arg(long, global = true)]
+    quality: Option<u32>,
+
+    /// Override `base_url` for this run
+    #[arg(long, global = true)]
+    base_url: Option<String>,
+
     #[command(subcommand)]
     command: Command,
 }
 
+impl Cli {
+    /// Config overrides from `--site-title`, `--quality` and `--base-url`,
+    /// merged above every `config.toml` and `SIMPLE_GAL_*` variable.
+    fn config_overrides(&self) -> config::SiteConfigLayer {
+        let mut layer = config::SiteConfigLayer::empty();
+        layer.site_title = self.site_title.clone();
+        layer.images.quality = self.quality;
+        layer.base_url = self.base_url.clone();
+        layer
+    }
+}
+
 #[derive(Subcommand)]
 enum Command {
+    /// Create a starter content directory with a sample album and pages
+    Init(InitArgs),
     /// Scan content directory into a manifest
     Scan(ScanArgs),
     /// Generate responsive image sizes and thumbnails
@@ -173,13 +284,15 @@ enum Command {
     /// Produce the final HTML site from processed images
     Generate,
     /// Run the full pipeline: scan → process → generate
-    Build(CacheArgs),
+    Build(BuildArgs),
     /// Validate content directory without building
-    Check,
+    Check(CheckArgs),
     /// Manage site configuration: gen, schema, list, get, set, unset
     Config(ConfigArgs),
     /// Normalize `NNN-` prefixes on albums, groups, pages, and images
     Reindex(ReindexArgs),
+    /// Write a CSV of every image (album, title, size, URL) from the processed manifest
+    ExportCsv(ExportCsvArgs),
 }
 
 /// Wrapper around any command error tagged with an [`ErrorKind`] so the
@@ -331,16 +444,29 @@ fn run(cli: &Cli, format: OutputFormat) -> Result<(), CliError> {
         OutputFormat::Json | OutputFormat::Ndjson | OutputFormat::Progress
     );
     let ndjson = matches!(format, OutputFormat::Ndjson | OutputFormat::Progress);
-    let quiet = cli.quiet;
+    let log = text_logger(cli, json_mode);
 
     match &cli.command {
-        Command::Scan(args) => run_scan(cli, args, format),
-        Command::Process(cache_args) => run_process(cli, cache_args, json_mode, ndjson, quiet),
-        Command::Generate => run_generate(cli, json_mode, ndjson, quiet),
-        Command::Build(cache_args) => run_build(cli, cache_args, format),
-        Command::Check => run_check(cli, json_mode, ndjson, quiet),
-        Command::Config(args) => run_config(cli, args, json_mode, ndjson),
-        Command::Reindex(args) => run_reindex(cli, args, json_mode, ndjson, quiet),
+        Command::Init(args) => run_init(cli, args, json_mode, ndjson, log),
+        Command::Scan(args) => run_scan(cli, args, format, log),
+        Command::Process(cache_args) => run_process(cli, cache_args, json_mode, ndjson, log),
+        Command::Generate => run_generate(cli, json_mode, ndjson, log),
+        Command::Build(args) => run_build(cli, args, format, log),
+        Command::Check(args) => run_check(cli, args, json_mode, ndjson, log),
+        Command::Config(args) => run_config(cli, args, json_mode, ndjson, log),
+        Command::Reindex(args) => run_reindex(cli, args, json_mode, ndjson, log),
+        Command::ExportCsv(args) => run_export_csv(cli, args, json_mode, ndjson, log),
+    }
+}
+
+/// Build the text-mode logger from `--quiet` / `--verbose`. In any JSON
+/// mode stdout belongs to the envelope, so the logger is disabled outright.
+fn text_logger(cli: &Cli, json_mode: bool) -> Logger {
+    if json_mode {
+        Logger::disabled()
+    } else {
+        Logger::new(Verbosity::from_flags(cli.quiet, cli.verbose))
+            .with_style(output::stdout_supports_style())
     }
 }
 
@@ -355,8 +481,43 @@ fn emit_json_result<T: Serialize>(compact: bool, value: &T) -> Result<(), CliErr
     }
 }
 
-fn run_scan(cli: &Cli, args: &ScanArgs, format: OutputFormat) -> Result<(), CliError> {
-    let manifest = scan::scan(&cli.source).tag(ErrorKind::Scan)?;
+fn run_init(
+    cli: &Cli,
+    args: &InitArgs,
+    json_mode: bool,
+    ndjson: bool,
+    log: Logger,
+) -> Result<(), CliError> {
+    let target = args.path.as_ref().unwrap_or(&cli.source);
+    let files = init::init(target, args.force).map_err(|e| {
+        let kind = match e {
+            init::InitError::NotEmpty(_) => ErrorKind::Usage,
+            _ => ErrorKind::Io,
+        };
+        CliError::new(kind, Box::new(e))
+    })?;
+
+    if json_mode {
+        let payload = InitPayload {
+            path: target,
+            files: &files,
+        };
+        emit_json_result(ndjson, &OkEnvelope::new("init", payload))?;
+    } else {
+        for file in &files {
+            log.detail(format!("  {}", target.join(file).display()));
+        }
+        log.result(format!(
+            "==> Created starter content in {} — run `simple-gal build` next.",
+            target.display()
+        ));
+    }
+    Ok(())
+}
+
+fn run_scan(cli: &Cli, args: &ScanArgs, format: OutputFormat, log: Logger) -> Result<(), CliError> {
+    let manifest =
+        scan::scan_with_overrides(&cli.source, &cli.config_overrides()).tag(ErrorKind::Scan)?;
 
     let saved_path = if let Some(path) = &args.save_manifest {
         let manifest_path = if path.as_os_str() == "__default__" {
@@ -383,8 +544,9 @@ fn run_scan(cli: &Cli, args: &ScanArgs, format: OutputFormat) -> Result<(), CliE
             )?;
         }
         OutputFormat::Text => {
-            if !cli.quiet {
-                output::print_scan_output(&manifest, &cli.source);
+            output::print_scan_output(&manifest, &cli.source, &log);
+            if let Some(path) = saved_path {
+                log.result(format!("==> Manifest saved: {}", path.display()));
             }
         }
     }
@@ -396,7 +558,7 @@ fn run_process(
     cache_args: &CacheArgs,
     json_mode: bool,
     ndjson: bool,
-    quiet: bool,
+    log: Logger,
 ) -> Result<(), CliError> {
     let scan_manifest_path = cli.temp_dir.join("manifest.json");
     let manifest_content = std::fs::read_to_string(&scan_manifest_path).tag(ErrorKind::Io)?;
@@ -405,23 +567,16 @@ fn run_process(
     let site_config: config::SiteConfig =
         serde_json::from_value(input_manifest.get("config").cloned().unwrap_or_default())
             .tag(ErrorKind::Config)?;
-    init_thread_pool(&site_config.processing);
+    init_thread_pool(&site_config.processing, log);
     let processed_dir = cli.temp_dir.join("processed");
-    maybe_reset_cache(
-        &processed_dir,
-        cache_args.auto_reset_cache,
-        !json_mode && !quiet,
-    )?;
+    maybe_reset_cache(&processed_dir, cache_args, log)?;
     let (tx, rx) = std::sync::mpsc::channel();
-    let suppress = (json_mode && !ndjson) || quiet;
     let printer = std::thread::spawn(move || {
         for event in rx {
             if ndjson {
                 json_output::emit_ndjson_progress(&event).ok();
-            } else if !suppress {
-                for line in output::format_process_event(&event) {
-                    println!("{}", line);
-                }
+            } else {
+                output::print_process_event(&event, &log);
             }
         }
     });
@@ -429,12 +584,14 @@ fn run_process(
         &scan_manifest_path,
         &cli.source,
         &processed_dir,
-        !cache_args.no_cache,
+        cache_args.mode(),
+        cache_args.cache_file.as_deref(),
         Some(tx),
     )
     .tag(ErrorKind::Process);
     join_printer(printer)?;
     let result = process_result?;
+    prune_untracked(&processed_dir, &result, cache_args, log)?;
     let output_manifest_path = processed_dir.join("manifest.json");
     let json = serde_json::to_string_pretty(&result.manifest).tag(ErrorKind::Internal)?;
     std::fs::write(&output_manifest_path, &json).tag(ErrorKind::Io)?;
@@ -444,188 +601,263 @@ fn run_process(
             processed_dir: processed_dir.clone(),
             manifest_path: output_manifest_path,
             cache: (&result.cache_stats).into(),
+            sizes: (&result.size_stats).into(),
         };
         emit_json_result(ndjson, &OkEnvelope::new("process", payload))?;
-    } else if !quiet {
-        println!("Cache: {}", result.cache_stats);
+    } else {
+        log.info(format!("Cache: {}", result.cache_stats));
+        log.info(format!("Size: {}", result.size_stats));
+        log.result(format!(
+            "==> Process complete: {}",
+            output_manifest_path.display()
+        ));
     }
     Ok(())
 }
 
-fn run_generate(cli: &Cli, json_mode: bool, ndjson: bool, quiet: bool) -> Result<(), CliError> {
+fn run_generate(cli: &Cli, json_mode: bool, ndjson: bool, log: Logger) -> Result<(), CliError> {
     let processed_dir = cli.temp_dir.join("processed");
     let processed_manifest_path = processed_dir.join("manifest.json");
-    generate::generate(
+    let generate::GenerateOutput {
+        manifest,
+        summary,
+        overwritten,
+        withheld_originals,
+        ..
+    } = generate::generate(
         &processed_manifest_path,
         &processed_dir,
         &cli.output,
         &cli.source,
     )
     .tag(ErrorKind::Generate)?;
+
+    if json_mode {
+        let payload = GeneratePayload::new(
+            &manifest,
+            &summary,
+            &cli.output,
+            &overwritten,
+            &withheld_originals,
+        );
+        emit_json_result(ndjson, &OkEnvelope::new("generate", payload))?;
+    } else {
+        output::print_generate_output(&manifest, &summary, &log);
+        output::print_overwrites(&overwritten, &log);
+        output::print_withheld_originals(&withheld_originals, &log);
+        log.result(format!("==> Generate complete: {}", cli.output.display()));
+    }
+    Ok(())
+}
+
+fn run_export_csv(
+    cli: &Cli,
+    args: &ExportCsvArgs,
+    json_mode: bool,
+    ndjson: bool,
+    log: Logger,
+) -> Result<(), CliError> {
+    let processed_manifest_path = cli.temp_dir.join("processed").join("manifest.json");
     let manifest_content = std::fs::read_to_string(&processed_manifest_path).tag(ErrorKind::Io)?;
     let manifest: generate::Manifest =
         serde_json::from_str(&manifest_content).tag(ErrorKind::Internal)?;
+    let (csv, rows) = export::manifest_csv(&manifest);
+    if let Some(parent) = args.path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent).tag(ErrorKind::Io)?;
+    }
+    std::fs::write(&args.path, csv).tag(ErrorKind::Io)?;
 
     if json_mode {
-        let payload = GeneratePayload::new(&manifest, &cli.output);
-        emit_json_result(ndjson, &OkEnvelope::new("generate", payload))?;
-    } else if !quiet {
-        output::print_generate_output(&manifest);
+        let payload = ExportCsvPayload {
+            path: &args.path,
+            rows,
+        };
+        emit_json_result(ndjson, &OkEnvelope::new("export-csv", payload))?;
+    } else {
+        log.result(format!(
+            "==> CSV written: {} ({} images)",
+            args.path.display(),
+            rows
+        ));
     }
     Ok(())
 }
 
-fn run_build(cli: &Cli, cache_args: &CacheArgs, format: OutputFormat) -> Result<(), CliError> {
-    let source = resolve_build_source(&cli.source);
+fn run_build(
+    cli: &Cli,
+    args: &BuildArgs,
+    format: OutputFormat,
+    log: Logger,
+) -> Result<(), CliError> {
+    let cache_args = &args.cache;
+    let source = pipeline::resolve_build_source(&cli.source);
     let json_mode = format != OutputFormat::Text;
     let ndjson = matches!(format, OutputFormat::Ndjson | OutputFormat::Progress);
     let progress_mode = format == OutputFormat::Progress;
-    let stage_text = !json_mode && !cli.quiet;
 
     std::fs::create_dir_all(&cli.temp_dir).tag(ErrorKind::Io)?;
 
     // === Stage 0: Auto-reindex (opt-in via [auto_indexing].auto) ===
-    maybe_auto_reindex(cli, &source, stage_text)?;
+    maybe_auto_reindex(cli, &source, log)?;
+    maybe_reset_cache(&cli.temp_dir.join("processed"), cache_args, log)?;
 
-    // === Stage 1: Scan ===
-    if stage_text {
-        println!("==> Stage 1: Scanning {}", source.display());
+    // === Stages 1-3: Scan, process, generate ===
+    log.info(format!("==> Stage 1: Scanning {}", source.display()));
+    if args.draft {
+        log.info("    Draft mode: reduced quality and sizes");
     }
-    let manifest = scan::scan(&source).tag(ErrorKind::Scan)?;
-    let scan_manifest_path = cli.temp_dir.join("manifest.json");
-    let json = serde_json::to_string_pretty(&manifest).tag(ErrorKind::Internal)?;
-    std::fs::write(&scan_manifest_path, &json).tag(ErrorKind::Io)?;
-    if stage_text {
-        output::print_scan_output(&manifest, &source);
-        println!("==> Stage 2: Processing images");
-    }
-
-    // Compute progress totals from scan results (used by --format progress).
-    // Sum per-album: each album may have different sizes/full_index config.
-    let total_images: usize = manifest.albums.iter().map(|a| a.images.len()).sum();
-    let variants_total: usize = manifest
-        .albums
-        .iter()
-        .map(|a| {
-            let variants_per = a.config.images.sizes.len()
-            + 1 // thumbnail
-            + usize::from(a.config.full_index.generates); // optional full-index thumbnail
-            a.images.len() * variants_per
-        })
-        .sum();
-
-    // Emit scan-complete progress event.
-    if progress_mode {
-        let tracker = json_output::ProgressTracker::with_totals(total_images, variants_total);
-        json_output::emit_progress(&tracker.scan_complete()).ok();
-    }
-
-    // === Stage 2: Process ===
-    init_thread_pool(&manifest.config.processing);
-    let processed_dir = cli.temp_dir.join("processed");
-    maybe_reset_cache(&processed_dir, cache_args.auto_reset_cache, stage_text)?;
     let (tx, rx) = std::sync::mpsc::channel();
-    let suppress = !stage_text && !ndjson;
+    let printer_source = source.clone();
+    let output_dir = cli.output.clone();
     let printer = std::thread::spawn(move || {
-        let mut tracker = if progress_mode {
-            Some(json_output::ProgressTracker::with_totals(
-                total_images,
-                variants_total,
-            ))
-        } else {
-            None
-        };
+        let mut tracker = None;
         for event in rx {
-            if let Some(ref mut t) = tracker {
-                if let process::ProcessEvent::ImageProcessed { ref variants, .. } = event {
-                    let ev = t.on_image_processed(variants.len());
-                    json_output::emit_progress(&ev).ok();
+            match event {
+                pipeline::BuildEvent::Scanned {
+                    manifest,
+                    thread_pool,
+                } => {
+                    output::print_scan_output(&manifest, &printer_source, &log);
+                    log.info("==> Stage 2: Processing images");
+                    print_thread_pool(&manifest.config.processing, thread_pool, log);
+                    if progress_mode {
+                        let (total_images, variants_total) = progress_totals(&manifest);
+                        let t =
+                            json_output::ProgressTracker::with_totals(total_images, variants_total);
+                        json_output::emit_progress(&t.scan_complete()).ok();
+                        tracker = Some(t);
+                    }
                 }
-            } else if ndjson {
-                json_output::emit_ndjson_progress(&event).ok();
-            } else if !suppress {
-                for line in output::format_process_event(&event) {
-                    println!("{}", line);
+                pipeline::BuildEvent::Process(event) => {
+                    if let Some(ref mut t) = tracker {
+                        if let process::ProcessEvent::ImageProcessed { ref variants, .. } = event {
+                            let ev = t.on_image_processed(variants.len());
+                            json_output::emit_progress(&ev).ok();
+                        }
+                    } else if ndjson {
+                        json_output::emit_ndjson_progress(&event).ok();
+                    } else {
+                        output::print_process_event(&event, &log);
+                    }
+                }
+                pipeline::BuildEvent::Processed {
+                    cache_stats,
+                    size_stats,
+                    untracked_removed,
+                } => {
+                    if untracked_removed > 0 {
+                        log.info(format!(
+                            "    Removed {untracked_removed} untracked processed files"
+                        ));
+                    }
+                    log.info(format!("Cache: {cache_stats}"));
+                    log.info(format!("Size: {size_stats}"));
+                    log.info(format!(
+                        "==> Stage 3: Generating HTML → {}",
+                        output_dir.display()
+                    ));
+                    if let Some(ref t) = tracker {
+                        json_output::emit_progress(&t.generate_started()).ok();
+                    }
                 }
             }
         }
-        // Return the tracker so we can use it for the generate stage.
-        tracker
     });
-    let process_result = process::process(
-        &scan_manifest_path,
-        &source,
-        &processed_dir,
-        !cache_args.no_cache,
-        Some(tx),
-    )
-    .tag(ErrorKind::Process);
-    // Always join the printer thread before propagating a process error,
-    // so output is flushed and the thread doesn't outlive the channel.
-    let tracker = printer.join().map_err(|_| {
-        let msg: Box<dyn std::error::Error + 'static> = "progress printer thread panicked".into();
-        CliError::new(ErrorKind::Internal, msg)
-    })?;
-    let result = process_result?;
-    let processed_manifest_path = processed_dir.join("manifest.json");
-    let json = serde_json::to_string_pretty(&result.manifest).tag(ErrorKind::Internal)?;
-    std::fs::write(&processed_manifest_path, &json).tag(ErrorKind::Io)?;
-    if stage_text {
-        println!("Cache: {}", result.cache_stats);
-        println!("==> Stage 3: Generating HTML → {}", cli.output.display());
-    }
-
-    // Emit generate-started progress event.
-    if let Some(ref t) = tracker {
-        json_output::emit_progress(&t.generate_started()).ok();
-    }
-
-    // === Stage 3: Generate ===
-    generate::generate(
-        &processed_manifest_path,
-        &processed_dir,
-        &cli.output,
-        &source,
-    )
-    .tag(ErrorKind::Generate)?;
-    let gen_manifest_content =
-        std::fs::read_to_string(&processed_manifest_path).tag(ErrorKind::Io)?;
-    let gen_manifest: generate::Manifest =
-        serde_json::from_str(&gen_manifest_content).tag(ErrorKind::Internal)?;
-
-    if stage_text {
-        output::print_generate_output(&gen_manifest);
-        println!("==> Build complete: {}", cli.output.display());
+    let options = pipeline::BuildOptions {
+        overrides: cli.config_overrides(),
+        draft: args.draft,
+        cache_mode: cache_args.mode(),
+        cache_file: cache_args.cache_file.clone(),
+        prune_untracked: cache_args.prune_untracked,
+        progress: Some(tx),
+    };
+    let build_result = pipeline::build(&source, &cli.output, &cli.temp_dir, &options);
+    // Drop the sender and join the printer before propagating a build
+    // error, so output is flushed and the thread doesn't outlive the channel.
+    drop(options);
+    join_printer(printer)?;
+    let built = build_result.map_err(tag_build_error)?;
+
+    output::print_generate_output(&built.manifest, &built.generate, &log);
+    output::print_overwrites(&built.overwritten, &log);
+    output::print_withheld_originals(&built.withheld_originals, &log);
+    log.info(format!("Time: {}", built.timings));
+    if let Some((path, elapsed)) = built.timings.slowest_album() {
+        log.detail(format!(
+            "    Slowest album: {path} ({})",
+            output::format_duration(elapsed)
+        ));
     }
+    log.result(format!("==> Build complete: {}", cli.output.display()));
 
     if json_mode {
-        let image_pages: usize = gen_manifest.albums.iter().map(|a| a.images.len()).sum();
-        let pages_count = gen_manifest.pages.iter().filter(|p| !p.is_link).count();
         let payload = BuildPayload {
-            source: &source,
+            source: &built.source,
             output: &cli.output,
-            counts: simple_gal::json_output::GenerateCounts {
-                albums: gen_manifest.albums.len(),
-                image_pages,
-                pages: pages_count,
-            },
-            cache: CacheStatsPayload::from(&result.cache_stats),
+            counts: simple_gal::json_output::GenerateCounts::from(&built.generate),
+            cache: CacheStatsPayload::from(&built.cache_stats),
+            sizes: SizeStatsPayload::from(&built.size_stats),
+            timings: TimingsPayload::from(&built.timings),
+            overwritten: &built.overwritten,
+            withheld_originals: &built.withheld_originals,
         };
         emit_json_result(ndjson, &OkEnvelope::new("build", payload))?;
     }
     Ok(())
 }
 
-fn run_check(cli: &Cli, json_mode: bool, ndjson: bool, quiet: bool) -> Result<(), CliError> {
-    let source = resolve_build_source(&cli.source);
-    if !json_mode && !quiet {
-        println!("==> Checking {}", source.display());
-    }
-    let manifest = scan::scan(&source).tag(ErrorKind::Scan)?;
-    if !json_mode && !quiet {
-        output::print_scan_output(&manifest, &source);
-        println!("==> Content is valid");
-    }
+/// Image and variant totals for `--format progress`, from scan results.
+/// Summed per album: each album may have different sizes/full_index config.
+fn progress_totals(manifest: &scan::Manifest) -> (usize, usize) {
+    let total_images = manifest.albums.iter().map(|a| a.images.len()).sum();
+    let variants_total = manifest
+        .albums
+        .iter()
+        .map(|a| {
+            let variants_per = a.config.images.sizes.max_count()
+            + 1 // thumbnail
+            + usize::from(a.config.full_index.generates); // optional full-index thumbnail
+            a.images.len() * variants_per
+        })
+        .sum();
+    (total_images, variants_total)
+}
+
+/// Tag a [`pipeline::BuildError`] with the kind of the stage that failed,
+/// keeping the stage's own error as the source so reporting matches the
+/// stage commands.
+fn tag_build_error(err: pipeline::BuildError) -> CliError {
+    let (kind, source): (ErrorKind, Box<dyn std::error::Error + 'static>) = match err {
+        pipeline::BuildError::Scan(e) => (ErrorKind::Scan, Box::new(e)),
+        pipeline::BuildError::Process(e) => (ErrorKind::Process, Box::new(e)),
+        pipeline::BuildError::Generate(e) => (ErrorKind::Generate, Box::new(e)),
+        pipeline::BuildError::Io(e) => (ErrorKind::Io, Box::new(e)),
+        pipeline::BuildError::Json(e) => (ErrorKind::Internal, Box::new(e)),
+    };
+    CliError::new(kind, source)
+}
+
+fn run_check(
+    cli: &Cli,
+    args: &CheckArgs,
+    json_mode: bool,
+    ndjson: bool,
+    log: Logger,
+) -> Result<(), CliError> {
+    let source = pipeline::resolve_build_source(&cli.source);
+    log.info(format!("==> Checking {}", source.display()));
+    let manifest =
+        scan::scan_with_overrides(&source, &cli.config_overrides()).tag(ErrorKind::Scan)?;
+    output::print_scan_output(&manifest, &source, &log);
+    let duplicates = if args.duplicates {
+        log.info("==> Looking for duplicate images");
+        let pairs = duplicates::find_duplicates(&manifest, &source);
+        output::print_duplicates(&pairs, &log);
+        pairs
+    } else {
+        Vec::new()
+    };
+    log.info("==> Content is valid");
     if json_mode {
         let images = manifest.albums.iter().map(|a| a.images.len()).sum();
         let payload = CheckPayload {
@@ -636,6 +868,9 @@ fn run_check(cli: &Cli, json_mode: bool, ndjson: bool, quiet: bool) -> Result<()
                 images,
                 pages: manifest.pages.len(),
             },
+            numbering_gaps: scan::numbering_gaps(&manifest),
+            unsupported_images: manifest.unsupported_images.clone(),
+            duplicates,
         };
         emit_json_result(ndjson, &OkEnvelope::new("check", payload))?;
     }
@@ -646,7 +881,13 @@ fn run_check(cli: &Cli, json_mode: bool, ndjson: bool, quiet: bool) -> Result<()
 /// clapfig. clapfig owns gen / schema / list / get / set / unset; we wrap
 /// the typed `ConfigResult` it returns in our JSON envelope when
 /// `--format json` is in effect, otherwise print it via `Display`.
-fn run_config(cli: &Cli, args: &ConfigArgs, json_mode: bool, ndjson: bool) -> Result<(), CliError> {
+fn run_config(
+    cli: &Cli,
+    args: &ConfigArgs,
+    json_mode: bool,
+    ndjson: bool,
+    log: Logger,
+) -> Result<(), CliError> {
     // ConfigArgs::into_action takes self, but we only have a &ConfigArgs
     // (we never own the Cli value). Mirror its dispatch by hand so we can
     // build a fresh ConfigAction without consuming the args.
@@ -665,7 +906,7 @@ fn run_config(cli: &Cli, args: &ConfigArgs, json_mode: bool, ndjson: bool) -> Re
         let payload = ConfigOpPayload::from_result(&result);
         emit_json_result(ndjson, &OkEnvelope::new("config", payload))?;
     } else {
-        println!("{result}");
+        log.result(result);
     }
     Ok(())
 }
@@ -705,22 +946,44 @@ fn config_action_from_args(args: &ConfigArgs) -> ConfigAction {
 /// Initialize the rayon thread pool based on processing config.
 ///
 /// Caps at the number of available CPU cores — user can constrain down, not up.
-fn init_thread_pool(processing: &config::ProcessingConfig) {
-    let threads = config::effective_threads(processing);
-    rayon::ThreadPoolBuilder::new()
-        .num_threads(threads)
-        .build_global()
-        .ok();
+/// Safe to call more than once; warns when the running pool can't match
+/// the configured count.
+fn init_thread_pool(processing: &config::ProcessingConfig, log: Logger) {
+    let init = process::init_thread_pool(config::effective_threads(processing));
+    print_thread_pool(processing, init, log);
 }
 
-/// Resolve the content source directory for the build command.
-fn resolve_build_source(cli_source: &Path) -> PathBuf {
-    cli_source.to_path_buf()
+/// Report the thread counts processing runs with, warning when the pool
+/// couldn't be sized as configured.
+fn print_thread_pool(
+    processing: &config::ProcessingConfig,
+    init: process::ThreadPoolInit,
+    log: Logger,
+) {
+    let io_threads = config::effective_io_threads(processing);
+    log.detail(format!(
+        "    {} encode threads, {io_threads} I/O threads",
+        config::effective_threads(processing),
+    ));
+    if let Some(requested) = processing.io_threads.filter(|&n| n > io_threads) {
+        log.warn(format!(
+            "Warning: io_threads = {requested} capped at {io_threads} \
+             ({} per CPU core)",
+            config::IO_THREADS_PER_CORE_MAX
+        ));
+    }
+    if let process::ThreadPoolInit::Mismatch { requested, actual } = init {
+        log.warn(format!(
+            "Warning: processing with {actual} threads ({requested} requested); \
+             the thread pool was already running"
+        ));
+    }
 }
 
 /// Honor `--auto-reset-cache`: when set and the cache manifest's
 /// `version` field doesn't match this binary's expected version, wipe
-/// `processed_dir` so the next load finds a clean slate.
+/// `processed_dir` (and a `--cache-file` kept outside it) so the next
+/// load finds a clean slate.
 ///
 /// Deliberately narrow: **only** a `VersionMismatch` triggers the
 /// wipe. `Io` and `Corrupt` errors bubble up so users see the real
@@ -732,24 +995,28 @@ fn resolve_build_source(cli_source: &Path) -> PathBuf {
 /// See `docs/dev/data-model-refactor.md` §5.2.
 fn maybe_reset_cache(
     processed_dir: &Path,
-    auto_reset: bool,
-    text_mode: bool,
+    cache_args: &CacheArgs,
+    log: Logger,
 ) -> Result<(), CliError> {
-    if !auto_reset || !processed_dir.exists() {
+    let cache_file = cache_args.cache_file(processed_dir);
+    if !cache_args.auto_reset_cache || !cache_file.exists() {
         return Ok(());
     }
-    match simple_gal::cache::CacheManifest::load_strict(processed_dir) {
+    match simple_gal::cache::CacheManifest::load_strict_from(&cache_file) {
         Ok(_) => Ok(()), // schema matches or no cache yet; nothing to do
         Err(simple_gal::cache::CacheLoadError::VersionMismatch {
             found, expected, ..
         }) => {
-            if text_mode {
-                println!(
-                    "==> --auto-reset-cache: wiping {} (cache version {found}, expected {expected})",
-                    processed_dir.display()
-                );
+            log.info(format!(
+                "==> --auto-reset-cache: wiping {} (cache version {found}, expected {expected})",
+                processed_dir.display()
+            ));
+            if processed_dir.exists() {
+                std::fs::remove_dir_all(processed_dir).tag(ErrorKind::Io)?;
+            }
+            if cache_file.exists() {
+                std::fs::remove_file(&cache_file).tag(ErrorKind::Io)?;
             }
-            std::fs::remove_dir_all(processed_dir).tag(ErrorKind::Io)?;
             Ok(())
         }
         Err(err) => {
@@ -761,6 +1028,25 @@ fn maybe_reset_cache(
     }
 }
 
+/// Honor `--prune-untracked`: sweep processed images that the finished
+/// run doesn't reference, including ones the cache manifest never tracked.
+fn prune_untracked(
+    processed_dir: &Path,
+    result: &process::ProcessResult,
+    cache_args: &CacheArgs,
+    log: Logger,
+) -> Result<(), CliError> {
+    if !cache_args.prune_untracked {
+        return Ok(());
+    }
+    let removed =
+        process::remove_untracked_outputs(processed_dir, &result.manifest).tag(ErrorKind::Io)?;
+    if removed > 0 {
+        log.info(format!("    Removed {removed} untracked processed files"));
+    }
+    Ok(())
+}
+
 /// Pre-scan hook: consult `[auto_indexing].sync_source_files` and reindex
 /// the source tree when the user has opted in.
 ///
@@ -783,7 +1069,7 @@ fn maybe_reset_cache(
 /// with confique's unknown-field error and are pointed at
 /// `sync_source_files` via the migration note on
 /// [`config::AutoIndexingConfig`].
-fn maybe_auto_reindex(cli: &Cli, source: &Path, text_mode: bool) -> Result<(), CliError> {
+fn maybe_auto_reindex(cli: &Cli, source: &Path, log: Logger) -> Result<(), CliError> {
     // Load from the resolved `source`, not `&cli.source` — `resolve_build_source`
     // is a passthrough today but could normalize/canonicalize in the future,
     // and a stale reference here would silently diverge.
@@ -794,9 +1080,9 @@ fn maybe_auto_reindex(cli: &Cli, source: &Path, text_mode: bool) -> Result<(), C
     let spacing = site_config.auto_indexing.spacing;
     let padding = site_config.auto_indexing.padding;
 
-    if text_mode {
-        println!("==> Stage 0: Auto-reindex source files (spacing={spacing}, padding={padding})");
-    }
+    log.info(format!(
+        "==> Stage 0: Auto-reindex source files (spacing={spacing}, padding={padding})"
+    ));
     let opts = reindex::WalkOptions {
         is_root: true,
         assets_dir: Some(site_config.assets_dir.as_str()),
@@ -805,13 +1091,18 @@ fn maybe_auto_reindex(cli: &Cli, source: &Path, text_mode: bool) -> Result<(), C
     let reports = reindex::reindex_tree(source, spacing, padding, false, false, &opts)
         .tag(ErrorKind::Reindex)?;
     let total_renames: usize = reports.iter().map(|r| r.plan.len()).sum();
-    if text_mode {
-        if total_renames == 0 {
-            println!("  (already normalized)");
-        } else {
-            let dirs = reports.iter().filter(|r| !r.plan.is_empty()).count();
-            let suffix = if dirs == 1 { "y" } else { "ies" };
-            println!("  {total_renames} rename(s) across {dirs} director{suffix}.");
+    if total_renames == 0 {
+        log.info("  (already normalized)");
+    } else {
+        let dirs = reports.iter().filter(|r| !r.plan.is_empty()).count();
+        let suffix = if dirs == 1 { "y" } else { "ies" };
+        log.info(format!(
+            "  {total_renames} rename(s) across {dirs} director{suffix}."
+        ));
+        for r in reports.iter().filter(|r| !r.plan.is_empty()) {
+            for rn in &r.plan {
+                log.detail(format!("    {}/{}  →  {}", r.dir.display(), rn.from, rn.to));
+            }
         }
     }
     // Coarse cache invalidation: if anything on disk moved, throw away
@@ -820,12 +1111,10 @@ fn maybe_auto_reindex(cli: &Cli, source: &Path, text_mode: bool) -> Result<(), C
     if total_renames > 0 {
         let processed_dir = cli.temp_dir.join("processed");
         if processed_dir.exists() {
-            if text_mode {
-                println!(
-                    "  Invalidating processing cache ({})",
-                    processed_dir.display()
-                );
-            }
+            log.info(format!(
+                "  Invalidating processing cache ({})",
+                processed_dir.display()
+            ));
             std::fs::remove_dir_all(&processed_dir).tag(ErrorKind::Io)?;
         }
     }
@@ -844,7 +1133,7 @@ fn run_reindex(
     args: &ReindexArgs,
     json_mode: bool,
     ndjson: bool,
-    quiet: bool,
+    log: Logger,
 ) -> Result<(), CliError> {
     // Target: positional PATH wins over --source; default to --source.
     let target = args.path.clone().unwrap_or_else(|| cli.source.clone());
@@ -869,17 +1158,15 @@ fn run_reindex(
     let total_renames: usize = planned.iter().map(|r| r.plan.len()).sum();
 
     // Text-mode preview & confirmation.
-    if !json_mode && !quiet {
-        print_reindex_plan(&target, &planned);
-    }
+    print_reindex_plan(&target, &planned, log);
 
     // Nothing to do.
     if total_renames == 0 {
         if json_mode {
             let payload = ReindexPayload::from_reports(&planned, args.dry_run, spacing, padding);
             emit_json_result(ndjson, &OkEnvelope::new("reindex", payload))?;
-        } else if !quiet {
-            println!("==> Nothing to reindex.");
+        } else {
+            log.info("==> Nothing to reindex.");
         }
         return Ok(());
     }
@@ -889,8 +1176,10 @@ fn run_reindex(
         if json_mode {
             let payload = ReindexPayload::from_reports(&planned, true, spacing, padding);
             emit_json_result(ndjson, &OkEnvelope::new("reindex", payload))?;
-        } else if !quiet {
-            println!("==> Dry run — {total_renames} rename(s) planned, none applied.");
+        } else {
+            log.result(format!(
+                "==> Dry run — {total_renames} rename(s) planned, none applied."
+            ));
         }
         return Ok(());
     }
@@ -899,7 +1188,7 @@ fn run_reindex(
     // assumes the flag is opt-in at the CLI level. Text mode prompts on
     // TTY; non-TTY without --yes is rejected.
     if !json_mode && !args.yes && !confirm_reindex(total_renames)? {
-        println!("Aborted.");
+        log.result("Aborted.");
         return Ok(());
     }
     if json_mode && !args.yes {
@@ -917,32 +1206,34 @@ fn run_reindex(
     if json_mode {
         let payload = ReindexPayload::from_reports(&applied, false, spacing, padding);
         emit_json_result(ndjson, &OkEnvelope::new("reindex", payload))?;
-    } else if !quiet {
+    } else {
         let applied_count: usize = applied.iter().map(|r| r.plan.len()).sum();
-        println!("==> Reindex complete — {applied_count} rename(s) applied.");
+        log.result(format!(
+            "==> Reindex complete — {applied_count} rename(s) applied."
+        ));
     }
     Ok(())
 }
 
 /// Human-readable dump of what reindex is about to do. Printed before the
 /// confirmation prompt in text mode.
-fn print_reindex_plan(target: &Path, reports: &[reindex::DirReport]) {
-    println!("==> Reindex plan ({})", target.display());
+fn print_reindex_plan(target: &Path, reports: &[reindex::DirReport], log: Logger) {
+    log.info(format!("==> Reindex plan ({})", target.display()));
     let mut total = 0usize;
     for r in reports {
         if r.plan.is_empty() {
             continue;
         }
-        println!("  {}/", r.dir.display());
+        log.info(format!("  {}/", r.dir.display()));
         for rn in &r.plan {
-            println!("    {}  →  {}", rn.from, rn.to);
+            log.info(format!("    {}  →  {}", rn.from, rn.to));
             total += 1;
         }
     }
     if total == 0 {
-        println!("  (nothing to do)");
+        log.info("  (nothing to do)");
     } else {
-        println!(
+        log.info(format!(
             "  {total} rename(s) across {} director{}.",
             reports.iter().filter(|r| !r.plan.is_empty()).count(),
             if reports.iter().filter(|r| !r.plan.is_empty()).count() == 1 {
@@ -950,7 +1241,7 @@ fn print_reindex_plan(target: &Path, reports: &[reindex::DirReport]) {
             } else {
                 "ies"
             }
-        );
+        ));
     }
 }
 
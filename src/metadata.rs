@@ -12,6 +12,23 @@
 //!   `001-My-Photo.txt` alongside `001-My-Photo.jpg`. Follows the same pattern
 //!   as `info.txt` for album descriptions — plain text, no special format.
 //!
+//! - **Per-image settings**: The same sidecar may open with a front-matter
+//!   block fenced by `---` lines, holding `key: value` settings for just that
+//!   image:
+//!
+//!   - `quality` (AVIF quality, 0–100) overrides the album's `images.quality`
+//!     for the image's responsive sizes.
+//!   - `focal` (`x,y` percentages from the top-left) is the point thumbnail
+//!     crops keep in frame instead of the center.
+//!
+//!   ```text
+//!   ---
+//!   quality: 95
+//!   focal: 50,30
+//!   ---
+//!   Frost on the window, shot at 1:1.
+//!   ```
+//!
 //! ## Embedded metadata sources (read during process phase)
 //!
 //! - **Title**: IPTC Object Name (`IPTC:2:05`). This is the "Title" field in
@@ -24,7 +41,8 @@
 //!
 //! ## Resolution priority
 //!
-//! Each field is resolved independently. The first non-empty value wins:
+//! Each field is resolved independently. The first non-empty value wins; by
+//! default:
 //!
 //! - **Title**: EXIF title → filename title → None
 //! - **Description**: sidecar `.txt` → EXIF caption → None
@@ -34,6 +52,10 @@
 //! mechanical filename extraction. For descriptions, sidecar files are explicit
 //! overrides — the user created a file on purpose — so they trump embedded metadata.
 //!
+//! Not everyone works that way, so `[metadata] title_source` and
+//! `description_source` can put the other source first (see
+//! [`resolve_title`] and [`resolve_description`]).
+//!
 //! ## Title sanitization
 //!
 //! Since resolved titles may end up in URLs and filenames (via the image page slug),
@@ -42,6 +64,9 @@
 //! This prevents filesystem errors from long titles and broken URLs from special
 //! characters.
 
+use crate::config::{DescriptionSource, TitleSource};
+use std::borrow::Cow;
+use std::io;
 use std::path::Path;
 
 /// Resolve a metadata field from multiple sources.
@@ -65,51 +90,209 @@ pub fn resolve(sources: &[Option<&str>]) -> Option<String> {
         .next()
 }
 
+/// Resolve an image title, trying `source` first and the other second.
+pub fn resolve_title(
+    iptc: Option<&str>,
+    filename: Option<&str>,
+    source: TitleSource,
+) -> Option<String> {
+    match source {
+        TitleSource::Iptc => resolve(&[iptc, filename]),
+        TitleSource::Filename => resolve(&[filename, iptc]),
+    }
+}
+
+/// Resolve an image description, trying `source` first and the other second.
+pub fn resolve_description(
+    sidecar: Option<&str>,
+    iptc: Option<&str>,
+    source: DescriptionSource,
+) -> Option<String> {
+    match source {
+        DescriptionSource::Sidecar => resolve(&[sidecar, iptc]),
+        DescriptionSource::Iptc => resolve(&[iptc, sidecar]),
+    }
+}
+
+/// Contents of an image's sidecar `.txt` file.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Sidecar {
+    /// Trimmed description text after any front matter; `None` if empty.
+    pub description: Option<String>,
+    /// `quality:` front-matter override for this image's AVIF encodes.
+    pub quality: Option<u32>,
+    /// `focal:` point for thumbnail crops, as `(x, y)` percentages.
+    pub focal: Option<(u32, u32)>,
+}
+
 /// Read a sidecar `.txt` file for an image.
 ///
 /// Given a path like `content/album/001-photo.jpg`, looks for
-/// `content/album/001-photo.txt` and returns its trimmed contents.
-/// Returns `None` if the file doesn't exist or is empty.
-pub fn read_sidecar(image_path: &Path) -> Option<String> {
+/// `content/album/001-photo.txt` and parses it with [`parse_sidecar`].
+/// A missing file is an empty [`Sidecar`]; malformed front matter is an
+/// error describing the problem.
+pub fn read_sidecar(image_path: &Path) -> Result<Sidecar, String> {
     let sidecar = image_path.with_extension("txt");
-    std::fs::read_to_string(sidecar)
-        .ok()
-        .map(|s| s.trim().to_string())
-        .filter(|s| !s.is_empty())
+    match read_text_file(&sidecar) {
+        Ok(text) => parse_sidecar(&text),
+        Err(_) => Ok(Sidecar::default()),
+    }
+}
+
+/// Read a user-written text file (sidecar, description, page).
+///
+/// Editors on Windows often save with a byte order mark: a leading UTF-8
+/// BOM is dropped so it doesn't end up in the page, and UTF-16 (either byte
+/// order) is recognised by its BOM and transcoded. Anything else must be
+/// UTF-8, as with [`std::fs::read_to_string`]. CRLF and lone CR line
+/// endings become LF, so paragraph splitting and single-line checks only
+/// have to look for `\n`.
+pub fn read_text_file(path: &Path) -> io::Result<String> {
+    let text = decode_text(std::fs::read(path)?)?;
+    Ok(normalize_line_endings(text))
+}
+
+fn normalize_line_endings(text: String) -> String {
+    if text.contains('\r') {
+        text.replace("\r\n", "\n").replace('\r', "\n")
+    } else {
+        text
+    }
+}
+
+fn decode_text(mut bytes: Vec<u8>) -> io::Result<String> {
+    let invalid = |what: &str| io::Error::new(io::ErrorKind::InvalidData, what.to_string());
+    let from_utf16 = |body: &[u8], unit: fn([u8; 2]) -> u16| {
+        if body.len() % 2 != 0 {
+            return Err(invalid("UTF-16 text has an odd number of bytes"));
+        }
+        let units: Vec<u16> = body.chunks_exact(2).map(|c| unit([c[0], c[1]])).collect();
+        String::from_utf16(&units).map_err(|_| invalid("stream did not contain valid UTF-16"))
+    };
+    if let Some(body) = bytes.strip_prefix(&[0xFF, 0xFE]) {
+        return from_utf16(body, u16::from_le_bytes);
+    }
+    if let Some(body) = bytes.strip_prefix(&[0xFE, 0xFF]) {
+        return from_utf16(body, u16::from_be_bytes);
+    }
+    if bytes.starts_with(&[0xEF, 0xBB, 0xBF]) {
+        bytes.drain(..3);
+    }
+    String::from_utf8(bytes).map_err(|_| invalid("stream did not contain valid UTF-8"))
+}
+
+/// Split `text` into its front-matter block and the text after it.
+///
+/// Front matter opens with a `+++` or `---` line on the first line and
+/// ends at the next line holding the same fence; pages and sidecars share
+/// this, and each parses the block its own way (TOML for pages, `key:
+/// value` lines for sidecars). Text that doesn't open with a fence has no
+/// front matter and is returned whole. A fence that is never closed is an
+/// error.
+pub fn split_front_matter(text: &str) -> Result<(Option<&str>, &str), String> {
+    let Some(fence) = ["+++", "---"]
+        .into_iter()
+        .find(|f| text.lines().next().is_some_and(|l| l.trim_end() == *f))
+    else {
+        return Ok((None, text));
+    };
+    let after_open = text.find('\n').map_or("", |i| &text[i + 1..]);
+    let mut offset = 0;
+    for line in after_open.split_inclusive('\n') {
+        if line.trim_end() == fence {
+            return Ok((
+                Some(&after_open[..offset]),
+                &after_open[offset + line.len()..],
+            ));
+        }
+        offset += line.len();
+    }
+    Err(format!(
+        "front matter is missing its closing `{fence}` line"
+    ))
+}
+
+/// Parse sidecar text: optional front matter (see [`split_front_matter`])
+/// of `key: value` lines, followed by the description.
+pub fn parse_sidecar(text: &str) -> Result<Sidecar, String> {
+    let mut sidecar = Sidecar::default();
+    let (front, body) = split_front_matter(text)?;
+    for line in front.unwrap_or_default().lines() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let Some((key, value)) = line.split_once(':') else {
+            return Err(format!(
+                "expected `key: value` in front matter, got {line:?}"
+            ));
+        };
+        let value = value.trim();
+        match key.trim() {
+            "quality" => {
+                let quality = value
+                    .parse::<u32>()
+                    .ok()
+                    .filter(|&q| q <= 100)
+                    .ok_or_else(|| format!("quality must be 0-100, got {value:?}"))?;
+                sidecar.quality = Some(quality);
+            }
+            "focal" => {
+                let percent = |v: &str| v.trim().parse::<u32>().ok().filter(|&p| p <= 100);
+                // `focal: "50,30"` is how YAML habits write it.
+                let value = value
+                    .strip_prefix('"')
+                    .and_then(|v| v.strip_suffix('"'))
+                    .unwrap_or(value);
+                let focal = value
+                    .split_once(',')
+                    .and_then(|(x, y)| Some((percent(x)?, percent(y)?)))
+                    .ok_or_else(|| {
+                        format!("focal must be `x,y` percentages 0-100, got {value:?}")
+                    })?;
+                sidecar.focal = Some(focal);
+            }
+            other => return Err(format!("unknown front-matter key {other:?}")),
+        }
+    }
+
+    let body = body.trim();
+    sidecar.description = (!body.is_empty()).then(|| body.to_string());
+    Ok(sidecar)
 }
 
 const MAX_SLUG_LEN: usize = 80;
 
-/// Sanitize a title string for use in URLs and filenames.
+/// Text a slug is built from: `text` itself, or its ASCII transliteration
+/// when `[output] ascii_slugs` is on ("Café Déjà" → "Cafe Deja").
+///
+/// Callers run their input through this before [`sanitize_slug`].
+pub fn slug_text(text: &str, ascii: bool) -> Cow<'_, str> {
+    if ascii && !text.is_ascii() {
+        Cow::Owned(deunicode::deunicode(text))
+    } else {
+        Cow::Borrowed(text)
+    }
+}
+
+/// Turn a title into a slug for URLs and filenames.
 ///
-/// - Replaces non-alphanumeric characters (except dashes) with dashes
+/// This is the one slug function: image slugs from IPTC titles and the
+/// image page directories `generate` writes both come from it, so a
+/// title can't yield two different URLs.
+///
+/// - Lowercases; letters and digits of any script are kept
+/// - Replaces everything else (spaces, dots, `&`, `'`, `/`, …) with dashes
 /// - Collapses consecutive dashes into one
 /// - Strips leading and trailing dashes
 /// - Truncates to `MAX_SLUG_LEN` characters (breaks at last dash before limit)
 pub fn sanitize_slug(title: &str) -> String {
-    let slug: String = title
-        .chars()
-        .map(|c| {
-            if c.is_ascii_alphanumeric() || c == '-' {
-                c
-            } else {
-                '-'
-            }
-        })
-        .collect();
-
-    // Collapse consecutive dashes
-    let mut collapsed = String::with_capacity(slug.len());
-    let mut prev_dash = false;
-    for c in slug.chars() {
-        if c == '-' {
-            if !prev_dash {
-                collapsed.push('-');
-            }
-            prev_dash = true;
-        } else {
-            collapsed.push(c);
-            prev_dash = false;
+    let mut collapsed = String::with_capacity(title.len());
+    for c in title.chars() {
+        if c.is_alphanumeric() {
+            collapsed.extend(c.to_lowercase());
+        } else if !collapsed.ends_with('-') {
+            collapsed.push('-');
         }
     }
 
@@ -117,13 +300,14 @@ pub fn sanitize_slug(title: &str) -> String {
     let trimmed = collapsed.trim_matches('-');
 
     // Truncate at word boundary (last dash before limit)
-    if trimmed.len() <= MAX_SLUG_LEN {
-        trimmed.to_string()
-    } else {
-        let truncated = &trimmed[..MAX_SLUG_LEN];
-        match truncated.rfind('-') {
-            Some(pos) => truncated[..pos].to_string(),
-            None => truncated.to_string(),
+    match trimmed.char_indices().nth(MAX_SLUG_LEN) {
+        None => trimmed.to_string(),
+        Some((cut, _)) => {
+            let truncated = &trimmed[..cut];
+            match truncated.rfind('-') {
+                Some(pos) => truncated[..pos].to_string(),
+                None => truncated.to_string(),
+            }
         }
     }
 }
@@ -188,6 +372,62 @@ mod tests {
         );
     }
 
+    #[test]
+    fn resolve_title_follows_title_source() {
+        let cases = [
+            (TitleSource::Iptc, Some("IPTC"), Some("File"), Some("IPTC")),
+            (TitleSource::Iptc, None, Some("File"), Some("File")),
+            (
+                TitleSource::Filename,
+                Some("IPTC"),
+                Some("File"),
+                Some("File"),
+            ),
+            (TitleSource::Filename, Some("IPTC"), None, Some("IPTC")),
+            (TitleSource::Filename, None, None, None),
+        ];
+        for (source, iptc, filename, expected) in cases {
+            assert_eq!(
+                resolve_title(iptc, filename, source).as_deref(),
+                expected,
+                "{source:?} with iptc={iptc:?} filename={filename:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn resolve_description_follows_description_source() {
+        let cases = [
+            (
+                DescriptionSource::Sidecar,
+                Some("Txt"),
+                Some("IPTC"),
+                Some("Txt"),
+            ),
+            (
+                DescriptionSource::Sidecar,
+                Some(" "),
+                Some("IPTC"),
+                Some("IPTC"),
+            ),
+            (
+                DescriptionSource::Iptc,
+                Some("Txt"),
+                Some("IPTC"),
+                Some("IPTC"),
+            ),
+            (DescriptionSource::Iptc, Some("Txt"), None, Some("Txt")),
+            (DescriptionSource::Iptc, None, None, None),
+        ];
+        for (source, sidecar, iptc, expected) in cases {
+            assert_eq!(
+                resolve_description(sidecar, iptc, source).as_deref(),
+                expected,
+                "{source:?} with sidecar={sidecar:?} iptc={iptc:?}"
+            );
+        }
+    }
+
     // =========================================================================
     // read_sidecar() tests
     // =========================================================================
@@ -201,7 +441,7 @@ mod tests {
         fs::write(&txt, "A beautiful sunset over the mountains").unwrap();
 
         assert_eq!(
-            read_sidecar(&img),
+            read_sidecar(&img).unwrap().description,
             Some("A beautiful sunset over the mountains".to_string())
         );
     }
@@ -210,7 +450,7 @@ mod tests {
     fn read_sidecar_returns_none_when_no_file() {
         let dir = TempDir::new().unwrap();
         let img = dir.path().join("001-photo.jpg");
-        assert_eq!(read_sidecar(&img), None);
+        assert_eq!(read_sidecar(&img).unwrap(), Sidecar::default());
     }
 
     #[test]
@@ -220,7 +460,7 @@ mod tests {
         let txt = dir.path().join("001-photo.txt");
         fs::write(&img, b"fake image").unwrap();
         fs::write(&txt, "").unwrap();
-        assert_eq!(read_sidecar(&img), None);
+        assert_eq!(read_sidecar(&img).unwrap(), Sidecar::default());
     }
 
     #[test]
@@ -230,7 +470,79 @@ mod tests {
         let txt = dir.path().join("001-photo.txt");
         fs::write(&img, b"fake image").unwrap();
         fs::write(&txt, "   \n  \t  ").unwrap();
-        assert_eq!(read_sidecar(&img), None);
+        assert_eq!(read_sidecar(&img).unwrap(), Sidecar::default());
+    }
+
+    /// `text` as UTF-16 with a byte order mark.
+    fn utf16(text: &str, big_endian: bool) -> Vec<u8> {
+        let mut bytes = if big_endian {
+            vec![0xFE, 0xFF]
+        } else {
+            vec![0xFF, 0xFE]
+        };
+        for unit in text.encode_utf16() {
+            if big_endian {
+                bytes.extend_from_slice(&unit.to_be_bytes());
+            } else {
+                bytes.extend_from_slice(&unit.to_le_bytes());
+            }
+        }
+        bytes
+    }
+
+    #[test]
+    fn read_text_file_strips_utf8_bom() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("description.txt");
+        fs::write(&path, "\u{feff}Café at dawn").unwrap();
+        assert_eq!(read_text_file(&path).unwrap(), "Café at dawn");
+    }
+
+    #[test]
+    fn read_text_file_transcodes_utf16() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("description.txt");
+        for big_endian in [false, true] {
+            fs::write(&path, utf16("Café at dawn", big_endian)).unwrap();
+            assert_eq!(read_text_file(&path).unwrap(), "Café at dawn");
+        }
+    }
+
+    #[test]
+    fn read_text_file_rejects_invalid_text() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("description.txt");
+        fs::write(&path, [0xFF, 0xFE, 0x41]).unwrap();
+        assert!(read_text_file(&path).is_err());
+        fs::write(&path, [0xC3, 0x28]).unwrap();
+        assert!(read_text_file(&path).is_err());
+    }
+
+    #[test]
+    fn read_text_file_normalizes_line_endings() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("description.txt");
+        fs::write(&path, "First.\r\n\r\nSecond.\rThird.\n").unwrap();
+        assert_eq!(
+            read_text_file(&path).unwrap(),
+            "First.\n\nSecond.\nThird.\n"
+        );
+    }
+
+    #[test]
+    fn read_sidecar_handles_bom_and_front_matter() {
+        let dir = TempDir::new().unwrap();
+        let img = dir.path().join("001-photo.jpg");
+        fs::write(&img, b"fake image").unwrap();
+        fs::write(
+            dir.path().join("001-photo.txt"),
+            utf16("---\nquality: 95\n---\nFrost", false),
+        )
+        .unwrap();
+
+        let sidecar = read_sidecar(&img).unwrap();
+        assert_eq!(sidecar.quality, Some(95));
+        assert_eq!(sidecar.description, Some("Frost".to_string()));
     }
 
     #[test]
@@ -241,7 +553,80 @@ mod tests {
         fs::write(&img, b"fake image").unwrap();
         fs::write(&txt, "\n  Some description  \n").unwrap();
 
-        assert_eq!(read_sidecar(&img), Some("Some description".to_string()));
+        assert_eq!(
+            read_sidecar(&img).unwrap().description,
+            Some("Some description".to_string())
+        );
+    }
+
+    #[test]
+    fn parse_sidecar_reads_front_matter_quality() {
+        let sidecar = parse_sidecar("---\nquality: 95\n---\nFrost on glass\n").unwrap();
+        assert_eq!(sidecar.quality, Some(95));
+        assert_eq!(sidecar.description, Some("Frost on glass".to_string()));
+    }
+
+    #[test]
+    fn parse_sidecar_reads_front_matter_focal() {
+        let sidecar = parse_sidecar("---\nfocal: 50, 30\n---\n").unwrap();
+        assert_eq!(sidecar.focal, Some((50, 30)));
+        let sidecar = parse_sidecar("---\nfocal: \"50,30\"\n---\n").unwrap();
+        assert_eq!(sidecar.focal, Some((50, 30)));
+        assert_eq!(parse_sidecar("Just text").unwrap().focal, None);
+    }
+
+    #[test]
+    fn parse_sidecar_front_matter_only() {
+        let sidecar = parse_sidecar("---\r\nquality: 80\r\n---\r\n").unwrap();
+        assert_eq!(
+            sidecar,
+            Sidecar {
+                description: None,
+                quality: Some(80),
+                focal: None,
+            }
+        );
+    }
+
+    #[test]
+    fn parse_sidecar_without_front_matter_keeps_dashes_in_text() {
+        let sidecar = parse_sidecar("Before --- after").unwrap();
+        assert_eq!(sidecar.description, Some("Before --- after".to_string()));
+        assert_eq!(sidecar.quality, None);
+    }
+
+    #[test]
+    fn parse_sidecar_rejects_bad_front_matter() {
+        for text in [
+            "---\nquality: 101\n---\n",
+            "---\nquality: high\n---\n",
+            "---\nsharpness: 2\n---\n",
+            "---\nquality 95\n---\n",
+            "---\nquality: 95\nNo closing fence",
+            "---\nfocal: 50\n---\n",
+            "---\nfocal: 50,130\n---\n",
+            "---\nfocal: left,top\n---\n",
+        ] {
+            assert!(parse_sidecar(text).is_err(), "{text:?}");
+        }
+    }
+
+    #[test]
+    fn split_front_matter_block_and_body() {
+        assert_eq!(
+            split_front_matter("+++\nslug = \"about\"\n+++\n# Body\n"),
+            Ok((Some("slug = \"about\"\n"), "# Body\n"))
+        );
+        // The closing fence must match the opening one.
+        assert_eq!(
+            split_front_matter("---\na: 1\n+++\n---\nBody"),
+            Ok((Some("a: 1\n+++\n"), "Body"))
+        );
+        assert_eq!(
+            split_front_matter("Before --- after"),
+            Ok((None, "Before --- after"))
+        );
+        assert!(split_front_matter("+++\nslug = \"x\"\n# Unclosed").is_err());
     }
 
     // =========================================================================
@@ -251,14 +636,15 @@ mod tests {
     #[test]
     fn sanitize_slug_alphanumeric_passthrough() {
         assert_eq!(sanitize_slug("hello-world"), "hello-world");
-        assert_eq!(sanitize_slug("Photo123"), "Photo123");
+        assert_eq!(sanitize_slug("Photo123"), "photo123");
     }
 
     #[test]
     fn sanitize_slug_replaces_spaces_and_special_chars() {
-        assert_eq!(sanitize_slug("My Great Photo!"), "My-Great-Photo");
-        assert_eq!(sanitize_slug("Hello World"), "Hello-World");
+        assert_eq!(sanitize_slug("My Great Photo!"), "my-great-photo");
+        assert_eq!(sanitize_slug("Hello World"), "hello-world");
         assert_eq!(sanitize_slug("foo@bar#baz"), "foo-bar-baz");
+        assert_eq!(sanitize_slug("St. Louis_2"), "st-louis-2");
     }
 
     #[test]
@@ -294,9 +680,32 @@ mod tests {
 
     #[test]
     fn sanitize_slug_handles_unicode() {
-        assert_eq!(sanitize_slug("café"), "caf");
-        assert_eq!(sanitize_slug("日本語"), "");
-        assert_eq!(sanitize_slug("München"), "M-nchen");
+        assert_eq!(sanitize_slug("café"), "café");
+        assert_eq!(sanitize_slug("日本語"), "日本語");
+        assert_eq!(sanitize_slug("München"), "münchen");
+        // Truncation counts characters and never splits one.
+        let long = "é".repeat(MAX_SLUG_LEN + 5);
+        assert_eq!(sanitize_slug(&long).chars().count(), MAX_SLUG_LEN);
+    }
+
+    #[test]
+    fn sanitize_slug_never_leaves_url_syntax() {
+        for (title, slug) in [
+            ("Rock & Roll", "rock-roll"),
+            ("Tom's Diner", "tom-s-diner"),
+            ("Before / After", "before-after"),
+            ("50% off?#top", "50-off-top"),
+        ] {
+            assert_eq!(sanitize_slug(title), slug, "{title}");
+            assert_eq!(sanitize_slug(&slug_text(title, true)), slug, "{title}");
+        }
+    }
+
+    #[test]
+    fn slug_text_folds_accents_only_when_asked() {
+        assert_eq!(slug_text("Café Déjà", true), "Cafe Deja");
+        assert_eq!(slug_text("Café Déjà", false), "Café Déjà");
+        assert_eq!(sanitize_slug(&slug_text("München", true)), "munchen");
     }
 
     #[test]
@@ -4,6 +4,19 @@
 //! an optional numeric prefix (`NNN-`) followed by a name. This module provides
 //! a single parsing function that extracts both parts consistently.
 //!
+//! `NNN` is shorthand: the prefix may be any run of ASCII digits (`1-`, `010-`,
+//! `01000-`) whose value fits in a `u32`. Leading zeros are insignificant.
+//!
+//! ## Separators
+//!
+//! The prefix is separated from the name by a single `-` or `_`, so both
+//! `001-sunset` and Lightroom-style `001_sunset` parse to number 1, name
+//! "sunset". Only the first separator is consumed; any further dashes or
+//! underscores belong to the name. Digits followed directly by a letter
+//! (`001sunset`, `2024summer`) are **not** a prefix — the whole string is
+//! treated as an unnumbered name, since such names are usually dates or
+//! camera filenames rather than sort keys.
+//!
 //! ## Display Titles
 //!
 //! Dashes in the name portion are converted to spaces for display (preserving
@@ -25,6 +38,54 @@ pub struct ParsedName {
     pub display_title: String,
 }
 
+/// Split a leading `NNN-` / `NNN_` (or bare `NNN`) prefix off `name`.
+///
+/// Returns the parsed number and the remainder after the separator, or
+/// `None` when `name` does not start with a digit run that is followed by
+/// a separator or the end of the string, or when the digits overflow `u32`.
+///
+/// Shared by [`parse_entry_name`] and the reindex walker so both agree on
+/// what counts as a numbered entry.
+pub fn split_number_prefix(name: &str) -> Option<(u32, &str)> {
+    let digits_end = name
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(name.len());
+    if digits_end == 0 {
+        return None;
+    }
+    let rest = &name[digits_end..];
+    let rest = match rest.chars().next() {
+        None => rest,
+        Some('-' | '_') => &rest[1..],
+        Some(_) => return None,
+    };
+    let num = name[..digits_end].parse::<u32>().ok()?;
+    Some((num, rest))
+}
+
+/// Turn a filename-derived title into title case.
+///
+/// Dashes and underscores become spaces, runs of whitespace collapse to one
+/// space, and the first letter of each word is uppercased. The rest of each
+/// word is left alone so acronyms survive (`"NYC_at-night"` → `"NYC At Night"`).
+///
+/// Used by the scan stage when `[naming] titleize = true`. IPTC titles never
+/// pass through here.
+pub fn titleize(title: &str) -> String {
+    title
+        .split(|c: char| c == '-' || c == '_' || c.is_whitespace())
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
 /// Normalize a name part into a URL-friendly slug: lowercase, spaces and underscores → hyphens.
 fn slugify(s: &str) -> String {
     s.to_lowercase().replace([' ', '_'], "-")
@@ -39,25 +100,14 @@ fn slugify(s: &str) -> String {
 /// - `"001-"` → number=Some(1), name="", display_title=""
 /// - `"Museum"` → number=None, name="museum", display_title="Museum"
 /// - `"wip-drafts"` → number=None, name="wip-drafts", display_title="wip drafts"
+/// - `"001_sunset"` → number=Some(1), name="sunset", display_title="sunset"
+/// - `"001sunset"` → number=None, name="001sunset", display_title="001sunset"
 pub fn parse_entry_name(name: &str) -> ParsedName {
-    // Try splitting on first dash
-    if let Some(dash_pos) = name.find('-') {
-        let prefix = &name[..dash_pos];
-        if let Ok(num) = prefix.parse::<u32>() {
-            let raw = &name[dash_pos + 1..];
-            return ParsedName {
-                number: Some(num),
-                name: slugify(raw),
-                display_title: raw.replace('-', " "),
-            };
-        }
-    }
-    // Check if the entire string is a pure number (no dash)
-    if let Ok(num) = name.parse::<u32>() {
+    if let Some((num, raw)) = split_number_prefix(name) {
         return ParsedName {
             number: Some(num),
-            name: String::new(),
-            display_title: String::new(),
+            name: slugify(raw),
+            display_title: raw.replace('-', " "),
         };
     }
     // No number prefix
@@ -152,6 +202,118 @@ mod tests {
         assert_eq!(p.display_title, "Last");
     }
 
+    #[test]
+    fn five_digit_prefix() {
+        let p = parse_entry_name("01000-Archive");
+        assert_eq!(p.number, Some(1000));
+        assert_eq!(p.name, "archive");
+        assert_eq!(p.display_title, "Archive");
+    }
+
+    #[test]
+    fn wide_prefix_up_to_u32_max() {
+        let p = parse_entry_name("4294967295-Last");
+        assert_eq!(p.number, Some(u32::MAX));
+        assert_eq!(p.name, "last");
+    }
+
+    #[test]
+    fn prefix_overflowing_u32_is_not_a_number() {
+        let p = parse_entry_name("4294967296-Huge");
+        assert_eq!(p.number, None);
+        assert_eq!(p.name, "4294967296-huge");
+    }
+
+    #[test]
+    fn underscore_separator() {
+        let p = parse_entry_name("001_sunset");
+        assert_eq!(p.number, Some(1));
+        assert_eq!(p.name, "sunset");
+        assert_eq!(p.display_title, "sunset");
+    }
+
+    #[test]
+    fn dash_separator() {
+        let p = parse_entry_name("001-sunset");
+        assert_eq!(p.number, Some(1));
+        assert_eq!(p.name, "sunset");
+        assert_eq!(p.display_title, "sunset");
+    }
+
+    #[test]
+    fn only_first_separator_is_consumed() {
+        let p = parse_entry_name("001_My-Title");
+        assert_eq!(p.number, Some(1));
+        assert_eq!(p.name, "my-title");
+        assert_eq!(p.display_title, "My Title");
+
+        let p = parse_entry_name("001-My_Title");
+        assert_eq!(p.number, Some(1));
+        assert_eq!(p.name, "my-title");
+        assert_eq!(p.display_title, "My_Title");
+
+        let p = parse_entry_name("001__double");
+        assert_eq!(p.number, Some(1));
+        assert_eq!(p.name, "-double");
+    }
+
+    #[test]
+    fn number_with_trailing_underscore() {
+        let p = parse_entry_name("001_");
+        assert_eq!(p.number, Some(1));
+        assert_eq!(p.name, "");
+        assert_eq!(p.display_title, "");
+    }
+
+    #[test]
+    fn digits_followed_by_letter_are_not_a_prefix() {
+        let p = parse_entry_name("001sunset");
+        assert_eq!(p.number, None);
+        assert_eq!(p.name, "001sunset");
+        assert_eq!(p.display_title, "001sunset");
+    }
+
+    #[test]
+    fn sign_is_not_part_of_prefix() {
+        assert_eq!(parse_entry_name("+5-x").number, None);
+    }
+
+    #[test]
+    fn split_number_prefix_cases() {
+        assert_eq!(split_number_prefix("010-Dawn"), Some((10, "Dawn")));
+        assert_eq!(split_number_prefix("010_Dawn"), Some((10, "Dawn")));
+        assert_eq!(split_number_prefix("010"), Some((10, "")));
+        assert_eq!(split_number_prefix("010x"), None);
+        assert_eq!(split_number_prefix("Dawn"), None);
+        assert_eq!(split_number_prefix(""), None);
+    }
+
+    #[test]
+    fn titleize_mixed_separators() {
+        assert_eq!(titleize("golden_gate bridge"), "Golden Gate Bridge");
+        assert_eq!(titleize("golden_gate-bridge"), "Golden Gate Bridge");
+    }
+
+    #[test]
+    fn titleize_collapses_repeated_separators() {
+        assert_eq!(titleize("__a--b  c_"), "A B C");
+    }
+
+    #[test]
+    fn titleize_preserves_inner_case() {
+        assert_eq!(titleize("NYC_at-night"), "NYC At Night");
+    }
+
+    #[test]
+    fn titleize_non_ascii() {
+        assert_eq!(titleize("équipe_été"), "Équipe Été");
+    }
+
+    #[test]
+    fn titleize_empty() {
+        assert_eq!(titleize(""), "");
+    }
+
     #[test]
     fn zero_prefix() {
         let p = parse_entry_name("000-First");
@@ -43,6 +43,8 @@
 //!
 //! ## Process
 //!
+//! Source and variant lines are only shown with `--verbose`.
+//!
 //! ```text
 //! Landscapes (5 photos)
 //!     001 dawn
@@ -50,6 +52,8 @@
 //!         800px: cached
 //!         1400px: encoded
 //!         thumbnail: cached
+//!     Cache: 2 cached, 1 encoded (3 total)
+//!     Time: 1.4s
 //! ```
 //!
 //! ## Generate
@@ -63,7 +67,7 @@
 //! Pages
 //! 001 About → about.html
 //!
-//! Generated 2 albums, 4 image pages, 1 page
+//! Generated 2 albums, 4 image pages, 1 pages (31 files)
 //! ```
 //!
 //! # Architecture
@@ -71,10 +75,216 @@
 //! Each stage has a `format_*` function (returns `Vec<String>`) for testability
 //! and a `print_*` wrapper that writes to stdout. Format functions are pure —
 //! no I/O, no side effects.
+//!
+//! # Verbosity
+//!
+//! Every line the CLI prints in text mode goes through a [`Logger`], which
+//! tags it with a [`Level`] and drops it unless the active [`Verbosity`]
+//! allows it:
+//!
+//! | Verbosity | `Result` | `Info` | `Detail` |
+//! |---|---|---|---|
+//! | `--quiet` | ✓ | | |
+//! | default | ✓ | ✓ | |
+//! | `--verbose` | ✓ | ✓ | ✓ |
+//!
+//! `Result` lines are the final outcome of a command (e.g. the output path of
+//! a build). `Detail` lines are per-file context such as the source path and
+//! per-variant cache decisions of the process stage. Errors are not routed
+//! through the logger — they always go to stderr. Warnings
+//! ([`Logger::warn`]) go to stderr too, at every verbosity, so `--quiet`
+//! doesn't hide them.
+//!
+//! # Styling
+//!
+//! Format functions always return plain text. When stdout is an interactive
+//! terminal and `NO_COLOR` is unset, the CLI turns on [`Logger::with_style`],
+//! which adds subtle ANSI styling as lines are printed: top-level headers in
+//! bold, `Source:` context lines dimmed. Piped output (CI logs, `| less`)
+//! stays strictly plain.
 
+use crate::text::{one_line, truncate_desc};
 use crate::types::NavItem;
+use std::fmt::Display;
+use std::io::IsTerminal;
 use std::path::Path;
 
+// ============================================================================
+// Verbosity-aware logger
+// ============================================================================
+
+/// How much text-mode output the user asked for (`--quiet` / `--verbose`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum Verbosity {
+    /// Only final results (and errors, which bypass the logger).
+    Quiet,
+    /// Stage headers, content inventory and summaries.
+    #[default]
+    Normal,
+    /// Everything, including per-file detail.
+    Verbose,
+}
+
+impl Verbosity {
+    /// Resolve the CLI flags. `--quiet` wins if both are somehow set.
+    pub fn from_flags(quiet: bool, verbose: bool) -> Self {
+        if quiet {
+            Verbosity::Quiet
+        } else if verbose {
+            Verbosity::Verbose
+        } else {
+            Verbosity::Normal
+        }
+    }
+
+    /// Whether a line tagged with `level` should be printed.
+    pub fn allows(self, level: Level) -> bool {
+        match level {
+            Level::Result => true,
+            Level::Info => self >= Verbosity::Normal,
+            Level::Detail => self >= Verbosity::Verbose,
+        }
+    }
+}
+
+/// Importance of a single output line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Level {
+    /// Final outcome of a command; printed even with `--quiet`.
+    Result,
+    /// Regular progress and inventory output.
+    Info,
+    /// Per-file detail, only with `--verbose`.
+    Detail,
+}
+
+/// Writes text-mode output to stdout, filtered by [`Verbosity`].
+///
+/// `Copy` so it can be handed to the progress printer thread by value.
+/// A [`Logger::disabled`] logger drops everything but warnings — used in
+/// JSON modes, where stdout belongs to the machine-readable envelope.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Logger {
+    verbosity: Option<Verbosity>,
+    styled: bool,
+}
+
+impl Logger {
+    /// A plain-text logger; see [`Logger::with_style`] for terminal styling.
+    pub fn new(verbosity: Verbosity) -> Self {
+        Self {
+            verbosity: Some(verbosity),
+            styled: false,
+        }
+    }
+
+    /// A logger that prints nothing at any level; warnings still go to
+    /// stderr.
+    pub fn disabled() -> Self {
+        Self {
+            verbosity: None,
+            styled: false,
+        }
+    }
+
+    /// Enable or disable ANSI styling of printed lines. Pass
+    /// [`stdout_supports_style`] to style only interactive output.
+    pub fn with_style(self, styled: bool) -> Self {
+        Self { styled, ..self }
+    }
+
+    /// The text printed for `line`: unchanged when plain, otherwise
+    /// decorated by [`style_line`].
+    fn render(&self, line: impl Display) -> String {
+        let line = line.to_string();
+        if self.styled { style_line(&line) } else { line }
+    }
+
+    /// Whether a line tagged with `level` would be printed.
+    pub fn enabled(&self, level: Level) -> bool {
+        self.verbosity.is_some_and(|v| v.allows(level))
+    }
+
+    /// Print one line at `level`.
+    pub fn log(&self, level: Level, line: impl Display) {
+        if self.enabled(level) {
+            println!("{}", self.render(line));
+        }
+    }
+
+    /// Print a batch of lines at `level`.
+    pub fn log_lines<I>(&self, level: Level, lines: I)
+    where
+        I: IntoIterator,
+        I::Item: Display,
+    {
+        if self.enabled(level) {
+            for line in lines {
+                println!("{}", self.render(line));
+            }
+        }
+    }
+
+    pub fn result(&self, line: impl Display) {
+        self.log(Level::Result, line);
+    }
+
+    pub fn info(&self, line: impl Display) {
+        self.log(Level::Info, line);
+    }
+
+    pub fn detail(&self, line: impl Display) {
+        self.log(Level::Detail, line);
+    }
+
+    /// Print a warning to stderr, whatever the verbosity. Never styled,
+    /// since stdout's terminal check says nothing about stderr.
+    pub fn warn(&self, line: impl Display) {
+        eprintln!("{line}");
+    }
+}
+
+// ============================================================================
+// Terminal styling
+// ============================================================================
+
+const BOLD: &str = "\x1b[1m";
+const DIM: &str = "\x1b[2m";
+const RESET: &str = "\x1b[0m";
+
+/// Whether stdout output should be styled: only for an interactive
+/// terminal, and never when `NO_COLOR` is set.
+pub fn stdout_supports_style() -> bool {
+    style_enabled(
+        std::env::var_os("NO_COLOR").as_deref(),
+        std::io::stdout().is_terminal(),
+    )
+}
+
+/// Styling decision per <https://no-color.org>: a non-empty `NO_COLOR`
+/// disables it, as does a non-terminal stdout.
+fn style_enabled(no_color: Option<&std::ffi::OsStr>, is_terminal: bool) -> bool {
+    is_terminal && no_color.is_none_or(|v| v.is_empty())
+}
+
+/// Decorate one formatted output line.
+///
+/// Unindented lines are headers (stage banners, album and section titles)
+/// and are bolded; `Source:` context lines are dimmed. Everything else is
+/// returned unchanged.
+fn style_line(line: &str) -> String {
+    let trimmed = line.trim_start();
+    if trimmed.is_empty() {
+        line.to_string()
+    } else if trimmed.len() == line.len() {
+        format!("{BOLD}{line}{RESET}")
+    } else if trimmed.starts_with("Source:") {
+        format!("{DIM}{line}{RESET}")
+    } else {
+        line.to_string()
+    }
+}
+
 // ============================================================================
 // Shared entity display helpers
 // ============================================================================
@@ -117,30 +327,6 @@ fn image_line(index: usize, title: Option<&str>, filename: &str) -> String {
     }
 }
 
-/// Strip HTML tags from a string (simple angle-bracket stripping).
-fn strip_html_tags(html: &str) -> String {
-    let mut result = String::with_capacity(html.len());
-    let mut in_tag = false;
-    for c in html.chars() {
-        match c {
-            '<' => in_tag = true,
-            '>' => in_tag = false,
-            _ if !in_tag => result.push(c),
-            _ => {}
-        }
-    }
-    result
-}
-
-/// Truncate text to `max` characters, appending `...` if truncated.
-fn truncate_desc(text: &str, max: usize) -> String {
-    if text.len() <= max {
-        text.to_string()
-    } else {
-        format!("{}...", &text[..max])
-    }
-}
-
 // ============================================================================
 // Tree walker
 // ============================================================================
@@ -214,9 +400,8 @@ pub fn format_scan_output(manifest: &crate::scan::Manifest, source_root: &Path)
             lines.push(format!("{}    Source: {}/", base_indent, node.source_dir));
 
             // Album description (truncated preview)
-            if let Some(ref desc) = album.description {
-                let plain = strip_html_tags(desc);
-                let truncated = truncate_desc(plain.trim(), 60);
+            if let Some(ref desc) = album.description_text {
+                let truncated = truncate_desc(&one_line(desc), 60);
                 if !truncated.is_empty() {
                     lines.push(format!("{}    {}", base_indent, truncated));
                 }
@@ -249,9 +434,8 @@ pub fn format_scan_output(manifest: &crate::scan::Manifest, source_root: &Path)
             let dir_name = album.path.split('/').next_back().unwrap_or(&album.path);
             let photo_count = album.images.len();
             lines.push(format!("    {} ({} photos)", dir_name, photo_count));
-            if let Some(ref desc) = album.description {
-                let plain = strip_html_tags(desc);
-                let truncated = truncate_desc(plain.trim(), 60);
+            if let Some(ref desc) = album.description_text {
+                let truncated = truncate_desc(&one_line(desc), 60);
                 if !truncated.is_empty() {
                     lines.push(format!("        {}", truncated));
                 }
@@ -287,13 +471,52 @@ pub fn format_scan_output(manifest: &crate::scan::Manifest, source_root: &Path)
         lines.push(format!("    {}/", manifest.config.assets_dir));
     }
 
+    // Warnings section: lints that don't stop the build
+    let gaps = crate::scan::numbering_gaps(manifest);
+    if !gaps.is_empty() || !manifest.unsupported_images.is_empty() {
+        lines.push(String::new());
+        lines.push("Warnings".to_string());
+        for unsupported in &manifest.unsupported_images {
+            lines.push(format!("    {}", unsupported.message()));
+        }
+        for gap in &gaps {
+            lines.push(format!(
+                "    {}: image numbers jump from {} to {} (typo? see auto_indexing.gap_warning)",
+                gap.album, gap.from, gap.to
+            ));
+        }
+    }
+
+    lines
+}
+
+/// Print scan output through the logger.
+pub fn print_scan_output(manifest: &crate::scan::Manifest, source_root: &Path, log: &Logger) {
+    if log.enabled(Level::Info) {
+        log.log_lines(Level::Info, format_scan_output(manifest, source_root));
+    }
+}
+
+/// Format `check --duplicates` results: each pair of look-alike images,
+/// or a line saying there are none.
+pub fn format_duplicates(pairs: &[crate::duplicates::DuplicatePair]) -> Vec<String> {
+    if pairs.is_empty() {
+        return vec!["No duplicate images".to_string()];
+    }
+    let mut lines = vec!["Possible duplicates".to_string()];
+    for pair in pairs {
+        lines.push(format!(
+            "    {} looks like {} (distance {})",
+            pair.first, pair.second, pair.distance
+        ));
+    }
     lines
 }
 
-/// Print scan output to stdout.
-pub fn print_scan_output(manifest: &crate::scan::Manifest, source_root: &Path) {
-    for line in format_scan_output(manifest, source_root) {
-        println!("{}", line);
+/// Print `check --duplicates` results through the logger.
+pub fn print_duplicates(pairs: &[crate::duplicates::DuplicatePair], log: &Logger) {
+    if log.enabled(Level::Info) {
+        log.log_lines(Level::Info, format_duplicates(pairs));
     }
 }
 
@@ -304,8 +527,12 @@ pub fn print_scan_output(manifest: &crate::scan::Manifest, source_root: &Path) {
 /// Format a single process progress event as display lines.
 ///
 /// Information-first: each image leads with its positional index and title.
-/// Source path and per-variant cache status are shown as indented context.
-pub fn format_process_event(event: &crate::process::ProcessEvent) -> Vec<String> {
+/// Source path and per-variant cache status are shown as indented context,
+/// but only when `verbosity` allows [`Level::Detail`].
+pub fn format_process_event(
+    event: &crate::process::ProcessEvent,
+    verbosity: Verbosity,
+) -> Vec<String> {
     use crate::process::{ProcessEvent, VariantStatus};
     match event {
         ProcessEvent::AlbumStarted { title, image_count } => {
@@ -327,6 +554,9 @@ pub fn format_process_event(event: &crate::process::ProcessEvent) -> Vec<String>
                 "    {}",
                 image_line(*index, title.as_deref(), &filename)
             ));
+            if !verbosity.allows(Level::Detail) {
+                return lines;
+            }
             lines.push(format!("        Source: {}", source_path));
 
             for variant in variants {
@@ -339,12 +569,68 @@ pub fn format_process_event(event: &crate::process::ProcessEvent) -> Vec<String>
             }
             lines
         }
+        ProcessEvent::SourceDownscaled {
+            source_path,
+            from,
+            to,
+        } => {
+            vec![format!(
+                "    Downscaled {} from {}x{} to {}x{} (processing.max_dimension)",
+                source_path, from.0, from.1, to.0, to.1
+            )]
+        }
+        ProcessEvent::SizesSkipped {
+            source_path,
+            dimensions,
+            skipped,
+        } => {
+            let sizes: Vec<String> = skipped.iter().map(|s| format!("{}px", s)).collect();
+            vec![format!(
+                "    Warning: {} is only {}x{}; skipped sizes {}",
+                source_path,
+                dimensions.0,
+                dimensions.1,
+                sizes.join(", ")
+            )]
+        }
+        ProcessEvent::SourceTooSmall {
+            source_path,
+            dimensions,
+            min_dimension,
+        } => {
+            vec![format!(
+                "    Warning: {} is only {}x{}, under images.min_dimension ({}px); wrong file?",
+                source_path, dimensions.0, dimensions.1, min_dimension
+            )]
+        }
+        ProcessEvent::AlbumFinished { cache, elapsed, .. } => {
+            vec![
+                format!("    Cache: {}", cache),
+                format!("    Time: {}", format_duration(*elapsed)),
+            ]
+        }
         ProcessEvent::CachePruned { removed } => {
             vec![format!("    Pruned {} stale cache entries", removed)]
         }
     }
 }
 
+/// Short human form of a stage or album duration: `"850ms"`, `"12.4s"`.
+pub fn format_duration(d: std::time::Duration) -> String {
+    if d.as_millis() < 1000 {
+        format!("{}ms", d.as_millis())
+    } else {
+        format!("{:.1}s", d.as_secs_f64())
+    }
+}
+
+/// Print a process progress event through the logger.
+pub fn print_process_event(event: &crate::process::ProcessEvent, log: &Logger) {
+    if let Some(verbosity) = log.verbosity {
+        log.log_lines(Level::Info, format_process_event(event, verbosity));
+    }
+}
+
 // ============================================================================
 // Stage 3: Generate output
 // ============================================================================
@@ -352,10 +638,15 @@ pub fn format_process_event(event: &crate::process::ProcessEvent) -> Vec<String>
 /// Format generate stage output showing generated HTML files.
 ///
 /// Information-first: each entity leads with its positional index and title,
-/// followed by `→` and the output path.
-pub fn format_generate_output(manifest: &crate::generate::Manifest) -> Vec<String> {
+/// followed by `→` and the output path. Ends with the [`GenerateSummary`]
+/// line.
+///
+/// [`GenerateSummary`]: crate::generate::GenerateSummary
+pub fn format_generate_output(
+    manifest: &crate::generate::Manifest,
+    summary: &crate::generate::GenerateSummary,
+) -> Vec<String> {
     let mut lines = Vec::new();
-    let mut total_image_pages = 0;
 
     // Home page
     lines.push("Home \u{2192} index.html".to_string());
@@ -382,11 +673,7 @@ pub fn format_generate_output(manifest: &crate::generate::Manifest) -> Vec<Strin
             ));
 
             for (idx, image) in album.images.iter().enumerate() {
-                let page_url = crate::generate::image_page_url(
-                    idx + 1,
-                    album.images.len(),
-                    image.title.as_deref(),
-                );
+                let page_url = album.image_page_dir(idx);
                 let display = match &image.title {
                     Some(t) if !t.is_empty() => format!("{} {}", format_index(idx + 1), t),
                     _ => format_index(idx + 1),
@@ -395,7 +682,6 @@ pub fn format_generate_output(manifest: &crate::generate::Manifest) -> Vec<Strin
                     "{}    {} \u{2192} {}/{}index.html",
                     base_indent, display, album.path, page_url
                 ));
-                total_image_pages += 1;
             }
         }
     }
@@ -409,11 +695,7 @@ pub fn format_generate_output(manifest: &crate::generate::Manifest) -> Vec<Strin
             ));
 
             for (idx, image) in album.images.iter().enumerate() {
-                let page_url = crate::generate::image_page_url(
-                    idx + 1,
-                    album.images.len(),
-                    image.title.as_deref(),
-                );
+                let page_url = album.image_page_dir(idx);
                 let display = match &image.title {
                     Some(t) if !t.is_empty() => format!("{} {}", format_index(idx + 1), t),
                     _ => format_index(idx + 1),
@@ -422,13 +704,11 @@ pub fn format_generate_output(manifest: &crate::generate::Manifest) -> Vec<Strin
                     "        {} \u{2192} {}/{}index.html",
                     display, album.path, page_url
                 ));
-                total_image_pages += 1;
             }
         }
     }
 
     // Pages section
-    let page_count = manifest.pages.iter().filter(|p| !p.is_link).count();
     if !manifest.pages.is_empty() {
         lines.push(String::new());
         lines.push("Pages".to_string());
@@ -450,80 +730,80 @@ pub fn format_generate_output(manifest: &crate::generate::Manifest) -> Vec<Strin
         }
     }
 
-    lines.push(format!(
-        "Generated {} albums, {} image pages, {} pages",
-        manifest.albums.len(),
-        total_image_pages,
-        page_count
-    ));
+    lines.push(summary.to_string());
 
     lines
 }
 
-/// Print generate output to stdout.
-pub fn print_generate_output(manifest: &crate::generate::Manifest) {
-    for line in format_generate_output(manifest) {
-        println!("{}", line);
+/// Print generate output through the logger.
+pub fn print_generate_output(
+    manifest: &crate::generate::Manifest,
+    summary: &crate::generate::GenerateSummary,
+    log: &Logger,
+) {
+    if log.enabled(Level::Info) {
+        log.log_lines(Level::Info, format_generate_output(manifest, summary));
     }
 }
 
-// ============================================================================
-// Tests
-// ============================================================================
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    // =========================================================================
-    // Helper tests
-    // =========================================================================
-
-    #[test]
-    fn strip_html_tags_removes_tags() {
-        assert_eq!(strip_html_tags("<p>Hello <b>world</b></p>"), "Hello world");
+/// Format the output paths that an asset and a processed image both wrote,
+/// naming the copy `[output] asset_precedence` kept. Empty when none clash.
+pub fn format_overwrites(overwritten: &[crate::generate::Overwrite]) -> Vec<String> {
+    if overwritten.is_empty() {
+        return Vec::new();
     }
-
-    #[test]
-    fn strip_html_tags_no_tags() {
-        assert_eq!(strip_html_tags("plain text"), "plain text");
+    let mut lines = vec!["Warnings".to_string()];
+    for o in overwritten {
+        lines.push(format!(
+            "    {}: {} replaced the {} (see output.asset_precedence)",
+            o.path.display(),
+            o.kept,
+            o.replaced
+        ));
     }
+    lines
+}
 
-    #[test]
-    fn strip_html_tags_empty() {
-        assert_eq!(strip_html_tags(""), "");
+/// Print output-path clashes as warnings, so `--quiet` doesn't hide them.
+pub fn print_overwrites(overwritten: &[crate::generate::Overwrite], log: &Logger) {
+    for line in format_overwrites(overwritten) {
+        log.warn(line);
     }
+}
 
-    #[test]
-    fn strip_html_tags_nested() {
-        assert_eq!(
-            strip_html_tags("<div><p>Some <em>text</em></p></div>"),
-            "Some text"
-        );
+/// Format the originals left out of album downloads because their metadata
+/// couldn't be removed. Empty when none were.
+pub fn format_withheld_originals(withheld: &[String]) -> Vec<String> {
+    if withheld.is_empty() {
+        return Vec::new();
     }
-
-    #[test]
-    fn truncate_desc_short() {
-        assert_eq!(truncate_desc("Short text", 40), "Short text");
+    let mut lines = vec!["Left out of album downloads".to_string()];
+    for path in withheld {
+        lines.push(format!(
+            "    {path}: metadata could not be removed (see images.strip_gps)"
+        ));
     }
+    lines
+}
 
-    #[test]
-    fn truncate_desc_exact() {
-        let text = "a".repeat(40);
-        assert_eq!(truncate_desc(&text, 40), text);
+/// Print withheld originals through the logger.
+pub fn print_withheld_originals(withheld: &[String], log: &Logger) {
+    if log.enabled(Level::Info) {
+        log.log_lines(Level::Info, format_withheld_originals(withheld));
     }
+}
 
-    #[test]
-    fn truncate_desc_long() {
-        let text = "a".repeat(50);
-        let expected = format!("{}...", "a".repeat(40));
-        assert_eq!(truncate_desc(&text, 40), expected);
-    }
+// ============================================================================
+// Tests
+// ============================================================================
 
-    #[test]
-    fn truncate_desc_empty() {
-        assert_eq!(truncate_desc("", 40), "");
-    }
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // =========================================================================
+    // Helper tests
+    // =========================================================================
 
     #[test]
     fn format_index_single_digit() {
@@ -678,10 +958,104 @@ mod tests {
             title: "Landscapes".to_string(),
             image_count: 5,
         };
-        let lines = format_process_event(&event);
+        let lines = format_process_event(&event, Verbosity::Verbose);
         assert_eq!(lines, vec!["Landscapes (5 photos)"]);
     }
 
+    #[test]
+    fn format_process_album_finished() {
+        use crate::cache::CacheStats;
+        use crate::process::ProcessEvent;
+        let event = ProcessEvent::AlbumFinished {
+            title: "Landscapes".to_string(),
+            cache: CacheStats {
+                hits: 8,
+                copies: 0,
+                misses: 2,
+            },
+            elapsed: std::time::Duration::from_millis(2340),
+        };
+        assert_eq!(
+            format_process_event(&event, Verbosity::Normal),
+            vec![
+                "    Cache: 8 cached, 2 encoded (10 total)",
+                "    Time: 2.3s"
+            ]
+        );
+    }
+
+    #[test]
+    fn format_overwrites_names_kept_copy() {
+        use crate::generate::{FileOrigin, Overwrite};
+        assert!(format_overwrites(&[]).is_empty());
+        let lines = format_overwrites(&[Overwrite {
+            path: "Landscapes/001-dawn-thumb.avif".into(),
+            kept: FileOrigin::Processed,
+            replaced: FileOrigin::Asset,
+        }]);
+        assert_eq!(
+            lines,
+            vec![
+                "Warnings",
+                "    Landscapes/001-dawn-thumb.avif: processed image replaced the asset (see output.asset_precedence)",
+            ]
+        );
+    }
+
+    #[test]
+    fn format_withheld_originals_names_each_file() {
+        assert!(format_withheld_originals(&[]).is_empty());
+        assert_eq!(
+            format_withheld_originals(&["Landscapes/001-dawn.heic".to_string()]),
+            vec![
+                "Left out of album downloads",
+                "    Landscapes/001-dawn.heic: metadata could not be removed (see images.strip_gps)",
+            ]
+        );
+    }
+
+    #[test]
+    fn format_duration_picks_unit() {
+        use std::time::Duration;
+        assert_eq!(format_duration(Duration::from_micros(400)), "0ms");
+        assert_eq!(format_duration(Duration::from_millis(999)), "999ms");
+        assert_eq!(format_duration(Duration::from_millis(1000)), "1.0s");
+        assert_eq!(format_duration(Duration::from_secs(95)), "95.0s");
+    }
+
+    #[test]
+    fn format_process_source_too_small() {
+        use crate::process::ProcessEvent;
+        let event = ProcessEvent::SourceTooSmall {
+            source_path: "010-Landscapes/001-sunset.jpg".to_string(),
+            dimensions: (300, 200),
+            min_dimension: 800,
+        };
+        assert_eq!(
+            format_process_event(&event, Verbosity::Normal),
+            vec![
+                "    Warning: 010-Landscapes/001-sunset.jpg is only 300x200, under images.min_dimension (800px); wrong file?"
+            ]
+        );
+    }
+
+    #[test]
+    fn format_process_sizes_skipped() {
+        use crate::process::ProcessEvent;
+        let event = ProcessEvent::SizesSkipped {
+            source_path: "010-Landscapes/001-sunset.jpg".to_string(),
+            dimensions: (1200, 800),
+            skipped: vec![1400, 2080],
+        };
+        let lines = format_process_event(&event, Verbosity::Normal);
+        assert_eq!(
+            lines,
+            vec![
+                "    Warning: 010-Landscapes/001-sunset.jpg is only 1200x800; skipped sizes 1400px, 2080px"
+            ]
+        );
+    }
+
     #[test]
     fn format_process_image_with_title() {
         use crate::process::{ProcessEvent, VariantInfo, VariantStatus};
@@ -704,7 +1078,7 @@ mod tests {
                 },
             ],
         };
-        let lines = format_process_event(&event);
+        let lines = format_process_event(&event, Verbosity::Verbose);
         assert_eq!(lines[0], "    001 The Sunset");
         assert_eq!(lines[1], "        Source: 010-Landscapes/001-sunset.jpg");
         assert_eq!(lines[2], "        800px: cached");
@@ -724,8 +1098,106 @@ mod tests {
                 status: VariantStatus::Cached,
             }],
         };
-        let lines = format_process_event(&event);
+        let lines = format_process_event(&event, Verbosity::Verbose);
         assert_eq!(lines[0], "    003 (38.avif)");
         assert_eq!(lines[1], "        Source: 002-NY/38.avif");
     }
+
+    #[test]
+    fn format_process_image_hides_detail_at_normal_verbosity() {
+        use crate::process::{ProcessEvent, VariantInfo, VariantStatus};
+        let event = ProcessEvent::ImageProcessed {
+            index: 1,
+            title: Some("Dawn".to_string()),
+            source_path: "010-Landscapes/001-dawn.jpg".to_string(),
+            variants: vec![VariantInfo {
+                label: "800px".to_string(),
+                status: VariantStatus::Cached,
+            }],
+        };
+        let lines = format_process_event(&event, Verbosity::Normal);
+        assert_eq!(lines, vec!["    001 Dawn"]);
+    }
+
+    // =========================================================================
+    // Verbosity tests
+    // =========================================================================
+
+    #[test]
+    fn verbosity_from_flags() {
+        assert_eq!(Verbosity::from_flags(false, false), Verbosity::Normal);
+        assert_eq!(Verbosity::from_flags(true, false), Verbosity::Quiet);
+        assert_eq!(Verbosity::from_flags(false, true), Verbosity::Verbose);
+        assert_eq!(Verbosity::from_flags(true, true), Verbosity::Quiet);
+    }
+
+    #[test]
+    fn verbosity_level_filtering() {
+        use Level::*;
+        assert!(Verbosity::Quiet.allows(Result));
+        assert!(!Verbosity::Quiet.allows(Info));
+        assert!(!Verbosity::Quiet.allows(Detail));
+
+        assert!(Verbosity::Normal.allows(Result));
+        assert!(Verbosity::Normal.allows(Info));
+        assert!(!Verbosity::Normal.allows(Detail));
+
+        assert!(Verbosity::Verbose.allows(Result));
+        assert!(Verbosity::Verbose.allows(Info));
+        assert!(Verbosity::Verbose.allows(Detail));
+    }
+
+    #[test]
+    fn disabled_logger_allows_nothing() {
+        let log = Logger::disabled();
+        assert!(!log.enabled(Level::Result));
+        assert!(!log.enabled(Level::Info));
+        assert!(!log.enabled(Level::Detail));
+    }
+
+    #[test]
+    fn logger_follows_verbosity() {
+        let log = Logger::new(Verbosity::Quiet);
+        assert!(log.enabled(Level::Result));
+        assert!(!log.enabled(Level::Info));
+    }
+
+    // =========================================================================
+    // Styling tests
+    // =========================================================================
+
+    #[test]
+    fn plain_logger_renders_lines_unchanged() {
+        let log = Logger::new(Verbosity::Normal);
+        assert_eq!(log.render("Landscapes (5 photos)"), "Landscapes (5 photos)");
+        assert_eq!(
+            log.render("    Source: 010-Landscapes/"),
+            "    Source: 010-Landscapes/"
+        );
+
+        let forced_plain = log.with_style(true).with_style(false);
+        assert_eq!(forced_plain.render("Albums"), "Albums");
+    }
+
+    #[test]
+    fn styled_logger_bolds_headers_and_dims_sources() {
+        let log = Logger::new(Verbosity::Normal).with_style(true);
+        assert_eq!(log.render("Albums"), "\x1b[1mAlbums\x1b[0m");
+        assert_eq!(
+            log.render("        Source: 001-dawn.jpg"),
+            "\x1b[2m        Source: 001-dawn.jpg\x1b[0m"
+        );
+        assert_eq!(log.render("    001 dawn"), "    001 dawn");
+        assert_eq!(log.render(""), "");
+    }
+
+    #[test]
+    fn style_enabled_requires_terminal_without_no_color() {
+        use std::ffi::OsStr;
+        assert!(style_enabled(None, true));
+        assert!(!style_enabled(None, false));
+        assert!(!style_enabled(Some(OsStr::new("1")), true));
+        // An empty NO_COLOR does not disable styling
+        assert!(style_enabled(Some(OsStr::new("")), true));
+    }
 }
@@ -0,0 +1,397 @@
+//! The whole pipeline in one call, for embedding Simple Gal in other tools.
+//!
+//! [`build`] runs scan → process → generate the way the `build` command
+//! does, writing the same intermediate manifests under `temp_dir`:
+//!
+//! ```text
+//! <temp_dir>/manifest.json             # scan output
+//! <temp_dir>/processed/                # AVIF variants + cache manifest
+//! <temp_dir>/processed/manifest.json   # process output
+//! <output>/                            # the generated site
+//! ```
+//!
+//! It prints nothing. Progress is available as [`BuildEvent`]s through
+//! [`BuildOptions::progress`], and the returned [`BuildSummary`] carries the
+//! counts, cache statistics and per-stage timings the CLI reports. The
+//! `build` command is this function plus printing.
+//!
+//! ```rust,ignore
+//! let summary = simple_gal::build(
+//!     Path::new("content"),
+//!     Path::new("dist"),
+//!     Path::new(".simple-gal-temp"),
+//!     &BuildOptions::default(),
+//! )?;
+//! println!("{} album pages in {:?}", summary.generate.albums, summary.timings.total());
+//! ```
+
+use crate::cache::{CacheMode, CacheStats};
+use crate::config::{self, SiteConfigLayer};
+use crate::generate::{self, GenerateError, GenerateSummary, Overwrite};
+use crate::output::format_duration;
+use crate::process::ThreadPoolInit;
+use crate::process::{self, ProcessError, ProcessEvent, SizeStats};
+use crate::scan::{self, Manifest, ScanError};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Sender};
+use std::time::{Duration, Instant};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum BuildError {
+    #[error(transparent)]
+    Scan(#[from] ScanError),
+    #[error(transparent)]
+    Process(#[from] ProcessError),
+    #[error(transparent)]
+    Generate(#[from] GenerateError),
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+/// Settings for [`build`]. The default is a plain cached build.
+#[derive(Clone)]
+pub struct BuildOptions {
+    /// Config values applied on top of every resolved `config.toml`, like
+    /// the CLI's `--site-title` / `--quality` / `--base-url` flags.
+    pub overrides: SiteConfigLayer,
+    /// Fast preview build (see [`config::draft_layer`]).
+    pub draft: bool,
+    /// How the processing cache is used.
+    pub cache_mode: CacheMode,
+    /// Cache manifest location; defaults to `<temp_dir>/processed/`.
+    pub cache_file: Option<PathBuf>,
+    /// Sweep processed images the finished run doesn't reference, including
+    /// ones the cache manifest never tracked.
+    pub prune_untracked: bool,
+    /// Receives a [`BuildEvent`] as each stage finishes and each image is
+    /// processed.
+    pub progress: Option<Sender<BuildEvent>>,
+}
+
+impl Default for BuildOptions {
+    fn default() -> Self {
+        Self {
+            overrides: SiteConfigLayer::empty(),
+            draft: false,
+            cache_mode: CacheMode::Enabled,
+            cache_file: None,
+            prune_untracked: false,
+            progress: None,
+        }
+    }
+}
+
+/// Progress reported by [`build`], in the order it happens.
+#[derive(Debug)]
+pub enum BuildEvent {
+    /// Scan finished and the encode thread pool is set up.
+    Scanned {
+        manifest: Box<Manifest>,
+        thread_pool: ThreadPoolInit,
+    },
+    /// An image was processed.
+    Process(ProcessEvent),
+    /// Processing finished; generation starts next.
+    Processed {
+        cache_stats: CacheStats,
+        size_stats: SizeStats,
+        /// Files swept by [`BuildOptions::prune_untracked`].
+        untracked_removed: u32,
+    },
+}
+
+/// Wall-clock time spent in each stage, and per album within process.
+#[derive(Debug, Clone, Default)]
+pub struct BuildTimings {
+    pub scan: Duration,
+    pub process: Duration,
+    pub generate: Duration,
+    /// `(album path, elapsed)` in processing order.
+    pub albums: Vec<(String, Duration)>,
+}
+
+impl BuildTimings {
+    pub fn total(&self) -> Duration {
+        self.scan + self.process + self.generate
+    }
+
+    /// The album that took longest to process, if any were processed.
+    pub fn slowest_album(&self) -> Option<(&str, Duration)> {
+        self.albums
+            .iter()
+            .max_by_key(|(_, elapsed)| *elapsed)
+            .map(|(path, elapsed)| (path.as_str(), *elapsed))
+    }
+}
+
+impl fmt::Display for BuildTimings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "scan {}, process {}, generate {} (total {})",
+            format_duration(self.scan),
+            format_duration(self.process),
+            format_duration(self.generate),
+            format_duration(self.total())
+        )
+    }
+}
+
+/// What a [`build`] produced.
+#[derive(Debug)]
+pub struct BuildSummary {
+    /// The content directory that was built.
+    pub source: PathBuf,
+    /// Source images across all albums.
+    pub images: usize,
+    /// The manifest the site was rendered from.
+    pub manifest: generate::Manifest,
+    /// Pages and files written to the output directory.
+    pub generate: GenerateSummary,
+    /// Every file written, relative to the output directory.
+    pub files: Vec<PathBuf>,
+    /// Output paths an asset and a processed image both wrote to.
+    pub overwritten: Vec<Overwrite>,
+    /// Originals left out of album downloads: their metadata couldn't be
+    /// removed.
+    pub withheld_originals: Vec<String>,
+    pub cache_stats: CacheStats,
+    pub size_stats: SizeStats,
+    pub timings: BuildTimings,
+}
+
+/// Resolve the content directory a build reads from.
+pub fn resolve_build_source(source: &Path) -> PathBuf {
+    source.to_path_buf()
+}
+
+/// Build the site in `source` into `output`, keeping the intermediate
+/// manifests and processed images in `temp_dir`.
+pub fn build(
+    source: &Path,
+    output: &Path,
+    temp_dir: &Path,
+    options: &BuildOptions,
+) -> Result<BuildSummary, BuildError> {
+    let source = resolve_build_source(source);
+    let mut timings = BuildTimings::default();
+    std::fs::create_dir_all(temp_dir)?;
+
+    let started = Instant::now();
+    let mut overrides = options.overrides.clone();
+    if options.draft {
+        overrides = overrides.with_fallback(config::draft_layer());
+    }
+    let manifest = scan::scan_with_overrides(&source, &overrides)?;
+    let scan_manifest_path = temp_dir.join("manifest.json");
+    std::fs::write(
+        &scan_manifest_path,
+        serde_json::to_string_pretty(&manifest)?,
+    )?;
+    let images = manifest.albums.iter().map(|a| a.images.len()).sum();
+    timings.scan = started.elapsed();
+
+    let started = Instant::now();
+    let thread_pool =
+        process::init_thread_pool(config::effective_threads(&manifest.config.processing));
+    report(
+        options,
+        BuildEvent::Scanned {
+            manifest: Box::new(manifest),
+            thread_pool,
+        },
+    );
+    let processed_dir = temp_dir.join("processed");
+    let result = process_with_progress(options, |events| {
+        process::process(
+            &scan_manifest_path,
+            &source,
+            &processed_dir,
+            options.cache_mode,
+            options.cache_file.as_deref(),
+            events,
+        )
+    })?;
+    let untracked_removed = if options.prune_untracked {
+        process::remove_untracked_outputs(&processed_dir, &result.manifest)?
+    } else {
+        0
+    };
+    let processed_manifest_path = processed_dir.join("manifest.json");
+    std::fs::write(
+        &processed_manifest_path,
+        serde_json::to_string_pretty(&result.manifest)?,
+    )?;
+    timings.process = started.elapsed();
+    timings.albums = result
+        .album_stats
+        .iter()
+        .map(|a| (a.path.clone(), a.elapsed))
+        .collect();
+    report(
+        options,
+        BuildEvent::Processed {
+            cache_stats: result.cache_stats,
+            size_stats: result.size_stats,
+            untracked_removed,
+        },
+    );
+
+    let started = Instant::now();
+    let generated = generate::generate(&processed_manifest_path, &processed_dir, output, &source)?;
+    timings.generate = started.elapsed();
+
+    Ok(BuildSummary {
+        source,
+        images,
+        manifest: generated.manifest,
+        generate: generated.summary,
+        files: generated.files,
+        overwritten: generated.overwritten,
+        withheld_originals: generated.withheld_originals,
+        cache_stats: result.cache_stats,
+        size_stats: result.size_stats,
+        timings,
+    })
+}
+
+fn report(options: &BuildOptions, event: BuildEvent) {
+    if let Some(progress) = &options.progress {
+        // A receiver that hung up only loses progress, not the build.
+        let _ = progress.send(event);
+    }
+}
+
+/// Run `process` with a [`ProcessEvent`] sender that forwards each event to
+/// [`BuildOptions::progress`] as a [`BuildEvent::Process`].
+fn process_with_progress<T>(
+    options: &BuildOptions,
+    process: impl FnOnce(Option<Sender<ProcessEvent>>) -> T,
+) -> T {
+    let Some(progress) = &options.progress else {
+        return process(None);
+    };
+    let (tx, rx) = mpsc::channel();
+    std::thread::scope(|scope| {
+        scope.spawn(move || {
+            for event in rx {
+                let _ = progress.send(BuildEvent::Process(event));
+            }
+        });
+        process(Some(tx))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::init;
+    use tempfile::TempDir;
+
+    #[test]
+    fn timings_add_up_and_name_the_slowest_album() {
+        let timings = BuildTimings {
+            scan: Duration::from_millis(120),
+            process: Duration::from_millis(3400),
+            generate: Duration::from_millis(480),
+            albums: vec![
+                ("Landscapes".to_string(), Duration::from_millis(900)),
+                ("Panoramas".to_string(), Duration::from_millis(2300)),
+                ("Travel".to_string(), Duration::from_millis(200)),
+            ],
+        };
+
+        assert_eq!(timings.total(), Duration::from_millis(4000));
+        assert_eq!(
+            timings.slowest_album(),
+            Some(("Panoramas", Duration::from_millis(2300)))
+        );
+        assert_eq!(
+            timings.to_string(),
+            "scan 120ms, process 3.4s, generate 480ms (total 4.0s)"
+        );
+        assert_eq!(BuildTimings::default().slowest_album(), None);
+    }
+
+    #[test]
+    fn build_writes_site_and_summary() {
+        let tmp = TempDir::new().unwrap();
+        let content = tmp.path().join("content");
+        let dist = tmp.path().join("dist");
+        let temp = tmp.path().join("temp");
+        init::init(&content, false).unwrap();
+        let options = BuildOptions {
+            draft: true,
+            ..BuildOptions::default()
+        };
+
+        let summary = build(&content, &dist, &temp, &options).unwrap();
+
+        assert!(dist.join("index.html").exists());
+        assert!(dist.join("Sample-Album/index.html").exists());
+        assert!(dist.join("about.html").exists());
+        assert!(temp.join("processed/manifest.json").exists());
+        assert_eq!(summary.source, content);
+        assert_eq!(summary.images, 2);
+        assert_eq!(summary.generate.albums, 1);
+        assert_eq!(summary.generate.image_pages, 2);
+        assert_eq!(summary.generate.content_pages, 1);
+        assert_eq!(summary.files.len(), summary.generate.files_written);
+        assert!(
+            summary
+                .files
+                .contains(&PathBuf::from("Sample-Album/index.html"))
+        );
+        assert_eq!(summary.cache_stats.hits, 0);
+        assert_eq!(summary.timings.albums.len(), 1);
+        assert!(summary.cache_stats.misses > 0);
+
+        // A second build is served from the cache.
+        let again = build(&content, &dist, &temp, &options).unwrap();
+        assert_eq!(again.cache_stats.misses, 0);
+        assert_eq!(again.cache_stats.hits, summary.cache_stats.misses);
+    }
+
+    #[test]
+    fn build_reports_stages_in_order() {
+        let tmp = TempDir::new().unwrap();
+        let content = tmp.path().join("content");
+        init::init(&content, false).unwrap();
+        let (tx, rx) = mpsc::channel();
+        let options = BuildOptions {
+            draft: true,
+            progress: Some(tx),
+            ..BuildOptions::default()
+        };
+
+        build(
+            &content,
+            &tmp.path().join("dist"),
+            &tmp.path().join("temp"),
+            &options,
+        )
+        .unwrap();
+        drop(options);
+        let events: Vec<BuildEvent> = rx.iter().collect();
+
+        assert!(
+            matches!(&events[0], BuildEvent::Scanned { manifest, .. } if manifest.albums.len() == 1)
+        );
+        assert!(matches!(
+            events.last(),
+            Some(BuildEvent::Processed {
+                untracked_removed: 0,
+                ..
+            })
+        ));
+        let processed = events
+            .iter()
+            .filter(|e| matches!(e, BuildEvent::Process(ProcessEvent::ImageProcessed { .. })))
+            .count();
+        assert_eq!(processed, 2);
+    }
+}
@@ -35,19 +35,31 @@
 //! └── ...
 //! ```
 //!
-use crate::cache::{self, CacheManifest, CacheStats};
-use crate::config::SiteConfig;
+//! ## Very large sources
+//!
+//! With `[processing] max_dimension` set, a source whose longest edge is
+//! larger is decoded once into a downscaled lossless copy, and every variant
+//! and thumbnail is made from that copy (see [`PixelSource`]). This bounds
+//! per-worker memory for 100MP panoramas on small CI runners.
+//!
+use crate::cache::{self, CacheManifest, CacheMode, CacheStats, SourceStamp};
+use crate::config::{ChangeDetection, SiteConfig};
+use crate::imaging::calculations::thumbnail_aspect;
+use crate::imaging::params::ResizeParams;
 use crate::imaging::{
-    BackendError, ImageBackend, Quality, ResponsiveConfig, RustBackend, Sharpening,
-    ThumbnailConfig, get_dimensions,
+    AspectMode, BackendError, ImageBackend, ImageSizes, Quality, ResponsiveConfig, RustBackend,
+    Sharpening, ThumbnailConfig, get_dimensions,
 };
 use crate::metadata;
-use crate::types::{NavItem, Page};
+use crate::scan;
+use crate::types::{AlbumMeta, NavItem, Page};
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 use std::path::{Path, PathBuf};
 use std::sync::Mutex;
 use std::sync::mpsc::Sender;
+use std::time::{Duration, Instant};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -71,15 +83,19 @@ pub enum ProcessError {
     /// underlying problem.
     #[error(transparent)]
     CacheLoad(#[from] cache::CacheLoadError),
+    #[error("Could not start the I/O thread pool: {0}")]
+    ThreadPool(#[from] rayon::ThreadPoolBuildError),
 }
 
 /// Configuration for image processing
 #[derive(Debug, Clone)]
 pub struct ProcessConfig {
-    pub sizes: Vec<u32>,
+    pub sizes: ImageSizes,
     pub quality: u32,
     pub thumbnail_aspect: (u32, u32), // width, height
     pub thumbnail_size: u32,          // size on the short edge
+    pub thumbnail_sharpen: bool,
+    pub thumbnail_aspect_mode: AspectMode,
 }
 
 impl ProcessConfig {
@@ -91,6 +107,8 @@ impl ProcessConfig {
             quality: config.images.quality,
             thumbnail_aspect: (ar[0], ar[1]),
             thumbnail_size: config.thumbnails.size,
+            thumbnail_sharpen: config.thumbnails.sharpen,
+            thumbnail_aspect_mode: config.thumbnails.aspect_mode,
         }
     }
 }
@@ -123,14 +141,23 @@ pub struct InputManifest {
 #[derive(Debug, Deserialize)]
 pub struct InputAlbum {
     pub path: String,
+    /// Source directory relative to the content root. Absent from
+    /// manifests written before album files were published.
+    #[serde(default)]
+    pub source_dir: String,
     pub title: String,
     pub description: Option<String>,
+    /// Plain-text rendering of `description`.
+    #[serde(default)]
+    pub description_text: Option<String>,
     pub preview_image: String,
     pub images: Vec<InputImage>,
     pub in_nav: bool,
     pub config: SiteConfig,
     #[serde(default)]
     pub support_files: Vec<String>,
+    #[serde(default)]
+    pub meta: Option<AlbumMeta>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -144,6 +171,13 @@ pub struct InputImage {
     pub title: Option<String>,
     #[serde(default)]
     pub description: Option<String>,
+    /// Sidecar `quality:` override; replaces the album quality for this
+    /// image's responsive sizes.
+    #[serde(default)]
+    pub quality: Option<u32>,
+    /// Sidecar `focal:` point; every thumbnail of this image crops around it.
+    #[serde(default)]
+    pub focal: Option<(u32, u32)>,
     /// Pointer into [`InputManifest::canonical_images`]. Populated by
     /// scan for manifests produced in v0.19.x or later; absent on
     /// older ones (back-compat path falls through to the ref's own
@@ -217,13 +251,29 @@ pub struct OutputAlbum {
     pub title: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    /// Plain-text rendering of `description`, for the search index and
+    /// album excerpts.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description_text: Option<String>,
     pub preview_image: String,
     pub thumbnail: String,
+    /// The preview image's double-size thumbnail, with `[thumbnails] hidpi`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thumbnail_2x: Option<String>,
+    /// Album-card preview made from the preview image, when
+    /// `[thumbnails] card_size` is set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub card_thumbnail: Option<String>,
+    /// Encoded size of `card_thumbnail`, for `[thumbnails] inline_below_bytes`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub card_thumbnail_bytes: Option<u64>,
     pub images: Vec<OutputImage>,
     pub in_nav: bool,
     pub config: SiteConfig,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub support_files: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub meta: Option<AlbumMeta>,
 }
 
 #[derive(Debug, Serialize)]
@@ -241,6 +291,14 @@ pub struct OutputImage {
     pub generated: std::collections::BTreeMap<String, GeneratedVariant>,
     /// Thumbnail path
     pub thumbnail: String,
+    /// Encoded size of the thumbnail file, so generate can inline small
+    /// ones under `[thumbnails] inline_below_bytes`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thumbnail_bytes: Option<u64>,
+    /// Thumbnail at twice the size, for `2x` in `srcset`, when
+    /// `[thumbnails] hidpi = true`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thumbnail_2x: Option<String>,
     /// Extra thumbnail generated for the site-wide "All Photos" page, when
     /// `[full_index] generates = true`. Uses full_index.thumb_ratio/thumb_size.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -263,6 +321,9 @@ pub struct GeneratedVariant {
 pub struct ProcessResult {
     pub manifest: OutputManifest,
     pub cache_stats: CacheStats,
+    /// Cache counts and wall-clock time per album, in processing order.
+    /// The cache counts sum to `cache_stats`.
+    pub album_stats: Vec<AlbumStats>,
     /// Source-hash dedup stats from the canonical-image lookup (Phase 2
     /// of the data-model refactor). `unique` counts distinct source
     /// byte streams that required a fresh `hash_file` read; `reused`
@@ -270,6 +331,27 @@ pub struct ProcessResult {
     /// previously-computed hash — one reuse per extra album that
     /// references the same canonical content.
     pub source_hash_stats: SourceHashStats,
+    /// Bytes of source images vs. the processed outputs made from them.
+    pub size_stats: SizeStats,
+}
+
+/// Cache counts and processing time for a single album.
+#[derive(Debug, Clone)]
+pub struct AlbumStats {
+    pub path: String,
+    pub title: String,
+    pub cache: CacheStats,
+    /// Wall-clock time from the album's first image to its last.
+    pub elapsed: Duration,
+}
+
+/// What the I/O pass learned about one source image, handed to the
+/// encode pass.
+struct PreparedImage {
+    source_path: PathBuf,
+    dimensions: (u32, u32),
+    exif: crate::imaging::backend::ImageMetadata,
+    source_hash: String,
 }
 
 /// Content-derived metadata for a canonical image. Populated once per
@@ -294,6 +376,78 @@ pub struct SourceHashStats {
     pub reused: u32,
 }
 
+/// Disk usage of the sources vs. the outputs they produced.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SizeStats {
+    /// Sum of source file sizes, each file counted once.
+    pub source_bytes: u64,
+    /// Sum of every live output AVIF (responsive variants and thumbnails).
+    pub output_bytes: u64,
+}
+
+/// Size in bytes of the file at `path`, or `None` when it can't be read.
+fn file_size(path: &Path) -> Option<u64> {
+    std::fs::metadata(path).ok().map(|m| m.len())
+}
+
+impl SizeStats {
+    /// Sum the sizes of `sources` and `outputs`. Duplicate paths are counted
+    /// once; missing files count as zero.
+    pub fn measure(
+        sources: impl IntoIterator<Item = PathBuf>,
+        outputs: impl IntoIterator<Item = PathBuf>,
+    ) -> Self {
+        fn total(paths: impl IntoIterator<Item = PathBuf>) -> u64 {
+            let unique: std::collections::HashSet<PathBuf> = paths.into_iter().collect();
+            unique
+                .iter()
+                .filter_map(|p| std::fs::metadata(p).ok())
+                .map(|m| m.len())
+                .sum()
+        }
+        Self {
+            source_bytes: total(sources),
+            output_bytes: total(outputs),
+        }
+    }
+
+    /// Output size as a fraction of the source size, or `None` with no
+    /// source bytes to compare against.
+    pub fn ratio(&self) -> Option<f64> {
+        (self.source_bytes > 0).then(|| self.output_bytes as f64 / self.source_bytes as f64)
+    }
+}
+
+impl std::fmt::Display for SizeStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} output from {} originals",
+            format_bytes(self.output_bytes),
+            format_bytes(self.source_bytes)
+        )?;
+        if let Some(ratio) = self.ratio() {
+            write!(f, " ({:.1}%)", ratio * 100.0)?;
+        }
+        Ok(())
+    }
+}
+
+/// Human-readable byte count in decimal units (e.g. "12.3 MB").
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+    if bytes < 1000 {
+        return format!("{} B", bytes);
+    }
+    let mut value = bytes as f64 / 1000.0;
+    let mut unit = 0;
+    while value >= 1000.0 && unit < UNITS.len() - 1 {
+        value /= 1000.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", value, UNITS[unit])
+}
+
 /// Cache outcome for a single processed variant (for progress reporting).
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "snake_case")]
@@ -347,15 +501,125 @@ pub enum ProcessEvent {
         /// Per-variant cache/encode status.
         variants: Vec<VariantInfo>,
     },
+    /// A source larger than `[processing] max_dimension` was downscaled
+    /// before its variants were encoded.
+    SourceDownscaled {
+        /// Relative source path (e.g., "010-Panoramas/001-ridge.jpg").
+        source_path: String,
+        /// Original `(width, height)`.
+        from: (u32, u32),
+        /// Downscaled `(width, height)` the outputs were made from.
+        to: (u32, u32),
+    },
+    /// Some configured `images.sizes` are larger than the source, so fewer
+    /// variants were generated than requested. Usually means a low-res
+    /// upload.
+    SizesSkipped {
+        /// Relative source path (e.g., "010-Landscapes/001-dawn.jpg").
+        source_path: String,
+        /// Source `(width, height)`.
+        dimensions: (u32, u32),
+        /// Requested sizes that exceed the source's longer edge.
+        skipped: Vec<u32>,
+    },
+    /// The source's longer edge is below `images.min_dimension`: probably
+    /// the wrong file.
+    SourceTooSmall {
+        /// Relative source path (e.g., "010-Landscapes/001-dawn.jpg").
+        source_path: String,
+        /// Source `(width, height)`.
+        dimensions: (u32, u32),
+        /// The configured `images.min_dimension`.
+        min_dimension: u32,
+    },
+    /// All images of an album are done; `cache` counts its variants.
+    AlbumFinished {
+        title: String,
+        cache: CacheStats,
+        /// Wall-clock time spent on the album.
+        #[serde(rename = "elapsed_ms", serialize_with = "serialize_millis")]
+        elapsed: Duration,
+    },
     /// Stale cache entries were pruned after processing.
     CachePruned { removed: u32 },
 }
 
+/// Durations go out as whole milliseconds in NDJSON progress events.
+fn serialize_millis<S: serde::Serializer>(d: &Duration, s: S) -> Result<S::Ok, S::Error> {
+    s.serialize_u64(d.as_millis() as u64)
+}
+
+/// Outcome of [`init_thread_pool`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThreadPoolInit {
+    /// The global pool runs the requested number of threads.
+    Ready(usize),
+    /// The global pool was already running with a different thread count
+    /// (configured by an earlier call, or started by rayon on first use),
+    /// so the request could not be honored.
+    Mismatch { requested: usize, actual: usize },
+}
+
+/// Content hash of the source at `path` (`relative` to the source root).
+///
+/// With `[processing] cache_mode = "mtime"`, a source whose size and mtime
+/// match the cache's stamp from the last run reuses the recorded hash
+/// without being read. Either way the fresh stamp is recorded.
+fn source_content_hash(
+    cache: &Mutex<CacheManifest>,
+    relative: &str,
+    path: &Path,
+    detection: ChangeDetection,
+) -> Result<String, ProcessError> {
+    let (size, mtime_ns) = cache::file_stamp(path)?;
+    if detection == ChangeDetection::Mtime
+        && let Some(hash) = cache.lock().unwrap().stamped_hash(relative, size, mtime_ns)
+    {
+        return Ok(hash.to_string());
+    }
+    let hash = cache::hash_file(path)?;
+    cache.lock().unwrap().record_source(
+        relative.to_string(),
+        SourceStamp {
+            size,
+            mtime_ns,
+            hash: hash.clone(),
+        },
+    );
+    Ok(hash)
+}
+
+/// Configure rayon's global thread pool for image processing.
+///
+/// Rayon's global pool can only be built once per process, so only the
+/// first call takes effect; later calls (e.g. one per rebuild in a watch
+/// loop) are no-ops that report whether the running pool matches.
+pub fn init_thread_pool(threads: usize) -> ThreadPoolInit {
+    static INIT: std::sync::Once = std::sync::Once::new();
+    INIT.call_once(|| {
+        // Fails only if rayon already started its default pool; the
+        // resulting thread count is checked below either way.
+        let _ = rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build_global();
+    });
+    let actual = rayon::current_num_threads();
+    if actual == threads {
+        ThreadPoolInit::Ready(actual)
+    } else {
+        ThreadPoolInit::Mismatch {
+            requested: threads,
+            actual,
+        }
+    }
+}
+
 pub fn process(
     manifest_path: &Path,
     source_root: &Path,
     output_dir: &Path,
-    use_cache: bool,
+    cache_mode: CacheMode,
+    cache_file: Option<&Path>,
     progress: Option<Sender<ProcessEvent>>,
 ) -> Result<ProcessResult, ProcessError> {
     let backend = RustBackend::new();
@@ -364,35 +628,50 @@ pub fn process(
         manifest_path,
         source_root,
         output_dir,
-        use_cache,
+        cache_mode,
+        cache_file,
         progress,
     )
 }
 
 /// Process images using a specific backend (allows testing with mock).
+///
+/// `cache_file` overrides where the cache manifest is read and written
+/// (`--cache-file`); by default it sits in `output_dir`.
 pub fn process_with_backend(
     backend: &impl ImageBackend,
     manifest_path: &Path,
     source_root: &Path,
     output_dir: &Path,
-    use_cache: bool,
+    cache_mode: CacheMode,
+    cache_file: Option<&Path>,
     progress: Option<Sender<ProcessEvent>>,
 ) -> Result<ProcessResult, ProcessError> {
     let manifest_content = std::fs::read_to_string(manifest_path)?;
     let input: InputManifest = serde_json::from_str(&manifest_content)?;
 
     std::fs::create_dir_all(output_dir)?;
+    let cache_file = cache_file.map_or_else(|| cache::manifest_path(output_dir), Path::to_path_buf);
 
     // Strict load: a schema-version mismatch surfaces as `ProcessError::CacheSchemaMismatch`
     // so the CLI can tell the user to wipe the processed dir (or pass
     // --auto-reset-cache). Other load errors (missing file → empty; IO /
     // corruption → error) behave as documented on `CacheLoadError`.
-    let cache = Mutex::new(if use_cache {
-        CacheManifest::load_strict(output_dir)?
+    let cache = Mutex::new(if cache_mode != CacheMode::Disabled {
+        CacheManifest::load_strict_from(&cache_file)?
     } else {
         CacheManifest::empty()
     });
-    let stats = Mutex::new(CacheStats::default());
+    let mut stats = CacheStats::default();
+    let mut album_stats = Vec::new();
+
+    // Hashing and metadata reads get their own pool, so they can be
+    // oversubscribed while encoding stays on the core-sized global pool.
+    let io_pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(crate::config::effective_io_threads(
+            &input.config.processing,
+        ))
+        .build()?;
 
     // Canonical-view lookup: map `ImageId` → `InputCanonicalImage` for
     // O(1) resolution from per-album refs. Empty on legacy manifests
@@ -435,6 +714,7 @@ pub fn process_with_backend(
     let mut output_albums = Vec::new();
 
     for album in &input.albums {
+        let album_started = Instant::now();
         if let Some(ref tx) = progress {
             tx.send(ProcessEvent::AlbumStarted {
                 title: album.title.clone(),
@@ -443,6 +723,8 @@ pub fn process_with_backend(
             .ok();
         }
 
+        copy_album_files(album, source_root, output_dir)?;
+
         // Per-album config from the resolved config chain
         let album_process = ProcessConfig::from_site_config(&album.config);
 
@@ -453,9 +735,11 @@ pub fn process_with_backend(
 
         let thumbnail_config = ThumbnailConfig {
             aspect: album_process.thumbnail_aspect,
+            aspect_mode: album_process.thumbnail_aspect_mode,
             short_edge: album_process.thumbnail_size,
             quality: Quality::new(album_process.quality),
-            sharpening: Some(Sharpening::light()),
+            sharpening: album_process.thumbnail_sharpen.then(Sharpening::light),
+            focal: None,
         };
 
         // Extra thumbnail for the site-wide "All Photos" page. Uses its own
@@ -466,81 +750,193 @@ pub fn process_with_backend(
                 let fi = &input.config.full_index;
                 Some(ThumbnailConfig {
                     aspect: (fi.thumb_ratio[0], fi.thumb_ratio[1]),
+                    aspect_mode: AspectMode::Fixed,
                     short_edge: fi.thumb_size,
                     quality: Quality::new(album_process.quality),
-                    sharpening: Some(Sharpening::light()),
+                    sharpening: album_process.thumbnail_sharpen.then(Sharpening::light),
+                    focal: None,
                 })
             } else {
                 None
             };
+        // Double-size thumbnail for high-density screens, when
+        // `[thumbnails] hidpi` is set.
+        let thumbnail_2x_config: Option<ThumbnailConfig> =
+            album.config.thumbnails.hidpi.then(|| ThumbnailConfig {
+                short_edge: thumbnail_config.short_edge * 2,
+                ..thumbnail_config.clone()
+            });
+        // Album-card preview, made only from the preview image when
+        // `[thumbnails] card_size` is set.
+        let card_thumbnail_config: Option<ThumbnailConfig> =
+            album
+                .config
+                .thumbnails
+                .card_size
+                .map(|short_edge| ThumbnailConfig {
+                    short_edge,
+                    ..thumbnail_config.clone()
+                });
         let album_output_dir = output_dir.join(&album.path);
         std::fs::create_dir_all(&album_output_dir)?;
+        let album_cache = Mutex::new(CacheStats::default());
+
+        // Phase 1, on the I/O pool: check each source exists, read its
+        // dimensions and IPTC metadata, and hash it for the cache. This work
+        // mostly waits on the disk, so `[processing] io_threads` may run
+        // more workers than there are cores.
+        let prepared: Result<Vec<PreparedImage>, ProcessError> = io_pool.install(|| {
+            album
+                .images
+                .par_iter()
+                .map(|image| {
+                    let source_path = source_root.join(&image.source_path);
+                    if !source_path.exists() {
+                        return Err(ProcessError::SourceNotFound(source_path));
+                    }
+
+                    // Phase 4b: check the canonical-metadata memo first. On
+                    // hit, skip `get_dimensions` and `read_metadata` entirely
+                    // — we already read them from one sibling ref in another
+                    // album. On miss, perform the reads, fill the cell, and
+                    // let subsequent refs to this canonical_id short-circuit.
+                    let metadata_cell: Option<MetadataCell> = image
+                        .canonical_id
+                        .as_ref()
+                        .filter(|id| canonical_by_id.contains_key(id.as_str()))
+                        .map(|id| {
+                            let mut memo = canonical_metadata_memo.lock().unwrap();
+                            memo.entry(id.clone())
+                                .or_insert_with(|| std::sync::Arc::new(Mutex::new(None)))
+                                .clone()
+                        });
+                    let (dimensions, exif) = if let Some(ref cell) = metadata_cell {
+                        // First worker on this canonical_id computes; others
+                        // block on the inner mutex and read the cached values.
+                        let mut slot = cell.lock().unwrap();
+                        match slot.clone() {
+                            Some(m) => {
+                                let dims = (m.width.unwrap_or(0), m.height.unwrap_or(0));
+                                // Keywords aren't carried in the memo — they're
+                                // not consumed downstream today, so an empty
+                                // `keywords` is correct for the memo-hit path.
+                                let exif = crate::imaging::backend::ImageMetadata {
+                                    title: m.iptc_title.clone(),
+                                    description: m.iptc_description.clone(),
+                                    keywords: Vec::new(),
+                                };
+                                (dims, exif)
+                            }
+                            None => {
+                                let dims = get_dimensions(backend, &source_path)?;
+                                let exif = backend.read_metadata(&source_path)?;
+                                *slot = Some(CanonicalMetadata {
+                                    iptc_title: exif.title.clone(),
+                                    iptc_description: exif.description.clone(),
+                                    width: Some(dims.0),
+                                    height: Some(dims.1),
+                                });
+                                (dims, exif)
+                            }
+                        }
+                    } else {
+                        let dims = get_dimensions(backend, &source_path)?;
+                        let exif = backend.read_metadata(&source_path)?;
+                        (dims, exif)
+                    };
+
+                    // Compute source hash once per canonical content across the
+                    // whole site. The memo key is `canonical_id` when the scan
+                    // stage stamped one AND that id actually resolves in
+                    // `canonical_by_id` — otherwise we fall back to the ref's
+                    // own `source_path`. Guarding on resolution prevents a
+                    // manifest where `canonical_id` was set without a matching
+                    // entry (inconsistent input) from causing two genuinely
+                    // distinct images to share a hash.
+                    let canonical = image
+                        .canonical_id
+                        .as_deref()
+                        .and_then(|id| canonical_by_id.get(id).map(|c| (id, *c)));
+                    let (memo_key, hash_source) = match canonical {
+                        Some((id, c)) => (id.to_string(), c.source_path.as_str()),
+                        None => (image.source_path.clone(), image.source_path.as_str()),
+                    };
+                    // Per-key cell: first worker locks it, hashes, stores; later
+                    // workers block on the same cell and just read the stored
+                    // hash. Workers on different keys run in parallel (each has
+                    // its own cell). The stats counter is now race-free because
+                    // only one thread ever reaches the miss branch per key.
+                    let cell: HashCell = {
+                        let mut memo = source_hash_memo.lock().unwrap();
+                        memo.entry(memo_key)
+                            .or_insert_with(|| std::sync::Arc::new(Mutex::new(None)))
+                            .clone()
+                    };
+                    let source_hash = {
+                        use std::sync::atomic::Ordering;
+                        let mut slot = cell.lock().unwrap();
+                        match slot.clone() {
+                            Some(h) => {
+                                source_hash_reused.fetch_add(1, Ordering::Relaxed);
+                                h
+                            }
+                            None => {
+                                let h = source_content_hash(
+                                    &cache,
+                                    hash_source,
+                                    &source_root.join(hash_source),
+                                    input.config.processing.cache_mode,
+                                )?;
+                                *slot = Some(h.clone());
+                                source_hash_unique.fetch_add(1, Ordering::Relaxed);
+                                h
+                            }
+                        }
+                    };
+                    Ok(PreparedImage {
+                        source_path,
+                        dimensions,
+                        exif,
+                        source_hash,
+                    })
+                })
+                .collect()
+        });
+        let prepared = prepared?;
 
-        // Process images in parallel (rayon thread pool sized by config)
+        // Phase 2, on the global pool sized to the cores: encode the
+        // variants, or serve them from the cache.
         let processed_images: Result<Vec<_>, ProcessError> = album
             .images
             .par_iter()
+            .zip(prepared)
             .enumerate()
-            .map(|(idx, image)| {
-                let source_path = source_root.join(&image.source_path);
-                if !source_path.exists() {
-                    return Err(ProcessError::SourceNotFound(source_path));
-                }
-
-                // Phase 4b: check the canonical-metadata memo first. On
-                // hit, skip `get_dimensions` and `read_metadata` entirely
-                // — we already read them from one sibling ref in another
-                // album. On miss, perform the reads, fill the cell, and
-                // let subsequent refs to this canonical_id short-circuit.
-                let metadata_cell: Option<MetadataCell> = image
-                    .canonical_id
-                    .as_ref()
-                    .filter(|id| canonical_by_id.contains_key(id.as_str()))
-                    .map(|id| {
-                        let mut memo = canonical_metadata_memo.lock().unwrap();
-                        memo.entry(id.clone())
-                            .or_insert_with(|| std::sync::Arc::new(Mutex::new(None)))
-                            .clone()
-                    });
-                let (dimensions, exif) = if let Some(ref cell) = metadata_cell {
-                    // First worker on this canonical_id computes; others
-                    // block on the inner mutex and read the cached values.
-                    let mut slot = cell.lock().unwrap();
-                    match slot.clone() {
-                        Some(m) => {
-                            let dims = (m.width.unwrap_or(0), m.height.unwrap_or(0));
-                            // Keywords aren't carried in the memo — they're
-                            // not consumed downstream today, so an empty
-                            // `keywords` is correct for the memo-hit path.
-                            let exif = crate::imaging::backend::ImageMetadata {
-                                title: m.iptc_title.clone(),
-                                description: m.iptc_description.clone(),
-                                keywords: Vec::new(),
-                            };
-                            (dims, exif)
-                        }
-                        None => {
-                            let dims = get_dimensions(backend, &source_path)?;
-                            let exif = backend.read_metadata(&source_path)?;
-                            *slot = Some(CanonicalMetadata {
-                                iptc_title: exif.title.clone(),
-                                iptc_description: exif.description.clone(),
-                                width: Some(dims.0),
-                                height: Some(dims.1),
-                            });
-                            (dims, exif)
-                        }
-                    }
-                } else {
-                    let dims = get_dimensions(backend, &source_path)?;
-                    let exif = backend.read_metadata(&source_path)?;
-                    (dims, exif)
-                };
-                let title = metadata::resolve(&[exif.title.as_deref(), image.title.as_deref()]);
-                let description =
-                    metadata::resolve(&[image.description.as_deref(), exif.description.as_deref()]);
-                let slug = if exif.title.is_some() && title.is_some() {
-                    metadata::sanitize_slug(title.as_deref().unwrap())
+            .map(|(idx, (image, prepared))| {
+                let PreparedImage {
+                    source_path,
+                    dimensions,
+                    exif,
+                    source_hash,
+                } = prepared;
+                let sources = &album.config.metadata;
+                let title = metadata::resolve_title(
+                    exif.title.as_deref(),
+                    image.title.as_deref(),
+                    sources.title_source,
+                );
+                let description = metadata::resolve_description(
+                    image.description.as_deref(),
+                    exif.description.as_deref(),
+                    sources.description_source,
+                );
+                // The slug follows the title only when the title is the IPTC one;
+                // filename titles already have a slug from scan.
+                let iptc_title = metadata::resolve(&[exif.title.as_deref()]);
+                let slug = if iptc_title.is_some() && title == iptc_title {
+                    metadata::sanitize_slug(&metadata::slug_text(
+                        title.as_deref().unwrap(),
+                        album.config.output.ascii_slugs,
+                    ))
                 } else {
                     image.slug.clone()
                 };
@@ -551,84 +947,85 @@ pub fn process_with_backend(
                     .to_str()
                     .unwrap();
 
-                // Compute source hash once per canonical content across the
-                // whole site. The memo key is `canonical_id` when the scan
-                // stage stamped one AND that id actually resolves in
-                // `canonical_by_id` — otherwise we fall back to the ref's
-                // own `source_path`. Guarding on resolution prevents a
-                // manifest where `canonical_id` was set without a matching
-                // entry (inconsistent input) from causing two genuinely
-                // distinct images to share a hash.
-                let canonical = image
-                    .canonical_id
-                    .as_deref()
-                    .and_then(|id| canonical_by_id.get(id).map(|c| (id, *c)));
-                let (memo_key, hash_input_path) = match canonical {
-                    Some((id, c)) => (id.to_string(), source_root.join(&c.source_path)),
-                    None => (image.source_path.clone(), source_path.clone()),
-                };
-                // Per-key cell: first worker locks it, hashes, stores; later
-                // workers block on the same cell and just read the stored
-                // hash. Workers on different keys run in parallel (each has
-                // its own cell). The stats counter is now race-free because
-                // only one thread ever reaches the miss branch per key.
-                let cell: HashCell = {
-                    let mut memo = source_hash_memo.lock().unwrap();
-                    memo.entry(memo_key)
-                        .or_insert_with(|| std::sync::Arc::new(Mutex::new(None)))
-                        .clone()
-                };
-                let source_hash = {
-                    use std::sync::atomic::Ordering;
-                    let mut slot = cell.lock().unwrap();
-                    match slot.clone() {
-                        Some(h) => {
-                            source_hash_reused.fetch_add(1, Ordering::Relaxed);
-                            h
-                        }
-                        None => {
-                            let h = cache::hash_file(&hash_input_path)?;
-                            *slot = Some(h.clone());
-                            source_hash_unique.fetch_add(1, Ordering::Relaxed);
-                            h
-                        }
-                    }
-                };
                 let ctx = CacheContext {
                     source_hash: &source_hash,
                     cache: &cache,
-                    stats: &stats,
+                    stats: &album_cache,
                     cache_root: output_dir,
+                    verify: cache_mode == CacheMode::Verify,
                 };
 
+                let pixels = PixelSource::new(
+                    &source_path,
+                    dimensions,
+                    album.config.processing.max_dimension,
+                    album_output_dir.join(format!("{stem}.prescaled.png")),
+                );
+
+                // A sidecar quality override changes the responsive encode
+                // params, and with them the cache key, for this image only.
+                let image_responsive_config = match image.quality {
+                    Some(quality) => Cow::Owned(ResponsiveConfig {
+                        quality: Quality::new(quality),
+                        ..responsive_config.clone()
+                    }),
+                    None => Cow::Borrowed(&responsive_config),
+                };
+                // Sized from the pixels actually read, so `auto` never
+                // plans variants larger than a downscaled copy.
                 let (raw_variants, responsive_statuses) = create_responsive_images_cached(
                     backend,
-                    &source_path,
+                    &pixels,
                     &album_output_dir,
                     stem,
-                    dimensions,
-                    &responsive_config,
+                    pixels.dimensions(),
+                    &image_responsive_config,
                     &ctx,
                 )?;
 
+                // A sidecar focal point moves the crop window of every
+                // thumbnail of this image, and is part of their cache keys.
+                let focused = |config: &ThumbnailConfig| ThumbnailConfig {
+                    focal: image.focal,
+                    ..config.clone()
+                };
                 let (thumbnail_path, thumb_status) = create_thumbnail_cached(
                     backend,
-                    &source_path,
+                    &pixels,
                     &album_output_dir,
                     stem,
-                    &thumbnail_config,
+                    dimensions,
+                    &focused(&thumbnail_config),
                     &ctx,
                 )?;
 
+                let thumb_2x = if let Some(ref cfg_2x) = thumbnail_2x_config {
+                    let (path, status) = create_thumbnail_cached_with_suffix(
+                        backend,
+                        &pixels,
+                        &album_output_dir,
+                        stem,
+                        "thumb-2x",
+                        "2x",
+                        dimensions,
+                        &focused(cfg_2x),
+                        &ctx,
+                    )?;
+                    Some((path, status))
+                } else {
+                    None
+                };
+
                 let full_index_thumb = if let Some(ref fi_cfg) = full_index_thumbnail_config {
                     let (path, status) = create_thumbnail_cached_with_suffix(
                         backend,
-                        &source_path,
+                        &pixels,
                         &album_output_dir,
                         stem,
                         "fi-thumb",
                         "full-index",
-                        fi_cfg,
+                        dimensions,
+                        &focused(fi_cfg),
                         &ctx,
                     )?;
                     Some((path, status))
@@ -636,6 +1033,23 @@ pub fn process_with_backend(
                     None
                 };
 
+                let card_thumb = match card_thumbnail_config {
+                    Some(ref card_cfg) if image.source_path == album.preview_image => {
+                        Some(create_thumbnail_cached_with_suffix(
+                            backend,
+                            &pixels,
+                            &album_output_dir,
+                            stem,
+                            "card-thumb",
+                            "card",
+                            dimensions,
+                            &focused(card_cfg),
+                            &ctx,
+                        )?)
+                    }
+                    _ => None,
+                };
+
                 // Build variant infos for progress event (before consuming raw_variants)
                 let variant_infos: Vec<VariantInfo> = if progress.is_some() {
                     let mut infos: Vec<VariantInfo> = raw_variants
@@ -650,12 +1064,24 @@ pub fn process_with_backend(
                         label: "thumbnail".to_string(),
                         status: thumb_status,
                     });
+                    if let Some((_, ref status_2x)) = thumb_2x {
+                        infos.push(VariantInfo {
+                            label: "2x thumbnail".to_string(),
+                            status: status_2x.clone(),
+                        });
+                    }
                     if let Some((_, ref fi_status)) = full_index_thumb {
                         infos.push(VariantInfo {
                             label: "all-photos thumbnail".to_string(),
                             status: fi_status.clone(),
                         });
                     }
+                    if let Some((_, ref card_status)) = card_thumb {
+                        infos.push(VariantInfo {
+                            label: "card thumbnail".to_string(),
+                            status: card_status.clone(),
+                        });
+                    }
                     infos
                 } else {
                     Vec::new()
@@ -676,6 +1102,35 @@ pub fn process_with_backend(
                     .collect();
 
                 if let Some(ref tx) = progress {
+                    let min_dimension = album.config.images.min_dimension;
+                    if dimensions.0.max(dimensions.1) < min_dimension {
+                        tx.send(ProcessEvent::SourceTooSmall {
+                            source_path: image.source_path.clone(),
+                            dimensions,
+                            min_dimension,
+                        })
+                        .ok();
+                    }
+                    let skipped = crate::imaging::calculations::oversized_sizes(
+                        dimensions,
+                        &responsive_config.sizes,
+                    );
+                    if !skipped.is_empty() {
+                        tx.send(ProcessEvent::SizesSkipped {
+                            source_path: image.source_path.clone(),
+                            dimensions,
+                            skipped,
+                        })
+                        .ok();
+                    }
+                    if let Some(to) = pixels.downscaled_to() {
+                        tx.send(ProcessEvent::SourceDownscaled {
+                            source_path: image.source_path.clone(),
+                            from: dimensions,
+                            to,
+                        })
+                        .ok();
+                    }
                     tx.send(ProcessEvent::ImageProcessed {
                         index: idx + 1,
                         title: title.clone(),
@@ -694,10 +1149,39 @@ pub fn process_with_backend(
                     title,
                     description,
                     slug,
+                    exif.description,
+                    card_thumb.map(|(p, _)| p),
+                    thumb_2x.map(|(p, _)| p),
                 ))
             })
             .collect();
         let processed_images = processed_images?;
+        let card_thumbnail = processed_images.iter().find_map(|p| p.9.clone());
+        let card_thumbnail_bytes = card_thumbnail
+            .as_deref()
+            .and_then(|path| file_size(&output_dir.join(path)));
+
+        // With `[albums] description_from_iptc`, an album without a
+        // description file takes its preview image's IPTC caption instead.
+        let iptc_caption = || {
+            if album.description.is_some() || !album.config.albums.description_from_iptc {
+                return None;
+            }
+            processed_images
+                .iter()
+                .find(|p| p.0.source_path == album.preview_image)
+                .and_then(|p| metadata::resolve(&[p.8.as_deref()]))
+        };
+        let (description, description_text) = match iptc_caption() {
+            Some(caption) => (
+                Some(scan::rewrite_description(
+                    &scan::plain_text_to_html(&caption),
+                    &album.config,
+                )),
+                Some(caption),
+            ),
+            None => (album.description.clone(), album.description_text.clone()),
+        };
 
         // Build output images (preserving order)
         let mut output_images: Vec<OutputImage> = processed_images
@@ -712,6 +1196,9 @@ pub fn process_with_backend(
                     title,
                     description,
                     slug,
+                    _caption,
+                    _card_thumbnail,
+                    thumbnail_2x,
                 )| {
                     OutputImage {
                         number: image.number,
@@ -721,7 +1208,9 @@ pub fn process_with_backend(
                         description,
                         dimensions,
                         generated,
+                        thumbnail_bytes: file_size(&output_dir.join(&thumbnail_path)),
                         thumbnail: thumbnail_path,
+                        thumbnail_2x,
                         full_index_thumbnail,
                         canonical_id: image.canonical_id.clone(),
                     }
@@ -733,47 +1222,77 @@ pub fn process_with_backend(
         output_images.sort_by_key(|img| img.number);
 
         // Find album thumbnail: the preview_image is always in the image list.
-        let album_thumbnail = output_images
+        let preview = output_images
             .iter()
             .find(|img| img.source_path == album.preview_image)
-            .expect("preview_image must be in the image list")
-            .thumbnail
-            .clone();
+            .expect("preview_image must be in the image list");
+        let album_thumbnail = preview.thumbnail.clone();
+        let album_thumbnail_2x = preview.thumbnail_2x.clone();
+
+        let album_cache = album_cache.into_inner().unwrap();
+        stats.merge(&album_cache);
+        let elapsed = album_started.elapsed();
+        if let Some(ref tx) = progress {
+            tx.send(ProcessEvent::AlbumFinished {
+                title: album.title.clone(),
+                cache: album_cache,
+                elapsed,
+            })
+            .ok();
+        }
+        album_stats.push(AlbumStats {
+            path: album.path.clone(),
+            title: album.title.clone(),
+            cache: album_cache,
+            elapsed,
+        });
 
         output_albums.push(OutputAlbum {
             path: album.path.clone(),
             title: album.title.clone(),
-            description: album.description.clone(),
+            description,
+            description_text,
             preview_image: album.preview_image.clone(),
             thumbnail: album_thumbnail,
+            thumbnail_2x: album_thumbnail_2x,
+            card_thumbnail,
+            card_thumbnail_bytes,
             images: output_images,
             in_nav: album.in_nav,
             config: album.config.clone(),
             support_files: album.support_files.clone(),
+            meta: album.meta.clone(),
         });
     }
 
-    // Collect all output paths that are live in this build
-    let live_paths: std::collections::HashSet<String> = output_albums
-        .iter()
-        .flat_map(|album| {
-            let image_paths = album.images.iter().flat_map(|img| {
-                let mut paths: Vec<String> =
-                    img.generated.values().map(|v| v.avif.clone()).collect();
-                paths.push(img.thumbnail.clone());
-                if let Some(ref fi) = img.full_index_thumbnail {
-                    paths.push(fi.clone());
-                }
-                paths
-            });
-            std::iter::once(album.thumbnail.clone()).chain(image_paths)
-        })
-        .collect();
+    let live_paths = live_output_paths(&output_albums);
+    let size_stats = SizeStats::measure(
+        output_albums
+            .iter()
+            .flat_map(|album| &album.images)
+            .map(|img| source_root.join(&img.source_path)),
+        live_paths
+            .iter()
+            .filter(|p| p.ends_with(".avif"))
+            .map(|p| output_dir.join(p)),
+    );
 
     let mut final_cache = cache.into_inner().unwrap();
-    let pruned = final_cache.prune(&live_paths, output_dir);
-    let final_stats = stats.into_inner().unwrap();
-    final_cache.save(output_dir)?;
+    let mut pruned = final_cache.prune(&live_paths, output_dir);
+    pruned += remove_stale_album_files(output_dir, &live_paths)?;
+    let live_sources: std::collections::HashSet<String> = input
+        .albums
+        .iter()
+        .flat_map(|album| album.images.iter().map(|img| img.source_path.clone()))
+        .chain(input.canonical_images.iter().map(|c| c.source_path.clone()))
+        .collect();
+    final_cache.retain_sources(&live_sources);
+    if cache_mode == CacheMode::Disabled {
+        // The run started from an empty manifest, so the previous one is
+        // the only record of outputs that are no longer produced.
+        pruned += CacheManifest::load_from(&cache_file).prune(&live_paths, output_dir);
+    }
+    final_cache.save_to(&cache_file)?;
 
     if let Some(ref tx) = progress
         && pruned > 0
@@ -834,17 +1353,215 @@ pub fn process_with_backend(
             config: input.config,
             canonical_images,
         },
-        cache_stats: final_stats,
+        cache_stats: stats,
+        album_stats,
         source_hash_stats,
+        size_stats,
     })
 }
 
+/// Copy the album's non-image files (scan lists those matching
+/// `album_file_extensions` in `support_files`) from its source directory
+/// next to its processed images. Generate copies `output_dir` into the
+/// site, so a description linking `prices.pdf` resolves on the album page.
+fn copy_album_files(
+    album: &InputAlbum,
+    source_root: &Path,
+    output_dir: &Path,
+) -> std::io::Result<()> {
+    let source_dir = source_root.join(&album.source_dir);
+    let dest_dir = output_dir.join(&album.path);
+    for name in album_files(&album.support_files, &album.config) {
+        std::fs::create_dir_all(&dest_dir)?;
+        std::fs::copy(source_dir.join(name), dest_dir.join(name))?;
+    }
+    Ok(())
+}
+
+/// The entries of `support_files` that are published with the album.
+fn album_files<'a>(
+    support_files: &'a [String],
+    config: &'a SiteConfig,
+) -> impl Iterator<Item = &'a String> {
+    support_files
+        .iter()
+        .filter(|name| config.is_album_file(name))
+}
+
+/// Delete files [`copy_album_files`] left in `output_dir` on an earlier run
+/// that no album publishes any more. Processed images and prescale scratch
+/// copies are left to the cache, and `.json` manifests are kept. Returns
+/// the number of files removed.
+fn remove_stale_album_files(
+    output_dir: &Path,
+    live_paths: &std::collections::HashSet<String>,
+) -> std::io::Result<u32> {
+    let mut stale = Vec::new();
+    // Album files sit inside album directories, never at the root.
+    for entry in walkdir::WalkDir::new(output_dir).min_depth(2) {
+        let entry = entry.map_err(std::io::Error::other)?;
+        let name = entry.file_name().to_string_lossy();
+        if !entry.file_type().is_file()
+            || name.ends_with(".avif")
+            || name.ends_with(".prescaled.png")
+            || name.ends_with(".json")
+        {
+            continue;
+        }
+        let relative = entry
+            .path()
+            .strip_prefix(output_dir)
+            .unwrap()
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        if !live_paths.contains(&relative) {
+            stale.push(entry.path().to_path_buf());
+        }
+    }
+    for path in &stale {
+        std::fs::remove_file(path)?;
+        cache::remove_empty_parents(path, output_dir);
+    }
+    Ok(stale.len() as u32)
+}
+
+/// Relative paths of every output referenced by `albums`: processed
+/// images and copied album files.
+fn live_output_paths(albums: &[OutputAlbum]) -> std::collections::HashSet<String> {
+    albums
+        .iter()
+        .flat_map(|album| {
+            let image_paths = album.images.iter().flat_map(|img| {
+                let mut paths: Vec<String> =
+                    img.generated.values().map(|v| v.avif.clone()).collect();
+                paths.push(img.thumbnail.clone());
+                if let Some(ref thumb_2x) = img.thumbnail_2x {
+                    paths.push(thumb_2x.clone());
+                }
+                if let Some(ref fi) = img.full_index_thumbnail {
+                    paths.push(fi.clone());
+                }
+                paths
+            });
+            let files = album_files(&album.support_files, &album.config)
+                .map(|name| format!("{}/{}", album.path, name));
+            std::iter::once(album.thumbnail.clone())
+                .chain(album.card_thumbnail.clone())
+                .chain(image_paths)
+                .chain(files)
+        })
+        .collect()
+}
+
+/// Delete processed images in `output_dir` that `manifest` doesn't reference.
+///
+/// Processing already prunes outputs the cache manifest tracked; this also
+/// sweeps files it never tracked (from older builds or interrupted runs).
+/// Opt-in via `--prune-untracked`. Returns the number of files removed.
+pub fn remove_untracked_outputs(
+    output_dir: &Path,
+    manifest: &OutputManifest,
+) -> std::io::Result<u32> {
+    cache::remove_untracked(output_dir, &live_output_paths(&manifest.albums))
+}
+
+/// Where backend operations for one image read their pixels from.
+///
+/// Normally the source file itself. When the source's longest edge exceeds
+/// `[processing] max_dimension`, the first operation that needs pixels
+/// writes a downscaled lossless PNG next to the outputs and every later
+/// operation reads that instead, so the full-resolution image is decoded
+/// once rather than once per variant. The copy is removed on drop. Cache
+/// hits never ask for pixels, so a fully cached image is not downscaled.
+struct PixelSource<'a> {
+    source: &'a Path,
+    dimensions: (u32, u32),
+    /// The downscale to perform on first use, if the source is oversized.
+    prescale: Option<ResizeParams>,
+    prescaled: std::cell::Cell<bool>,
+}
+
+impl<'a> PixelSource<'a> {
+    fn new(
+        source: &'a Path,
+        dimensions: (u32, u32),
+        max_dimension: Option<u32>,
+        scratch: PathBuf,
+    ) -> Self {
+        use crate::imaging::calculations::calculate_responsive_sizes;
+
+        let prescale = max_dimension
+            .filter(|&max| dimensions.0.max(dimensions.1) > max)
+            .map(|max| {
+                let size =
+                    &calculate_responsive_sizes(dimensions, &ImageSizes::Explicit(vec![max]))[0];
+                ResizeParams {
+                    source: source.to_path_buf(),
+                    output: scratch,
+                    width: size.width,
+                    height: size.height,
+                    quality: Quality::new(100),
+                }
+            });
+        Self {
+            source,
+            dimensions,
+            prescale,
+            prescaled: std::cell::Cell::new(false),
+        }
+    }
+
+    /// Dimensions of the pixels [`path`](Self::path) yields: the downscaled
+    /// copy's when the source is oversized, the source's otherwise.
+    fn dimensions(&self) -> (u32, u32) {
+        self.prescale
+            .as_ref()
+            .map_or(self.dimensions, |p| (p.width, p.height))
+    }
+
+    /// Path to read pixels from, downscaling first if needed.
+    fn path(&self, backend: &impl ImageBackend) -> Result<&Path, ProcessError> {
+        match &self.prescale {
+            None => Ok(self.source),
+            Some(params) => {
+                if !self.prescaled.get() {
+                    backend.resize(params)?;
+                    self.prescaled.set(true);
+                }
+                Ok(&params.output)
+            }
+        }
+    }
+
+    /// Dimensions of the downscaled copy, if one was made.
+    fn downscaled_to(&self) -> Option<(u32, u32)> {
+        self.prescale
+            .as_ref()
+            .filter(|_| self.prescaled.get())
+            .map(|p| (p.width, p.height))
+    }
+}
+
+impl Drop for PixelSource<'_> {
+    fn drop(&mut self) {
+        if let Some(params) = &self.prescale
+            && self.prescaled.get()
+        {
+            let _ = std::fs::remove_file(&params.output);
+        }
+    }
+}
+
 /// Shared cache state passed to per-image encoding functions.
 struct CacheContext<'a> {
     source_hash: &'a str,
     cache: &'a Mutex<CacheManifest>,
     stats: &'a Mutex<CacheStats>,
     cache_root: &'a Path,
+    /// Check hits against their recorded output hash (`--verify-cache`).
+    verify: bool,
 }
 
 /// Result of checking the content-based cache.
@@ -874,10 +1591,22 @@ fn check_cache_and_copy(
     ctx: &CacheContext<'_>,
 ) -> CacheLookup {
     let mut cache = ctx.cache.lock().unwrap();
-    let cached_path = cache.find_cached(source_hash, params_hash, ctx.cache_root);
+    let cached_path = if ctx.verify {
+        cache.find_verified(source_hash, params_hash, ctx.cache_root)
+    } else {
+        cache.find_cached(source_hash, params_hash, ctx.cache_root)
+    };
 
     match cached_path {
-        Some(ref stored) if stored == expected_path => CacheLookup::ExactHit,
+        Some(ref stored) if stored == expected_path => {
+            // Backfill entries written before output hashes were recorded.
+            if cache.entries[stored].output_hash.is_none()
+                && let Ok(hash) = cache::hash_file(&ctx.cache_root.join(stored))
+            {
+                cache.set_output_hash(stored, hash);
+            }
+            CacheLookup::ExactHit
+        }
         Some(ref stored) => {
             let old_file = ctx.cache_root.join(stored);
             let new_file = ctx.cache_root.join(expected_path);
@@ -886,11 +1615,15 @@ fn check_cache_and_copy(
             }
             match std::fs::copy(&old_file, &new_file) {
                 Ok(_) => {
+                    let output_hash = cache.entries[stored].output_hash.clone();
                     cache.insert(
                         expected_path.to_string(),
                         source_hash.to_string(),
                         params_hash.to_string(),
                     );
+                    if let Some(hash) = output_hash {
+                        cache.set_output_hash(expected_path, hash);
+                    }
                     CacheLookup::Copied
                 }
                 Err(_) => CacheLookup::Miss,
@@ -900,6 +1633,26 @@ fn check_cache_and_copy(
     }
 }
 
+/// Record a freshly encoded output in the cache, along with the hash of the
+/// file the backend wrote.
+fn record_encoded(
+    output_path: &Path,
+    relative_path: &str,
+    params_hash: String,
+    ctx: &CacheContext<'_>,
+) {
+    let output_hash = cache::hash_file(output_path).ok();
+    let mut cache = ctx.cache.lock().unwrap();
+    cache.insert(
+        relative_path.to_string(),
+        ctx.source_hash.to_string(),
+        params_hash,
+    );
+    if let Some(hash) = output_hash {
+        cache.set_output_hash(relative_path, hash);
+    }
+}
+
 /// Create responsive images with cache awareness.
 ///
 /// For each variant, checks the cache before encoding. On a cache hit the
@@ -907,7 +1660,7 @@ fn check_cache_and_copy(
 /// album was renamed) and no backend call is made.
 fn create_responsive_images_cached(
     backend: &impl ImageBackend,
-    source: &Path,
+    source: &PixelSource<'_>,
     output_dir: &Path,
     filename_stem: &str,
     original_dims: (u32, u32),
@@ -947,18 +1700,14 @@ fn create_responsive_images_cached(
             }
             CacheLookup::Miss => {
                 let avif_path = output_dir.join(&avif_name);
-                backend.resize(&crate::imaging::params::ResizeParams {
-                    source: source.to_path_buf(),
-                    output: avif_path,
+                backend.resize(&ResizeParams {
+                    source: source.path(backend)?.to_path_buf(),
+                    output: avif_path.clone(),
                     width: size.width,
                     height: size.height,
                     quality: config.quality,
                 })?;
-                ctx.cache.lock().unwrap().insert(
-                    relative_path.clone(),
-                    ctx.source_hash.to_string(),
-                    params_hash,
-                );
+                record_encoded(&avif_path, &relative_path, params_hash, ctx);
                 ctx.stats.lock().unwrap().miss();
             }
         }
@@ -978,9 +1727,10 @@ fn create_responsive_images_cached(
 /// Create a thumbnail with cache awareness.
 fn create_thumbnail_cached(
     backend: &impl ImageBackend,
-    source: &Path,
+    source: &PixelSource<'_>,
     output_dir: &Path,
     filename_stem: &str,
+    original_dims: (u32, u32),
     config: &ThumbnailConfig,
     ctx: &CacheContext<'_>,
 ) -> Result<(String, VariantStatus), ProcessError> {
@@ -991,6 +1741,7 @@ fn create_thumbnail_cached(
         filename_stem,
         "thumb",
         "",
+        original_dims,
         config,
         ctx,
     )
@@ -1008,11 +1759,12 @@ fn create_thumbnail_cached(
 #[allow(clippy::too_many_arguments)]
 fn create_thumbnail_cached_with_suffix(
     backend: &impl ImageBackend,
-    source: &Path,
+    source: &PixelSource<'_>,
     output_dir: &Path,
     filename_stem: &str,
     suffix: &str,
     variant_tag: &str,
+    original_dims: (u32, u32),
     config: &ThumbnailConfig,
     ctx: &CacheContext<'_>,
 ) -> Result<(String, VariantStatus), ProcessError> {
@@ -1024,12 +1776,16 @@ fn create_thumbnail_cached_with_suffix(
         .unwrap();
     let relative_path = format!("{}/{}", relative_dir, thumb_name);
 
+    // The resolved per-image aspect, so switching `aspect_mode` re-crops
+    // only the thumbnails whose shape actually changes.
+    let aspect = thumbnail_aspect(config.aspect_mode, config.aspect, original_dims);
     let sharpening_tuple = config.sharpening.map(|s| (s.sigma, s.threshold));
     let params_hash = cache::hash_thumbnail_variant_params(
-        config.aspect,
+        aspect,
         config.short_edge,
         config.quality.value(),
         sharpening_tuple,
+        config.focal,
         variant_tag,
     );
 
@@ -1043,13 +1799,14 @@ fn create_thumbnail_cached_with_suffix(
         }
         CacheLookup::Miss => {
             let thumb_path = output_dir.join(&thumb_name);
-            let params = crate::imaging::operations::plan_thumbnail(source, &thumb_path, config);
-            backend.thumbnail(&params)?;
-            ctx.cache.lock().unwrap().insert(
-                relative_path.clone(),
-                ctx.source_hash.to_string(),
-                params_hash,
+            let params = crate::imaging::operations::plan_thumbnail(
+                source.path(backend)?,
+                &thumb_path,
+                original_dims,
+                config,
             );
+            backend.thumbnail(&params)?;
+            record_encoded(&thumb_path, &relative_path, params_hash, ctx);
             ctx.stats.lock().unwrap().miss();
         }
     }
@@ -1069,10 +1826,75 @@ mod tests {
     // =========================================================================
 
     #[test]
-    fn process_config_default_values() {
-        let config = ProcessConfig::default();
+    fn init_thread_pool_is_idempotent() {
+        // Other tests may have started rayon's default pool already, so
+        // only the consistency of repeated calls is asserted.
+        let first = init_thread_pool(2);
+        assert_eq!(init_thread_pool(2), first);
+
+        let actual = rayon::current_num_threads();
+        assert_eq!(init_thread_pool(actual), ThreadPoolInit::Ready(actual));
+        assert_eq!(
+            init_thread_pool(actual + 1),
+            ThreadPoolInit::Mismatch {
+                requested: actual + 1,
+                actual,
+            }
+        );
+    }
 
-        assert_eq!(config.sizes, vec![800, 1400, 2080]);
+    #[test]
+    fn size_stats_sums_unique_files() {
+        let tmp = TempDir::new().unwrap();
+        let src_a = tmp.path().join("a.jpg");
+        let src_b = tmp.path().join("b.jpg");
+        let out_a = tmp.path().join("a-800.avif");
+        let out_thumb = tmp.path().join("a-thumb.avif");
+        fs::write(&src_a, vec![0u8; 3000]).unwrap();
+        fs::write(&src_b, vec![0u8; 1000]).unwrap();
+        fs::write(&out_a, vec![0u8; 700]).unwrap();
+        fs::write(&out_thumb, vec![0u8; 100]).unwrap();
+
+        let stats = SizeStats::measure(
+            // Duplicate and missing paths are ignored
+            [src_a.clone(), src_b, src_a, tmp.path().join("missing.jpg")],
+            [out_a, out_thumb],
+        );
+
+        assert_eq!(
+            stats,
+            SizeStats {
+                source_bytes: 4000,
+                output_bytes: 800,
+            }
+        );
+        assert_eq!(stats.ratio(), Some(0.2));
+        assert_eq!(
+            stats.to_string(),
+            "800 B output from 4.0 KB originals (20.0%)"
+        );
+    }
+
+    #[test]
+    fn size_stats_without_sources_has_no_ratio() {
+        let stats = SizeStats::default();
+        assert_eq!(stats.ratio(), None);
+        assert_eq!(stats.to_string(), "0 B output from 0 B originals");
+    }
+
+    #[test]
+    fn format_bytes_units() {
+        assert_eq!(format_bytes(999), "999 B");
+        assert_eq!(format_bytes(1_500), "1.5 KB");
+        assert_eq!(format_bytes(12_345_678), "12.3 MB");
+        assert_eq!(format_bytes(2_000_000_000), "2.0 GB");
+    }
+
+    #[test]
+    fn process_config_default_values() {
+        let config = ProcessConfig::default();
+
+        assert_eq!(config.sizes, ImageSizes::Explicit(vec![800, 1400, 2080]));
         assert_eq!(config.quality, 90);
         assert_eq!(config.thumbnail_aspect, (4, 5));
         assert_eq!(config.thumbnail_size, 400);
@@ -1081,13 +1903,15 @@ mod tests {
     #[test]
     fn process_config_custom_values() {
         let config = ProcessConfig {
-            sizes: vec![100, 200],
+            sizes: ImageSizes::Explicit(vec![100, 200]),
             quality: 85,
             thumbnail_aspect: (1, 1),
             thumbnail_size: 150,
+            thumbnail_sharpen: false,
+            thumbnail_aspect_mode: AspectMode::Fixed,
         };
 
-        assert_eq!(config.sizes, vec![100, 200]);
+        assert_eq!(config.sizes, ImageSizes::Explicit(vec![100, 200]));
         assert_eq!(config.quality, 85);
         assert_eq!(config.thumbnail_aspect, (1, 1));
         assert_eq!(config.thumbnail_size, 150);
@@ -1192,6 +2016,7 @@ mod tests {
                 "path": "test-album",
                 "title": "Test Album",
                 "description": null,
+                "source_dir": "test-album",
                 "preview_image": "test-album/001-test.jpg",
                 "images": [{{
                     "number": 1,
@@ -1242,7 +2067,8 @@ mod tests {
             &manifest_path,
             &source_dir,
             &output_dir,
-            false,
+            CacheMode::Disabled,
+            None,
             None,
         )
         .unwrap();
@@ -1257,6 +2083,91 @@ mod tests {
         assert!(!image.thumbnail.is_empty());
     }
 
+    #[test]
+    fn process_copies_album_files() {
+        let tmp = TempDir::new().unwrap();
+        let source_dir = tmp.path().join("source");
+        let output_dir = tmp.path().join("output");
+        create_dummy_source(&source_dir.join("test-album/001-test.jpg"));
+        fs::write(source_dir.join("test-album/prices.pdf"), "%PDF").unwrap();
+
+        let manifest_path = create_test_manifest(tmp.path());
+        let manifest = fs::read_to_string(&manifest_path).unwrap().replace(
+            r#""in_nav": true,"#,
+            r#""in_nav": true, "support_files": ["config.toml", "prices.pdf"],"#,
+        );
+        fs::write(&manifest_path, manifest).unwrap();
+
+        let backend = MockBackend::with_dimensions(vec![Dimensions {
+            width: 200,
+            height: 250,
+        }]);
+        process_with_backend(
+            &backend,
+            &manifest_path,
+            &source_dir,
+            &output_dir,
+            CacheMode::Disabled,
+            None,
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(
+            fs::read_to_string(output_dir.join("test-album/prices.pdf")).unwrap(),
+            "%PDF"
+        );
+        assert!(!output_dir.join("test-album/config.toml").exists());
+    }
+
+    #[test]
+    fn process_removes_album_files_no_longer_published() {
+        let tmp = TempDir::new().unwrap();
+        let source_dir = tmp.path().join("source");
+        let output_dir = tmp.path().join("output");
+        create_dummy_source(&source_dir.join("010-test-album/001-test.jpg"));
+        fs::write(source_dir.join("010-test-album/prices.pdf"), "%PDF").unwrap();
+        fs::create_dir_all(output_dir.join("test-album")).unwrap();
+        fs::write(output_dir.join("test-album/old-prices.pdf"), "%PDF").unwrap();
+
+        // The source directory keeps its number prefix; the output path doesn't.
+        let manifest_path = create_test_manifest(tmp.path());
+        let manifest = fs::read_to_string(&manifest_path)
+            .unwrap()
+            .replace(
+                r#""source_dir": "test-album""#,
+                r#""source_dir": "010-test-album""#,
+            )
+            .replace(
+                r#""test-album/001-test.jpg""#,
+                r#""010-test-album/001-test.jpg""#,
+            )
+            .replace(
+                r#""in_nav": true,"#,
+                r#""in_nav": true, "support_files": ["prices.pdf"],"#,
+            );
+        fs::write(&manifest_path, manifest).unwrap();
+
+        let backend = MockBackend::with_dimensions(vec![Dimensions {
+            width: 200,
+            height: 250,
+        }]);
+        process_with_backend(
+            &backend,
+            &manifest_path,
+            &source_dir,
+            &output_dir,
+            CacheMode::Disabled,
+            None,
+            None,
+        )
+        .unwrap();
+
+        assert!(output_dir.join("test-album/prices.pdf").exists());
+        assert!(!output_dir.join("test-album/old-prices.pdf").exists());
+        assert!(output_dir.join(".cache-manifest.json").exists());
+    }
+
     #[test]
     fn process_with_mock_records_correct_operations() {
         let tmp = TempDir::new().unwrap();
@@ -1283,7 +2194,8 @@ mod tests {
             &manifest_path,
             &source_dir,
             &output_dir,
-            false,
+            CacheMode::Disabled,
+            None,
             None,
         )
         .unwrap();
@@ -1309,6 +2221,52 @@ mod tests {
         assert!(matches!(&ops[4], RecordedOp::Thumbnail { .. }));
     }
 
+    #[test]
+    fn process_with_auto_sizes_uses_source_ladder() {
+        let tmp = TempDir::new().unwrap();
+        let source_dir = tmp.path().join("source");
+        let output_dir = tmp.path().join("output");
+
+        let image_path = source_dir.join("test-album/001-test.jpg");
+        create_dummy_source(&image_path);
+
+        let manifest_path =
+            create_test_manifest_with_config(tmp.path(), r#"{"images": {"sizes": "auto"}}"#);
+
+        let backend = MockBackend::with_dimensions(vec![Dimensions {
+            width: 3000,
+            height: 2000,
+        }]);
+
+        let result = process_with_backend(
+            &backend,
+            &manifest_path,
+            &source_dir,
+            &output_dir,
+            CacheMode::Disabled,
+            None,
+            None,
+        )
+        .unwrap();
+
+        use crate::imaging::backend::tests::RecordedOp;
+        let widths: Vec<(u32, u32)> = backend
+            .get_operations()
+            .iter()
+            .filter_map(|op| match op {
+                RecordedOp::Resize { width, height, .. } => Some((*width, *height)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(widths, vec![(640, 427), (1280, 853), (2560, 1707)]);
+
+        let generated = &result.manifest.albums[0].images[0].generated;
+        assert_eq!(
+            generated.keys().cloned().collect::<Vec<_>>(),
+            ["1280", "2560", "640"]
+        );
+    }
+
     #[test]
     fn process_with_mock_skips_larger_sizes() {
         let tmp = TempDir::new().unwrap();
@@ -1335,7 +2293,8 @@ mod tests {
             &manifest_path,
             &source_dir,
             &output_dir,
-            false,
+            CacheMode::Disabled,
+            None,
             None,
         )
         .unwrap();
@@ -1346,6 +2305,264 @@ mod tests {
         assert!(image.generated.contains_key("500"));
     }
 
+    #[test]
+    fn process_reports_sizes_skipped_for_small_source() {
+        let tmp = TempDir::new().unwrap();
+        let source_dir = tmp.path().join("source");
+        let output_dir = tmp.path().join("output");
+
+        let image_path = source_dir.join("test-album/001-test.jpg");
+        create_dummy_source(&image_path);
+
+        let manifest_path = create_test_manifest_with_config(
+            tmp.path(),
+            r#"{"images": {"sizes": [400, 800, 1400]}}"#,
+        );
+
+        let backend = MockBackend::with_dimensions(vec![Dimensions {
+            width: 500,
+            height: 400,
+        }]);
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        process_with_backend(
+            &backend,
+            &manifest_path,
+            &source_dir,
+            &output_dir,
+            CacheMode::Disabled,
+            None,
+            Some(tx),
+        )
+        .unwrap();
+
+        let skipped: Vec<_> = rx
+            .try_iter()
+            .filter_map(|e| match e {
+                ProcessEvent::SizesSkipped {
+                    source_path,
+                    dimensions,
+                    skipped,
+                } => Some((source_path, dimensions, skipped)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            skipped,
+            vec![(
+                "test-album/001-test.jpg".to_string(),
+                (500, 400),
+                vec![800, 1400]
+            )]
+        );
+    }
+
+    #[test]
+    fn process_warns_about_sources_below_min_dimension() {
+        let tmp = TempDir::new().unwrap();
+        let source_dir = tmp.path().join("source");
+        create_dummy_source(&source_dir.join("test-album/001-test.jpg"));
+
+        let too_small = |width: u32, height: u32| {
+            // Fresh output per run, so no dimensions come from the cache.
+            let output_dir = tmp.path().join(format!("output-{width}x{height}"));
+            let manifest_path = create_test_manifest_with_config(
+                tmp.path(),
+                r#"{"images": {"min_dimension": 600}}"#,
+            );
+            let backend = MockBackend::with_dimensions(vec![Dimensions { width, height }]);
+            let (tx, rx) = std::sync::mpsc::channel();
+            process_with_backend(
+                &backend,
+                &manifest_path,
+                &source_dir,
+                &output_dir,
+                CacheMode::Disabled,
+                None,
+                Some(tx),
+            )
+            .unwrap();
+            rx.try_iter()
+                .filter_map(|e| match e {
+                    ProcessEvent::SourceTooSmall {
+                        source_path,
+                        dimensions,
+                        min_dimension,
+                    } => Some((source_path, dimensions, min_dimension)),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            too_small(300, 200),
+            vec![("test-album/001-test.jpg".to_string(), (300, 200), 600)]
+        );
+        // The longer edge counts: a 400px-tall panorama is fine.
+        assert!(too_small(1600, 400).is_empty());
+    }
+
+    #[test]
+    fn process_downscales_sources_above_max_dimension() {
+        let tmp = TempDir::new().unwrap();
+        let source_dir = tmp.path().join("source");
+        let output_dir = tmp.path().join("output");
+
+        let image_path = source_dir.join("test-album/001-test.jpg");
+        create_dummy_source(&image_path);
+
+        let manifest_path = create_test_manifest_with_config(
+            tmp.path(),
+            r#"{"images": {"sizes": [800, 1400]}, "processing": {"max_dimension": 4000}}"#,
+        );
+
+        // ~100MP panorama
+        let backend = MockBackend::with_dimensions(vec![Dimensions {
+            width: 20000,
+            height: 5000,
+        }]);
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        let result = process_with_backend(
+            &backend,
+            &manifest_path,
+            &source_dir,
+            &output_dir,
+            CacheMode::Disabled,
+            None,
+            Some(tx),
+        )
+        .unwrap();
+
+        use crate::imaging::backend::tests::RecordedOp;
+        let ops = backend.get_operations();
+
+        // identify + read_metadata + prescale + 2 resizes + 1 thumbnail
+        assert_eq!(ops.len(), 6);
+        let prescaled = match &ops[2] {
+            RecordedOp::Resize {
+                source,
+                output,
+                width: 4000,
+                height: 1000,
+                ..
+            } => {
+                assert!(source.ends_with("001-test.jpg"));
+                assert!(output.ends_with(".prescaled.png"));
+                output.clone()
+            }
+            other => panic!("expected prescale resize, got {other:?}"),
+        };
+
+        // Every output reads the downscaled copy, never the original
+        for op in &ops[3..] {
+            match op {
+                RecordedOp::Resize { source, .. } | RecordedOp::Thumbnail { source, .. } => {
+                    assert_eq!(source, &prescaled)
+                }
+                other => panic!("unexpected op {other:?}"),
+            }
+        }
+
+        // Reported dimensions stay those of the original
+        let image = &result.manifest.albums[0].images[0];
+        assert_eq!(image.dimensions, (20000, 5000));
+        assert!(!Path::new(&prescaled).exists());
+
+        let downscaled: Vec<_> = rx
+            .try_iter()
+            .filter_map(|e| match e {
+                ProcessEvent::SourceDownscaled { from, to, .. } => Some((from, to)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(downscaled, vec![((20000, 5000), (4000, 1000))]);
+    }
+
+    #[test]
+    fn auto_sizes_follow_the_downscaled_copy() {
+        let tmp = TempDir::new().unwrap();
+        let source_dir = tmp.path().join("source");
+        create_dummy_source(&source_dir.join("test-album/001-test.jpg"));
+        let manifest_path = create_test_manifest_with_config(
+            tmp.path(),
+            r#"{"images": {"sizes": "auto"}, "processing": {"max_dimension": 1000}}"#,
+        );
+        let backend = MockBackend::with_dimensions(vec![Dimensions {
+            width: 6000,
+            height: 4000,
+        }]);
+
+        process_with_backend(
+            &backend,
+            &manifest_path,
+            &source_dir,
+            &tmp.path().join("output"),
+            CacheMode::Disabled,
+            None,
+            None,
+        )
+        .unwrap();
+
+        use crate::imaging::backend::tests::RecordedOp;
+        let variants: Vec<_> = backend
+            .get_operations()
+            .into_iter()
+            .filter_map(|op| match op {
+                RecordedOp::Resize {
+                    output,
+                    width,
+                    height,
+                    ..
+                } if output.ends_with(".avif") => Some((width, height)),
+                _ => None,
+            })
+            .collect();
+        // The 1000px copy yields 500 and 1000, not the 640/1280/2560
+        // ladder of the 6000px original.
+        assert_eq!(variants, vec![(500, 334), (1000, 667)]);
+    }
+
+    #[test]
+    fn process_leaves_sources_within_max_dimension_alone() {
+        let tmp = TempDir::new().unwrap();
+        let source_dir = tmp.path().join("source");
+        let output_dir = tmp.path().join("output");
+
+        let image_path = source_dir.join("test-album/001-test.jpg");
+        create_dummy_source(&image_path);
+
+        let manifest_path = create_test_manifest_with_config(
+            tmp.path(),
+            r#"{"images": {"sizes": [800]}, "processing": {"max_dimension": 4000}}"#,
+        );
+
+        let backend = MockBackend::with_dimensions(vec![Dimensions {
+            width: 4000,
+            height: 3000,
+        }]);
+
+        process_with_backend(
+            &backend,
+            &manifest_path,
+            &source_dir,
+            &output_dir,
+            CacheMode::Disabled,
+            None,
+            None,
+        )
+        .unwrap();
+
+        use crate::imaging::backend::tests::RecordedOp;
+        let ops = backend.get_operations();
+        assert_eq!(ops.len(), 4);
+        assert!(ops.iter().all(|op| match op {
+            RecordedOp::Resize { source, .. } | RecordedOp::Thumbnail { source, .. } =>
+                source.ends_with("001-test.jpg"),
+            _ => true,
+        }));
+    }
+
     #[test]
     fn full_index_thumbnail_cache_does_not_collide_with_regular_thumbnail() {
         // Regression: when `[full_index]` and `[thumbnails]` share the same
@@ -1400,7 +2617,8 @@ mod tests {
             &manifest_path,
             &source_dir,
             &output_dir,
-            true,
+            CacheMode::Enabled,
+            None,
             None,
         )
         .unwrap();
@@ -1425,6 +2643,245 @@ mod tests {
         );
     }
 
+    #[test]
+    fn aspect_mode_shapes_each_thumbnail() {
+        use crate::imaging::backend::tests::RecordedOp;
+
+        let thumb_size = |mode: &str, width: u32, height: u32| {
+            let tmp = TempDir::new().unwrap();
+            let source_dir = tmp.path().join("source");
+            create_dummy_source(&source_dir.join("test-album/001-test.jpg"));
+            let manifest_path = create_test_manifest_with_config(
+                tmp.path(),
+                &format!(r#"{{"thumbnails": {{"aspect_mode": "{mode}"}}}}"#),
+            );
+            let backend = MockBackend::with_dimensions(vec![Dimensions { width, height }]);
+            process_with_backend(
+                &backend,
+                &manifest_path,
+                &source_dir,
+                &tmp.path().join("output"),
+                CacheMode::Disabled,
+                None,
+                None,
+            )
+            .unwrap();
+            backend
+                .get_operations()
+                .into_iter()
+                .find_map(|op| match op {
+                    RecordedOp::Thumbnail {
+                        crop_width,
+                        crop_height,
+                        ..
+                    } => Some((crop_width, crop_height)),
+                    _ => None,
+                })
+                .unwrap()
+        };
+
+        // Default 4:5 ratio, 400px short edge.
+        assert_eq!(thumb_size("fixed", 2000, 1500), (400, 500));
+        assert_eq!(thumb_size("fixed", 1500, 2000), (400, 500));
+        assert_eq!(thumb_size("original", 2000, 1500), (533, 400));
+        assert_eq!(thumb_size("original", 1500, 2000), (400, 533));
+        assert_eq!(thumb_size("adaptive", 2000, 1500), (500, 400));
+        assert_eq!(thumb_size("adaptive", 1500, 2000), (400, 500));
+    }
+
+    #[test]
+    fn hidpi_generates_double_size_thumbnails() {
+        let tmp = TempDir::new().unwrap();
+        let source_dir = tmp.path().join("source");
+        let output_dir = tmp.path().join("output");
+        create_dummy_source(&source_dir.join("test-album/001-test.jpg"));
+        let manifest_path =
+            create_test_manifest_with_config(tmp.path(), r#"{"thumbnails": {"hidpi": true}}"#);
+        let backend = MockBackend::with_dimensions(vec![Dimensions {
+            width: 2000,
+            height: 1500,
+        }]);
+
+        let result = process_with_backend(
+            &backend,
+            &manifest_path,
+            &source_dir,
+            &output_dir,
+            CacheMode::Disabled,
+            None,
+            None,
+        )
+        .unwrap();
+
+        let album = &result.manifest.albums[0];
+        let image = &album.images[0];
+        assert_eq!(image.thumbnail, "test-album/001-test-thumb.avif");
+        assert_eq!(
+            image.thumbnail_2x.as_deref(),
+            Some("test-album/001-test-thumb-2x.avif")
+        );
+        assert_eq!(album.thumbnail_2x, image.thumbnail_2x);
+
+        use crate::imaging::backend::tests::RecordedOp;
+        let thumbs: Vec<_> = backend
+            .get_operations()
+            .into_iter()
+            .filter_map(|op| match op {
+                RecordedOp::Thumbnail {
+                    output,
+                    crop_width,
+                    crop_height,
+                    ..
+                } => Some((output, crop_width, crop_height)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(thumbs.len(), 2);
+        assert!(
+            thumbs
+                .iter()
+                .any(|(o, w, h)| o.ends_with("001-test-thumb.avif") && (*w, *h) == (400, 500))
+        );
+        assert!(
+            thumbs
+                .iter()
+                .any(|(o, w, h)| o.ends_with("001-test-thumb-2x.avif") && (*w, *h) == (800, 1000))
+        );
+    }
+
+    #[test]
+    fn thumb_designated_preview_gets_responsive_sizes() {
+        // A `thumb` image is an ordinary album image that also serves as the
+        // preview, so it gets every responsive size and its own page, not
+        // just a thumbnail.
+        let tmp = TempDir::new().unwrap();
+        let source_dir = tmp.path().join("source");
+        let output_dir = tmp.path().join("output");
+        create_dummy_source(&source_dir.join("test-album/001-first.jpg"));
+        create_dummy_source(&source_dir.join("test-album/002-thumb-detail.jpg"));
+
+        let manifest = r##"{
+            "navigation": [],
+            "albums": [{
+                "path": "test-album",
+                "title": "Test Album",
+                "description": null,
+                "preview_image": "test-album/002-thumb-detail.jpg",
+                "images": [
+                    {"number": 1, "source_path": "test-album/001-first.jpg", "filename": "001-first.jpg"},
+                    {"number": 2, "source_path": "test-album/002-thumb-detail.jpg", "filename": "002-thumb-detail.jpg"}
+                ],
+                "in_nav": true,
+                "config": {"images": {"sizes": [800, 1400]}}
+            }],
+            "config": {}
+        }"##;
+        let manifest_path = tmp.path().join("manifest.json");
+        fs::write(&manifest_path, manifest).unwrap();
+        let dims = Dimensions {
+            width: 2000,
+            height: 1500,
+        };
+        let backend = MockBackend::with_dimensions(vec![dims, dims]);
+
+        let result = process_with_backend(
+            &backend,
+            &manifest_path,
+            &source_dir,
+            &output_dir,
+            CacheMode::Disabled,
+            None,
+            None,
+        )
+        .unwrap();
+
+        let album = &result.manifest.albums[0];
+        assert_eq!(album.thumbnail, "test-album/002-thumb-detail-thumb.avif");
+        let thumb = &album.images[1];
+        assert_eq!(thumb.source_path, "test-album/002-thumb-detail.jpg");
+        assert_eq!(
+            thumb.generated.keys().collect::<Vec<_>>(),
+            ["1400", "800"],
+            "{:?}",
+            thumb.generated
+        );
+        assert_eq!(
+            thumb.generated["1400"].avif,
+            "test-album/002-thumb-detail-1400.avif"
+        );
+    }
+
+    #[test]
+    fn card_size_adds_a_card_thumbnail_for_the_preview_image() {
+        let tmp = TempDir::new().unwrap();
+        let source_dir = tmp.path().join("source");
+        let output_dir = tmp.path().join("output");
+        create_dummy_source(&source_dir.join("test-album/001-first.jpg"));
+        create_dummy_source(&source_dir.join("test-album/002-cover.jpg"));
+
+        let manifest = r##"{
+            "navigation": [],
+            "albums": [{
+                "path": "test-album",
+                "title": "Test Album",
+                "description": null,
+                "preview_image": "test-album/002-cover.jpg",
+                "images": [
+                    {"number": 1, "source_path": "test-album/001-first.jpg", "filename": "001-first.jpg"},
+                    {"number": 2, "source_path": "test-album/002-cover.jpg", "filename": "002-cover.jpg"}
+                ],
+                "in_nav": true,
+                "config": {"thumbnails": {"card_size": 600}}
+            }],
+            "config": {}
+        }"##;
+        let manifest_path = tmp.path().join("manifest.json");
+        fs::write(&manifest_path, manifest).unwrap();
+        let dims = Dimensions {
+            width: 2000,
+            height: 1500,
+        };
+        let backend = MockBackend::with_dimensions(vec![dims, dims]);
+
+        let result = process_with_backend(
+            &backend,
+            &manifest_path,
+            &source_dir,
+            &output_dir,
+            CacheMode::Disabled,
+            None,
+            None,
+        )
+        .unwrap();
+
+        let album = &result.manifest.albums[0];
+        assert_eq!(album.thumbnail, "test-album/002-cover-thumb.avif");
+        assert_eq!(
+            album.card_thumbnail.as_deref(),
+            Some("test-album/002-cover-card-thumb.avif")
+        );
+
+        use crate::imaging::backend::tests::RecordedOp;
+        let cards: Vec<_> = backend
+            .get_operations()
+            .into_iter()
+            .filter_map(|op| match op {
+                RecordedOp::Thumbnail {
+                    output,
+                    crop_width,
+                    crop_height,
+                    ..
+                } if output.ends_with("-card-thumb.avif") => {
+                    Some((output, crop_width, crop_height))
+                }
+                _ => None,
+            })
+            .collect();
+        assert_eq!(cards.len(), 1, "only the preview image gets a card");
+        assert!(cards[0].0.ends_with("002-cover-card-thumb.avif"));
+        assert_eq!((cards[0].1, cards[0].2), (600, 750));
+    }
+
     #[test]
     fn process_source_not_found_error() {
         let tmp = TempDir::new().unwrap();
@@ -1440,7 +2897,8 @@ mod tests {
             &manifest_path,
             &source_dir,
             &output_dir,
-            false,
+            CacheMode::Disabled,
+            None,
             None,
         );
 
@@ -1459,9 +2917,16 @@ mod tests {
         dims: Vec<Dimensions>,
     ) -> (Vec<crate::imaging::backend::tests::RecordedOp>, CacheStats) {
         let backend = MockBackend::with_dimensions(dims);
-        let result =
-            process_with_backend(&backend, manifest_path, source_dir, output_dir, true, None)
-                .unwrap();
+        let result = process_with_backend(
+            &backend,
+            manifest_path,
+            source_dir,
+            output_dir,
+            CacheMode::Enabled,
+            None,
+            None,
+        )
+        .unwrap();
         (backend.get_operations(), result.cache_stats)
     }
 
@@ -1523,7 +2988,236 @@ mod tests {
             .iter()
             .filter(|op| matches!(op, RecordedOp::Resize { .. } | RecordedOp::Thumbnail { .. }))
             .collect();
-        assert_eq!(encode_ops.len(), 0);
+        assert_eq!(encode_ops.len(), 0);
+    }
+
+    #[test]
+    fn custom_cache_file_is_used_across_runs() {
+        let tmp = TempDir::new().unwrap();
+        let source_dir = tmp.path().join("source");
+        let output_dir = tmp.path().join("output");
+        let cache_file = tmp.path().join("persisted/cache.json");
+        create_dummy_source(&source_dir.join("test-album/001-test.jpg"));
+        let manifest_path = create_test_manifest_with_config(
+            tmp.path(),
+            r#"{"images": {"sizes": [800, 1400], "quality": 85}}"#,
+        );
+        let run = || {
+            let backend = MockBackend::with_dimensions(vec![Dimensions {
+                width: 2000,
+                height: 1500,
+            }]);
+            process_with_backend(
+                &backend,
+                &manifest_path,
+                &source_dir,
+                &output_dir,
+                CacheMode::Enabled,
+                Some(&cache_file),
+                None,
+            )
+            .unwrap()
+            .cache_stats
+        };
+
+        let first = run();
+        assert_eq!(first.misses, 3);
+        assert!(cache_file.exists());
+        assert!(!cache::manifest_path(&output_dir).exists());
+
+        // Entries stay relative to the output dir, not the cache file.
+        let stored = cache::CacheManifest::load_from(&cache_file);
+        for entry in stored.entries.keys() {
+            let path = output_dir.join(entry);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(&path, "fake avif").unwrap();
+        }
+
+        let second = run();
+        assert_eq!(second.hits, 3);
+        assert_eq!(second.misses, 0);
+        assert!(!cache::manifest_path(&output_dir).exists());
+    }
+
+    #[test]
+    fn mtime_cache_mode_skips_hashing_unchanged_sources() {
+        let tmp = TempDir::new().unwrap();
+        let source_dir = tmp.path().join("source");
+        let output_dir = tmp.path().join("output");
+        let source = source_dir.join("test-album/001-test.jpg");
+        fs::create_dir_all(source.parent().unwrap()).unwrap();
+        fs::write(&source, "aaaa").unwrap();
+        let manifest_path =
+            create_test_manifest_with_config(tmp.path(), r#"{"images": {"sizes": [800]}}"#);
+        let base_manifest = fs::read_to_string(&manifest_path).unwrap();
+        let run = |detection: &str| {
+            let site_config =
+                format!(r#""config": {{"processing": {{"cache_mode": "{detection}"}}}}"#);
+            fs::write(
+                &manifest_path,
+                base_manifest.replace(r#""config": {}"#, &site_config),
+            )
+            .unwrap();
+            let backend = MockBackend::with_dimensions(vec![Dimensions {
+                width: 2000,
+                height: 1500,
+            }]);
+            process_with_backend(
+                &backend,
+                &manifest_path,
+                &source_dir,
+                &output_dir,
+                CacheMode::Enabled,
+                None,
+                None,
+            )
+            .unwrap()
+            .cache_stats
+        };
+
+        assert_eq!(run("mtime").misses, 2);
+        touch_cached_outputs(&output_dir);
+
+        // Same size, same mtime, different bytes: only a re-hash would
+        // notice, so a hit proves the source wasn't read.
+        let mtime = fs::metadata(&source).unwrap().modified().unwrap();
+        fs::write(&source, "bbbb").unwrap();
+        fs::File::options()
+            .write(true)
+            .open(&source)
+            .unwrap()
+            .set_modified(mtime)
+            .unwrap();
+        assert_eq!(run("mtime").hits, 2);
+        assert_eq!(run("hash").misses, 2, "hash mode reads the new bytes");
+        touch_cached_outputs(&output_dir);
+
+        // A size change always falls back to hashing.
+        fs::write(&source, "ccccc").unwrap();
+        fs::File::options()
+            .write(true)
+            .open(&source)
+            .unwrap()
+            .set_modified(mtime)
+            .unwrap();
+        assert_eq!(run("mtime").misses, 2);
+    }
+
+    #[test]
+    fn verify_cache_reencodes_damaged_output() {
+        let tmp = TempDir::new().unwrap();
+        let source_dir = tmp.path().join("source");
+        let output_dir = tmp.path().join("output");
+        create_dummy_source(&source_dir.join("test-album/001-test.jpg"));
+        let manifest_path = create_test_manifest_with_config(
+            tmp.path(),
+            r#"{"images": {"sizes": [800, 1400], "quality": 85}}"#,
+        );
+        let run = |mode| {
+            let backend = MockBackend::with_dimensions(vec![Dimensions {
+                width: 2000,
+                height: 1500,
+            }]);
+            let result = process_with_backend(
+                &backend,
+                &manifest_path,
+                &source_dir,
+                &output_dir,
+                mode,
+                None,
+                None,
+            )
+            .unwrap();
+            (backend.get_operations(), result.cache_stats)
+        };
+
+        run(CacheMode::Enabled);
+        touch_cached_outputs(&output_dir);
+        // A plain hit records the hash of the file already on disk
+        let (_, stats) = run(CacheMode::Enabled);
+        assert_eq!(stats.hits, 3);
+
+        fs::write(output_dir.join("test-album/001-test-800.avif"), "damaged").unwrap();
+        let (_, stats) = run(CacheMode::Enabled);
+        assert_eq!(stats.hits, 3, "without verify the damaged file is a hit");
+
+        let (ops, stats) = run(CacheMode::Verify);
+        assert_eq!(stats.hits, 2);
+        assert_eq!(stats.misses, 1);
+        use crate::imaging::backend::tests::RecordedOp;
+        let resizes: Vec<_> = ops
+            .iter()
+            .filter_map(|op| match op {
+                RecordedOp::Resize { width, .. } => Some(*width),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(resizes, vec![800]);
+    }
+
+    #[test]
+    fn album_stats_show_which_album_missed() {
+        let tmp = TempDir::new().unwrap();
+        let source_dir = tmp.path().join("source");
+        let output_dir = tmp.path().join("output");
+        for (album, content) in [("album-a", "a"), ("album-b", "b")] {
+            fs::create_dir_all(source_dir.join(album)).unwrap();
+            fs::write(source_dir.join(album).join("001-x.jpg"), content).unwrap();
+        }
+        let album = |path: &str, title: &str| {
+            format!(
+                r#"{{"path": "{path}", "title": "{title}", "description": null,
+                "preview_image": "{path}/001-x.jpg",
+                "images": [{{"number": 1, "source_path": "{path}/001-x.jpg", "filename": "001-x.jpg"}}],
+                "in_nav": true, "config": {{"images": {{"sizes": [800]}}}}}}"#
+            )
+        };
+        let manifest_path = tmp.path().join("manifest.json");
+        fs::write(
+            &manifest_path,
+            format!(
+                r#"{{"navigation": [], "albums": [{}, {}], "config": {{}}}}"#,
+                album("album-a", "Album A"),
+                album("album-b", "Album B")
+            ),
+        )
+        .unwrap();
+        let run = || {
+            let dims = Dimensions {
+                width: 2000,
+                height: 1500,
+            };
+            let backend = MockBackend::with_dimensions(vec![dims, dims]);
+            process_with_backend(
+                &backend,
+                &manifest_path,
+                &source_dir,
+                &output_dir,
+                CacheMode::Enabled,
+                None,
+                None,
+            )
+            .unwrap()
+        };
+
+        run();
+        touch_cached_outputs(&output_dir);
+        fs::write(source_dir.join("album-b/001-x.jpg"), "edited").unwrap();
+        let result = run();
+
+        let albums: Vec<_> = result
+            .album_stats
+            .iter()
+            .map(|a| (a.title.as_str(), a.cache.hits, a.cache.misses))
+            .collect();
+        // One 800px variant and one thumbnail per album
+        assert_eq!(albums, vec![("Album A", 2, 0), ("Album B", 0, 2)]);
+
+        let mut sum = CacheStats::default();
+        for album in &result.album_stats {
+            sum.merge(&album.cache);
+        }
+        assert_eq!(sum, result.cache_stats);
     }
 
     #[test]
@@ -1624,6 +3318,66 @@ mod tests {
         assert_eq!(stats2.hits, 0);
     }
 
+    #[test]
+    fn draft_outputs_not_served_to_full_build() {
+        let tmp = TempDir::new().unwrap();
+        let source_dir = tmp.path().join("source");
+        let output_dir = tmp.path().join("output");
+        create_dummy_source(&source_dir.join("test-album/001-test.jpg"));
+        let dims = || {
+            vec![Dimensions {
+                width: 2000,
+                height: 1500,
+            }]
+        };
+
+        // Draft run: the `--draft` preset on top of the album config
+        let mut draft = SiteConfig::default();
+        let overrides = crate::config::draft_layer();
+        draft.images.quality = overrides.images.quality.unwrap();
+        draft.images.sizes = overrides.images.sizes.clone().unwrap();
+        draft.thumbnails.sharpen = overrides.thumbnails.sharpen.unwrap();
+        let manifest_path =
+            create_test_manifest_with_config(tmp.path(), &serde_json::to_string(&draft).unwrap());
+        let (ops1, stats1) = run_cached(&source_dir, &output_dir, &manifest_path, dims());
+        assert_eq!(stats1.misses, 2); // one 800px variant + thumbnail
+
+        use crate::imaging::backend::tests::RecordedOp;
+        assert!(ops1.iter().any(|op| matches!(
+            op,
+            RecordedOp::Resize {
+                width: 800,
+                quality: 40,
+                ..
+            }
+        )));
+        assert!(
+            !ops1
+                .iter()
+                .any(|op| matches!(op, RecordedOp::Resize { width, .. } if *width > 800))
+        );
+        assert!(ops1.iter().any(|op| matches!(
+            op,
+            RecordedOp::Thumbnail {
+                sharpening: None,
+                ..
+            }
+        )));
+
+        for entry in cache::CacheManifest::load(&output_dir).entries.keys() {
+            let path = output_dir.join(entry);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(&path, "draft avif").unwrap();
+        }
+
+        // Full run at stock settings: nothing comes from the draft cache
+        let manifest_path = create_test_manifest_with_config(tmp.path(), "{}");
+        let (_ops2, stats2) = run_cached(&source_dir, &output_dir, &manifest_path, dims());
+        assert_eq!(stats2.hits, 0);
+        assert_eq!(stats2.copies, 0);
+        assert_eq!(stats2.misses, 4); // 800, 1400, 2080 + thumbnail
+    }
+
     #[test]
     fn no_cache_flag_forces_full_reprocess() {
         let tmp = TempDir::new().unwrap();
@@ -1654,7 +3408,7 @@ mod tests {
             fs::write(&path, "fake").unwrap();
         }
 
-        // Second run with use_cache=false (simulates --no-cache)
+        // Second run with CacheMode::Disabled (simulates --no-cache)
         let backend = MockBackend::with_dimensions(vec![Dimensions {
             width: 2000,
             height: 1500,
@@ -1664,7 +3418,8 @@ mod tests {
             &manifest_path,
             &source_dir,
             &output_dir,
-            false,
+            CacheMode::Disabled,
+            None,
             None,
         )
         .unwrap();
@@ -1736,7 +3491,8 @@ mod tests {
             &manifest_path2,
             &source_dir,
             &output_dir,
-            true,
+            CacheMode::Enabled,
+            None,
             None,
         )
         .unwrap();
@@ -1778,6 +3534,298 @@ mod tests {
         );
     }
 
+    /// Manifest with one album holding `filenames` (first is the preview),
+    /// written to `tmp/<name>`.
+    fn create_album_manifest(tmp: &Path, name: &str, filenames: &[&str]) -> PathBuf {
+        let images: Vec<String> = filenames
+            .iter()
+            .enumerate()
+            .map(|(i, f)| {
+                format!(
+                    r#"{{"number": {}, "source_path": "test-album/{f}", "filename": "{f}"}}"#,
+                    i + 1
+                )
+            })
+            .collect();
+        let manifest = format!(
+            r##"{{
+            "navigation": [],
+            "albums": [{{
+                "path": "test-album",
+                "title": "Test Album",
+                "description": null,
+                "preview_image": "test-album/{preview}",
+                "images": [{images}],
+                "in_nav": true,
+                "config": {{"images": {{"sizes": [800]}}}}
+            }}],
+            "config": {{}}
+        }}"##,
+            preview = filenames[0],
+            images = images.join(", "),
+        );
+        let manifest_path = tmp.join(name);
+        fs::write(&manifest_path, manifest).unwrap();
+        manifest_path
+    }
+
+    /// Write a placeholder file for every cache manifest entry (the mock
+    /// backend doesn't write outputs).
+    fn touch_cached_outputs(output_dir: &Path) {
+        for entry in cache::CacheManifest::load(output_dir).entries.keys() {
+            let path = output_dir.join(entry);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(&path, "fake avif").unwrap();
+        }
+    }
+
+    /// Two images with distinct content so they don't share cache entries.
+    fn create_two_sources(source_dir: &Path) {
+        fs::create_dir_all(source_dir.join("test-album")).unwrap();
+        fs::write(source_dir.join("test-album/001-a.jpg"), "a").unwrap();
+        fs::write(source_dir.join("test-album/002-b.jpg"), "b").unwrap();
+    }
+
+    #[test]
+    fn sidecar_quality_override_keys_cache_per_image() {
+        use crate::imaging::backend::tests::RecordedOp;
+
+        let tmp = TempDir::new().unwrap();
+        let source_dir = tmp.path().join("source");
+        let output_dir = tmp.path().join("output");
+        create_two_sources(&source_dir);
+
+        let manifest_path = tmp.path().join("manifest.json");
+        fs::write(
+            &manifest_path,
+            r##"{
+            "navigation": [],
+            "albums": [{
+                "path": "test-album",
+                "title": "Test Album",
+                "description": null,
+                "preview_image": "test-album/001-a.jpg",
+                "images": [
+                    {"number": 1, "source_path": "test-album/001-a.jpg", "filename": "001-a.jpg"},
+                    {"number": 2, "source_path": "test-album/002-b.jpg", "filename": "002-b.jpg",
+                     "quality": 95}
+                ],
+                "in_nav": true,
+                "config": {"images": {"sizes": [800], "quality": 80}}
+            }],
+            "config": {}
+        }"##,
+        )
+        .unwrap();
+
+        let (ops, stats) = run_cached(&source_dir, &output_dir, &manifest_path, dims(2));
+        assert_eq!(stats.misses, 4);
+        let resize_quality = |output_suffix: &str| {
+            ops.iter()
+                .find_map(|op| match op {
+                    RecordedOp::Resize {
+                        output, quality, ..
+                    } if output.ends_with(output_suffix) => Some(*quality),
+                    _ => None,
+                })
+                .unwrap()
+        };
+        assert_eq!(resize_quality("001-a-800.avif"), 80);
+        assert_eq!(resize_quality("002-b-800.avif"), 95);
+        // Thumbnails keep the album quality.
+        assert!(ops.iter().all(|op| !matches!(
+            op,
+            RecordedOp::Thumbnail { quality, .. } if *quality != 80
+        )));
+
+        let manifest = cache::CacheManifest::load(&output_dir);
+        let params = |path: &str| manifest.entries[path].params_hash.clone();
+        assert_eq!(
+            params("test-album/001-a-800.avif"),
+            cache::hash_responsive_params(800, 80)
+        );
+        assert_eq!(
+            params("test-album/002-b-800.avif"),
+            cache::hash_responsive_params(800, 95)
+        );
+
+        // Both entries hit on a rerun: the override doesn't disturb the
+        // neighbour's cache entry, nor its own.
+        touch_cached_outputs(&output_dir);
+        let (_ops, stats) = run_cached(&source_dir, &output_dir, &manifest_path, dims(2));
+        assert_eq!(stats.misses, 0);
+    }
+
+    #[test]
+    fn sidecar_focal_point_shifts_thumbnail_crop_and_cache_key() {
+        use crate::imaging::backend::tests::RecordedOp;
+
+        let tmp = TempDir::new().unwrap();
+        let source_dir = tmp.path().join("source");
+        let output_dir = tmp.path().join("output");
+        create_two_sources(&source_dir);
+        let write_manifest = |focal: &str| {
+            let manifest_path = tmp.path().join("manifest.json");
+            let json = r##"{
+            "navigation": [],
+            "albums": [{
+                "path": "test-album",
+                "title": "Test Album",
+                "description": null,
+                "preview_image": "test-album/001-a.jpg",
+                "images": [
+                    {"number": 1, "source_path": "test-album/001-a.jpg", "filename": "001-a.jpg"},
+                    {"number": 2, "source_path": "test-album/002-b.jpg", "filename": "002-b.jpg",
+                     "focal": FOCAL}
+                ],
+                "in_nav": true,
+                "config": {"images": {"sizes": [800]}}
+            }],
+            "config": {}
+        }"##;
+            fs::write(&manifest_path, json.replace("FOCAL", focal)).unwrap();
+            manifest_path
+        };
+
+        let manifest_path = write_manifest("[50, 30]");
+        let (ops, _stats) = run_cached(&source_dir, &output_dir, &manifest_path, dims(2));
+        let thumb_focal = |ops: &[RecordedOp], suffix: &str| {
+            ops.iter()
+                .find_map(|op| match op {
+                    RecordedOp::Thumbnail { output, focal, .. } if output.ends_with(suffix) => {
+                        Some(*focal)
+                    }
+                    _ => None,
+                })
+                .unwrap()
+        };
+        assert_eq!(thumb_focal(&ops, "001-a-thumb.avif"), None);
+        assert_eq!(thumb_focal(&ops, "002-b-thumb.avif"), Some((50, 30)));
+
+        let manifest = cache::CacheManifest::load(&output_dir);
+        assert_eq!(
+            manifest.entries["test-album/002-b-thumb.avif"].params_hash,
+            cache::hash_thumbnail_variant_params(
+                (4, 5),
+                400,
+                90,
+                Some((0.5, 0)),
+                Some((50, 30)),
+                ""
+            )
+        );
+
+        // Moving the focal point re-crops that thumbnail and nothing else.
+        touch_cached_outputs(&output_dir);
+        let manifest_path = write_manifest("[50, 60]");
+        let (ops, stats) = run_cached(&source_dir, &output_dir, &manifest_path, dims(2));
+        assert_eq!(stats.misses, 1);
+        assert_eq!(
+            ops.iter()
+                .filter(|op| matches!(op, RecordedOp::Thumbnail { .. }))
+                .count(),
+            1
+        );
+        assert_eq!(thumb_focal(&ops, "002-b-thumb.avif"), Some((50, 60)));
+    }
+
+    fn dims(n: usize) -> Vec<Dimensions> {
+        vec![
+            Dimensions {
+                width: 2000,
+                height: 1500,
+            };
+            n
+        ]
+    }
+
+    #[test]
+    fn cache_prunes_outputs_of_deleted_image() {
+        let tmp = TempDir::new().unwrap();
+        let source_dir = tmp.path().join("source");
+        let output_dir = tmp.path().join("output");
+        create_two_sources(&source_dir);
+
+        let both = create_album_manifest(tmp.path(), "m1.json", &["001-a.jpg", "002-b.jpg"]);
+        run_cached(&source_dir, &output_dir, &both, dims(2));
+        touch_cached_outputs(&output_dir);
+        assert!(output_dir.join("test-album/002-b-800.avif").exists());
+
+        // 002-b deleted from the content
+        let one = create_album_manifest(tmp.path(), "m2.json", &["001-a.jpg"]);
+        let (_ops, stats) = run_cached(&source_dir, &output_dir, &one, dims(1));
+        assert_eq!(stats.misses, 0);
+
+        let manifest = cache::CacheManifest::load(&output_dir);
+        for stale in ["test-album/002-b-800.avif", "test-album/002-b-thumb.avif"] {
+            assert!(!manifest.entries.contains_key(stale), "{stale}");
+            assert!(!output_dir.join(stale).exists(), "{stale}");
+        }
+        for live in ["test-album/001-a-800.avif", "test-album/001-a-thumb.avif"] {
+            assert!(manifest.entries.contains_key(live), "{live}");
+            assert!(output_dir.join(live).exists(), "{live}");
+        }
+    }
+
+    #[test]
+    fn no_cache_run_still_prunes_previous_outputs() {
+        let tmp = TempDir::new().unwrap();
+        let source_dir = tmp.path().join("source");
+        let output_dir = tmp.path().join("output");
+        create_two_sources(&source_dir);
+
+        let both = create_album_manifest(tmp.path(), "m1.json", &["001-a.jpg", "002-b.jpg"]);
+        run_cached(&source_dir, &output_dir, &both, dims(2));
+        touch_cached_outputs(&output_dir);
+
+        let one = create_album_manifest(tmp.path(), "m2.json", &["001-a.jpg"]);
+        let backend = MockBackend::with_dimensions(dims(1));
+        process_with_backend(
+            &backend,
+            &one,
+            &source_dir,
+            &output_dir,
+            CacheMode::Disabled,
+            None,
+            None,
+        )
+        .unwrap();
+
+        assert!(!output_dir.join("test-album/002-b-800.avif").exists());
+        assert!(!output_dir.join("test-album/002-b-thumb.avif").exists());
+    }
+
+    #[test]
+    fn remove_untracked_outputs_sweeps_files_missing_from_cache() {
+        let tmp = TempDir::new().unwrap();
+        let source_dir = tmp.path().join("source");
+        let output_dir = tmp.path().join("output");
+        create_two_sources(&source_dir);
+
+        let one = create_album_manifest(tmp.path(), "m1.json", &["001-a.jpg"]);
+        let backend = MockBackend::with_dimensions(dims(1));
+        let result = process_with_backend(
+            &backend,
+            &one,
+            &source_dir,
+            &output_dir,
+            CacheMode::Enabled,
+            None,
+            None,
+        )
+        .unwrap();
+        touch_cached_outputs(&output_dir);
+        // Never recorded in the cache manifest, so regular pruning misses it
+        fs::write(output_dir.join("test-album/stray-800.avif"), "old").unwrap();
+
+        let removed = remove_untracked_outputs(&output_dir, &result.manifest).unwrap();
+
+        assert_eq!(removed, 1);
+        assert!(!output_dir.join("test-album/stray-800.avif").exists());
+        assert!(output_dir.join("test-album/001-a-800.avif").exists());
+        assert!(output_dir.join("test-album/001-a-thumb.avif").exists());
+    }
+
     // =========================================================================
     // Phase 2: canonical-image hash memoization
     // =========================================================================
@@ -1861,7 +3909,8 @@ mod tests {
             &manifest_path,
             &source_dir,
             &output_dir,
-            false,
+            CacheMode::Disabled,
+            None,
             None,
         )
         .unwrap();
@@ -1903,7 +3952,8 @@ mod tests {
             &manifest_path,
             &source_dir,
             &output_dir,
-            false,
+            CacheMode::Disabled,
+            None,
             None,
         )
         .unwrap();
@@ -1980,7 +4030,8 @@ mod tests {
             &manifest_path,
             &source_dir,
             &output_dir,
-            false,
+            CacheMode::Disabled,
+            None,
             None,
         )
         .unwrap();
@@ -2061,7 +4112,8 @@ mod tests {
             &manifest_path,
             &source_dir,
             &output_dir,
-            false,
+            CacheMode::Disabled,
+            None,
             None,
         )
         .unwrap();
@@ -2118,7 +4170,8 @@ mod tests {
             &manifest_path,
             &source_dir,
             &output_dir,
-            false,
+            CacheMode::Disabled,
+            None,
             None,
         )
         .unwrap();
@@ -2131,6 +4184,153 @@ mod tests {
         assert_eq!(canonical.height, Some(3000));
     }
 
+    #[test]
+    fn iptc_title_bypasses_titleize() {
+        // `[naming] titleize` is applied by scan to the filename title only;
+        // an IPTC title must come through verbatim.
+        let tmp = TempDir::new().unwrap();
+        let source_dir = tmp.path().join("source");
+        let output_dir = tmp.path().join("output");
+        create_dummy_source(&source_dir.join("test-album/001-test.jpg"));
+
+        let manifest_path =
+            create_test_manifest_with_config(tmp.path(), r#"{"naming": {"titleize": true}}"#);
+        let backend = MockBackend::with_metadata(
+            vec![Dimensions {
+                width: 200,
+                height: 250,
+            }],
+            vec![ImageMetadata {
+                title: Some("golden_gate at dusk".to_string()),
+                description: None,
+                keywords: Vec::new(),
+            }],
+        );
+
+        let result = process_with_backend(
+            &backend,
+            &manifest_path,
+            &source_dir,
+            &output_dir,
+            CacheMode::Disabled,
+            None,
+            None,
+        )
+        .unwrap();
+
+        let image = &result.manifest.albums[0].images[0];
+        assert_eq!(image.title.as_deref(), Some("golden_gate at dusk"));
+    }
+
+    #[test]
+    fn album_description_falls_back_to_preview_iptc_caption() {
+        let tmp = TempDir::new().unwrap();
+        let source_dir = tmp.path().join("source");
+        let output_dir = tmp.path().join("output");
+        create_dummy_source(&source_dir.join("test-album/001-test.jpg"));
+
+        let run = |config: &str| {
+            let manifest_path = create_test_manifest_with_config(tmp.path(), config);
+            let backend = MockBackend::with_metadata(
+                vec![Dimensions {
+                    width: 200,
+                    height: 250,
+                }],
+                vec![ImageMetadata {
+                    title: None,
+                    description: Some("A statement piece.".to_string()),
+                    keywords: Vec::new(),
+                }],
+            );
+            process_with_backend(
+                &backend,
+                &manifest_path,
+                &source_dir,
+                &output_dir,
+                CacheMode::Disabled,
+                None,
+                None,
+            )
+            .unwrap()
+        };
+
+        let result = run(r#"{"albums": {"description_from_iptc": true}}"#);
+        assert_eq!(
+            result.manifest.albums[0].description.as_deref(),
+            Some("<p>A statement piece.</p>")
+        );
+        assert_eq!(
+            result.manifest.albums[0].description_text.as_deref(),
+            Some("A statement piece.")
+        );
+
+        let result = run("{}");
+        assert_eq!(result.manifest.albums[0].description, None);
+        assert_eq!(result.manifest.albums[0].description_text, None);
+    }
+
+    #[test]
+    fn metadata_sources_pick_title_and_description() {
+        let tmp = TempDir::new().unwrap();
+        let source_dir = tmp.path().join("source");
+        let output_dir = tmp.path().join("output");
+        create_dummy_source(&source_dir.join("test-album/001-test.jpg"));
+
+        let run = |config: &str| {
+            let manifest_path = create_test_manifest_with_config(tmp.path(), config);
+            let manifest = fs::read_to_string(&manifest_path).unwrap().replace(
+                r#""filename": "001-test.jpg""#,
+                r#""filename": "001-test.jpg", "slug": "001-test",
+                   "title": "Test", "description": "From the sidecar""#,
+            );
+            fs::write(&manifest_path, manifest).unwrap();
+            let backend = MockBackend::with_metadata(
+                vec![Dimensions {
+                    width: 200,
+                    height: 250,
+                }],
+                vec![ImageMetadata {
+                    title: Some("Harbor at Dawn".to_string()),
+                    description: Some("From IPTC".to_string()),
+                    keywords: Vec::new(),
+                }],
+            );
+            let result = process_with_backend(
+                &backend,
+                &manifest_path,
+                &source_dir,
+                &output_dir,
+                CacheMode::Disabled,
+                None,
+                None,
+            )
+            .unwrap();
+            let image = &result.manifest.albums[0].images[0];
+            (
+                image.title.clone().unwrap(),
+                image.description.clone().unwrap(),
+                image.slug.clone(),
+            )
+        };
+
+        assert_eq!(
+            run("{}"),
+            (
+                "Harbor at Dawn".to_string(),
+                "From the sidecar".to_string(),
+                "Harbor-at-Dawn".to_string()
+            )
+        );
+        assert_eq!(
+            run(r#"{"metadata": {"title_source": "filename", "description_source": "iptc"}}"#),
+            (
+                "Test".to_string(),
+                "From IPTC".to_string(),
+                "001-test".to_string()
+            )
+        );
+    }
+
     #[test]
     fn canonical_metadata_first_ref_wins_on_duplicate_reads() {
         // First ref has its own IPTC values; second ref returns
@@ -2177,7 +4377,8 @@ mod tests {
             &manifest_path,
             &source_dir,
             &output_dir,
-            false,
+            CacheMode::Disabled,
+            None,
             None,
         )
         .unwrap();
@@ -2222,7 +4423,8 @@ mod tests {
             &manifest_path,
             &source_dir,
             &output_dir,
-            false,
+            CacheMode::Disabled,
+            None,
             None,
         )
         .unwrap();
@@ -2261,7 +4463,8 @@ mod tests {
             &manifest_path,
             &source_dir,
             &output_dir,
-            false,
+            CacheMode::Disabled,
+            None,
             None,
         )
         .unwrap();
@@ -20,6 +20,7 @@ use std::path::{Path, PathBuf};
 use thiserror::Error;
 
 use crate::imaging;
+use crate::naming;
 
 /// One on-disk artifact belonging to an [`Entry`].
 ///
@@ -426,6 +427,8 @@ struct DiskName {
 /// - `"020-Travel"`   → `number=20, stem="Travel", suffix=""`
 /// - `"040-about.md"` → `number=40, stem="about", suffix=".md"`
 /// - `"001"` / `"001-"` → `number=1, stem="", suffix=""`
+/// - `"001_sunset.jpg"` → `number=1, stem="sunset", suffix=".jpg"` (the
+///   rename normalizes the separator to `-`)
 /// - `"site.md"`      → `number=None, stem="site", suffix=".md"`
 fn parse_disk_name(name: &str, is_dir: bool) -> DiskName {
     // Split the extension off files; directories keep their whole name.
@@ -440,21 +443,12 @@ fn parse_disk_name(name: &str, is_dir: bool) -> DiskName {
             _ => (name, String::new()),
         }
     };
-    // Parse `base` for `NNN-stem` or pure `NNN`.
-    if let Some(dash) = base.find('-') {
-        let prefix = &base[..dash];
-        if let Ok(num) = prefix.parse::<u32>() {
-            return DiskName {
-                number: Some(num),
-                stem: base[dash + 1..].to_string(),
-                suffix,
-            };
-        }
-    }
-    if let Ok(num) = base.parse::<u32>() {
+    // Parse `base` for `NNN-stem`, `NNN_stem` or pure `NNN` — same rules as
+    // the scan stage, so anything scan considers numbered gets reindexed.
+    if let Some((num, stem)) = naming::split_number_prefix(base) {
         return DiskName {
             number: Some(num),
-            stem: String::new(),
+            stem: stem.to_string(),
             suffix,
         };
     }
@@ -1537,6 +1531,24 @@ mod tests {
         assert_eq!(stems, vec!["Alpha", "Zebra"]);
     }
 
+    #[test]
+    fn walker_treats_underscore_prefix_as_numbered() {
+        let tmp = TempDir::new().unwrap();
+        touch(tmp.path(), "001_sunset.jpg");
+        touch(tmp.path(), "001_sunset.txt");
+        touch(tmp.path(), "002sunrise.jpg");
+        let entries = read_entries(tmp.path(), &WalkOptions::default()).unwrap();
+        assert_eq!(entries[0].number, Some(1));
+        assert_eq!(entries[0].stem, "sunset");
+        assert_eq!(entries[0].members.len(), 2);
+        // No separator after the digits: not a prefix.
+        assert_eq!(entries[1].number, None);
+
+        let plan = plan_reindex(&entries, 1, 3);
+        assert!(plan.contains(&r("001_sunset.jpg", "010-sunset.jpg")));
+        assert!(plan.contains(&r("001_sunset.txt", "010-sunset.txt")));
+    }
+
     // ----- tree driver -----
 
     #[test]
@@ -53,10 +53,13 @@
 //! - No duplicate image numbers within an album
 //! - Every album must have at least one image
 
-use crate::config::{self, SiteConfig, SiteConfigLayer};
+use crate::config::{self, SiteConfig, SiteConfigLayer, SortOrder};
+use crate::links;
 use crate::metadata;
 use crate::naming::parse_entry_name;
-use crate::types::{NavItem, Page};
+use crate::text;
+use crate::types::{AlbumMeta, NavItem, Page};
+use crate::typography;
 use confique::Layer;
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
@@ -70,12 +73,68 @@ pub enum ScanError {
     Io(#[from] std::io::Error),
     #[error("Config error: {0}")]
     Config(#[from] config::ConfigError),
-    #[error("Directory contains both images and subdirectories: {0}")]
-    MixedContent(PathBuf),
+    #[error(
+        "Directory contains both images and subdirectories: {}\n  \
+         images: {}\n  \
+         subdirectories: {}\n\
+         A directory is either an album (images only) or a group (subdirectories only). \
+         Move the images into a subdirectory of their own, or move the subdirectories out.",
+        .dir.display(),
+        entry_list(.images),
+        entry_list(.subdirs)
+    )]
+    MixedContent {
+        dir: PathBuf,
+        /// File names of the images found, sorted.
+        images: Vec<String>,
+        /// Names of the subdirectories found, sorted.
+        subdirs: Vec<String>,
+    },
     #[error("Duplicate image number {0} in {1}")]
     DuplicateNumber(u32, PathBuf),
     #[error("Multiple thumb-designated images in {0}")]
     DuplicateThumb(PathBuf),
+    #[error(
+        "Directories {1} and {2} both map to output path \"{0}\" after removing number prefixes"
+    )]
+    DuplicatePath(String, PathBuf, PathBuf),
+    #[error("Album has no displayable images: {0}")]
+    EmptyAlbum(PathBuf),
+    #[error("Featured image \"{0}\" not found in {1}")]
+    FeaturedNotFound(String, PathBuf),
+    #[error("Invalid sidecar {0}: {1}")]
+    InvalidSidecar(PathBuf, String),
+    #[error("Invalid album metadata {0}: {1}")]
+    InvalidAlbumMeta(PathBuf, String),
+    #[error("Invalid page front-matter {0}: {1}")]
+    InvalidFrontMatter(PathBuf, String),
+    #[error("Page {0} has slug \"index\", which would replace the site's home page")]
+    ReservedPageSlug(PathBuf),
+    #[error("Pages {1} and {2} both publish as \"{0}.html\"")]
+    DuplicatePageSlug(String, PathBuf, PathBuf),
+    #[error(
+        "Unnumbered images in {0} have no number to sort after image {1}; \
+         give the numbered images smaller numbers"
+    )]
+    UnnumberedOverflow(PathBuf, u32),
+    #[error("Symlink cycle: {} leads back to {}", .dir.display(), .ancestor.display())]
+    SymlinkCycle { dir: PathBuf, ancestor: PathBuf },
+}
+
+/// Comma-separated names for an error message, capped at five so a big
+/// album doesn't flood the terminal.
+fn entry_list(names: &[String]) -> String {
+    const SHOWN: usize = 5;
+    let mut list = names
+        .iter()
+        .take(SHOWN)
+        .map(String::as_str)
+        .collect::<Vec<_>>()
+        .join(", ");
+    if names.len() > SHOWN {
+        list.push_str(&format!(" and {} more", names.len() - SHOWN));
+    }
+    list
 }
 
 /// Manifest output from the scan stage
@@ -100,6 +159,10 @@ pub struct Manifest {
     /// and drop the nested one.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub canonical_images: Vec<CanonicalImage>,
+    /// Photos in formats simple-gal can't decode, which the site leaves
+    /// out. Reported as warnings; see [`UnsupportedImages`].
+    #[serde(skip)]
+    pub unsupported_images: Vec<UnsupportedImages>,
 }
 
 /// Stable, content-addressed identity for an image.
@@ -154,9 +217,18 @@ pub struct CanonicalImage {
 #[derive(Debug, Serialize)]
 pub struct Album {
     pub path: String,
+    /// The album's directory relative to the content root, as on disk
+    /// (`path` has its number prefixes stripped).
+    pub source_dir: String,
     pub title: String,
+    /// Rendered HTML from `description.md` / `description.txt`.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    /// The same description as plain text (markdown with its markup
+    /// removed), for consumers that shouldn't have to strip tags: CLI
+    /// output, album excerpts and the search index.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description_text: Option<String>,
     pub preview_image: String,
     pub images: Vec<Image>,
     pub in_nav: bool,
@@ -165,6 +237,9 @@ pub struct Album {
     /// Supporting files found in the album directory (e.g. config.toml, description.md).
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub support_files: Vec<String>,
+    /// Location/date/camera details from `meta.toml`, if present.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub meta: Option<AlbumMeta>,
 }
 
 /// Image metadata
@@ -176,7 +251,9 @@ pub struct Album {
 /// - `Museum.jpg` → unnumbered, title=Some("Museum")
 ///
 /// The sequence number controls sort order; the title (if present) is
-/// displayed in the breadcrumb on the image detail page.
+/// displayed in the breadcrumb on the image detail page. Unnumbered images
+/// sort after all numbered ones, by filename, and get a synthetic `number`
+/// of at least `1_000_000`.
 #[derive(Debug, Serialize)]
 pub struct Image {
     pub number: u32,
@@ -186,9 +263,18 @@ pub struct Image {
     pub slug: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub title: Option<String>,
-    /// Image description from sidecar `.txt` file (e.g., `001-photo.txt` for `001-photo.jpg`)
+    /// Image description from sidecar `.txt` file (e.g., `001-photo.txt` for `001-photo.jpg`).
+    /// Kept as raw text; generate renders it to HTML for the image page.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    /// AVIF quality override from the sidecar's `quality:` front matter;
+    /// replaces the album's `images.quality` for this image's responsive sizes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quality: Option<u32>,
+    /// Thumbnail focal point from the sidecar's `focal:` front matter, as
+    /// `(x, y)` percentages; thumbnails crop around it instead of the center.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub focal: Option<(u32, u32)>,
     /// Pointer into [`Manifest::canonical_images`] — shared across every
     /// album that references the same byte-identical source. Populated
     /// by the dedup pass at the end of [`scan`]; absent only on manifests
@@ -197,15 +283,147 @@ pub struct Image {
     pub canonical_id: Option<ImageId>,
 }
 
+/// A jump in an album's image numbering larger than the album's
+/// `auto_indexing.gap_warning`. Not an error — gaps are allowed — but a
+/// big one is often a mistyped prefix.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct NumberingGap {
+    /// Album path, as in [`Album::path`].
+    pub album: String,
+    /// Numbers of the two neighboring images.
+    pub from: u32,
+    pub to: u32,
+}
+
+/// Every suspiciously large numbering gap in `manifest`'s albums, in album
+/// order. Unnumbered images (sorted from [`UNNUMBERED_IMAGE_BASE`] on) are
+/// left out: the jump to them isn't a typo.
+pub fn numbering_gaps(manifest: &Manifest) -> Vec<NumberingGap> {
+    let mut gaps = Vec::new();
+    for album in &manifest.albums {
+        let threshold = album.config.auto_indexing.gap_warning;
+        if threshold == 0 {
+            continue;
+        }
+        let mut numbers = album
+            .images
+            .iter()
+            .map(|i| i.number)
+            .filter(|&n| n < UNNUMBERED_IMAGE_BASE)
+            .collect::<Vec<_>>();
+        numbers.sort_unstable();
+        for pair in numbers.windows(2) {
+            if pair[1] - pair[0] > threshold {
+                gaps.push(NumberingGap {
+                    album: album.path.clone(),
+                    from: pair[0],
+                    to: pair[1],
+                });
+            }
+        }
+    }
+    gaps
+}
+
+/// Photo formats that can't be decoded without system libraries: HEIC/HEIF
+/// (the iPhone default) and camera raw files. Scan reports them instead of
+/// skipping them silently, so an album of iPhone photos doesn't just look
+/// empty.
+const UNSUPPORTED_IMAGE_EXTENSIONS: &[&str] = &[
+    "heic", "heif", "dng", "cr2", "cr3", "nef", "arw", "raf", "orf", "rw2",
+];
+
+/// Photos in one content directory that were skipped because their format
+/// can't be decoded (see [`UNSUPPORTED_IMAGE_EXTENSIONS`]).
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct UnsupportedImages {
+    /// Directory relative to the content root; empty for the root itself.
+    pub dir: String,
+    /// File names, sorted.
+    pub files: Vec<String>,
+}
+
+impl UnsupportedImages {
+    /// One-line warning with the files and what to do about them.
+    pub fn message(&self) -> String {
+        let dir = if self.dir.is_empty() { "." } else { &self.dir };
+        format!(
+            "{dir}/: skipped {} in an unsupported format ({}); convert to JPEG to include them",
+            match self.files.len() {
+                1 => "1 photo".to_string(),
+                n => format!("{n} photos"),
+            },
+            entry_list(&self.files)
+        )
+    }
+}
+
+/// Walk the content tree for files in [`UNSUPPORTED_IMAGE_EXTENSIONS`],
+/// skipping what scanning skips: hidden entries, build output and the
+/// root's assets directory.
+fn find_unsupported_images(root: &Path, assets_dir: &str) -> Vec<UnsupportedImages> {
+    let mut found: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    let walker = walkdir::WalkDir::new(root)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|e| {
+            let name = e.file_name().to_string_lossy();
+            e.depth() == 0
+                || !(name.starts_with('.')
+                    || name == "processed"
+                    || name == "dist"
+                    || (e.depth() == 1 && name == assets_dir))
+        });
+    for entry in walker.filter_map(Result::ok) {
+        let path = entry.path();
+        let unsupported = entry.file_type().is_file()
+            && path.extension().is_some_and(|ext| {
+                UNSUPPORTED_IMAGE_EXTENSIONS
+                    .contains(&ext.to_string_lossy().to_lowercase().as_str())
+            });
+        if !unsupported {
+            continue;
+        }
+        let dir = path
+            .parent()
+            .and_then(|p| p.strip_prefix(root).ok())
+            .map(|p| p.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let file = entry.file_name().to_string_lossy().into_owned();
+        found.entry(dir).or_default().push(file);
+    }
+    found
+        .into_iter()
+        .map(|(dir, files)| UnsupportedImages { dir, files })
+        .collect()
+}
+
 pub fn scan(root: &Path) -> Result<Manifest, ScanError> {
+    scan_with_overrides(root, &SiteConfigLayer::empty())
+}
+
+/// [`scan`] with `overrides` (e.g. from command-line flags) applied on top
+/// of every resolved config — the root one and each album's cascade leaf.
+pub fn scan_with_overrides(
+    root: &Path,
+    overrides: &SiteConfigLayer,
+) -> Result<Manifest, ScanError> {
     let mut albums = Vec::new();
     let mut nav_items = Vec::new();
 
     // Resolve the root config (used for assets_dir, site_description_file,
     // and the manifest output) and capture the root layer to seed the
     // per-directory cascade.
-    let root_layer = config::load_layer(root)?.unwrap_or_else(SiteConfigLayer::empty);
-    let root_config = config::finalize_layer(root_layer.clone())?;
+    let root_layer = config::cascade_layer(
+        config::load_layer(root)?.unwrap_or_else(SiteConfigLayer::empty),
+        &SiteConfigLayer::empty(),
+    )?;
+    let root_config = config::finalize_layer_with(root_layer.clone(), overrides)?;
+
+    // Pages are parsed first: images they embed from the content root are
+    // page assets, not a root-level album.
+    let pages = parse_pages(root, &root_config.site_description_file)?;
+    let page_assets: Vec<String> = pages.iter().flat_map(|p| p.assets.clone()).collect();
 
     scan_directory(
         root,
@@ -213,9 +431,16 @@ pub fn scan(root: &Path) -> Result<Manifest, ScanError> {
         &mut albums,
         &mut nav_items,
         &root_layer,
+        overrides,
         &root_config.assets_dir,
+        &page_assets,
     )?;
 
+    // Two source directories that differ only in their number prefix (or
+    // in case/underscores) would be written to the same output directory,
+    // silently overwriting each other. Refuse before slugifying.
+    check_path_collisions(root, &albums, &nav_items)?;
+
     // Strip number prefixes from output paths (used for URLs and output dirs).
     // Sorting has already happened with original paths, so this is safe.
     for album in &mut albums {
@@ -223,8 +448,9 @@ pub fn scan(root: &Path) -> Result<Manifest, ScanError> {
     }
     slugify_nav_paths(&mut nav_items);
 
-    let description = read_description(root, &root_config.site_description_file)?;
-    let pages = parse_pages(root, &root_config.site_description_file)?;
+    let mut description =
+        read_description(root, &root_config.site_description_file)?.map(|d| d.html);
+    rewrite_descriptions(&root_config, &mut description, &mut albums, &mut nav_items);
 
     // Root-level resolved config for CSS generation
     let config = root_config;
@@ -235,6 +461,7 @@ pub fn scan(root: &Path) -> Result<Manifest, ScanError> {
     // for the rationale. This runs after all albums have been collected so
     // one pass covers the whole site.
     let canonical_images = build_canonical_index(root, &mut albums)?;
+    let unsupported_images = find_unsupported_images(root, &config.assets_dir);
 
     Ok(Manifest {
         navigation: nav_items,
@@ -243,6 +470,7 @@ pub fn scan(root: &Path) -> Result<Manifest, ScanError> {
         description,
         config,
         canonical_images,
+        unsupported_images,
     })
 }
 
@@ -319,6 +547,50 @@ fn slug_path(rel_path: &str) -> String {
         .join("/")
 }
 
+/// Reject albums and nav entries whose source paths differ but whose slug
+/// paths (see [`slug_path`]) are identical.
+///
+/// Must run before slugification, while `album.path` / `item.path` still
+/// hold the original relative directory paths. An album shows up both in
+/// `albums` and in the nav tree under the same source path; that's not a
+/// collision.
+fn check_path_collisions(
+    root: &Path,
+    albums: &[Album],
+    nav_items: &[NavItem],
+) -> Result<(), ScanError> {
+    use std::collections::HashMap;
+
+    fn collect_nav<'a>(items: &'a [NavItem], out: &mut Vec<&'a str>) {
+        for item in items {
+            out.push(&item.path);
+            collect_nav(&item.children, out);
+        }
+    }
+
+    let mut sources: Vec<&str> = albums.iter().map(|a| a.path.as_str()).collect();
+    collect_nav(nav_items, &mut sources);
+
+    let mut seen: HashMap<String, &str> = HashMap::new();
+    for source in sources {
+        let slug = slug_path(source);
+        match seen.get(&slug) {
+            Some(&existing) if existing != source => {
+                return Err(ScanError::DuplicatePath(
+                    slug,
+                    root.join(existing),
+                    root.join(source),
+                ));
+            }
+            Some(_) => {}
+            None => {
+                seen.insert(slug, source);
+            }
+        }
+    }
+    Ok(())
+}
+
 /// Recursively strip number prefixes from all NavItem paths.
 fn slugify_nav_paths(items: &mut [NavItem]) {
     for item in items.iter_mut() {
@@ -335,6 +607,8 @@ fn slugify_nav_paths(items: &mut [NavItem]) {
 /// The `site_description_stem` file (e.g. `site.md`) is excluded — it is
 /// rendered on the index page, not as a standalone page.
 fn parse_pages(root: &Path, site_description_stem: &str) -> Result<Vec<Page>, ScanError> {
+    use std::collections::HashMap;
+
     let exclude_filename = format!("{}.md", site_description_stem);
     let mut md_files: Vec<PathBuf> = fs::read_dir(root)?
         .filter_map(|e| e.ok())
@@ -353,6 +627,7 @@ fn parse_pages(root: &Path, site_description_stem: &str) -> Result<Vec<Page>, Sc
     md_files.sort();
 
     let mut pages = Vec::new();
+    let mut slug_paths: HashMap<String, &PathBuf> = HashMap::new();
     for md_path in &md_files {
         let stem = md_path
             .file_stem()
@@ -362,10 +637,13 @@ fn parse_pages(root: &Path, site_description_stem: &str) -> Result<Vec<Page>, Sc
         let parsed = parse_entry_name(&stem);
         let in_nav = parsed.number.is_some();
         let sort_key = parsed.number.unwrap_or(u32::MAX);
-        let link_title = parsed.display_title;
-        let slug = parsed.name;
 
-        let content = fs::read_to_string(md_path)?;
+        let raw = metadata::read_text_file(md_path)?;
+        let (front, content) = split_front_matter(&raw)
+            .map_err(|e| ScanError::InvalidFrontMatter(md_path.clone(), e))?;
+        let content = content.to_string();
+        let link_title = front.nav_title.unwrap_or(parsed.display_title);
+        let slug = front.slug.unwrap_or(parsed.name);
         let trimmed = content.trim();
 
         // A page whose only content is a URL becomes an external link.
@@ -378,7 +656,9 @@ fn parse_pages(root: &Path, site_description_stem: &str) -> Result<Vec<Page>, Sc
         let is_link =
             !url.contains('\n') && (url.starts_with("http://") || url.starts_with("https://"));
 
-        let title = if is_link {
+        let title = if let Some(title) = front.title {
+            title
+        } else if is_link {
             link_title.clone()
         } else {
             content
@@ -388,6 +668,25 @@ fn parse_pages(root: &Path, site_description_stem: &str) -> Result<Vec<Page>, Sc
                 .unwrap_or_else(|| link_title.clone())
         };
 
+        // Link pages aren't written out, so only content pages claim a slug.
+        if !is_link {
+            if slug == "index" {
+                return Err(ScanError::ReservedPageSlug(md_path.clone()));
+            }
+            if let Some(other) = slug_paths.insert(slug.clone(), md_path) {
+                return Err(ScanError::DuplicatePageSlug(
+                    slug,
+                    other.clone(),
+                    md_path.clone(),
+                ));
+            }
+        }
+
+        let assets = if is_link {
+            Vec::new()
+        } else {
+            page_sibling_images(root, &content)
+        };
         let body = if is_link { url.to_string() } else { content };
 
         pages.push(Page {
@@ -398,6 +697,8 @@ fn parse_pages(root: &Path, site_description_stem: &str) -> Result<Vec<Page>, Sc
             in_nav,
             sort_key,
             is_link,
+            assets,
+            toc: front.toc,
         });
     }
 
@@ -405,39 +706,161 @@ fn parse_pages(root: &Path, site_description_stem: &str) -> Result<Vec<Page>, Sc
     Ok(pages)
 }
 
+/// File extensions a page may embed from next to its markdown file.
+const PAGE_IMAGE_EXTENSIONS: &[&str] = &["jpg", "jpeg", "png", "gif", "webp", "avif", "svg"];
+
+/// Image files in the content root that a page's markdown embeds by bare
+/// name (`![](photo.jpg)` or `![](./photo.jpg)`).
+///
+/// Pages are written to the output root, so once generate copies these
+/// files there the page's relative `src` resolves unchanged. URLs with a
+/// scheme, an absolute path or a directory component are left alone, as
+/// are names that don't exist on disk.
+fn page_sibling_images(root: &Path, markdown: &str) -> Vec<String> {
+    use pulldown_cmark::{Event, Parser, Tag};
+
+    let mut found: Vec<String> = Vec::new();
+    for event in Parser::new(markdown) {
+        let Event::Start(Tag::Image { dest_url, .. }) = event else {
+            continue;
+        };
+        let name = dest_url.strip_prefix("./").unwrap_or(&dest_url);
+        let is_image = Path::new(name)
+            .extension()
+            .map(|e| e.to_string_lossy().to_lowercase())
+            .is_some_and(|e| PAGE_IMAGE_EXTENSIONS.contains(&e.as_str()));
+        if is_image
+            && !name.contains(['/', '\\', ':'])
+            && root.join(name).is_file()
+            && !found.iter().any(|f| f == name)
+        {
+            found.push(name.to_string());
+        }
+    }
+    found
+}
+
+/// Optional TOML front-matter at the top of a markdown page, overriding
+/// what is otherwise derived from the filename and first heading.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PageFrontMatter {
+    /// URL slug, replacing the filename stem without its number prefix.
+    slug: Option<String>,
+    /// Nav label, replacing the one derived from the filename.
+    nav_title: Option<String>,
+    /// Page title, replacing the first `# heading`.
+    title: Option<String>,
+    /// Whether to render a table of contents, overriding `[pages] toc`.
+    toc: Option<bool>,
+}
+
+/// Split a page into its front-matter and the markdown after it.
+///
+/// Front-matter is TOML fenced by `+++` or `---` lines, starting on the
+/// first line of the file (see [`metadata::split_front_matter`]). A page
+/// without a fence on its first line has no front-matter and is returned
+/// unchanged; an unclosed fence or invalid TOML is an error.
+fn split_front_matter(text: &str) -> Result<(PageFrontMatter, &str), String> {
+    let (block, body) = metadata::split_front_matter(text)?;
+    let Some(block) = block else {
+        return Ok((PageFrontMatter::default(), body));
+    };
+    let front: PageFrontMatter = toml::from_str(block).map_err(|e| e.to_string())?;
+    if let Some(slug) = &front.slug
+        && (slug.is_empty()
+            || slug.starts_with('.')
+            || !slug
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || "-_.".contains(c)))
+    {
+        return Err(format!(
+            "slug = {slug:?} must be letters, digits, '-', '_' or '.'"
+        ));
+    }
+    Ok((front, body))
+}
+
+#[allow(clippy::too_many_arguments)]
 fn scan_directory(
     path: &Path,
     root: &Path,
     albums: &mut Vec<Album>,
     nav_items: &mut Vec<NavItem>,
     inherited_layer: &SiteConfigLayer,
+    overrides: &SiteConfigLayer,
     assets_dir: &str,
+    page_assets: &[String],
 ) -> Result<(), ScanError> {
+    // Symlinked directories are followed: an album linked into several
+    // groups becomes one album per group, sharing its canonical images.
+    // A link back up the tree would recurse forever.
+    if let Some(ancestor) = symlink_cycle(path)? {
+        return Err(ScanError::SymlinkCycle {
+            dir: path.to_path_buf(),
+            ancestor,
+        });
+    }
+
     let entries = collect_entries(path, if path == root { Some(assets_dir) } else { None })?;
 
-    let images = entries.iter().filter(|e| is_image(e)).collect::<Vec<_>>();
+    // Root-level images embedded by a page belong to that page.
+    let is_page_asset = |e: &PathBuf| {
+        path == root
+            && e.file_name()
+                .is_some_and(|n| page_assets.iter().any(|a| n == a.as_str()))
+    };
+    let images = entries
+        .iter()
+        .filter(|e| is_image(e) && !is_page_asset(e))
+        .collect::<Vec<_>>();
 
     let subdirs = entries.iter().filter(|e| e.is_dir()).collect::<Vec<_>>();
 
     // Check for mixed content
     if !images.is_empty() && !subdirs.is_empty() {
-        return Err(ScanError::MixedContent(path.to_path_buf()));
+        let names = |entries: &[&PathBuf]| {
+            let mut names = entries
+                .iter()
+                .filter_map(|e| e.file_name())
+                .map(|n| n.to_string_lossy().into_owned())
+                .collect::<Vec<_>>();
+            names.sort();
+            names
+        };
+        return Err(ScanError::MixedContent {
+            dir: path.to_path_buf(),
+            images: names(&images),
+            subdirs: names(&subdirs),
+        });
     }
 
     // Layer any local config.toml onto the inherited layer (skip root — its
     // file was already folded into `inherited_layer` by `scan`).
-    let effective_layer = if path != root {
-        match config::load_layer(path)? {
-            Some(local) => local.with_fallback(inherited_layer.clone()),
-            None => inherited_layer.clone(),
-        }
+    let local_layer = if path != root {
+        config::load_layer(path)?
     } else {
-        inherited_layer.clone()
+        None
+    };
+    // `featured` names an image in this directory, and `[album]` describes
+    // this album, so both only apply where they are written and never
+    // cascade into subdirectories.
+    let local_featured = local_layer
+        .as_ref()
+        .and_then(|l| l.thumbnails.featured.clone());
+    let local_album = local_layer.as_ref().map(|l| l.album.clone());
+    let mut effective_layer = match local_layer {
+        Some(local) => config::cascade_layer(local, inherited_layer)?,
+        None => inherited_layer.clone(),
     };
+    if path != root {
+        effective_layer.thumbnails.featured = local_featured;
+        effective_layer.album = local_album.unwrap_or_else(Layer::empty);
+    }
 
     if !images.is_empty() {
         // This is an album — resolve and validate the cascade leaf.
-        let effective_config = config::finalize_layer(effective_layer)?;
+        let effective_config = config::finalize_layer_with(effective_layer, overrides)?;
         let album = build_album(path, root, &images, effective_config)?;
         let in_nav = album.in_nav;
         let title = album.title.clone();
@@ -460,22 +883,56 @@ fn scan_directory(
         // This is a container directory
         let mut child_nav = Vec::new();
 
-        // Sort subdirs by their number prefix
-        let mut sorted_subdirs = subdirs.clone();
-        sorted_subdirs.sort_by_key(|d| {
-            let name = d.file_name().unwrap().to_string_lossy().to_string();
-            (parse_entry_name(&name).number.unwrap_or(u32::MAX), name)
-        });
-
-        for subdir in sorted_subdirs {
+        // Scan each subdir on its own, then order them by number prefix, or
+        // by title with `[ordering] sort = "title"`. Titles are compared
+        // once scanned, so an album's `[album] title` counts.
+        let sort = effective_layer.ordering.sort.unwrap_or_default();
+        let mut scanned = Vec::new();
+        for subdir in subdirs {
+            let mut sub_albums = Vec::new();
+            let mut sub_nav = Vec::new();
             scan_directory(
                 subdir,
                 root,
-                albums,
-                &mut child_nav,
+                &mut sub_albums,
+                &mut sub_nav,
                 &effective_layer,
+                overrides,
                 assets_dir,
+                page_assets,
             )?;
+            scanned.push((subdir, sub_albums, sub_nav));
+        }
+        scanned.sort_by_cached_key(|(subdir, sub_albums, _)| {
+            let name = subdir.file_name().unwrap().to_string_lossy().to_string();
+            let parsed = parse_entry_name(&name);
+            match sort {
+                SortOrder::Number => (parsed.number.unwrap_or(u32::MAX), String::new(), name),
+                SortOrder::Title => {
+                    // A subdir holding images is an album, titled like
+                    // `build_album` titles it; a group keeps its own name.
+                    let rel_path = subdir.strip_prefix(root).unwrap();
+                    let title = sub_albums
+                        .iter()
+                        .find(|a| Path::new(&a.source_dir) == rel_path)
+                        .map_or(parsed.display_title, |a| a.title.clone());
+                    (0, title.to_lowercase(), name)
+                }
+            }
+        });
+        for (_, sub_albums, sub_nav) in scanned {
+            albums.extend(sub_albums);
+            child_nav.extend(sub_nav);
+        }
+
+        // Children are pushed in scan order, but an unnumbered child group
+        // lifts its own children to this level, so put the list in order.
+        match sort {
+            SortOrder::Number => child_nav.sort_by_key(|item| {
+                let dir_name = item.path.split('/').next_back().unwrap_or("");
+                parse_entry_name(dir_name).number.unwrap_or(u32::MAX)
+            }),
+            SortOrder::Title => child_nav.sort_by_key(|item| item.title.to_lowercase()),
         }
 
         // If this directory is numbered, add it to nav with children
@@ -484,7 +941,7 @@ fn scan_directory(
             let parsed = parse_entry_name(&dir_name);
             if parsed.number.is_some() {
                 let rel_path = path.strip_prefix(root).unwrap();
-                let description = read_album_description(path)?;
+                let description = read_album_description(path)?.map(|d| d.html);
                 nav_items.push(NavItem {
                     title: parsed.display_title,
                     path: rel_path.to_string_lossy().to_string(),
@@ -502,15 +959,22 @@ fn scan_directory(
         }
     }
 
-    // Sort nav_items by their original directory number
-    nav_items.sort_by_key(|item| {
-        let dir_name = item.path.split('/').next_back().unwrap_or("");
-        parse_entry_name(dir_name).number.unwrap_or(u32::MAX)
-    });
-
     Ok(())
 }
 
+/// The directory above `dir` that `dir` resolves to through symlinks, if
+/// any. "Above" follows the path as written, not link targets, so a loop
+/// through several links is caught where it closes.
+pub(crate) fn symlink_cycle(dir: &Path) -> std::io::Result<Option<PathBuf>> {
+    let target = fs::canonicalize(dir)?;
+    for ancestor in std::path::absolute(dir)?.ancestors().skip(1) {
+        if fs::canonicalize(ancestor)? == target {
+            return Ok(Some(ancestor.to_path_buf()));
+        }
+    }
+    Ok(None)
+}
+
 fn collect_entries(path: &Path, assets_dir: Option<&str>) -> Result<Vec<PathBuf>, ScanError> {
     let mut entries: Vec<PathBuf> = fs::read_dir(path)?
         .filter_map(|e| e.ok())
@@ -544,46 +1008,122 @@ fn is_image(path: &Path) -> bool {
     crate::imaging::supported_input_extensions().contains(&ext.as_str())
 }
 
+/// A description as plain text and as rendered HTML.
+struct Description {
+    text: String,
+    html: String,
+}
+
 /// Read a description from `<stem>.md` or `<stem>.txt` in the given directory.
 ///
-/// - `.md` takes priority and is rendered as markdown HTML.
+/// - `.md` takes priority and is rendered as markdown HTML; its text is the
+///   markdown with the markup removed.
 /// - `.txt` is converted to HTML with smart paragraph handling and URL linkification.
 /// - Returns `None` if neither file exists or contents are empty.
-fn read_description(dir: &Path, stem: &str) -> Result<Option<String>, ScanError> {
+fn read_description(dir: &Path, stem: &str) -> Result<Option<Description>, ScanError> {
     let md_path = dir.join(format!("{}.md", stem));
     if md_path.exists() {
-        let content = fs::read_to_string(&md_path)?.trim().to_string();
+        let content = metadata::read_text_file(&md_path)?.trim().to_string();
         if content.is_empty() {
             return Ok(None);
         }
         let parser = pulldown_cmark::Parser::new(&content);
         let mut html = String::new();
         pulldown_cmark::html::push_html(&mut html, parser);
-        return Ok(Some(html));
+        return Ok(Some(Description {
+            text: text::markdown_to_text(&content),
+            html,
+        }));
     }
 
     let txt_path = dir.join(format!("{}.txt", stem));
     if txt_path.exists() {
-        let content = fs::read_to_string(&txt_path)?.trim().to_string();
+        let content = metadata::read_text_file(&txt_path)?.trim().to_string();
         if content.is_empty() {
             return Ok(None);
         }
-        return Ok(Some(plain_text_to_html(&content)));
+        return Ok(Some(Description {
+            html: plain_text_to_html(&content),
+            text: content,
+        }));
     }
 
     Ok(None)
 }
 
+/// Post-process rendered description HTML with the opt-in rewrites
+/// (`smart_typography`, `external_links_new_tab`) enabled in `config`.
+pub(crate) fn rewrite_description(html: &str, config: &SiteConfig) -> String {
+    let mut html = html.to_string();
+    if config.smart_typography {
+        html = typography::smarten(&html);
+    }
+    if config.external_links_new_tab {
+        html = links::open_external_in_new_tab(&html, config.base_url.as_deref());
+    }
+    html
+}
+
+/// Apply [`rewrite_description`] to every rendered description. The site
+/// and group descriptions follow the root config; each album follows its
+/// own resolved config, so a gallery's `config.toml` can opt in or out.
+fn rewrite_descriptions(
+    root_config: &SiteConfig,
+    description: &mut Option<String>,
+    albums: &mut [Album],
+    nav_items: &mut [NavItem],
+) {
+    fn rewrite_nav(items: &mut [NavItem], config: &SiteConfig) {
+        for item in items {
+            if let Some(d) = &mut item.description {
+                *d = rewrite_description(d, config);
+            }
+            rewrite_nav(&mut item.children, config);
+        }
+    }
+    for album in albums.iter_mut() {
+        if let Some(d) = &mut album.description {
+            *d = rewrite_description(d, &album.config);
+        }
+    }
+    if let Some(d) = description {
+        *d = rewrite_description(d, root_config);
+    }
+    rewrite_nav(nav_items, root_config);
+}
+
 /// Read an album description from `description.md` or `description.txt`.
-fn read_album_description(album_dir: &Path) -> Result<Option<String>, ScanError> {
+fn read_album_description(album_dir: &Path) -> Result<Option<Description>, ScanError> {
     read_description(album_dir, "description")
 }
 
+/// Read an album's optional `meta.toml` (location, date, camera).
+///
+/// `date` may be a string or a bare TOML date (`date = 2024-04-12`); unknown
+/// keys are an error. A file with no values is treated as absent.
+fn read_album_meta(album_dir: &Path) -> Result<Option<AlbumMeta>, ScanError> {
+    let path = album_dir.join("meta.toml");
+    if !path.is_file() {
+        return Ok(None);
+    }
+    let text = fs::read_to_string(&path)?;
+    let parse = || -> Result<AlbumMeta, toml::de::Error> {
+        let mut table: toml::Table = text.parse()?;
+        if let Some(toml::Value::Datetime(date)) = table.get("date") {
+            let date = date.to_string();
+            table.insert("date".into(), toml::Value::String(date));
+        }
+        toml::Value::Table(table).try_into()
+    };
+    let meta = parse().map_err(|e| ScanError::InvalidAlbumMeta(path.clone(), e.to_string()))?;
+    Ok((!meta.entries().is_empty()).then_some(meta))
+}
+
 /// Convert plain text to HTML with smart paragraph detection and URL linkification.
 ///
 /// - Double newlines (`\n\n`) split text into `<p>` elements.
 /// - URLs starting with `http://` or `https://` are wrapped in `<a>` tags.
-fn plain_text_to_html(text: &str) -> String {
+pub(crate) fn plain_text_to_html(text: &str) -> String {
     let paragraphs: Vec<&str> = text.split("\n\n").collect();
     paragraphs
         .iter()
@@ -625,6 +1165,10 @@ fn linkify_urls(text: &str) -> String {
     result
 }
 
+/// Sort key assigned to the first unnumbered image in an album (or one
+/// past the highest numbered image, whichever is larger).
+const UNNUMBERED_IMAGE_BASE: u32 = 1_000_000;
+
 fn build_album(
     path: &Path,
     root: &Path,
@@ -636,16 +1180,16 @@ fn build_album(
 
     let parsed_dir = parse_entry_name(&dir_name);
     let in_nav = parsed_dir.number.is_some();
-    let title = if in_nav {
-        parsed_dir.display_title
-    } else {
-        dir_name.to_string()
+    let title = match &config.album.title {
+        Some(title) => title.clone(),
+        None if in_nav => parsed_dir.display_title,
+        None => dir_name.to_string(),
     };
 
     // Parse image names and check for duplicates.
     // Store ParsedName alongside each image to avoid double-parsing.
     let mut numbered_images: BTreeMap<u32, (&PathBuf, crate::naming::ParsedName)> = BTreeMap::new();
-    let mut unnumbered_counter = 0u32;
+    let mut unnumbered_images = Vec::new();
     for img in images {
         let filename = img.file_name().unwrap().to_string_lossy();
         let stem = Path::new(&*filename).file_stem().unwrap().to_string_lossy();
@@ -656,13 +1200,30 @@ fn build_album(
             }
             numbered_images.insert(num, (img, parsed));
         } else {
-            // Images without numbers get sorted to the end, preserving filename order
-            let high_num = 1_000_000 + unnumbered_counter;
-            unnumbered_counter += 1;
-            numbered_images.insert(high_num, (img, parsed));
+            unnumbered_images.push((*img, parsed));
         }
     }
 
+    // Images without numbers sort after every numbered image, ordered
+    // lexicographically by filename (byte order, so `Apple.jpg` comes
+    // before `banana.jpg`). Sorted here rather than relying on the
+    // caller's order so the result never depends on `read_dir`.
+    unnumbered_images.sort_by(|(a, _), (b, _)| a.file_name().cmp(&b.file_name()));
+    let max_number = numbered_images.last_key_value().map(|(&max, _)| max);
+    let unnumbered_base = max_number.map_or(Some(UNNUMBERED_IMAGE_BASE), |max| {
+        max.checked_add(1)
+            .map(|next| next.max(UNNUMBERED_IMAGE_BASE))
+    });
+    for (i, entry) in unnumbered_images.into_iter().enumerate() {
+        let key = unnumbered_base
+            .zip(u32::try_from(i).ok())
+            .and_then(|(base, i)| base.checked_add(i))
+            .ok_or_else(|| {
+                ScanError::UnnumberedOverflow(path.to_path_buf(), max_number.unwrap_or(u32::MAX))
+            })?;
+        numbered_images.insert(key, entry);
+    }
+
     // Detect thumb-designated images (name starts with "thumb", case-insensitive)
     let thumb_keys: Vec<u32> = numbered_images
         .iter()
@@ -679,8 +1240,28 @@ fn build_album(
 
     let thumb_key = thumb_keys.first().copied();
 
-    // Find preview image: thumb > #1 > first by sort order
-    let preview_image = if let Some(key) = thumb_key {
+    // `[thumbnails] featured` names the preview image by number prefix or
+    // file name.
+    let featured_key = match config.thumbnails.featured.as_deref() {
+        Some(featured) => {
+            let wanted_number = featured.parse::<u32>().ok();
+            let key = numbered_images
+                .iter()
+                .find(|(_, (img, parsed))| {
+                    (wanted_number.is_some() && parsed.number == wanted_number)
+                        || img.file_name().is_some_and(|name| name == featured)
+                })
+                .map(|(&key, _)| key)
+                .ok_or_else(|| {
+                    ScanError::FeaturedNotFound(featured.to_string(), path.to_path_buf())
+                })?;
+            Some(key)
+        }
+        None => None,
+    };
+
+    // Find preview image: featured > thumb > #1 > first by sort order
+    let preview_image = if let Some(key) = featured_key.or(thumb_key) {
         numbered_images.get(&key).map(|(p, _)| *p).unwrap()
     } else {
         numbered_images
@@ -688,8 +1269,7 @@ fn build_album(
             .find(|&(&num, _)| num == 1)
             .map(|(_, (p, _))| *p)
             .or_else(|| numbered_images.values().next().map(|(p, _)| *p))
-            // Safe: build_album is only called with non-empty images
-            .unwrap()
+            .ok_or_else(|| ScanError::EmptyAlbum(path.to_path_buf()))?
     };
 
     let preview_rel = preview_image.strip_prefix(root).unwrap();
@@ -702,56 +1282,88 @@ fn build_album(
 
             let title = if parsed.display_title.is_empty() {
                 None
+            } else if config.naming.titleize {
+                Some(crate::naming::titleize(&parsed.display_title))
             } else {
                 Some(parsed.display_title.clone())
             };
             let slug = parsed.name.clone();
 
             let source = img_path.strip_prefix(root).unwrap();
-            let description = metadata::read_sidecar(img_path);
-            Image {
+            let sidecar = metadata::read_sidecar(img_path)
+                .map_err(|e| ScanError::InvalidSidecar(img_path.with_extension("txt"), e))?;
+            Ok(Image {
                 number: num,
                 source_path: source.to_string_lossy().to_string(),
                 filename,
                 slug,
                 title,
-                description,
+                description: sidecar.description,
+                quality: sidecar.quality,
+                focal: sidecar.focal,
                 // Populated in `build_canonical_index` after scan collects
                 // every image across every album.
                 canonical_id: None,
-            }
+            })
         })
-        .collect();
+        .collect::<Result<_, ScanError>>()?;
 
     // Read description: description.md takes priority over description.txt
-    let description = read_album_description(path)?;
+    // `[album] description` in config.toml wins over the description files
+    let description = match config.album.description.as_deref().map(str::trim) {
+        Some(text) if !text.is_empty() => Some(Description {
+            html: plain_text_to_html(text),
+            text: text.to_string(),
+        }),
+        _ => read_album_description(path)?,
+    };
+    let (description, description_text) = match description {
+        Some(d) => (Some(d.html), Some(d.text)),
+        None => (None, None),
+    };
+    let meta = read_album_meta(path)?;
 
     // Detect supporting files
     let mut support_files = Vec::new();
     if path.join("config.toml").exists() {
         support_files.push("config.toml".to_string());
     }
+    if meta.is_some() {
+        support_files.push("meta.toml".to_string());
+    }
     if path.join("description.md").exists() {
         support_files.push("description.md".to_string());
     } else if path.join("description.txt").exists() {
         support_files.push("description.txt".to_string());
     }
+    // Non-image files published with the album (`album_file_extensions`)
+    support_files.extend(
+        collect_entries(path, None)?
+            .iter()
+            .filter(|p| p.is_file() && !is_image(p))
+            .filter_map(|p| p.file_name().map(|n| n.to_string_lossy().to_string()))
+            .filter(|name| config.is_album_file(name)),
+    );
 
     Ok(Album {
         path: rel_path.to_string_lossy().to_string(),
+        source_dir: rel_path.to_string_lossy().to_string(),
         title,
         description,
+        description_text,
         preview_image: preview_rel.to_string_lossy().to_string(),
         images,
         in_nav,
         config,
         support_files,
+        meta,
     })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::imaging::ImageSizes;
     use crate::test_helpers::*;
     use std::fs;
     use tempfile::TempDir;
@@ -880,6 +1492,54 @@ mod tests {
         assert!(desc.contains("<p>Second paragraph.</p>"));
     }
 
+    #[test]
+    fn description_with_byte_order_mark_renders_without_it() {
+        let tmp = TempDir::new().unwrap();
+        let album = tmp.path().join("010-Test");
+        fs::create_dir_all(&album).unwrap();
+        fs::write(album.join("001-test.jpg"), "fake image").unwrap();
+        fs::write(album.join("description.txt"), "\u{feff}Saved on Windows.").unwrap();
+        let group = tmp.path().join("020-Group");
+        fs::create_dir_all(group.join("010-Inner")).unwrap();
+        fs::write(group.join("010-Inner/001-a.jpg"), "fake image").unwrap();
+        let utf16: Vec<u8> = [0xFF, 0xFE]
+            .into_iter()
+            .chain("# UTF-16 *group*".encode_utf16().flat_map(u16::to_le_bytes))
+            .collect();
+        fs::write(group.join("description.md"), utf16).unwrap();
+
+        let manifest = scan(tmp.path()).unwrap();
+
+        let desc = manifest.albums[0].description.as_ref().unwrap();
+        assert_eq!(desc, "<p>Saved on Windows.</p>");
+        let group = manifest
+            .navigation
+            .iter()
+            .find(|n| n.title == "Group")
+            .unwrap();
+        assert_eq!(
+            group.description.as_deref(),
+            Some("<h1>UTF-16 <em>group</em></h1>\n")
+        );
+    }
+
+    #[test]
+    fn description_txt_with_crlf_splits_paragraphs() {
+        let tmp = TempDir::new().unwrap();
+        let album = tmp.path().join("010-Test");
+        fs::create_dir_all(&album).unwrap();
+        fs::write(album.join("001-test.jpg"), "fake image").unwrap();
+        fs::write(
+            album.join("description.txt"),
+            "First paragraph.\r\n\r\nSecond paragraph.\r\n",
+        )
+        .unwrap();
+
+        let manifest = scan(tmp.path()).unwrap();
+        let desc = manifest.albums[0].description.as_ref().unwrap();
+        assert_eq!(desc, "<p>First paragraph.</p>\n<p>Second paragraph.</p>");
+    }
+
     #[test]
     fn description_txt_linkifies_urls() {
         let tmp = TempDir::new().unwrap();
@@ -897,6 +1557,75 @@ mod tests {
         assert!(desc.contains(r#"<a href="https://example.com">https://example.com</a>"#));
     }
 
+    #[test]
+    fn smart_typography_applies_to_descriptions() {
+        let tmp = TempDir::new().unwrap();
+        fs::write(tmp.path().join("config.toml"), "smart_typography = true\n").unwrap();
+        fs::write(tmp.path().join("site.txt"), "Light -- \"mostly\"...").unwrap();
+        let album = tmp.path().join("010-Test");
+        fs::create_dir_all(&album).unwrap();
+        fs::write(album.join("001-test.jpg"), "fake image").unwrap();
+        fs::write(
+            album.join("description.md"),
+            "It's \"film\" -- shot with `--iso=400`.",
+        )
+        .unwrap();
+
+        let manifest = scan(tmp.path()).unwrap();
+        assert_eq!(
+            manifest.description.as_deref(),
+            Some("<p>Light — “mostly”…</p>")
+        );
+        let desc = manifest.albums[0].description.as_ref().unwrap();
+        assert!(desc.contains("It’s “film” — shot with <code>--iso=400</code>."));
+    }
+
+    #[test]
+    fn smart_typography_off_by_default() {
+        let tmp = TempDir::new().unwrap();
+        let album = tmp.path().join("010-Test");
+        fs::create_dir_all(&album).unwrap();
+        fs::write(album.join("001-test.jpg"), "fake image").unwrap();
+        fs::write(album.join("description.txt"), "It's -- fine").unwrap();
+
+        let manifest = scan(tmp.path()).unwrap();
+        let desc = manifest.albums[0].description.as_ref().unwrap();
+        assert_eq!(desc, "<p>It's -- fine</p>");
+    }
+
+    #[test]
+    fn external_description_links_open_in_new_tab() {
+        let tmp = TempDir::new().unwrap();
+        fs::write(
+            tmp.path().join("config.toml"),
+            "base_url = \"https://photos.example.com\"\nexternal_links_new_tab = true\n",
+        )
+        .unwrap();
+        let album = tmp.path().join("010-Test");
+        fs::create_dir_all(&album).unwrap();
+        fs::write(album.join("001-test.jpg"), "fake image").unwrap();
+        fs::write(
+            album.join("description.md"),
+            "See [the shop](https://shop.example.org) and [more](https://photos.example.com/ny/).",
+        )
+        .unwrap();
+        fs::write(
+            tmp.path().join("site.txt"),
+            "Prints at https://shop.example.org",
+        )
+        .unwrap();
+
+        let manifest = scan(tmp.path()).unwrap();
+        let desc = manifest.albums[0].description.as_ref().unwrap();
+        assert!(desc.contains(
+            r#"<a href="https://shop.example.org" target="_blank" rel="noopener noreferrer">the shop</a>"#
+        ));
+        assert!(desc.contains(r#"<a href="https://photos.example.com/ny/">more</a>"#));
+        assert!(manifest.description.as_ref().unwrap().contains(
+            r#"<a href="https://shop.example.org" target="_blank" rel="noopener noreferrer">"#
+        ));
+    }
+
     #[test]
     fn description_md_renders_markdown() {
         let tmp = TempDir::new().unwrap();
@@ -915,6 +1644,27 @@ mod tests {
         assert!(desc.contains("<em>italic</em>"));
     }
 
+    #[test]
+    fn description_keeps_plain_text_alongside_html() {
+        let tmp = TempDir::new().unwrap();
+        let album = tmp.path().join("010-Test");
+        fs::create_dir_all(&album).unwrap();
+        fs::write(album.join("001-test.jpg"), "fake image").unwrap();
+        fs::write(
+            album.join("description.md"),
+            "\nFish & *chips*\r\n\r\nSee <https://example.com>\n",
+        )
+        .unwrap();
+
+        let manifest = scan(tmp.path()).unwrap();
+        let album = &manifest.albums[0];
+        let html = album.description.as_deref().unwrap();
+        let text = album.description_text.as_deref().unwrap();
+        assert!(html.contains("<p>Fish &amp; <em>chips</em></p>"));
+        assert_eq!(text, "Fish & chips\n\nSee https://example.com");
+        assert_ne!(html, text);
+    }
+
     #[test]
     fn description_empty_file_returns_none() {
         let tmp = TempDir::new().unwrap();
@@ -970,18 +1720,287 @@ mod tests {
         fs::write(mixed.join("001-photo.jpg"), "fake image").unwrap();
 
         let result = scan(tmp.path());
-        assert!(matches!(result, Err(ScanError::MixedContent(_))));
+        assert!(matches!(result, Err(ScanError::MixedContent { .. })));
     }
 
     #[test]
-    fn duplicate_number_is_error() {
+    fn mixed_content_error_lists_entries_and_fix() {
+        let tmp = TempDir::new().unwrap();
+        let mixed = tmp.path().join("010-Mixed");
+        fs::create_dir_all(mixed.join("020-Sub")).unwrap();
+        fs::create_dir_all(mixed.join("010-Other")).unwrap();
+        fs::write(mixed.join("002-b.jpg"), "fake image").unwrap();
+        fs::write(mixed.join("001-a.jpg"), "fake image").unwrap();
+
+        let err = scan(tmp.path()).unwrap_err();
+        let ScanError::MixedContent {
+            images, subdirs, ..
+        } = &err
+        else {
+            panic!("expected MixedContent, got {err:?}");
+        };
+        assert_eq!(images, &["001-a.jpg", "002-b.jpg"]);
+        assert_eq!(subdirs, &["010-Other", "020-Sub"]);
+        let message = err.to_string();
+        assert!(
+            message.contains("images: 001-a.jpg, 002-b.jpg"),
+            "{message}"
+        );
+        assert!(
+            message.contains("subdirectories: 010-Other, 020-Sub"),
+            "{message}"
+        );
+        assert!(
+            message.contains("Move the images into a subdirectory"),
+            "{message}"
+        );
+    }
+
+    #[test]
+    fn numbering_gap_warns_on_large_jump_only() {
         let tmp = TempDir::new().unwrap();
+        let typo = tmp.path().join("010-Typo");
+        let gapped = tmp.path().join("020-Gapped");
+        fs::create_dir_all(&typo).unwrap();
+        fs::create_dir_all(&gapped).unwrap();
+        for name in ["001-a.jpg", "002-b.jpg", "900-c.jpg"] {
+            fs::write(typo.join(name), name).unwrap();
+        }
+        // The unnumbered image sorts at UNNUMBERED_IMAGE_BASE: no gap.
+        for name in [
+            "010-a.jpg",
+            "030-b.jpg",
+            "100-c.jpg",
+            "190-d.jpg",
+            "unnumbered.jpg",
+        ] {
+            fs::write(gapped.join(name), name).unwrap();
+        }
+
+        let manifest = scan(tmp.path()).unwrap();
+        assert_eq!(
+            numbering_gaps(&manifest),
+            vec![NumberingGap {
+                album: find_album(&manifest, "Typo").path.clone(),
+                from: 2,
+                to: 900,
+            }]
+        );
+    }
 
+    #[test]
+    fn numbering_gap_threshold_configurable() {
+        let tmp = TempDir::new().unwrap();
         let album = tmp.path().join("010-Album");
         fs::create_dir_all(&album).unwrap();
+        fs::write(album.join("001-a.jpg"), "a").unwrap();
+        fs::write(album.join("050-b.jpg"), "b").unwrap();
 
-        // Create two images with the same number (scan only checks extension)
-        fs::write(album.join("001-first.jpg"), "fake image").unwrap();
+        fs::write(
+            tmp.path().join("config.toml"),
+            "[auto_indexing]\ngap_warning = 20\n",
+        )
+        .unwrap();
+        assert_eq!(numbering_gaps(&scan(tmp.path()).unwrap()).len(), 1);
+
+        fs::write(
+            album.join("config.toml"),
+            "[auto_indexing]\ngap_warning = 0\n",
+        )
+        .unwrap();
+        assert!(numbering_gaps(&scan(tmp.path()).unwrap()).is_empty());
+    }
+
+    #[test]
+    fn unsupported_formats_reported_not_silently_skipped() {
+        let tmp = TempDir::new().unwrap();
+        let iphone = tmp.path().join("010-iPhone");
+        fs::create_dir_all(&iphone).unwrap();
+        fs::write(iphone.join("IMG_0002.HEIC"), "heic").unwrap();
+        fs::write(iphone.join("IMG_0001.heic"), "heic").unwrap();
+        let mixed = tmp.path().join("020-Mixed");
+        fs::create_dir_all(&mixed).unwrap();
+        fs::write(mixed.join("001-a.jpg"), "a").unwrap();
+        fs::write(mixed.join("002-b.dng"), "raw").unwrap();
+        // Hidden and build directories are not content.
+        fs::create_dir_all(tmp.path().join(".cache")).unwrap();
+        fs::write(tmp.path().join(".cache/x.heic"), "heic").unwrap();
+
+        let manifest = scan(tmp.path()).unwrap();
+        assert_eq!(
+            manifest.unsupported_images,
+            vec![
+                UnsupportedImages {
+                    dir: "010-iPhone".to_string(),
+                    files: vec!["IMG_0001.heic".to_string(), "IMG_0002.HEIC".to_string()],
+                },
+                UnsupportedImages {
+                    dir: "020-Mixed".to_string(),
+                    files: vec!["002-b.dng".to_string()],
+                },
+            ]
+        );
+        let message = manifest.unsupported_images[0].message();
+        assert!(message.contains("IMG_0001.heic"), "{message}");
+        assert!(message.contains("convert to JPEG"), "{message}");
+        // The supported image still makes an album.
+        assert_eq!(manifest.albums.len(), 1);
+    }
+
+    #[test]
+    fn entry_list_caps_long_lists() {
+        let names = (1..=7).map(|i| format!("{i:03}.jpg")).collect::<Vec<_>>();
+        assert_eq!(
+            entry_list(&names),
+            "001.jpg, 002.jpg, 003.jpg, 004.jpg, 005.jpg and 2 more"
+        );
+    }
+
+    #[test]
+    fn sibling_dirs_colliding_after_prefix_strip_is_error() {
+        let tmp = TempDir::new().unwrap();
+        for dir in ["010-Foo", "020-Foo"] {
+            let album = tmp.path().join(dir);
+            fs::create_dir_all(&album).unwrap();
+            fs::write(album.join(format!("001-{dir}.jpg")), dir).unwrap();
+        }
+
+        let err = scan(tmp.path()).unwrap_err();
+        match err {
+            ScanError::DuplicatePath(slug, first, second) => {
+                assert_eq!(slug, "foo");
+                assert!(first.ends_with("010-Foo"));
+                assert!(second.ends_with("020-Foo"));
+            }
+            other => panic!("expected DuplicatePath, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn nested_albums_colliding_after_prefix_strip_is_error() {
+        let tmp = TempDir::new().unwrap();
+        for dir in ["010-Travel/010-Japan", "020-Travel/010-Japan"] {
+            let album = tmp.path().join(dir);
+            fs::create_dir_all(&album).unwrap();
+            fs::write(album.join("001-tokyo.jpg"), dir).unwrap();
+        }
+
+        let result = scan(tmp.path());
+        assert!(
+            matches!(result, Err(ScanError::DuplicatePath(ref slug, _, _)) if slug == "travel/japan"),
+            "got {result:?}"
+        );
+    }
+
+    #[test]
+    fn unnumbered_images_sort_after_numbered_by_filename() {
+        let tmp = TempDir::new().unwrap();
+        let album = tmp.path().join("010-Album");
+        fs::create_dir_all(&album).unwrap();
+        for name in ["banana.jpg", "Apple.jpg", "002-second.jpg", "001-first.jpg"] {
+            fs::write(album.join(name), name).unwrap();
+        }
+
+        // Feed images in deliberately scrambled order: the result must not
+        // depend on the caller's (i.e. `read_dir`'s) ordering.
+        let paths: Vec<PathBuf> = ["banana.jpg", "002-second.jpg", "Apple.jpg", "001-first.jpg"]
+            .iter()
+            .map(|n| album.join(n))
+            .collect();
+        let refs: Vec<&PathBuf> = paths.iter().collect();
+        let built = build_album(&album, tmp.path(), &refs, SiteConfig::default()).unwrap();
+
+        let filenames: Vec<&str> = built.images.iter().map(|i| i.filename.as_str()).collect();
+        assert_eq!(
+            filenames,
+            ["001-first.jpg", "002-second.jpg", "Apple.jpg", "banana.jpg"]
+        );
+        assert_eq!(built.images[2].number, UNNUMBERED_IMAGE_BASE);
+        assert_eq!(built.images[3].number, UNNUMBERED_IMAGE_BASE + 1);
+    }
+
+    #[test]
+    fn unnumbered_images_sort_after_very_large_numbers() {
+        let tmp = TempDir::new().unwrap();
+        let album = tmp.path().join("010-Album");
+        fs::create_dir_all(&album).unwrap();
+        fs::write(album.join("2000000-big.jpg"), "a").unwrap();
+        fs::write(album.join("loose.jpg"), "b").unwrap();
+
+        let manifest = scan(tmp.path()).unwrap();
+        let filenames: Vec<&str> = manifest.albums[0]
+            .images
+            .iter()
+            .map(|i| i.filename.as_str())
+            .collect();
+        assert_eq!(filenames, ["2000000-big.jpg", "loose.jpg"]);
+    }
+
+    #[test]
+    fn unnumbered_images_after_the_largest_number_are_an_error() {
+        let tmp = TempDir::new().unwrap();
+        let album = tmp.path().join("010-Album");
+        fs::create_dir_all(&album).unwrap();
+        fs::write(album.join(format!("{}-big.jpg", u32::MAX - 1)), "a").unwrap();
+        fs::write(album.join("loose.jpg"), "b").unwrap();
+
+        // One slot is left after the largest number: a single unnumbered
+        // image still fits.
+        let manifest = scan(tmp.path()).unwrap();
+        assert_eq!(manifest.albums[0].images[1].number, u32::MAX);
+
+        fs::write(album.join("looser.jpg"), "c").unwrap();
+        let err = scan(tmp.path()).unwrap_err();
+        assert!(
+            matches!(err, ScanError::UnnumberedOverflow(_, n) if n == u32::MAX - 1),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn titleize_applies_to_filename_image_titles() {
+        let tmp = TempDir::new().unwrap();
+        let album = tmp.path().join("010-Bay_Area");
+        fs::create_dir_all(&album).unwrap();
+        fs::write(album.join("001-golden_gate-bridge.jpg"), "a").unwrap();
+        fs::write(album.join("002-alcatraz.jpg"), "b").unwrap();
+        fs::write(
+            tmp.path().join("config.toml"),
+            "[naming]\ntitleize = true\n",
+        )
+        .unwrap();
+
+        let manifest = scan(tmp.path()).unwrap();
+        let album = &manifest.albums[0];
+        assert_eq!(album.images[0].title.as_deref(), Some("Golden Gate Bridge"));
+        assert_eq!(album.images[1].title.as_deref(), Some("Alcatraz"));
+        // Slugs are unaffected.
+        assert_eq!(album.images[0].slug, "golden-gate-bridge");
+    }
+
+    #[test]
+    fn titleize_off_keeps_raw_filename_title() {
+        let tmp = TempDir::new().unwrap();
+        let album = tmp.path().join("010-Album");
+        fs::create_dir_all(&album).unwrap();
+        fs::write(album.join("001-golden_gate-bridge.jpg"), "a").unwrap();
+
+        let manifest = scan(tmp.path()).unwrap();
+        assert_eq!(
+            manifest.albums[0].images[0].title.as_deref(),
+            Some("golden_gate bridge")
+        );
+    }
+
+    #[test]
+    fn duplicate_number_is_error() {
+        let tmp = TempDir::new().unwrap();
+
+        let album = tmp.path().join("010-Album");
+        fs::create_dir_all(&album).unwrap();
+
+        // Create two images with the same number (scan only checks extension)
+        fs::write(album.join("001-first.jpg"), "fake image").unwrap();
         fs::write(album.join("001-second.jpg"), "fake image").unwrap();
 
         let result = scan(tmp.path());
@@ -1135,6 +2154,150 @@ mod tests {
         assert_eq!(titles, vec!["First", "Second", "Third"]);
     }
 
+    #[test]
+    fn front_matter_overrides_slug_and_nav_title() {
+        let tmp = TempDir::new().unwrap();
+
+        fs::write(
+            tmp.path().join("010-who-am-i.md"),
+            "+++\nslug = \"about\"\nnav_title = \"About me\"\n+++\n# Hello\n\nSome content.",
+        )
+        .unwrap();
+
+        let album = tmp.path().join("010-Test");
+        fs::create_dir_all(&album).unwrap();
+        fs::write(album.join("001-test.jpg"), "fake image").unwrap();
+
+        let manifest = scan(tmp.path()).unwrap();
+
+        let page = manifest.pages.first().unwrap();
+        assert_eq!(page.slug, "about");
+        assert_eq!(page.link_title, "About me");
+        assert_eq!(page.title, "Hello");
+        assert_eq!(page.sort_key, 10);
+        assert!(page.in_nav);
+        assert!(page.body.starts_with("# Hello"));
+        assert!(!page.body.contains("+++"));
+    }
+
+    #[test]
+    fn front_matter_after_byte_order_mark() {
+        let tmp = TempDir::new().unwrap();
+        fs::write(
+            tmp.path().join("010-who-am-i.md"),
+            "\u{feff}+++\nslug = \"about\"\n+++\n# Hello",
+        )
+        .unwrap();
+
+        let manifest = scan(tmp.path()).unwrap();
+
+        let page = manifest.pages.first().unwrap();
+        assert_eq!(page.slug, "about");
+        assert_eq!(page.body, "# Hello");
+    }
+
+    #[test]
+    fn front_matter_with_dashes_overrides_title_of_a_link_page() {
+        let tmp = TempDir::new().unwrap();
+
+        fs::write(
+            tmp.path().join("020-github.md"),
+            "---\ntitle = \"My code\"\n---\nhttps://github.com/example\n",
+        )
+        .unwrap();
+
+        let album = tmp.path().join("010-Test");
+        fs::create_dir_all(&album).unwrap();
+        fs::write(album.join("001-test.jpg"), "fake image").unwrap();
+
+        let manifest = scan(tmp.path()).unwrap();
+
+        let page = manifest.pages.first().unwrap();
+        assert!(page.is_link);
+        assert_eq!(page.title, "My code");
+        assert_eq!(page.link_title, "github");
+        assert_eq!(page.slug, "github");
+    }
+
+    #[test]
+    fn invalid_front_matter_rejected() {
+        for body in [
+            "+++\nslgu = \"x\"\n+++\n# Typo",
+            "+++\nslug = \"a/b\"\n+++\n# Slash",
+            "+++\nslug = \"x\"\n# Unclosed",
+            "---\ntoc = \"yes\"\n---\n# Not a bool",
+            "---\nslug: about\n---\n# Not TOML",
+            "---\n\nA page opening with a rule",
+        ] {
+            let tmp = TempDir::new().unwrap();
+            fs::write(tmp.path().join("010-page.md"), body).unwrap();
+            let album = tmp.path().join("010-Test");
+            fs::create_dir_all(&album).unwrap();
+            fs::write(album.join("001-test.jpg"), "fake image").unwrap();
+
+            let err = scan(tmp.path()).unwrap_err();
+            assert!(
+                matches!(&err, ScanError::InvalidFrontMatter(path, _) if path.ends_with("010-page.md")),
+                "{body:?}: {err}"
+            );
+        }
+    }
+
+    #[test]
+    fn reserved_or_duplicate_page_slugs_rejected() {
+        let tmp = TempDir::new().unwrap();
+        fs::write(
+            tmp.path().join("010-home.md"),
+            "+++\nslug = \"index\"\n+++\n# Home",
+        )
+        .unwrap();
+        let err = scan(tmp.path()).unwrap_err();
+        assert!(
+            matches!(&err, ScanError::ReservedPageSlug(path) if path.ends_with("010-home.md")),
+            "{err}"
+        );
+
+        let tmp = TempDir::new().unwrap();
+        fs::write(tmp.path().join("010-about.md"), "# About").unwrap();
+        fs::write(
+            tmp.path().join("020-me.md"),
+            "+++\nslug = \"about\"\n+++\n# Me",
+        )
+        .unwrap();
+        // Links aren't written out, so they don't take the slug.
+        fs::write(tmp.path().join("030-about.md"), "https://example.com").unwrap();
+        let err = scan(tmp.path()).unwrap_err();
+        assert!(
+            matches!(&err, ScanError::DuplicatePageSlug(slug, a, b)
+                if slug == "about" && a.ends_with("010-about.md") && b.ends_with("020-me.md")),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn page_embedded_sibling_images_are_page_assets() {
+        let tmp = TempDir::new().unwrap();
+
+        fs::write(
+            tmp.path().join("040-about.md"),
+            "# About\n\n![Me](portrait.jpg)\n![Again](./portrait.jpg)\n\
+             ![Remote](https://example.com/x.jpg)\n![Missing](gone.jpg)",
+        )
+        .unwrap();
+        fs::write(tmp.path().join("portrait.jpg"), "fake image").unwrap();
+
+        let album = tmp.path().join("010-Test");
+        fs::create_dir_all(&album).unwrap();
+        fs::write(album.join("001-test.jpg"), "fake image").unwrap();
+
+        // A root-level image would otherwise be mixed content next to albums.
+        let manifest = scan(tmp.path()).unwrap();
+
+        let page = find_page(&manifest, "about");
+        assert_eq!(page.assets, vec!["portrait.jpg"]);
+        assert_eq!(manifest.albums.len(), 1);
+    }
+
     #[test]
     fn link_page_in_fixtures() {
         let tmp = setup_fixtures();
@@ -1158,7 +2321,10 @@ mod tests {
         // Root config.toml overrides ALL defaults — verify a sample from each section
         assert_eq!(manifest.config.thumbnails.aspect_ratio, [3, 4]);
         assert_eq!(manifest.config.images.quality, 85);
-        assert_eq!(manifest.config.images.sizes, vec![600, 1200, 1800]);
+        assert_eq!(
+            manifest.config.images.sizes,
+            ImageSizes::Explicit(vec![600, 1200, 1800])
+        );
         assert_eq!(manifest.config.theme.thumbnail_gap, "0.75rem");
         assert_eq!(manifest.config.theme.mat_x.size, "4vw");
         assert_eq!(manifest.config.theme.mat_y.min, "1.5rem");
@@ -1299,7 +2465,7 @@ mod tests {
         // Other defaults preserved
         assert_eq!(
             manifest.albums[0].config.images.sizes,
-            vec![800, 1400, 2080]
+            ImageSizes::Explicit(vec![800, 1400, 2080])
         );
     }
 
@@ -1352,7 +2518,10 @@ mod tests {
         // Japan: quality from its own config (70), aspect from group (1:1), sizes from stock
         assert_eq!(japan_album.config.images.quality, 70);
         assert_eq!(japan_album.config.thumbnails.aspect_ratio, [1, 1]);
-        assert_eq!(japan_album.config.images.sizes, vec![800, 1400, 2080]);
+        assert_eq!(
+            japan_album.config.images.sizes,
+            ImageSizes::Explicit(vec![800, 1400, 2080])
+        );
 
         let italy_album = manifest.albums.iter().find(|a| a.title == "Italy").unwrap();
         // Italy: quality from root (85), aspect from group (1:1)
@@ -1360,6 +2529,117 @@ mod tests {
         assert_eq!(italy_album.config.thumbnails.aspect_ratio, [1, 1]);
     }
 
+    #[test]
+    fn album_exclude_sizes_trims_inherited_sizes() {
+        let tmp = TempDir::new().unwrap();
+        fs::write(
+            tmp.path().join("config.toml"),
+            "[images]\nsizes = [600, 1200, 2400]\n",
+        )
+        .unwrap();
+        let small = tmp.path().join("010-Small");
+        fs::create_dir_all(&small).unwrap();
+        fs::write(small.join("001-a.jpg"), "fake image").unwrap();
+        fs::write(
+            small.join("config.toml"),
+            "[images]\nexclude_sizes = [2400]\n",
+        )
+        .unwrap();
+        let large = tmp.path().join("020-Large");
+        fs::create_dir_all(&large).unwrap();
+        fs::write(large.join("001-b.jpg"), "fake image").unwrap();
+
+        let manifest = scan(tmp.path()).unwrap();
+
+        assert_eq!(
+            find_album(&manifest, "Small").config.images.sizes,
+            ImageSizes::Explicit(vec![600, 1200])
+        );
+        assert_eq!(
+            find_album(&manifest, "Large").config.images.sizes,
+            ImageSizes::Explicit(vec![600, 1200, 2400])
+        );
+    }
+
+    #[test]
+    fn ordering_title_sorts_a_groups_children_alphabetically() {
+        let tmp = TempDir::new().unwrap();
+        for group in ["010-Reference", "020-Travel"] {
+            for child in ["010-Zebra", "020-apple", "030-Mango"] {
+                let dir = tmp.path().join(group).join(child);
+                fs::create_dir_all(&dir).unwrap();
+                fs::write(dir.join("001-a.jpg"), "fake image").unwrap();
+            }
+        }
+        fs::write(
+            tmp.path().join("010-Reference/config.toml"),
+            "[ordering]\nsort = \"title\"\n",
+        )
+        .unwrap();
+
+        let manifest = scan(tmp.path()).unwrap();
+
+        let children = |group: &str| {
+            let item = manifest
+                .navigation
+                .iter()
+                .find(|n| n.title == group)
+                .unwrap();
+            item.children
+                .iter()
+                .map(|c| c.title.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(children("Reference"), ["apple", "Mango", "Zebra"]);
+        assert_eq!(children("Travel"), ["Zebra", "apple", "Mango"]);
+        // Top-level order is still by number.
+        let top: Vec<_> = manifest
+            .navigation
+            .iter()
+            .map(|n| n.title.as_str())
+            .collect();
+        assert_eq!(top, ["Reference", "Travel"]);
+        // Albums are scanned in the same order.
+        let reference: Vec<_> = manifest
+            .albums
+            .iter()
+            .filter(|a| a.path.starts_with("Reference/"))
+            .map(|a| a.title.as_str())
+            .collect();
+        assert_eq!(reference, ["apple", "Mango", "Zebra"]);
+    }
+
+    #[test]
+    fn ordering_title_uses_album_title_override() {
+        let tmp = TempDir::new().unwrap();
+        fs::write(
+            tmp.path().join("config.toml"),
+            "[ordering]\nsort = \"title\"\n",
+        )
+        .unwrap();
+        for child in ["010-Apple", "020-Mango", "030-Zebra"] {
+            let dir = tmp.path().join(child);
+            fs::create_dir_all(&dir).unwrap();
+            fs::write(dir.join("001-a.jpg"), "fake image").unwrap();
+        }
+        fs::write(
+            tmp.path().join("010-Apple/config.toml"),
+            "[album]\ntitle = \"Orchard\"\n",
+        )
+        .unwrap();
+
+        let manifest = scan(tmp.path()).unwrap();
+
+        let nav: Vec<_> = manifest
+            .navigation
+            .iter()
+            .map(|n| n.title.as_str())
+            .collect();
+        assert_eq!(nav, ["Mango", "Orchard", "Zebra"]);
+        let albums: Vec<_> = manifest.albums.iter().map(|a| a.title.as_str()).collect();
+        assert_eq!(albums, nav);
+    }
+
     #[test]
     fn fixture_per_gallery_config_overrides_root() {
         let tmp = setup_fixtures();
@@ -1370,7 +2650,10 @@ mod tests {
         assert_eq!(landscapes.config.images.quality, 75);
         assert_eq!(landscapes.config.thumbnails.aspect_ratio, [1, 1]);
         // Other values inherited from root config
-        assert_eq!(landscapes.config.images.sizes, vec![600, 1200, 1800]);
+        assert_eq!(
+            landscapes.config.images.sizes,
+            ImageSizes::Explicit(vec![600, 1200, 1800])
+        );
         assert_eq!(landscapes.config.colors.light.background, "#fafafa");
     }
 
@@ -1423,7 +2706,10 @@ mod tests {
         assert_eq!(ls.config.font.font_type, crate::config::FontType::Serif);
 
         // Inherited from root config — image sizes (not overridden by gallery)
-        assert_eq!(ls.config.images.sizes, vec![600, 1200, 1800]);
+        assert_eq!(
+            ls.config.images.sizes,
+            ImageSizes::Explicit(vec![600, 1200, 1800])
+        );
     }
 
     #[test]
@@ -1769,6 +3055,293 @@ mod tests {
         assert!(manifest.albums[0].images.iter().any(|i| i.number == 3));
     }
 
+    #[test]
+    fn thumb_only_album_is_a_one_image_album() {
+        let tmp = TempDir::new().unwrap();
+        let album = tmp.path().join("010-Test");
+        fs::create_dir_all(&album).unwrap();
+        fs::write(album.join("001-thumb.jpg"), "fake image").unwrap();
+
+        let manifest = scan(tmp.path()).unwrap();
+        let album = &manifest.albums[0];
+        // The thumb is both the preview and the only gallery image, so the
+        // album gets a non-empty grid and one image page.
+        assert!(album.preview_image.contains("001-thumb"));
+        assert_eq!(album.images.len(), 1);
+        assert_eq!(album.images[0].source_path, album.preview_image);
+    }
+
+    #[test]
+    fn build_album_without_images_is_error() {
+        let tmp = TempDir::new().unwrap();
+        let album = tmp.path().join("010-Empty");
+        fs::create_dir_all(&album).unwrap();
+
+        let result = build_album(&album, tmp.path(), &[], SiteConfig::default());
+        assert!(matches!(result, Err(ScanError::EmptyAlbum(p)) if p == album));
+    }
+
+    #[test]
+    fn featured_image_overrides_preview_and_stays_in_grid() {
+        let tmp = TempDir::new().unwrap();
+        let album = tmp.path().join("010-Test");
+        fs::create_dir_all(&album).unwrap();
+        fs::write(album.join("001-first.jpg"), "fake image").unwrap();
+        fs::write(album.join("002-thumb.jpg"), "fake image").unwrap();
+        fs::write(album.join("003-harbor.jpg"), "fake image").unwrap();
+        fs::write(
+            album.join("config.toml"),
+            "[thumbnails]\nfeatured = \"003\"\n",
+        )
+        .unwrap();
+
+        let manifest = scan(tmp.path()).unwrap();
+        let album = &manifest.albums[0];
+        // Featured wins over both the thumb file and image #1...
+        assert!(album.preview_image.ends_with("003-harbor.jpg"));
+        // ...without changing the album's own order or contents.
+        let numbers: Vec<u32> = album.images.iter().map(|i| i.number).collect();
+        assert_eq!(numbers, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn featured_image_by_file_name() {
+        let tmp = TempDir::new().unwrap();
+        let album = tmp.path().join("010-Test");
+        fs::create_dir_all(&album).unwrap();
+        fs::write(album.join("001-first.jpg"), "fake image").unwrap();
+        fs::write(album.join("sunset.jpg"), "fake image").unwrap();
+        fs::write(
+            album.join("config.toml"),
+            "[thumbnails]\nfeatured = \"sunset.jpg\"\n",
+        )
+        .unwrap();
+
+        let manifest = scan(tmp.path()).unwrap();
+        assert!(manifest.albums[0].preview_image.ends_with("sunset.jpg"));
+        assert_eq!(manifest.albums[0].images.len(), 2);
+    }
+
+    #[test]
+    fn featured_image_missing_is_error() {
+        let tmp = TempDir::new().unwrap();
+        let album = tmp.path().join("010-Test");
+        fs::create_dir_all(&album).unwrap();
+        fs::write(album.join("001-first.jpg"), "fake image").unwrap();
+        fs::write(
+            album.join("config.toml"),
+            "[thumbnails]\nfeatured = \"7\"\n",
+        )
+        .unwrap();
+
+        let result = scan(tmp.path());
+        assert!(matches!(result, Err(ScanError::FeaturedNotFound(f, _)) if f == "7"));
+    }
+
+    #[test]
+    fn album_meta_toml_is_parsed() {
+        let tmp = TempDir::new().unwrap();
+        let album = tmp.path().join("010-Test");
+        fs::create_dir_all(&album).unwrap();
+        fs::write(album.join("001-first.jpg"), "fake image").unwrap();
+        fs::write(
+            album.join("meta.toml"),
+            "location = \"Kyoto, Japan\"\ndate = 2024-04-12\ncamera = \"Fujifilm X100V\"\n",
+        )
+        .unwrap();
+
+        let manifest = scan(tmp.path()).unwrap();
+        let album = &manifest.albums[0];
+        assert_eq!(
+            album.meta,
+            Some(AlbumMeta {
+                location: Some("Kyoto, Japan".to_string()),
+                date: Some("2024-04-12".to_string()),
+                camera: Some("Fujifilm X100V".to_string()),
+            })
+        );
+        assert!(album.support_files.contains(&"meta.toml".to_string()));
+    }
+
+    #[test]
+    fn album_files_listed_in_support_files() {
+        let tmp = TempDir::new().unwrap();
+        let album = tmp.path().join("010-Test");
+        fs::create_dir_all(&album).unwrap();
+        fs::write(album.join("001-test.jpg"), "fake image").unwrap();
+        fs::write(album.join("prices.pdf"), "%PDF").unwrap();
+        fs::write(album.join("notes.zip"), "zip").unwrap();
+
+        let manifest = scan(tmp.path()).unwrap();
+        let album = &manifest.albums[0];
+        assert!(album.support_files.contains(&"prices.pdf".to_string()));
+        assert!(!album.support_files.contains(&"notes.zip".to_string()));
+    }
+
+    #[test]
+    fn album_without_meta_toml_has_no_meta() {
+        let tmp = TempDir::new().unwrap();
+        let album = tmp.path().join("010-Test");
+        fs::create_dir_all(&album).unwrap();
+        fs::write(album.join("001-first.jpg"), "fake image").unwrap();
+        fs::write(album.join("meta.toml"), "# nothing yet\n").unwrap();
+
+        let manifest = scan(tmp.path()).unwrap();
+        assert_eq!(manifest.albums[0].meta, None);
+    }
+
+    #[test]
+    fn album_meta_unknown_key_is_error() {
+        let tmp = TempDir::new().unwrap();
+        let album = tmp.path().join("010-Test");
+        fs::create_dir_all(&album).unwrap();
+        fs::write(album.join("001-first.jpg"), "fake image").unwrap();
+        fs::write(album.join("meta.toml"), "camra = \"X100V\"\n").unwrap();
+
+        let err = scan(tmp.path()).unwrap_err();
+        assert!(
+            matches!(&err, ScanError::InvalidAlbumMeta(path, _) if path.ends_with("meta.toml"))
+        );
+        assert!(err.to_string().contains("camra"), "{err}");
+    }
+
+    #[test]
+    fn sidecar_front_matter_sets_image_quality() {
+        let tmp = TempDir::new().unwrap();
+        let album = tmp.path().join("010-Test");
+        fs::create_dir_all(&album).unwrap();
+        fs::write(album.join("001-first.jpg"), "fake image").unwrap();
+        fs::write(album.join("002-frost.jpg"), "fake image").unwrap();
+        fs::write(
+            album.join("002-frost.txt"),
+            "---\nquality: 95\n---\nFrost\n",
+        )
+        .unwrap();
+
+        let manifest = scan(tmp.path()).unwrap();
+        let images = &manifest.albums[0].images;
+        assert_eq!(images[0].quality, None);
+        assert_eq!(images[1].quality, Some(95));
+        assert_eq!(images[1].description.as_deref(), Some("Frost"));
+    }
+
+    #[test]
+    fn sidecar_quality_out_of_range_is_error() {
+        let tmp = TempDir::new().unwrap();
+        let album = tmp.path().join("010-Test");
+        fs::create_dir_all(&album).unwrap();
+        fs::write(album.join("001-first.jpg"), "fake image").unwrap();
+        fs::write(album.join("001-first.txt"), "---\nquality: 120\n---\n").unwrap();
+
+        let result = scan(tmp.path());
+        assert!(
+            matches!(&result, Err(ScanError::InvalidSidecar(path, _)) if path.ends_with("001-first.txt")),
+            "{result:?}"
+        );
+    }
+
+    #[test]
+    fn featured_image_is_not_inherited() {
+        let tmp = TempDir::new().unwrap();
+        let group = tmp.path().join("010-Group");
+        let album = group.join("010-Album");
+        fs::create_dir_all(&album).unwrap();
+        fs::write(
+            group.join("config.toml"),
+            "[thumbnails]\nfeatured = \"2\"\n",
+        )
+        .unwrap();
+        fs::write(album.join("001-first.jpg"), "fake image").unwrap();
+        fs::write(album.join("002-second.jpg"), "fake image").unwrap();
+
+        let manifest = scan(tmp.path()).unwrap();
+        let album = &manifest.albums[0];
+        assert!(album.preview_image.ends_with("001-first.jpg"));
+        assert_eq!(album.config.thumbnails.featured, None);
+    }
+
+    #[test]
+    fn album_config_title_and_description_win() {
+        let tmp = TempDir::new().unwrap();
+        let album = tmp.path().join("010-dawn-patrol");
+        fs::create_dir_all(&album).unwrap();
+        fs::write(album.join("001-first.jpg"), "fake image").unwrap();
+        fs::write(album.join("description.md"), "From the *file*").unwrap();
+        fs::write(
+            album.join("config.toml"),
+            "[album]\ntitle = \"Dawn Patrol, 2024\"\ndescription = \"From config.\\n\\nTwo paragraphs.\"\n",
+        )
+        .unwrap();
+
+        let manifest = scan(tmp.path()).unwrap();
+        let album = &manifest.albums[0];
+        assert_eq!(album.title, "Dawn Patrol, 2024");
+        assert_eq!(manifest.navigation[0].title, "Dawn Patrol, 2024");
+        assert_eq!(
+            album.description_text.as_deref(),
+            Some("From config.\n\nTwo paragraphs.")
+        );
+        let html = album.description.as_deref().unwrap();
+        assert!(html.contains("<p>From config.</p>"), "{html}");
+        assert!(!html.contains("file"));
+        // The album's path still comes from its directory.
+        assert_eq!(album.path, "dawn-patrol");
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn symlink_back_up_the_tree_is_an_error() {
+        let tmp = TempDir::new().unwrap();
+        let group = tmp.path().join("010-Group");
+        let album = group.join("010-Album");
+        fs::create_dir_all(&album).unwrap();
+        fs::write(album.join("001-first.jpg"), "fake image").unwrap();
+        std::os::unix::fs::symlink(&group, group.join("020-Loop")).unwrap();
+
+        let result = scan(tmp.path());
+        assert!(
+            matches!(&result, Err(ScanError::SymlinkCycle { dir, ancestor })
+                if dir.ends_with("010-Group/020-Loop") && ancestor.ends_with("010-Group")),
+            "{result:?}"
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn symlinked_album_is_shared_between_groups() {
+        let tmp = TempDir::new().unwrap();
+        let album = tmp.path().join("010-Travel/010-Rome");
+        fs::create_dir_all(&album).unwrap();
+        fs::create_dir_all(tmp.path().join("020-Favorites")).unwrap();
+        fs::write(album.join("001-forum.jpg"), "fake image").unwrap();
+        std::os::unix::fs::symlink(&album, tmp.path().join("020-Favorites/010-Rome")).unwrap();
+
+        let manifest = scan(tmp.path()).unwrap();
+        let paths: Vec<&str> = manifest.albums.iter().map(|a| a.path.as_str()).collect();
+        assert_eq!(paths, ["Travel/Rome", "Favorites/Rome"]);
+        assert_eq!(manifest.canonical_images.len(), 1);
+    }
+
+    #[test]
+    fn album_config_is_not_inherited() {
+        let tmp = TempDir::new().unwrap();
+        let group = tmp.path().join("010-Group");
+        let album = group.join("010-Album");
+        fs::create_dir_all(&album).unwrap();
+        fs::write(
+            group.join("config.toml"),
+            "[album]\ntitle = \"Not mine\"\ndescription = \"Nor this\"\n",
+        )
+        .unwrap();
+        fs::write(album.join("001-first.jpg"), "fake image").unwrap();
+
+        let manifest = scan(tmp.path()).unwrap();
+        let album = &manifest.albums[0];
+        assert_eq!(album.title, "Album");
+        assert_eq!(album.description, None);
+        assert_eq!(album.config.album.title, None);
+    }
+
     #[test]
     fn duplicate_thumb_is_error() {
         let tmp = TempDir::new().unwrap();
@@ -0,0 +1,267 @@
+//! Plain-text helpers shared by the CLI output and the generated site.
+//!
+//! Descriptions travel through the manifests as rendered HTML, and album
+//! descriptions also as plain text from [`markdown_to_text`]. The CLI
+//! tree, album excerpts and the search index want them as one line of
+//! plain text, built from [`one_line`] and [`truncate_desc`], falling back
+//! to [`strip_html_tags`] where only the HTML is at hand.
+
+/// Render markdown as plain text: the words without the markup.
+///
+/// Emphasis, code spans and links keep their text (an autolink keeps its
+/// URL), images keep their alt text, and raw HTML is dropped. Blocks are
+/// separated by a blank line and line breaks are kept, so the result reads
+/// like a `description.txt`.
+pub fn markdown_to_text(markdown: &str) -> String {
+    use pulldown_cmark::{Event, Parser, TagEnd};
+
+    let mut text = String::with_capacity(markdown.len());
+    for event in Parser::new(markdown) {
+        match event {
+            Event::Text(s) | Event::Code(s) => text.push_str(&s),
+            Event::SoftBreak | Event::HardBreak => text.push('\n'),
+            Event::End(
+                TagEnd::Paragraph
+                | TagEnd::Heading(_)
+                | TagEnd::Item
+                | TagEnd::CodeBlock
+                | TagEnd::BlockQuote(_),
+            ) => {
+                let trimmed = text.trim_end().len();
+                text.truncate(trimmed);
+                text.push_str("\n\n");
+            }
+            _ => {}
+        }
+    }
+    text.trim().to_string()
+}
+
+/// Strip HTML tags from a string and decode character entities.
+///
+/// Used where only rendered HTML is at hand (the generate manifest).
+/// Inside a tag, quoted attribute values are skipped as a whole so a `>` in
+/// e.g. `title="a > b"` doesn't end the tag early. Outside tags, entities
+/// are decoded via [`decode_html_entity`].
+pub fn strip_html_tags(html: &str) -> String {
+    let mut result = String::with_capacity(html.len());
+    let mut chars = html.char_indices().peekable();
+    // Inside a tag: `Some(None)`; inside a quoted attribute: `Some(Some(q))`.
+    let mut tag: Option<Option<char>> = None;
+    while let Some((i, c)) = chars.next() {
+        match (tag, c) {
+            (None, '<') => tag = Some(None),
+            (None, '&') => match decode_html_entity(&html[i..]) {
+                Some((decoded, len)) => {
+                    result.push(decoded);
+                    // Skip the rest of the entity (the `&` is already consumed).
+                    while chars.peek().is_some_and(|&(j, _)| j < i + len) {
+                        chars.next();
+                    }
+                }
+                None => result.push('&'),
+            },
+            (None, _) => result.push(c),
+            (Some(None), '"' | '\'') => tag = Some(Some(c)),
+            (Some(None), '>') => tag = None,
+            (Some(Some(q)), _) if c == q => tag = Some(None),
+            (Some(_), _) => {}
+        }
+    }
+    result
+}
+
+/// Decode the HTML entity at the start of `s` (which begins with `&`).
+///
+/// Returns the decoded character and the entity's byte length including
+/// the trailing `;`. Handles the named entities our own HTML escaping
+/// produces plus `&nbsp;`, and numeric `&#NN;` / `&#xHH;` references.
+/// Anything else returns `None` and is kept as literal text.
+fn decode_html_entity(s: &str) -> Option<(char, usize)> {
+    // Longest entity we accept is `&#x10FFFF;` (10 chars).
+    let (end, _) = s.char_indices().take(12).find(|&(_, c)| c == ';')?;
+    let body = &s[1..end];
+    let decoded = match body {
+        "amp" => '&',
+        "lt" => '<',
+        "gt" => '>',
+        "quot" => '"',
+        "apos" => '\'',
+        "nbsp" => '\u{a0}',
+        _ => {
+            let num = body.strip_prefix('#')?;
+            let code = match num.strip_prefix(['x', 'X']) {
+                Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                None => num.parse().ok()?,
+            };
+            char::from_u32(code)?
+        }
+    };
+    Some((decoded, end + 1))
+}
+
+/// Collapse a multi-paragraph description onto one line for a preview.
+pub fn one_line(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Truncate text to `max` characters, appending `...` if truncated.
+///
+/// Counts and cuts on `char` boundaries, so multibyte descriptions
+/// (accented, CJK) never split a code point.
+pub fn truncate_desc(text: &str, max: usize) -> String {
+    match text.char_indices().nth(max) {
+        Some((cut, _)) => format!("{}...", &text[..cut]),
+        None => text.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn markdown_to_text_drops_markup() {
+        assert_eq!(
+            markdown_to_text(
+                "# Trip\n\nFish & *chips* at `Joe's`,\nsee [the menu](menu.pdf).\n\n\
+                 - one\n- two\n\nVia <https://example.com> ![a gull](gull.jpg) <br>"
+            ),
+            "Trip\n\nFish & chips at Joe's,\nsee the menu.\n\none\n\ntwo\n\n\
+             Via https://example.com a gull"
+        );
+    }
+
+    #[test]
+    fn markdown_to_text_plain_text_unchanged() {
+        assert_eq!(
+            markdown_to_text("Just words.\n\nTwo paragraphs."),
+            "Just words.\n\nTwo paragraphs."
+        );
+    }
+
+    #[test]
+    fn strip_html_tags_removes_tags() {
+        assert_eq!(strip_html_tags("<p>Hello <b>world</b></p>"), "Hello world");
+    }
+
+    #[test]
+    fn strip_html_tags_no_tags() {
+        assert_eq!(strip_html_tags("plain text"), "plain text");
+    }
+
+    #[test]
+    fn strip_html_tags_empty() {
+        assert_eq!(strip_html_tags(""), "");
+    }
+
+    #[test]
+    fn strip_html_tags_nested() {
+        assert_eq!(
+            strip_html_tags("<div><p>Some <em>text</em></p></div>"),
+            "Some text"
+        );
+    }
+
+    #[test]
+    fn strip_html_tags_decodes_entities() {
+        assert_eq!(
+            strip_html_tags("<p>Fish &amp; Chips &lt;3 &quot;yum&quot; &gt;_&gt;</p>"),
+            "Fish & Chips <3 \"yum\" >_>"
+        );
+    }
+
+    #[test]
+    fn strip_html_tags_decodes_numeric_entities() {
+        assert_eq!(strip_html_tags("it&#39;s &#x263A;"), "it's \u{263A}");
+    }
+
+    #[test]
+    fn strip_html_tags_keeps_unknown_or_bare_ampersands() {
+        assert_eq!(
+            strip_html_tags("A & B &bogus; &#xZZ;"),
+            "A & B &bogus; &#xZZ;"
+        );
+    }
+
+    #[test]
+    fn strip_html_tags_ampersand_before_multibyte_text() {
+        assert_eq!(
+            strip_html_tags("a &東京の夜景と富士山"),
+            "a &東京の夜景と富士山"
+        );
+    }
+
+    #[test]
+    fn strip_html_tags_gt_inside_quoted_attribute() {
+        assert_eq!(
+            strip_html_tags(r#"<a href="x" title="a > b">link</a> after"#),
+            "link after"
+        );
+        assert_eq!(strip_html_tags("<span data-x='1>0'>text</span>"), "text");
+    }
+
+    #[test]
+    fn strip_html_tags_linkified_url() {
+        // Shape produced by scan's linkify_urls.
+        assert_eq!(
+            strip_html_tags(
+                r#"<p>See <a href="https://example.com/?a=1&amp;b=2">https://example.com/?a=1&amp;b=2</a></p>"#
+            ),
+            "See https://example.com/?a=1&b=2"
+        );
+    }
+
+    #[test]
+    fn truncate_desc_short() {
+        assert_eq!(truncate_desc("Short text", 40), "Short text");
+    }
+
+    #[test]
+    fn truncate_desc_exact() {
+        let text = "a".repeat(40);
+        assert_eq!(truncate_desc(&text, 40), text);
+    }
+
+    #[test]
+    fn truncate_desc_long() {
+        let text = "a".repeat(50);
+        let expected = format!("{}...", "a".repeat(40));
+        assert_eq!(truncate_desc(&text, 40), expected);
+    }
+
+    #[test]
+    fn truncate_desc_multibyte_at_byte_boundary_inside_char() {
+        // Byte 1 falls inside "é" (2 bytes); slicing by bytes would panic.
+        assert_eq!(truncate_desc("é", 1), "é");
+        assert_eq!(truncate_desc("ééé", 1), "é...");
+    }
+
+    #[test]
+    fn truncate_desc_cjk() {
+        let text = "東京の夜景と富士山";
+        assert_eq!(truncate_desc(text, 4), "東京の夜...");
+        assert_eq!(truncate_desc(text, 9), text);
+    }
+
+    #[test]
+    fn truncate_desc_exact_multibyte_length_not_truncated() {
+        let text = "café crème";
+        assert_eq!(truncate_desc(text, 10), text);
+        assert_eq!(truncate_desc(text, 4), "café...");
+    }
+
+    #[test]
+    fn truncate_desc_empty() {
+        assert_eq!(truncate_desc("", 40), "");
+    }
+
+    #[test]
+    fn one_line_collapses_whitespace() {
+        assert_eq!(
+            one_line("First line\n\n  second\tline "),
+            "First line second line"
+        );
+        assert_eq!(one_line(" \n "), "");
+    }
+}
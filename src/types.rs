@@ -28,6 +28,48 @@ pub struct Page {
     pub sort_key: u32,
     /// If true, body is a URL and this page is an external link
     pub is_link: bool,
+    /// Image files next to the markdown file that the page embeds
+    /// (`![](photo.jpg)`), copied to the output root by generate
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub assets: Vec<String>,
+    /// Table of contents override from front-matter; `None` follows
+    /// `[pages] toc`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub toc: Option<bool>,
+}
+
+/// Structured album details from the album's optional `meta.toml`, shown
+/// as a small block under the album title.
+///
+/// Every field is free text and optional. Unknown keys are rejected at scan
+/// time, the same as unknown keys in `config.toml`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AlbumMeta {
+    /// Where the photos were taken, e.g. `"Kyoto, Japan"`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+    /// When they were taken, e.g. `"Spring 2024"` or `"2024-04-12"`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub date: Option<String>,
+    /// Camera (and lens) credit.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub camera: Option<String>,
+}
+
+impl AlbumMeta {
+    /// Present fields as `(label, value)` pairs, in display order.
+    pub fn entries(&self) -> Vec<(&'static str, &str)> {
+        [
+            ("Location", &self.location),
+            ("Date", &self.date),
+            ("Camera", &self.camera),
+        ]
+        .into_iter()
+        .filter_map(|(label, value)| Some((label, value.as_deref()?.trim())))
+        .filter(|(_, value)| !value.is_empty())
+        .collect()
+    }
 }
 
 /// Navigation tree item (only numbered directories).
@@ -0,0 +1,165 @@
+//! Opt-in "smart" typography for rendered prose (`smart_typography`).
+//!
+//! [`smarten`] rewrites the text of an HTML fragment: straight quotes become
+//! curly ones, `--` (or `---`) an em dash and `...` an ellipsis. Tags and
+//! their attribute values pass through untouched, as does everything inside
+//! `<code>`, `<pre>`, `<script>` and `<style>`.
+//!
+//! It runs on HTML that simple-gal produced itself (markdown output and
+//! escaped plain-text descriptions), so it only needs to understand tags,
+//! not arbitrary markup.
+
+/// Inline elements that continue the surrounding text. Any other tag starts
+/// a new run of text, so a quote right after it is an opening quote.
+const INLINE_TAGS: &[&str] = &[
+    "a", "abbr", "b", "cite", "em", "i", "mark", "q", "s", "small", "span", "strong", "sub", "sup",
+    "u",
+];
+
+/// Elements whose content is left exactly as written.
+const VERBATIM_TAGS: &[&str] = &["code", "pre", "script", "style"];
+
+/// Apply smart typography to the text of an HTML fragment.
+pub fn smarten(html: &str) -> String {
+    let mut out = String::with_capacity(html.len());
+    let mut verbatim = 0usize;
+    // Last text character seen, deciding whether a quote opens or closes.
+    let mut prev = ' ';
+    let mut rest = html;
+    while !rest.is_empty() {
+        if rest.starts_with('<') {
+            let end = rest.find('>').map_or(rest.len(), |i| i + 1);
+            let tag = &rest[..end];
+            let name = tag
+                .trim_start_matches(['<', '/'])
+                .chars()
+                .take_while(char::is_ascii_alphanumeric)
+                .collect::<String>()
+                .to_ascii_lowercase();
+            if VERBATIM_TAGS.contains(&name.as_str()) {
+                if tag.starts_with("</") {
+                    verbatim = verbatim.saturating_sub(1);
+                } else {
+                    verbatim += 1;
+                }
+            }
+            if !INLINE_TAGS.contains(&name.as_str()) {
+                prev = ' ';
+            }
+            out.push_str(tag);
+            rest = &rest[end..];
+            continue;
+        }
+        let end = rest.find('<').unwrap_or(rest.len());
+        let text = &rest[..end];
+        if verbatim > 0 {
+            out.push_str(text);
+            prev = text.chars().last().unwrap_or(prev);
+        } else {
+            smarten_text(text, &mut prev, &mut out);
+        }
+        rest = &rest[end..];
+    }
+    out
+}
+
+/// Whether a quote following `prev` opens rather than closes.
+fn opens(prev: char) -> bool {
+    prev.is_whitespace() || "([{“‘—–-/".contains(prev)
+}
+
+/// Rewrite one run of (HTML-escaped) text between tags.
+fn smarten_text(text: &str, prev: &mut char, out: &mut String) {
+    let mut rest = text;
+    while let Some(c) = rest.chars().next() {
+        let (replacement, len) = if rest.starts_with("&quot;") || c == '"' {
+            let len = if c == '"' { 1 } else { "&quot;".len() };
+            (if opens(*prev) { '“' } else { '”' }, len)
+        } else if c == '\'' {
+            (if opens(*prev) { '‘' } else { '’' }, 1)
+        } else if rest.starts_with("...") {
+            ('…', 3)
+        } else if c == '-' {
+            let run = rest.find(|d| d != '-').unwrap_or(rest.len());
+            if !matches!(run, 2 | 3) {
+                // A lone hyphen, or a run of four or more (a rule): unchanged.
+                out.push_str(&rest[..run]);
+                *prev = '-';
+                rest = &rest[run..];
+                continue;
+            }
+            ('—', run)
+        } else if c == '&' {
+            // Any other entity is copied whole and reads as a letter.
+            let len = rest.find(';').filter(|&i| i <= 10).map_or(1, |i| i + 1);
+            out.push_str(&rest[..len]);
+            *prev = 'x';
+            rest = &rest[len..];
+            continue;
+        } else {
+            (c, c.len_utf8())
+        };
+        out.push(replacement);
+        *prev = replacement;
+        rest = &rest[len..];
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn curls_double_and_single_quotes() {
+        assert_eq!(
+            smarten(r#"<p>She said "it's fine" and left.</p>"#),
+            "<p>She said “it’s fine” and left.</p>"
+        );
+        assert_eq!(
+            smarten("<p>&quot;Quoted&quot;, 'single'</p>"),
+            "<p>“Quoted”, ‘single’</p>"
+        );
+    }
+
+    #[test]
+    fn quote_after_block_tag_opens() {
+        assert_eq!(
+            smarten("<p>End.</p>\n<p>\"Start\"</p>"),
+            "<p>End.</p>\n<p>“Start”</p>"
+        );
+        assert_eq!(
+            smarten(r#"<p>a <em>"word"</em></p>"#),
+            "<p>a <em>“word”</em></p>"
+        );
+    }
+
+    #[test]
+    fn dashes_and_ellipsis() {
+        assert_eq!(
+            smarten("<p>light -- and shadow --- wait...</p>"),
+            "<p>light — and shadow — wait…</p>"
+        );
+        assert_eq!(
+            smarten("<p>well-known ---- rule</p>"),
+            "<p>well-known ---- rule</p>"
+        );
+    }
+
+    #[test]
+    fn code_and_attributes_untouched() {
+        let html = r#"<p><a href="https://x.com/a--b" title="it's">link</a> <code>"raw" -- ...</code></p>
+<pre><code>let s = 'x';
+</code></pre>"#;
+        assert_eq!(
+            smarten(html),
+            r#"<p><a href="https://x.com/a--b" title="it's">link</a> <code>"raw" -- ...</code></p>
+<pre><code>let s = 'x';
+</code></pre>"#
+        );
+    }
+
+    #[test]
+    fn other_entities_kept() {
+        assert_eq!(smarten("<p>R&amp;D's</p>"), "<p>R&amp;D’s</p>");
+    }
+}
@@ -5,11 +5,14 @@
     var prevUrl = prev && prev.getAttribute('href');
     var nextUrl = next && next.getAttribute('href');
 
-    // Parent URL: last <a> in the breadcrumb trail (the level above this page).
+    // Parent URL: the image page's back-to-album control, otherwise the last
+    // <a> in the breadcrumb trail (the level above this page).
     // On the home page the only link is the home link itself — no parent to go to.
+    var back = document.querySelector('.back-to-album');
     var crumbs = document.querySelectorAll('.breadcrumb a');
     var lastCrumb = crumbs.length > 1 ? crumbs[crumbs.length - 1] : null;
-    var parentUrl = lastCrumb ? lastCrumb.getAttribute('href') : null;
+    var parentLink = back || lastCrumb;
+    var parentUrl = parentLink ? parentLink.getAttribute('href') : null;
 
     // Position click zones so they overlap ~20% of the image on each side
     // and extend outward to the page edges.
@@ -26,8 +29,23 @@
         window.addEventListener('resize', sizeNavZones);
     }
 
+    // Fade the photo in once it has decoded ([theme] image_fade). An image
+    // that is already complete is left alone so it doesn't flicker.
+    var fadeImg = document.querySelector('img.image-fade');
+    if (fadeImg && !fadeImg.complete) {
+        fadeImg.classList.add('is-loading');
+        var reveal = function() { fadeImg.classList.remove('is-loading'); };
+        fadeImg.addEventListener('load', function() {
+            if (fadeImg.decode) fadeImg.decode().then(reveal, reveal);
+            else reveal();
+        });
+        fadeImg.addEventListener('error', reveal);
+    }
+
     // Keyboard navigation
     document.addEventListener('keydown', function(e) {
+        // Typing in the search box is not navigation
+        if (e.target.matches('input[type="search"]')) return;
         // Previous: ArrowLeft, h, k
         if (e.key === 'ArrowLeft' || e.key === 'h' || e.key === 'k') {
             if (prevUrl) location.href = prevUrl;
@@ -0,0 +1,72 @@
+// Simple Gal - Client-side search ([search] enabled)
+(function() {
+    var form = document.querySelector('.site-search');
+    if (!form) return;
+    var input = form.querySelector('input');
+    var results = form.querySelector('.search-results');
+    var MAX_RESULTS = 30;
+    var entries = null;
+
+    // The index is fetched on first use, so visitors who never search
+    // don't download it.
+    function load() {
+        if (entries) return Promise.resolve(entries);
+        return fetch(form.getAttribute('data-index'))
+            .then(function(r) { return r.json(); })
+            .then(function(data) {
+                entries = data.map(function(e) {
+                    e.text = [e.title, e.album, e.description].join(' ').toLowerCase();
+                    return e;
+                });
+                return entries;
+            });
+    }
+
+    // Every whitespace-separated term must appear somewhere in the entry.
+    function render(query) {
+        var terms = query.toLowerCase().split(/\s+/).filter(Boolean);
+        results.textContent = '';
+        if (!terms.length) {
+            results.hidden = true;
+            return;
+        }
+        var matches = entries.filter(function(e) {
+            return terms.every(function(t) { return e.text.indexOf(t) !== -1; });
+        });
+        matches.slice(0, MAX_RESULTS).forEach(function(e) {
+            var item = document.createElement('li');
+            var link = document.createElement('a');
+            link.href = e.url;
+            link.textContent = e.title;
+            item.appendChild(link);
+            if (e.album) {
+                var album = document.createElement('span');
+                album.className = 'search-album';
+                album.textContent = e.album;
+                item.appendChild(album);
+            }
+            results.appendChild(item);
+        });
+        if (!matches.length) {
+            var empty = document.createElement('li');
+            empty.className = 'search-empty';
+            empty.textContent = 'No matches';
+            results.appendChild(empty);
+        }
+        results.hidden = false;
+    }
+
+    input.addEventListener('input', function() {
+        var query = input.value;
+        load().then(function() {
+            if (input.value === query) render(query);
+        });
+    });
+
+    // Enter opens the first result.
+    form.addEventListener('submit', function(e) {
+        e.preventDefault();
+        var first = results.querySelector('a');
+        if (first) location.href = first.href;
+    });
+})();
@@ -1,13 +1,14 @@
 const CACHE_NAME = 'simple-gal-v1';
 const IMAGE_CACHE_NAME = CACHE_NAME + '-images';
 const MAX_CACHED_IMAGES = 200;
+// Relative to this script, so the site works under any base path.
 const ASSETS_TO_CACHE = [
-    '/',
-    '/index.html',
-    '/site.webmanifest',
-    '/icon-192.png',
-    '/icon-512.png',
-    '/apple-touch-icon.png'
+    './',
+    './index.html',
+    './site.webmanifest',
+    './icon-192.png',
+    './icon-512.png',
+    './apple-touch-icon.png'
 ];
 
 // Install event: cache core assets
@@ -45,6 +46,9 @@ self.addEventListener('fetch', (event) => {
     // (cross-document @view-transition { navigation: auto }).
     if (event.request.mode === 'navigate') return;
 
+    // Album downloads can be hundreds of megabytes — never cache them.
+    if (url.pathname.endsWith('.zip')) return;
+
     // Image requests - Cache First, fall back to network
     // Uses a separate bounded cache (MAX_CACHED_IMAGES) to prevent unbounded storage growth.
     // When the limit is exceeded, the oldest entries are evicted (FIFO).
@@ -720,3 +720,24 @@ fn sizes_attr_mobile_always_100vw() {
         );
     }
 }
+
+// ---------------------------------------------------------------------------
+// Keyboard navigation
+// ---------------------------------------------------------------------------
+
+#[test]
+#[ignore]
+fn escape_follows_back_to_album_control() {
+    // Breadcrumb links are root-relative, so under file:// they point outside
+    // the generated site. The back-to-album control links `../`, so landing
+    // in the album directory shows Escape used the control.
+    let tab = load_page(page::no_description::LANDSCAPE, &[]);
+    tab.press_key("Escape").unwrap();
+    tab.wait_until_navigated().unwrap();
+
+    let url = tab.get_url();
+    assert!(
+        url.ends_with("/tests/browser/generated/no-description/"),
+        "Escape should open the album page: {url}"
+    );
+}
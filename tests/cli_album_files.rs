@@ -0,0 +1,71 @@
+//! End-to-end test for publishing non-image album files
+//! (`album_file_extensions`).
+//!
+//! Builds a content tree whose album holds a PDF linked from its
+//! description, and checks the PDF lands next to the album page in the
+//! output so the link resolves.
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use tempfile::TempDir;
+
+fn simple_gal() -> Command {
+    Command::new(env!("CARGO_BIN_EXE_simple-gal"))
+}
+
+fn sample_image_bytes() -> Vec<u8> {
+    let path =
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures/content/010-Landscapes/001-dawn.jpg");
+    fs::read(path).expect("fixture image missing")
+}
+
+#[test]
+fn album_pdf_linked_from_description_is_published() {
+    let workspace = TempDir::new().unwrap();
+    let source = workspace.path().join("content");
+    let temp = workspace.path().join("temp");
+    let output = workspace.path().join("dist");
+
+    let album = source.join("010-Prints");
+    fs::create_dir_all(&album).unwrap();
+    fs::write(
+        source.join("config.toml"),
+        "site_title = \"Test\"\n\n[images]\nsizes = [400]\nquality = 70\n",
+    )
+    .unwrap();
+    fs::write(album.join("001-first.jpg"), sample_image_bytes()).unwrap();
+    fs::write(album.join("prices.pdf"), "%PDF-1.4").unwrap();
+    fs::write(
+        album.join("description.md"),
+        "Order prints: [price list](prices.pdf).",
+    )
+    .unwrap();
+
+    let result = simple_gal()
+        .args([
+            "--source",
+            source.to_str().unwrap(),
+            "--temp-dir",
+            temp.to_str().unwrap(),
+            "--output",
+            output.to_str().unwrap(),
+            "--quiet",
+            "build",
+        ])
+        .output()
+        .expect("build command failed to spawn");
+    assert!(
+        result.status.success(),
+        "build failed unexpectedly.\nstdout: {}\nstderr: {}",
+        String::from_utf8_lossy(&result.stdout),
+        String::from_utf8_lossy(&result.stderr)
+    );
+
+    assert_eq!(
+        fs::read_to_string(output.join("prints/prices.pdf")).unwrap(),
+        "%PDF-1.4"
+    );
+    let page = fs::read_to_string(output.join("prints/index.html")).unwrap();
+    assert!(page.contains(r#"<a href="prices.pdf">price list</a>"#));
+}
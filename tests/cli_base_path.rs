@@ -0,0 +1,119 @@
+//! End-to-end test for deploying under a subdirectory (`base_path`).
+//!
+//! Builds a tiny content tree with `base_path = "/portfolio"` and checks
+//! that no generated page links back to the bare domain root: every
+//! root-absolute `href`/`src` must start with `/portfolio/`, and absolute
+//! Open Graph URLs built from `base_url` include it too.
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use tempfile::TempDir;
+
+fn simple_gal() -> Command {
+    Command::new(env!("CARGO_BIN_EXE_simple-gal"))
+}
+
+fn sample_image_bytes() -> Vec<u8> {
+    let path =
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures/content/010-Landscapes/001-dawn.jpg");
+    fs::read(path).expect("fixture image missing")
+}
+
+/// One album with two images, an `about` page and a favicon, so the
+/// build exercises nav, breadcrumbs, album cards, page links and icons.
+fn seed_content(root: &Path) {
+    fs::create_dir_all(root).unwrap();
+    fs::write(
+        root.join("config.toml"),
+        r#"
+site_title = "Test"
+base_url = "https://user.github.io"
+base_path = "/portfolio"
+
+[images]
+sizes = [400]
+quality = 70
+"#,
+    )
+    .unwrap();
+    let album = root.join("010-Album");
+    fs::create_dir_all(&album).unwrap();
+    let bytes = sample_image_bytes();
+    fs::write(album.join("001-first.jpg"), &bytes).unwrap();
+    fs::write(album.join("002-second.jpg"), &bytes).unwrap();
+    fs::write(root.join("020-about.md"), "# About\n\nHello.\n").unwrap();
+    fs::create_dir_all(root.join("assets")).unwrap();
+    fs::write(root.join("assets/favicon.svg"), "<svg/>").unwrap();
+}
+
+#[test]
+fn base_path_build_has_no_bare_root_urls() {
+    let workspace = TempDir::new().unwrap();
+    let source = workspace.path().join("content");
+    let temp = workspace.path().join("temp");
+    let output = workspace.path().join("dist");
+    seed_content(&source);
+
+    let result = simple_gal()
+        .args([
+            "--source",
+            source.to_str().unwrap(),
+            "--temp-dir",
+            temp.to_str().unwrap(),
+            "--output",
+            output.to_str().unwrap(),
+            "--quiet",
+            "build",
+        ])
+        .output()
+        .expect("build command failed to spawn");
+    assert!(
+        result.status.success(),
+        "build failed unexpectedly.\nstdout: {}\nstderr: {}",
+        String::from_utf8_lossy(&result.stdout),
+        String::from_utf8_lossy(&result.stderr)
+    );
+
+    let manifest: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(output.join("site.webmanifest")).unwrap())
+            .unwrap();
+    assert_eq!(manifest["scope"], "/portfolio/");
+    assert_eq!(manifest["start_url"], "/portfolio/");
+
+    let mut pages = 0;
+    for entry in walkdir::WalkDir::new(&output) {
+        let path = entry.unwrap().into_path();
+        if path.extension().is_none_or(|e| e != "html") {
+            continue;
+        }
+        let html = fs::read_to_string(&path).unwrap();
+        if !html.starts_with("<!DOCTYPE html>") {
+            continue;
+        }
+        pages += 1;
+        assert!(
+            html.contains("register('/portfolio/sw.js')"),
+            "{}: service worker not registered under base path",
+            path.display()
+        );
+        for attr in [r#"href="/"#, r#"src="/"#] {
+            for (i, _) in html.match_indices(attr) {
+                let url = &html[i + attr.len() - 1..];
+                assert!(
+                    url.starts_with("/portfolio/"),
+                    "{}: bare-root URL {}",
+                    path.display(),
+                    &url[..url.find('"').unwrap_or(url.len())]
+                );
+            }
+        }
+    }
+    assert!(pages >= 4, "expected index, album, image and about pages");
+
+    let album = fs::read_to_string(output.join("Album/index.html")).unwrap();
+    assert!(
+        album.contains(r#"property="og:url" content="https://user.github.io/portfolio/Album/""#),
+        "album og:url is missing the base path"
+    );
+}
@@ -0,0 +1,93 @@
+//! End-to-end tests for `simple-gal init`.
+//!
+//! Scaffolds a starter content directory, builds it, and checks that an
+//! existing non-empty directory is only written into with `--force`.
+
+use std::fs;
+use std::process::Command;
+use tempfile::TempDir;
+
+fn simple_gal() -> Command {
+    Command::new(env!("CARGO_BIN_EXE_simple-gal"))
+}
+
+#[test]
+fn init_scaffold_builds() {
+    let workspace = TempDir::new().unwrap();
+    let source = workspace.path().join("content");
+    let temp = workspace.path().join("temp");
+    let output = workspace.path().join("dist");
+
+    let init = simple_gal()
+        .args(["--quiet", "init", source.to_str().unwrap()])
+        .output()
+        .expect("init command failed to spawn");
+    assert!(
+        init.status.success(),
+        "init failed: {}",
+        String::from_utf8_lossy(&init.stderr)
+    );
+
+    let build = simple_gal()
+        .args([
+            "--source",
+            source.to_str().unwrap(),
+            "--temp-dir",
+            temp.to_str().unwrap(),
+            "--output",
+            output.to_str().unwrap(),
+            "--quiet",
+            "build",
+        ])
+        .output()
+        .expect("build command failed to spawn");
+    assert!(
+        build.status.success(),
+        "build failed unexpectedly.\nstdout: {}\nstderr: {}",
+        String::from_utf8_lossy(&build.stdout),
+        String::from_utf8_lossy(&build.stderr)
+    );
+    assert!(output.join("index.html").exists());
+    assert!(output.join("about.html").exists());
+    assert!(output.join("Sample-Album/index.html").exists());
+}
+
+#[test]
+fn init_refuses_non_empty_dir_without_force() {
+    let workspace = TempDir::new().unwrap();
+    let source = workspace.path().join("content");
+    fs::create_dir_all(&source).unwrap();
+    fs::write(source.join("config.toml"), "site_title = \"Mine\"\n").unwrap();
+
+    let refused = simple_gal()
+        .args(["--format", "json", "init", source.to_str().unwrap()])
+        .output()
+        .unwrap();
+    assert_eq!(refused.status.code(), Some(2));
+    let envelope: serde_json::Value = serde_json::from_slice(&refused.stderr).unwrap();
+    assert_eq!(envelope["kind"], "usage");
+    assert_eq!(
+        fs::read_to_string(source.join("config.toml")).unwrap(),
+        "site_title = \"Mine\"\n"
+    );
+
+    let forced = simple_gal()
+        .args([
+            "--format",
+            "json",
+            "init",
+            "--force",
+            source.to_str().unwrap(),
+        ])
+        .output()
+        .unwrap();
+    assert!(forced.status.success());
+    let envelope: serde_json::Value = serde_json::from_slice(&forced.stdout).unwrap();
+    assert_eq!(envelope["command"], "init");
+    assert!(
+        envelope["data"]["files"]
+            .as_array()
+            .unwrap()
+            .contains(&"config.toml".into())
+    );
+}
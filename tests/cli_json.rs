@@ -92,6 +92,49 @@ fn config_schema_json_envelope() {
     assert!(schema["properties"]["colors"]["properties"]["light"].is_object());
 }
 
+/// Assert every key `SiteConfig` serializes is declared in `schema`,
+/// recursing into nested sections.
+fn assert_schema_covers(schema: &serde_json::Value, value: &serde_json::Value, path: &str) {
+    let Some(fields) = value.as_object() else {
+        return;
+    };
+    let props = schema["properties"]
+        .as_object()
+        .unwrap_or_else(|| panic!("schema for `{path}` has no properties"));
+    for (key, field) in fields {
+        let key_path = if path.is_empty() {
+            key.clone()
+        } else {
+            format!("{path}.{key}")
+        };
+        let field_schema = props
+            .get(key)
+            .unwrap_or_else(|| panic!("schema is missing `{key_path}`"));
+        if field_schema["properties"].is_object() {
+            assert_schema_covers(field_schema, field, &key_path);
+        }
+    }
+}
+
+#[test]
+fn config_schema_covers_every_site_config_field() {
+    let output = simple_gal()
+        .args(["--format", "json", "config", "schema"])
+        .output()
+        .expect("run simple-gal");
+    assert!(output.status.success(), "exit={}", output.status);
+    let v = parse_json(&output.stdout);
+    let schema = &v["data"]["schema"];
+
+    assert_eq!(
+        schema["properties"]["images"]["properties"]["quality"]["type"],
+        "integer"
+    );
+
+    let defaults = serde_json::to_value(simple_gal::config::SiteConfig::default()).unwrap();
+    assert_schema_covers(schema, &defaults, "");
+}
+
 #[test]
 fn scan_envelope_has_counts_and_source() {
     let output = simple_gal()
@@ -264,6 +307,35 @@ fn quiet_suppresses_scan_text_tree() {
     );
 }
 
+#[test]
+fn quiet_and_verbose_conflict() {
+    let output = simple_gal()
+        .args([
+            "--source",
+            fixtures_dir().to_str().unwrap(),
+            "--quiet",
+            "--verbose",
+            "check",
+        ])
+        .output()
+        .expect("run simple-gal");
+
+    assert!(!output.status.success());
+    assert!(output.stdout.is_empty());
+}
+
+#[test]
+fn verbose_check_still_prints_inventory() {
+    let output = simple_gal()
+        .args(["--source", fixtures_dir().to_str().unwrap(), "-v", "check"])
+        .output()
+        .expect("run simple-gal");
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("==> Content is valid"), "got: {stdout}");
+}
+
 // ============================================================================
 // --format ndjson
 // ============================================================================
@@ -626,3 +698,39 @@ fn progress_error_is_compact_single_line() {
     let v: serde_json::Value = serde_json::from_str(stderr_lines[0]).unwrap();
     assert_eq!(v["ok"], false);
 }
+
+#[test]
+fn check_duplicates_reports_look_alike_images() {
+    let tmp = tempfile::TempDir::new().unwrap();
+    let album = tmp.path().join("010-Landscapes");
+    std::fs::create_dir_all(&album).unwrap();
+    let picture = |flipped: bool| {
+        image::RgbImage::from_fn(120, 90, move |x, y| {
+            let x = if flipped { 119 - x } else { x };
+            image::Rgb([(x * 2) as u8, y as u8, 128])
+        })
+    };
+    picture(false).save(album.join("001-dawn.png")).unwrap();
+    picture(false)
+        .save(album.join("002-dawn-again.jpg"))
+        .unwrap();
+    picture(true).save(album.join("003-dusk.png")).unwrap();
+
+    let output = simple_gal()
+        .args([
+            "--source",
+            tmp.path().to_str().unwrap(),
+            "--format",
+            "json",
+            "check",
+            "--duplicates",
+        ])
+        .output()
+        .expect("run simple-gal");
+    assert!(output.status.success(), "exit={}", output.status);
+    let v = parse_json(&output.stdout);
+    let pairs = v["data"]["duplicates"].as_array().unwrap();
+    assert_eq!(pairs.len(), 1, "{pairs:?}");
+    assert_eq!(pairs[0]["first"], "010-Landscapes/001-dawn.png");
+    assert_eq!(pairs[0]["second"], "010-Landscapes/002-dawn-again.jpg");
+}
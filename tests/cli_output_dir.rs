@@ -0,0 +1,51 @@
+//! End-to-end test for the output directory guard.
+//!
+//! `generate` replaces the output directory as a whole, so an output that
+//! holds the content or temp directory must be refused rather than
+//! swapped aside with everything in it.
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use tempfile::TempDir;
+
+fn simple_gal() -> Command {
+    Command::new(env!("CARGO_BIN_EXE_simple-gal"))
+}
+
+fn sample_image_bytes() -> Vec<u8> {
+    let path =
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures/content/010-Landscapes/001-dawn.jpg");
+    fs::read(path).expect("fixture image missing")
+}
+
+#[test]
+fn output_dot_with_default_paths_is_refused() {
+    let workspace = TempDir::new().unwrap();
+    let album = workspace.path().join("content/010-Album");
+    fs::create_dir_all(&album).unwrap();
+    fs::write(
+        workspace.path().join("content/config.toml"),
+        "site_title = \"Test\"\n\n[images]\nsizes = [400]\nquality = 70\n",
+    )
+    .unwrap();
+    fs::write(album.join("001-first.jpg"), sample_image_bytes()).unwrap();
+
+    let result = simple_gal()
+        .current_dir(workspace.path())
+        .args(["--output", ".", "--quiet", "build"])
+        .output()
+        .expect("build command failed to spawn");
+    assert!(
+        !result.status.success(),
+        "build into . succeeded.\nstdout: {}\nstderr: {}",
+        String::from_utf8_lossy(&result.stdout),
+        String::from_utf8_lossy(&result.stderr)
+    );
+    let stderr = String::from_utf8_lossy(&result.stderr);
+    assert!(stderr.contains("won't replace"), "{stderr}");
+
+    assert!(album.join("001-first.jpg").exists());
+    assert!(workspace.path().join(".simple-gal-temp/processed").is_dir());
+    assert!(!workspace.path().join("index.html").exists());
+}
@@ -243,3 +243,95 @@ fn scan_json_format_with_save_manifest() {
     let saved: serde_json::Value = serde_json::from_str(&content).unwrap();
     assert!(saved.get("albums").is_some());
 }
+
+// =========================================================================
+// SIMPLE_GAL_* environment overrides
+// =========================================================================
+
+#[test]
+fn scan_env_override_sets_site_title_everywhere() {
+    let output = simple_gal()
+        .args(["--source", fixtures_dir().to_str().unwrap(), "scan"])
+        .env("SIMPLE_GAL_SITE_TITLE", "Staging Gallery")
+        .env("SIMPLE_GAL_BASE_URL", "https://staging.example.com")
+        .output()
+        .expect("failed to run simple-gal");
+    assert!(output.status.success());
+
+    let parsed: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let manifest = &parsed["data"]["manifest"];
+    assert_eq!(manifest["config"]["site_title"], "Staging Gallery");
+    assert_eq!(
+        manifest["config"]["base_url"],
+        "https://staging.example.com"
+    );
+    for album in manifest["albums"].as_array().unwrap() {
+        assert_eq!(album["config"]["site_title"], "Staging Gallery");
+    }
+}
+
+#[test]
+fn scan_without_env_override_keeps_config_value() {
+    let output = simple_gal()
+        .args(["--source", fixtures_dir().to_str().unwrap(), "scan"])
+        .env_remove("SIMPLE_GAL_SITE_TITLE")
+        .env_remove("SIMPLE_GAL_BASE_URL")
+        .output()
+        .expect("failed to run simple-gal");
+    assert!(output.status.success());
+
+    let parsed: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    // The fixture config leaves site_title at its stock default.
+    assert_eq!(
+        parsed["data"]["manifest"]["config"]["site_title"],
+        "Gallery"
+    );
+}
+
+// =========================================================================
+// --site-title / --quality / --base-url overrides
+// =========================================================================
+
+#[test]
+fn scan_quality_flag_beats_config_toml() {
+    let output = simple_gal()
+        .args([
+            "--source",
+            fixtures_dir().to_str().unwrap(),
+            "--quality",
+            "50",
+            "--site-title",
+            "Draft",
+            "scan",
+        ])
+        .env("SIMPLE_GAL_SITE_TITLE", "From Env")
+        .output()
+        .expect("failed to run simple-gal");
+    assert!(output.status.success());
+
+    let parsed: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let manifest = &parsed["data"]["manifest"];
+    // The fixture config.toml sets quality = 85.
+    assert_eq!(manifest["config"]["images"]["quality"], 50);
+    assert_eq!(manifest["config"]["site_title"], "Draft");
+    for album in manifest["albums"].as_array().unwrap() {
+        assert_eq!(album["config"]["images"]["quality"], 50);
+    }
+}
+
+#[test]
+fn scan_quality_flag_is_validated() {
+    let output = simple_gal()
+        .args([
+            "--source",
+            fixtures_dir().to_str().unwrap(),
+            "--quality",
+            "150",
+            "scan",
+        ])
+        .output()
+        .expect("failed to run simple-gal");
+    assert_eq!(output.status.code(), Some(3));
+    let stderr: serde_json::Value = serde_json::from_slice(&output.stderr).unwrap();
+    assert_eq!(stderr["kind"], "config");
+}
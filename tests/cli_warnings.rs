@@ -0,0 +1,100 @@
+//! End-to-end tests for build warnings.
+//!
+//! Warnings go to stderr at every verbosity, so a `--quiet` build still
+//! reports configuration it couldn't honor while keeping stdout to the
+//! final result.
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use tempfile::TempDir;
+
+fn simple_gal() -> Command {
+    Command::new(env!("CARGO_BIN_EXE_simple-gal"))
+}
+
+fn sample_image_bytes() -> Vec<u8> {
+    let path =
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures/content/010-Landscapes/001-dawn.jpg");
+    fs::read(path).expect("fixture image missing")
+}
+
+/// One album with one image, plus whatever extra config `extra` adds.
+fn seed_content(root: &Path, extra: &str) {
+    fs::create_dir_all(root).unwrap();
+    fs::write(
+        root.join("config.toml"),
+        format!(
+            r#"
+site_title = "Test"
+
+[images]
+sizes = [400]
+quality = 70
+{extra}"#
+        ),
+    )
+    .unwrap();
+    let album = root.join("010-Album");
+    fs::create_dir_all(&album).unwrap();
+    fs::write(album.join("001-first.jpg"), sample_image_bytes()).unwrap();
+}
+
+/// Run a `--quiet` build of `source` and assert it succeeded.
+fn run_quiet_build(workspace: &Path, source: &Path) -> std::process::Output {
+    let result = simple_gal()
+        .args([
+            "--source",
+            source.to_str().unwrap(),
+            "--temp-dir",
+            workspace.join("temp").to_str().unwrap(),
+            "--output",
+            workspace.join("dist").to_str().unwrap(),
+            "--quiet",
+            "build",
+        ])
+        .output()
+        .expect("build command failed to spawn");
+    assert!(
+        result.status.success(),
+        "build failed unexpectedly.\nstdout: {}\nstderr: {}",
+        String::from_utf8_lossy(&result.stdout),
+        String::from_utf8_lossy(&result.stderr)
+    );
+    result
+}
+
+#[test]
+fn capped_io_threads_warns_on_stderr_when_quiet() {
+    let workspace = TempDir::new().unwrap();
+    let source = workspace.path().join("content");
+    seed_content(&source, "\n[processing]\nio_threads = 1000000\n");
+
+    let result = run_quiet_build(workspace.path(), &source);
+    let stdout = String::from_utf8_lossy(&result.stdout);
+    let stderr = String::from_utf8_lossy(&result.stderr);
+    assert!(
+        stderr.contains("Warning: io_threads = 1000000 capped at"),
+        "stderr: {stderr}"
+    );
+    assert!(!stdout.contains("Warning"), "stdout: {stdout}");
+}
+
+#[test]
+fn asset_clash_warns_on_stderr_when_quiet() {
+    let workspace = TempDir::new().unwrap();
+    let source = workspace.path().join("content");
+    seed_content(&source, "");
+    let clash = source.join("assets/Album");
+    fs::create_dir_all(&clash).unwrap();
+    fs::write(clash.join("001-first-thumb.avif"), "not an image").unwrap();
+
+    let result = run_quiet_build(workspace.path(), &source);
+    let stdout = String::from_utf8_lossy(&result.stdout);
+    let stderr = String::from_utf8_lossy(&result.stderr);
+    assert!(
+        stderr.contains("Album/001-first-thumb.avif: processed image replaced the asset"),
+        "stderr: {stderr}"
+    );
+    assert!(!stdout.contains("Warnings"), "stdout: {stdout}");
+}